@@ -12,6 +12,7 @@ extern crate http;
 
 pub use http::header::{self, HeaderName, HeaderValue};
 
+use std::borrow::Cow;
 use std::error;
 use std::fmt::{self, Display, Formatter};
 
@@ -35,34 +36,159 @@ pub trait Header {
     /// `HeaderValue` should have been caught when parsing or constructing
     /// this value.
     fn encode<E: Extend<HeaderValue>>(&self, values: &mut E);
+
+    /// Encode this type, possibly as several `HeaderValue`s.
+    ///
+    /// The default just calls `encode`. A header that is a comma-separated
+    /// list can override this to emit the values it holds as they are,
+    /// instead of joining them into a newly allocated one. The fields
+    /// have the same meaning either way.
+    fn encode_to<W: Extend<HeaderValue>>(&self, out: &mut W) {
+        self.encode(out)
+    }
+
+    /// Whether each field of this header is a separate value.
+    ///
+    /// Most headers can have multiple fields combined into one
+    /// comma-separated list without changing their meaning, and the default
+    /// is `false`. A header like `Set-Cookie`, whose fields must never be
+    /// merged, should return `true`.
+    fn separate_fields() -> bool {
+        false
+    }
 }
 
 /// Errors trying to decode a header.
+///
+/// Besides its kind, an `Error` can carry the name of the header, the
+/// value that was rejected, and a message explaining why, when the code
+/// that produced it knew them.
 #[derive(Debug)]
 pub struct Error {
     kind: Kind,
+    name: Option<HeaderName>,
+    value: Option<HeaderValue>,
+    message: Option<Cow<'static, str>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 enum Kind {
     Invalid,
+    Missing,
 }
 
 impl Error {
     /// Create an 'invalid' Error.
     pub fn invalid() -> Error {
+        Error::new(Kind::Invalid)
+    }
+
+    /// Create a 'missing' Error, for when there were no values to decode.
+    pub fn missing() -> Error {
+        Error::new(Kind::Missing)
+    }
+
+    fn new(kind: Kind) -> Error {
         Error {
-            kind: Kind::Invalid,
+            kind,
+            name: None,
+            value: None,
+            message: None,
         }
     }
+
+    /// Sets the name of the header that failed to decode.
+    pub fn with_name(mut self, name: HeaderName) -> Error {
+        self.name = Some(name);
+        self
+    }
+
+    /// Sets the header value that was rejected.
+    pub fn with_value(mut self, value: HeaderValue) -> Error {
+        self.value = Some(value);
+        self
+    }
+
+    /// Sets a message describing why decoding failed.
+    pub fn with_message<M: Into<Cow<'static, str>>>(mut self, message: M) -> Error {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Returns true if a value was present but couldn't be decoded.
+    pub fn is_invalid(&self) -> bool {
+        self.kind == Kind::Invalid
+    }
+
+    /// Returns true if there were no values to decode.
+    pub fn is_missing(&self) -> bool {
+        self.kind == Kind::Missing
+    }
+
+    /// Returns the name of the header that failed to decode, if known.
+    pub fn name(&self) -> Option<&HeaderName> {
+        self.name.as_ref()
+    }
+
+    /// Returns the header value that was rejected, if known.
+    pub fn value(&self) -> Option<&HeaderValue> {
+        self.value.as_ref()
+    }
+
+    /// Returns the message describing why decoding failed, if any.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_ref().map(|message| message.as_ref())
+    }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        match &self.kind {
-            Kind::Invalid => f.write_str("invalid HTTP header"),
+        let kind = match self.kind {
+            Kind::Invalid => "invalid",
+            Kind::Missing => "missing",
+        };
+        match self.name {
+            Some(ref name) => write!(f, "{} {} header", kind, name)?,
+            None => write!(f, "{} HTTP header", kind)?,
         }
+        if let Some(ref value) = self.value {
+            write!(f, " {:?}", value)?;
+        }
+        if let Some(ref message) = self.message {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
     }
 }
 
 impl error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_display() {
+        assert_eq!(Error::invalid().to_string(), "invalid HTTP header");
+        assert_eq!(Error::missing().to_string(), "missing HTTP header");
+
+        let err = Error::invalid()
+            .with_name(header::CONTENT_LENGTH)
+            .with_value(HeaderValue::from_static("ten"))
+            .with_message("not a number");
+        assert_eq!(
+            err.to_string(),
+            "invalid content-length header \"ten\": not a number"
+        );
+    }
+
+    #[test]
+    fn error_accessors() {
+        let err = Error::missing().with_name(header::HOST);
+        assert!(err.is_missing());
+        assert!(!err.is_invalid());
+        assert_eq!(err.name(), Some(&header::HOST));
+        assert_eq!(err.value(), None);
+        assert_eq!(err.message(), None);
+    }
+}
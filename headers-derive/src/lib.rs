@@ -8,8 +8,23 @@ extern crate syn;
 
 use proc_macro::TokenStream;
 use proc_macro2::Span;
-use syn::{Data, Fields, Ident, Lit, Meta, NestedMeta};
+use syn::{Attribute, Data, DataEnum, Fields, Ident, Lit, Meta, NestedMeta};
 
+/// Derives `Header` for a newtype or an enum of tokens.
+///
+/// `#[header(csv)]` only works on structs:
+///
+/// ```compile_fail
+/// # extern crate headers;
+/// # extern crate headers_core;
+/// # #[macro_use] extern crate headers_derive;
+/// #[derive(Header)]
+/// #[header(csv, name_const = "ALLOW")]
+/// enum Methods {
+///     Get,
+/// }
+/// # fn main() {}
+/// ```
 #[proc_macro_derive(Header, attributes(header))]
 pub fn derive_header(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).unwrap();
@@ -22,18 +37,23 @@ fn impl_header(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
         Err(msg) => {
             return quote! {
                 compile_error!(#msg);
-            }
-            .into();
+            };
         }
     };
 
     let decode = fns.decode;
     let encode = fns.encode;
+    let extern_headers = if fns.csv {
+        quote! {
+            extern crate headers as __headers;
+        }
+    } else {
+        quote! {}
+    };
 
     let ty = &ast.ident;
     let hname = fns.name.unwrap_or_else(|| to_header_name(&ty.to_string()));
     let hname_ident = Ident::new(&hname, Span::call_site());
-    let dummy_const = Ident::new(&format!("_IMPL_HEADER_FOR_{}", hname), Span::call_site());
     let impl_block = quote! {
         impl __hc::Header for #ty {
             fn name() -> &'static __hc::HeaderName {
@@ -52,8 +72,9 @@ fn impl_header(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
     };
 
     quote! {
-        const #dummy_const: () = {
+        const _: () = {
             extern crate headers_core as __hc;
+            #extern_headers
             #impl_block
         };
     }
@@ -63,20 +84,16 @@ struct Fns {
     encode: proc_macro2::TokenStream,
     decode: proc_macro2::TokenStream,
     name: Option<String>,
+    csv: bool,
 }
 
 fn impl_fns(ast: &syn::DeriveInput) -> Result<Fns, String> {
     let ty = &ast.ident;
 
-    // Only structs are allowed...
-    let st = match ast.data {
-        Data::Struct(ref st) => st,
-        _ => return Err("derive(Header) only works on structs".into()),
-    };
-
     // Check attributes for `#[header(...)]` that may influence the code
     // that is generated...
     let mut name = None;
+    let mut csv = false;
     for attr in &ast.attrs {
         if attr.path.segments.len() != 1 {
             continue;
@@ -107,6 +124,12 @@ fn impl_fns(ast: &syn::DeriveInput) -> Result<Fns, String> {
                                 }
                             };
                         }
+                        NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("csv") => {
+                            if csv {
+                                return Err("repeated 'csv' option in #[header] attribute".into());
+                            }
+                            csv = true;
+                        }
                         _ => return Err("illegal option in #[header(..)] attribute".into()),
                     }
                 }
@@ -120,8 +143,39 @@ fn impl_fns(ast: &syn::DeriveInput) -> Result<Fns, String> {
         }
     }
 
-    let decode_res = quote! {
-        ::util::TryFromValues::try_from_values(values)
+    // Only structs and enums are allowed...
+    let st = match ast.data {
+        Data::Struct(ref st) => st,
+        Data::Enum(_) if csv => {
+            return Err("#[header(csv)] only works on structs".into());
+        }
+        Data::Enum(ref en) => {
+            let (decode, encode) = impl_enum_fns(ty, en)?;
+            return Ok(Fns {
+                decode,
+                encode,
+                name,
+                csv,
+            });
+        }
+        _ => return Err("derive(Header) only works on structs and enums".into()),
+    };
+
+    // With `#[header(csv)]`, the field is a `Vec<T>` of list elements,
+    // parsed with `FromStr` and joined again with `Display`. The list is
+    // split by the public `headers::CsvList`, which skips empty elements.
+    let decode_res = if csv {
+        quote! {
+            __headers::CsvList::decode(values).and_then(|csv| {
+                csv.iter()
+                    .map(|elem| elem.parse().map_err(|_| __hc::Error::invalid()))
+                    .collect::<Result<::std::vec::Vec<_>, __hc::Error>>()
+            })
+        }
+    } else {
+        quote! {
+            ::util::TryFromValues::try_from_values(values)
+        }
     };
 
     let (decode, encode_name) = match st.fields {
@@ -172,8 +226,21 @@ fn impl_fns(ast: &syn::DeriveInput) -> Result<Fns, String> {
                 (&self.0)
             }
         };
-        quote! {
-            values.extend(::std::iter::once((#field).into()));
+        if csv {
+            quote! {
+                let joined = #field
+                    .iter()
+                    .map(|elem| elem.to_string())
+                    .collect::<::std::vec::Vec<_>>()
+                    .join(", ");
+                let value = __hc::HeaderValue::from_str(&joined)
+                    .expect("derive(Header) csv elements must be valid HeaderValues");
+                values.extend(::std::iter::once(value));
+            }
+        } else {
+            quote! {
+                values.extend(::std::iter::once((#field).into()));
+            }
         }
     };
 
@@ -181,9 +248,160 @@ fn impl_fns(ast: &syn::DeriveInput) -> Result<Fns, String> {
         decode,
         encode,
         name,
+        csv,
     })
 }
 
+// An enum of tokens, such as `enum Policy { NoReferrer, Origin }`.
+//
+// Each fieldless variant matches its name in kebab-case (`no-referrer`),
+// case-insensitively, unless renamed with `#[header(name = "..")]`. A
+// single `#[header(other)]` tuple variant can hold any other token, which
+// is converted with `From<&str>`.
+fn impl_enum_fns(
+    ty: &Ident,
+    en: &DataEnum,
+) -> Result<(proc_macro2::TokenStream, proc_macro2::TokenStream), String> {
+    let mut decode_arms = Vec::new();
+    let mut encode_arms = Vec::new();
+    let mut other = None;
+
+    for variant in &en.variants {
+        let ident = &variant.ident;
+        let attr = variant_attr(&variant.attrs)?;
+
+        if attr.other {
+            if other.is_some() {
+                return Err("repeated #[header(other)] variant".into());
+            }
+            if attr.name.is_some() {
+                return Err("#[header(other)] variant can't also have a name".into());
+            }
+            match variant.fields {
+                Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => (),
+                _ => return Err("#[header(other)] variant must have exactly one field".into()),
+            }
+            encode_arms.push(quote! {
+                #ty::#ident(ref other) => {
+                    let other: &str = other.as_ref();
+                    __hc::HeaderValue::from_str(other)
+                        .expect("derive(Header) other variant must be a valid HeaderValue")
+                }
+            });
+            other = Some(ident);
+            continue;
+        }
+
+        match variant.fields {
+            Fields::Unit => (),
+            _ => return Err(
+                "derive(Header) only supports fieldless enum variants, besides #[header(other)]"
+                    .into(),
+            ),
+        }
+
+        let token = attr.name.unwrap_or_else(|| to_token(&ident.to_string()));
+        if token.is_empty() || !token.bytes().all(is_tchar) {
+            return Err(format!("header token {:?} isn't a valid token", token));
+        }
+        decode_arms.push(quote! {
+            if s.eq_ignore_ascii_case(#token) {
+                return Ok(#ty::#ident);
+            }
+        });
+        encode_arms.push(quote! {
+            #ty::#ident => __hc::HeaderValue::from_static(#token)
+        });
+    }
+
+    let fallback = match other {
+        Some(ident) => quote! {
+            Ok(#ty::#ident(s.into()))
+        },
+        None => quote! {
+            Err(__hc::Error::invalid())
+        },
+    };
+
+    let decode = quote! {
+        let value = values.next().ok_or_else(__hc::Error::invalid)?;
+        if values.next().is_some() {
+            return Err(__hc::Error::invalid());
+        }
+        let s = value.to_str().map_err(|_| __hc::Error::invalid())?.trim();
+        #(#decode_arms)*
+        #fallback
+    };
+
+    let encode = quote! {
+        let value = match *self {
+            #(#encode_arms,)*
+        };
+        values.extend(::std::iter::once(value));
+    };
+
+    Ok((decode, encode))
+}
+
+struct VariantAttr {
+    name: Option<String>,
+    other: bool,
+}
+
+fn variant_attr(attrs: &[Attribute]) -> Result<VariantAttr, String> {
+    let mut out = VariantAttr {
+        name: None,
+        other: false,
+    };
+    for attr in attrs {
+        if !attr.path.is_ident("header") {
+            continue;
+        }
+
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            _ => return Err("illegal #[header] attribute on enum variant".into()),
+        };
+        for meta in &list.nested {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(ref kv)) if kv.path.is_ident("name") => {
+                    if out.name.is_some() {
+                        return Err("repeated 'name' option in #[header] attribute".into());
+                    }
+                    out.name = match kv.lit {
+                        Lit::Str(ref s) => Some(s.value()),
+                        _ => return Err("illegal literal in #[header(name = ..)] attribute".into()),
+                    };
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("other") => {
+                    out.other = true;
+                }
+                _ => return Err("illegal option in #[header(..)] attribute".into()),
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn to_token(variant_name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in variant_name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            out.push('-');
+        }
+        out.push(c.to_ascii_lowercase());
+    }
+    out
+}
+
+fn is_tchar(b: u8) -> bool {
+    match b {
+        b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' | b'-' | b'.' | b'^' | b'_'
+        | b'`' | b'|' | b'~' => true,
+        _ => b.is_ascii_alphanumeric(),
+    }
+}
+
 fn to_header_name(ty_name: &str) -> String {
     let mut out = String::new();
     let mut first = true;
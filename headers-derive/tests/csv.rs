@@ -0,0 +1,69 @@
+extern crate headers;
+extern crate headers_core;
+#[macro_use]
+extern crate headers_derive;
+extern crate http;
+
+use headers::{HeaderMap, HeaderMapExt};
+
+#[derive(Clone, Debug, PartialEq, Header)]
+#[header(csv, name_const = "ALLOW")]
+struct Methods(Vec<http::Method>);
+
+#[derive(Clone, Debug, PartialEq, Header)]
+#[header(csv, name_const = "VARY")]
+struct Names {
+    names: Vec<String>,
+}
+
+#[test]
+fn decode_merges_fields() {
+    let mut map = HeaderMap::new();
+    map.append("allow", "GET, HEAD".parse().unwrap());
+    map.append("allow", " POST ".parse().unwrap());
+
+    assert_eq!(
+        map.typed_get::<Methods>(),
+        Some(Methods(vec![
+            http::Method::GET,
+            http::Method::HEAD,
+            http::Method::POST,
+        ]))
+    );
+}
+
+#[test]
+fn decode_skips_empty_elements() {
+    let mut map = HeaderMap::new();
+    map.append("vary", "accept,, origin,".parse().unwrap());
+    map.append("vary", "".parse().unwrap());
+
+    assert_eq!(
+        map.typed_get::<Names>(),
+        Some(Names {
+            names: vec!["accept".to_owned(), "origin".to_owned()],
+        })
+    );
+}
+
+#[test]
+fn decode_invalid_element() {
+    let mut map = HeaderMap::new();
+    map.append("allow", "GET, G(E)T".parse().unwrap());
+
+    assert_eq!(map.typed_get::<Methods>(), None);
+}
+
+#[test]
+fn encode_joins_elements() {
+    let mut map = HeaderMap::new();
+    map.typed_insert(Methods(vec![http::Method::GET, http::Method::PUT]));
+    assert_eq!(map["allow"], "GET, PUT");
+
+    let names = Names {
+        names: vec!["accept".to_owned(), "origin".to_owned()],
+    };
+    map.typed_insert(names.clone());
+    assert_eq!(map["vary"], "accept, origin");
+    assert_eq!(map.typed_get::<Names>(), Some(names));
+}
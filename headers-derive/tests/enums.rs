@@ -0,0 +1,86 @@
+extern crate headers;
+extern crate headers_core;
+#[macro_use]
+extern crate headers_derive;
+extern crate http;
+
+use headers::{HeaderMap, HeaderMapExt};
+
+#[derive(Clone, Debug, PartialEq, Header)]
+#[header(name_const = "REFERRER_POLICY")]
+enum Policy {
+    NoReferrer,
+    StrictOriginWhenCrossOrigin,
+    #[header(name = "same-origin")]
+    Same,
+}
+
+#[derive(Clone, Debug, PartialEq, Header)]
+#[header(name_const = "X_FRAME_OPTIONS")]
+enum FrameOptions {
+    Deny,
+    #[header(other)]
+    Other(String),
+}
+
+fn decode<H: headers_core::Header>(values: &[&'static str]) -> Option<H> {
+    let mut map = HeaderMap::new();
+    for val in values {
+        map.append(H::name(), val.parse().unwrap());
+    }
+    map.typed_get()
+}
+
+fn encode<H: headers_core::Header>(header: H) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    map.typed_insert(header);
+    map
+}
+
+#[test]
+fn round_trip() {
+    for policy in vec![
+        Policy::NoReferrer,
+        Policy::StrictOriginWhenCrossOrigin,
+        Policy::Same,
+    ] {
+        let map = encode(policy.clone());
+        assert_eq!(map.typed_get::<Policy>(), Some(policy));
+    }
+
+    assert_eq!(
+        encode(Policy::StrictOriginWhenCrossOrigin)["referrer-policy"],
+        "strict-origin-when-cross-origin"
+    );
+    assert_eq!(encode(Policy::Same)["referrer-policy"], "same-origin");
+}
+
+#[test]
+fn decode_ignores_case() {
+    assert_eq!(decode(&["No-Referrer"]), Some(Policy::NoReferrer));
+    assert_eq!(decode(&[" SAME-ORIGIN "]), Some(Policy::Same));
+    assert_eq!(decode(&["deny"]), Some(FrameOptions::Deny));
+}
+
+#[test]
+fn decode_unknown_variant() {
+    assert_eq!(decode::<Policy>(&["no-referrer-ever"]), None);
+    assert_eq!(decode::<Policy>(&["same"]), None);
+    assert_eq!(decode::<Policy>(&[""]), None);
+
+    assert_eq!(
+        decode(&["allow-from https://example.com"]),
+        Some(FrameOptions::Other(
+            "allow-from https://example.com".to_owned()
+        ))
+    );
+    assert_eq!(
+        encode(FrameOptions::Other("sameorigin".to_owned()))["x-frame-options"],
+        "sameorigin"
+    );
+}
+
+#[test]
+fn decode_multiple_values() {
+    assert_eq!(decode::<Policy>(&["no-referrer", "same-origin"]), None);
+}
@@ -0,0 +1,251 @@
+use std::iter::FromIterator;
+
+use mime::{self, Mime};
+
+use util::csv::encode_list;
+use util::{Quality, QualityValue};
+use HeaderValue;
+
+/// `Accept` header, defined in [RFC7231](http://tools.ietf.org/html/rfc7231#section-5.3.2)
+///
+/// The `Accept` header field can be used by user agents to specify
+/// response media types that are acceptable.  Accept header fields can
+/// be used to indicate that the request is specifically limited to a
+/// small set of desired types, as in the case of a request for an
+/// in-line image
+///
+/// # ABNF
+///
+/// ```text
+/// Accept = #( media-range [ accept-params ] )
+///
+/// media-range    = ( "*/*"
+///                  / ( type "/" "*" )
+///                  / ( type "/" subtype )
+///                  ) *( OWS ";" OWS parameter )
+/// accept-params  = weight *( accept-ext )
+/// accept-ext = OWS ";" OWS token [ "=" ( token / quoted-string ) ]
+/// ```
+///
+/// # Example values
+/// * `audio/*; q=0.2, audio/basic`
+/// * `text/plain; q=0.5, text/html, text/x-dvi; q=0.8, text/x-c`
+///
+/// # Examples
+///
+/// ```
+/// # extern crate headers;
+/// extern crate mime;
+/// use headers::Accept;
+///
+/// let accept = vec![mime::TEXT_HTML, mime::APPLICATION_JSON]
+///     .into_iter()
+///     .collect::<Accept>();
+///
+/// let available = [mime::APPLICATION_JSON];
+/// assert_eq!(accept.negotiate(&available), Some(&mime::APPLICATION_JSON));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Accept(Vec<QualityValue<Mime>>);
+
+impl ::Header for Accept {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::ACCEPT
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let ranges = QualityValue::decode_list(values)?;
+
+        if ranges.is_empty() {
+            return Err(::Error::invalid());
+        }
+        Ok(Accept(ranges))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_list(&self.0)));
+    }
+}
+
+impl Accept {
+    /// Returns an iterator over the media ranges and their weights, in the
+    /// order they were sent.
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a Mime, f32)> + 'a {
+        self.0
+            .iter()
+            .map(|range| (range.value(), range.quality().as_f32()))
+    }
+
+    /// Picks the best of the `available` media types for this request.
+    ///
+    /// Each available type is weighted by the most specific media range
+    /// that matches it, so `text/html` beats `text/*`, which beats `*/*`.
+    /// The type with the highest weight wins, preferring more specific
+    /// matches and then the order of `available` to break ties. Types
+    /// weighted with `q=0` are never returned.
+    pub fn negotiate<'a>(&self, available: &'a [Mime]) -> Option<&'a Mime> {
+        let mut best: Option<(&'a Mime, Quality, Specificity)> = None;
+
+        for mime in available {
+            let (quality, specificity) = match self.weigh(mime) {
+                Some(weight) => weight,
+                None => continue,
+            };
+            if quality == Quality::ZERO {
+                continue;
+            }
+            let better = match best {
+                Some((_, best_q, best_spec)) => (quality, specificity) > (best_q, best_spec),
+                None => true,
+            };
+            if better {
+                best = Some((mime, quality, specificity));
+            }
+        }
+
+        best.map(|(mime, _, _)| mime)
+    }
+
+    /// Finds the weight of the most specific range matching `mime`.
+    fn weigh(&self, mime: &Mime) -> Option<(Quality, Specificity)> {
+        self.0
+            .iter()
+            .filter_map(|range| specificity(range.value(), mime).map(|s| (range.quality(), s)))
+            .max_by_key(|&(_, s)| s)
+    }
+}
+
+// Ordered from least to most specific: a wildcard level, then the number
+// of parameters that had to match.
+type Specificity = (u8, usize);
+
+fn specificity(range: &Mime, mime: &Mime) -> Option<Specificity> {
+    let level = if range.type_() == mime::STAR && range.subtype() == mime::STAR {
+        0
+    } else if range.type_() != mime.type_() {
+        return None;
+    } else if range.subtype() == mime::STAR {
+        1
+    } else if range.subtype() != mime.subtype() || range.suffix() != mime.suffix() {
+        return None;
+    } else {
+        2
+    };
+
+    let mut params = 0;
+    for (name, value) in range.params() {
+        if mime.get_param(name) != Some(value) {
+            return None;
+        }
+        params += 1;
+    }
+
+    Some((level, params))
+}
+
+impl FromIterator<Mime> for Accept {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Mime>,
+    {
+        Accept(iter.into_iter().map(QualityValue::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    fn mimes(strs: &[&str]) -> Vec<Mime> {
+        strs.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn decode_multiple_lines() {
+        let accept = test_decode::<Accept>(&["text/html, text/*;q=0.5", "*/*; q=0.1"]).unwrap();
+        let parsed = accept
+            .iter()
+            .map(|(mime, q)| (mime.to_string(), q))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            parsed,
+            vec![
+                ("text/html".to_owned(), 1.0),
+                ("text/*".to_owned(), 0.5),
+                ("*/*".to_owned(), 0.1),
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_clamps_quality() {
+        let accept = test_decode::<Accept>(&["text/html;q=2.0"]).unwrap();
+        assert_eq!(accept.iter().next().unwrap().1, 1.0);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Accept>(&["not a mime"]), None);
+        assert_eq!(test_decode::<Accept>(&["text/html;q=high"]), None);
+        assert_eq!(test_decode::<Accept>(&[""]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let accept = test_decode::<Accept>(&["text/html", "application/json;q=0.50"]).unwrap();
+        let headers = test_encode(accept);
+        assert_eq!(headers["accept"], "text/html, application/json; q=0.5");
+    }
+
+    #[test]
+    fn negotiate_specificity() {
+        let accept = test_decode::<Accept>(&["*/*;q=0.1, text/*;q=0.5, text/html;q=0.8"]).unwrap();
+
+        let available = mimes(&["image/png", "text/plain", "text/html"]);
+        assert_eq!(accept.negotiate(&available), Some(&available[2]));
+
+        let available = mimes(&["image/png", "text/plain"]);
+        assert_eq!(accept.negotiate(&available), Some(&available[1]));
+
+        let available = mimes(&["image/png"]);
+        assert_eq!(accept.negotiate(&available), Some(&available[0]));
+    }
+
+    #[test]
+    fn negotiate_specific_range_overrides_wildcard() {
+        // text/html is explicitly excluded, even though text/* allows it
+        let accept = test_decode::<Accept>(&["text/*, text/html;q=0"]).unwrap();
+
+        let available = mimes(&["text/html"]);
+        assert_eq!(accept.negotiate(&available), None);
+
+        let available = mimes(&["text/html", "text/plain"]);
+        assert_eq!(accept.negotiate(&available), Some(&available[1]));
+    }
+
+    #[test]
+    fn negotiate_ties() {
+        let accept = test_decode::<Accept>(&["text/*, application/json"]).unwrap();
+        let available = mimes(&["text/plain", "application/json"]);
+        assert_eq!(accept.negotiate(&available), Some(&available[1]));
+
+        let accept = test_decode::<Accept>(&["*/*"]).unwrap();
+        let available = mimes(&["text/plain", "application/json"]);
+        assert_eq!(accept.negotiate(&available), Some(&available[0]));
+    }
+
+    #[test]
+    fn negotiate_params() {
+        let accept = test_decode::<Accept>(&["text/html;level=1, text/html;q=0.2"]).unwrap();
+        let available = mimes(&["text/html", "text/html;level=1"]);
+        assert_eq!(accept.negotiate(&available), Some(&available[1]));
+    }
+
+    #[test]
+    fn negotiate_none() {
+        let accept = test_decode::<Accept>(&["application/json"]).unwrap();
+        assert_eq!(accept.negotiate(&mimes(&["text/html"])), None);
+        assert_eq!(accept.negotiate(&[]), None);
+    }
+}
@@ -0,0 +1,422 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+use util::csv::encode_list;
+use util::{quoted_string, Quality, QualityValue};
+use HeaderValue;
+
+/// `Accept-Encoding` header, defined in
+/// [RFC7231](http://tools.ietf.org/html/rfc7231#section-5.3.4)
+///
+/// The `Accept-Encoding` header field can be used by user agents to
+/// indicate what response content-codings are
+/// acceptable in the response.  An  `identity` token is used as a synonym
+/// for "no encoding" in order to communicate when no encoding is
+/// preferred.
+///
+/// # ABNF
+///
+/// ```text
+/// Accept-Encoding  = #( codings [ weight ] )
+/// codings          = content-coding / "identity" / "*"
+/// ```
+///
+/// # Example values
+/// * `compress, gzip`
+/// * ``
+/// * `*`
+/// * `compress;q=0.5, gzip;q=1`
+/// * `gzip;q=1.0, identity; q=0.5, *;q=0`
+///
+/// # Examples
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{AcceptEncoding, ContentCoding};
+///
+/// let accept_enc = vec![ContentCoding::Brotli, ContentCoding::Gzip]
+///     .into_iter()
+///     .collect::<AcceptEncoding>();
+///
+/// let available = [ContentCoding::Gzip, ContentCoding::Identity];
+/// assert_eq!(accept_enc.best(&available), Some(ContentCoding::Gzip));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AcceptEncoding(Vec<QualityValue<Coding>>);
+
+/// A content-coding, as used by `Accept-Encoding` and `Content-Encoding`.
+///
+/// Codings are case-insensitive, and are always compared that way, so
+/// `Other("ZSTD".into())` equals `Other("zstd".into())`.
+#[derive(Clone, Debug)]
+pub enum ContentCoding {
+    /// `gzip`, which also matches the legacy `x-gzip` alias.
+    Gzip,
+    /// `br`
+    Brotli,
+    /// `deflate`
+    Deflate,
+    /// `identity`, meaning no coding was applied.
+    Identity,
+    /// Any other coding, such as `compress` or `zstd`.
+    Other(String),
+}
+
+// A coding as it appears in `Accept-Encoding`, where `*` is also allowed.
+#[derive(Clone, Debug, PartialEq)]
+enum Coding {
+    Any,
+    Coding(ContentCoding),
+}
+
+impl ::Header for AcceptEncoding {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::ACCEPT_ENCODING
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let mut values = values.peekable();
+        if values.peek().is_none() {
+            return Err(::Error::invalid());
+        }
+
+        QualityValue::decode_list(&mut values).map(AcceptEncoding)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_list(&self.0)));
+    }
+}
+
+impl AcceptEncoding {
+    /// Returns the weight the client gave to a coding, if any.
+    ///
+    /// A coding that isn't listed takes the weight of `*`. If neither is
+    /// present, `None` is returned, even for `identity`.
+    pub fn prefers(&self, coding: &str) -> Option<f32> {
+        self.quality_of(coding).map(|q| q.as_f32())
+    }
+
+    /// Picks the best of the `available` codings for this request.
+    ///
+    /// This follows the rules of
+    /// [RFC7231](http://tools.ietf.org/html/rfc7231#section-5.3.4): codings
+    /// weighted `q=0` are never returned, `*` matches anything not listed
+    /// explicitly, and `identity` is acceptable unless it has been
+    /// excluded, either directly or with `*;q=0`. Unlisted `identity` is
+    /// only picked when nothing else is acceptable. Ties go to the earlier
+    /// entry in `available`.
+    pub fn best(&self, available: &[ContentCoding]) -> Option<ContentCoding> {
+        let mut best: Option<(&ContentCoding, Quality)> = None;
+        let mut identity = None;
+
+        for coding in available {
+            let q = match self.quality_of(coding.as_str()) {
+                Some(q) => q,
+                None => {
+                    if *coding == ContentCoding::Identity && identity.is_none() {
+                        identity = Some(coding);
+                    }
+                    continue;
+                }
+            };
+            let better = match best {
+                Some((_, best_q)) => q > best_q,
+                None => true,
+            };
+            if q > Quality::ZERO && better {
+                best = Some((coding, q));
+            }
+        }
+
+        best.map(|(coding, _)| coding).or(identity).cloned()
+    }
+
+    fn quality_of(&self, coding: &str) -> Option<Quality> {
+        let mut any = None;
+        for value in &self.0 {
+            match *value.value() {
+                Coding::Coding(ref c) if c.as_str().eq_ignore_ascii_case(coding) => {
+                    return Some(value.quality());
+                }
+                Coding::Any => any = any.or(Some(value.quality())),
+                Coding::Coding(_) => (),
+            }
+        }
+        any
+    }
+}
+
+/// `Other` codings that aren't valid tokens are skipped.
+impl FromIterator<ContentCoding> for AcceptEncoding {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = ContentCoding>,
+    {
+        AcceptEncoding(
+            iter.into_iter()
+                .filter(ContentCoding::is_valid)
+                .map(|coding| QualityValue::from(Coding::Coding(coding)))
+                .collect(),
+        )
+    }
+}
+
+impl FromStr for Coding {
+    type Err = ::Error;
+
+    fn from_str(s: &str) -> Result<Coding, ::Error> {
+        if s == "*" {
+            Ok(Coding::Any)
+        } else {
+            s.parse().map(Coding::Coding)
+        }
+    }
+}
+
+impl fmt::Display for Coding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Coding::Any => f.write_str("*"),
+            Coding::Coding(ref coding) => fmt::Display::fmt(coding, f),
+        }
+    }
+}
+
+// ===== impl ContentCoding =====
+
+impl ContentCoding {
+    /// Returns the name of this coding, as it would appear in a header.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            ContentCoding::Gzip => "gzip",
+            ContentCoding::Brotli => "br",
+            ContentCoding::Deflate => "deflate",
+            ContentCoding::Identity => "identity",
+            ContentCoding::Other(ref s) => s,
+        }
+    }
+
+    // `Other` is public, so it can hold anything, not just a token.
+    fn is_valid(&self) -> bool {
+        match *self {
+            ContentCoding::Other(ref s) => s != "*" && quoted_string::is_token(s),
+            _ => true,
+        }
+    }
+}
+
+impl FromStr for ContentCoding {
+    type Err = ::Error;
+
+    fn from_str(s: &str) -> Result<ContentCoding, ::Error> {
+        let coding = match s {
+            _ if s.eq_ignore_ascii_case("gzip") || s.eq_ignore_ascii_case("x-gzip") => {
+                ContentCoding::Gzip
+            }
+            _ if s.eq_ignore_ascii_case("br") => ContentCoding::Brotli,
+            _ if s.eq_ignore_ascii_case("deflate") => ContentCoding::Deflate,
+            _ if s.eq_ignore_ascii_case("identity") => ContentCoding::Identity,
+            // `*` is only meaningful in `Accept-Encoding`, it's not a coding.
+            "*" => return Err(::Error::invalid()),
+            _ if quoted_string::is_token(s) => ContentCoding::Other(s.to_ascii_lowercase()),
+            _ => return Err(::Error::invalid()),
+        };
+        Ok(coding)
+    }
+}
+
+impl fmt::Display for ContentCoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq for ContentCoding {
+    fn eq(&self, other: &ContentCoding) -> bool {
+        self.as_str().eq_ignore_ascii_case(other.as_str())
+    }
+}
+
+impl Eq for ContentCoding {}
+
+impl Hash for ContentCoding {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for b in self.as_str().bytes() {
+            state.write_u8(b.to_ascii_lowercase());
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a HeaderValue> for ContentCoding {
+    type Error = ::Error;
+
+    fn try_from(value: &'a HeaderValue) -> Result<ContentCoding, ::Error> {
+        value
+            .to_str()
+            .map_err(|_| ::Error::invalid())
+            .and_then(|s| s.trim().parse())
+    }
+}
+
+impl TryFrom<ContentCoding> for HeaderValue {
+    type Error = ::Error;
+
+    /// Fails if an `Other` coding isn't a valid token.
+    fn try_from(coding: ContentCoding) -> Result<HeaderValue, ::Error> {
+        if !coding.is_valid() {
+            return Err(::Error::invalid());
+        }
+        HeaderValue::from_str(coding.as_str()).map_err(|_| ::Error::invalid())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let accept = test_decode::<AcceptEncoding>(&["gzip, br;q=0.5", "*;q=0.1"]).unwrap();
+        assert_eq!(accept.prefers("gzip"), Some(1.0));
+        assert_eq!(accept.prefers("BR"), Some(0.5));
+        assert_eq!(accept.prefers("zstd"), Some(0.1));
+    }
+
+    #[test]
+    fn decode_empty() {
+        let accept = test_decode::<AcceptEncoding>(&[""]).unwrap();
+        assert_eq!(accept.prefers("gzip"), None);
+        assert_eq!(
+            accept.best(&[ContentCoding::Gzip, ContentCoding::Identity]),
+            Some(ContentCoding::Identity)
+        );
+
+        assert_eq!(test_decode::<AcceptEncoding>(&[]), None);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<AcceptEncoding>(&["gzip;q=foo"]), None);
+        assert_eq!(test_decode::<AcceptEncoding>(&["g z i p"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let accept = test_decode::<AcceptEncoding>(&["GZIP, br;q=0.5", "*;q=0"]).unwrap();
+        let headers = test_encode(accept);
+        assert_eq!(headers["accept-encoding"], "gzip, br; q=0.5, *; q=0");
+    }
+
+    #[test]
+    fn best_by_weight() {
+        let accept = test_decode::<AcceptEncoding>(&["gzip;q=0.8, br"]).unwrap();
+        let available = [ContentCoding::Gzip, ContentCoding::Brotli];
+        assert_eq!(accept.best(&available), Some(ContentCoding::Brotli));
+
+        let accept = test_decode::<AcceptEncoding>(&["gzip, br"]).unwrap();
+        assert_eq!(accept.best(&available), Some(ContentCoding::Gzip));
+    }
+
+    #[test]
+    fn best_identity() {
+        // unlisted identity is acceptable, but only as a last resort
+        let accept = test_decode::<AcceptEncoding>(&["gzip;q=0.5"]).unwrap();
+        let available = [ContentCoding::Identity, ContentCoding::Gzip];
+        assert_eq!(accept.best(&available), Some(ContentCoding::Gzip));
+        assert_eq!(
+            accept.best(&[ContentCoding::Identity, ContentCoding::Brotli]),
+            Some(ContentCoding::Identity)
+        );
+
+        let accept = test_decode::<AcceptEncoding>(&["gzip;q=0, identity;q=0"]).unwrap();
+        assert_eq!(accept.best(&available), None);
+    }
+
+    #[test]
+    fn best_wildcard() {
+        let accept = test_decode::<AcceptEncoding>(&["*;q=0.5, gzip;q=0"]).unwrap();
+        let available = [ContentCoding::Gzip, ContentCoding::Brotli];
+        assert_eq!(accept.best(&available), Some(ContentCoding::Brotli));
+
+        // `*;q=0` forbids identity, unless it's listed
+        let accept = test_decode::<AcceptEncoding>(&["*;q=0"]).unwrap();
+        assert_eq!(accept.best(&[ContentCoding::Identity]), None);
+
+        let accept = test_decode::<AcceptEncoding>(&["identity, *;q=0"]).unwrap();
+        assert_eq!(
+            accept.best(&[ContentCoding::Gzip, ContentCoding::Identity]),
+            Some(ContentCoding::Identity)
+        );
+    }
+
+    #[test]
+    fn content_coding_round_trip() {
+        for &(s, ref coding) in &[
+            ("gzip", ContentCoding::Gzip),
+            ("br", ContentCoding::Brotli),
+            ("deflate", ContentCoding::Deflate),
+            ("identity", ContentCoding::Identity),
+            ("zstd", ContentCoding::Other("zstd".into())),
+        ] {
+            let value = HeaderValue::try_from(coding.clone()).unwrap();
+            assert_eq!(value, s);
+            assert_eq!(ContentCoding::try_from(&value).unwrap(), *coding);
+        }
+
+        assert_eq!(
+            ContentCoding::try_from(&HeaderValue::from_static("x-gzip")).unwrap(),
+            ContentCoding::Gzip
+        );
+        assert!(ContentCoding::try_from(&HeaderValue::from_static("*")).is_err());
+        assert!(ContentCoding::try_from(&HeaderValue::from_static("")).is_err());
+    }
+
+    #[test]
+    fn content_coding_invalid_other() {
+        for s in &["a\nb", "a b", "*", ""] {
+            let coding = ContentCoding::Other(s.to_string());
+            assert!(HeaderValue::try_from(coding.clone()).is_err(), "{:?}", s);
+
+            let accept = vec![coding, ContentCoding::Gzip]
+                .into_iter()
+                .collect::<AcceptEncoding>();
+            let headers = test_encode(accept);
+            assert_eq!(headers["accept-encoding"], "gzip");
+        }
+    }
+
+    #[test]
+    fn content_coding_case_insensitive() {
+        use std::collections::HashSet;
+
+        assert_eq!(
+            ContentCoding::Other("ZSTD".into()),
+            ContentCoding::Other("zstd".into())
+        );
+        assert_ne!(
+            ContentCoding::Other("zstd".into()),
+            ContentCoding::Other("compress".into())
+        );
+
+        let set = vec![
+            ContentCoding::Other("Zstd".into()),
+            ContentCoding::Other("zstd".into()),
+        ]
+        .into_iter()
+        .collect::<HashSet<_>>();
+        assert_eq!(set.len(), 1);
+
+        let accept = vec![ContentCoding::Other("ZSTD".into())]
+            .into_iter()
+            .collect::<AcceptEncoding>();
+        assert_eq!(
+            accept.best(&[ContentCoding::Other("zstd".into())]),
+            Some(ContentCoding::Other("zstd".into()))
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+use util::csv::encode_list;
+use util::{Quality, QualityValue};
+use HeaderValue;
+
+/// `Accept-Language` header, defined in
+/// [RFC7231](http://tools.ietf.org/html/rfc7231#section-5.3.5)
+///
+/// The `Accept-Language` header field can be used by user agents to
+/// indicate the set of natural languages that are preferred in the
+/// response.
+///
+/// # ABNF
+///
+/// ```text
+/// Accept-Language = 1#( language-range [ weight ] )
+/// language-range  = <language-range, see [RFC4647], Section 2.1>
+/// ```
+///
+/// # Example values
+/// * `da, en-gb;q=0.8, en;q=0.7`
+/// * `en-us;q=1.0, en;q=0.5, fr`
+///
+/// # Examples
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{AcceptLanguage, LanguageTag};
+///
+/// let accept_lang = vec!["da".parse::<LanguageTag>().unwrap()]
+///     .into_iter()
+///     .collect::<AcceptLanguage>();
+///
+/// let available = ["en".parse().unwrap(), "da-DK".parse().unwrap()];
+/// assert_eq!(accept_lang.matches(&available), Some(available[1].clone()));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AcceptLanguage(Vec<QualityValue<LanguageRange>>);
+
+/// A language tag, such as `en` or `en-US`, as defined in
+/// [RFC5646](https://tools.ietf.org/html/rfc5646).
+///
+/// Only the general shape of the tag is checked: one to eight letters,
+/// followed by any number of `-` separated subtags of one to eight
+/// letters or digits. Tags are compared case-insensitively.
+#[derive(Clone, Debug)]
+pub struct LanguageTag(String);
+
+// A language-range from RFC4647, which is a tag or the `*` wildcard.
+#[derive(Clone, Debug, PartialEq)]
+enum LanguageRange {
+    Any,
+    Tag(LanguageTag),
+}
+
+impl ::Header for AcceptLanguage {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::ACCEPT_LANGUAGE
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let ranges = QualityValue::decode_list(values)?;
+
+        if ranges.is_empty() {
+            return Err(::Error::invalid());
+        }
+        Ok(AcceptLanguage(ranges))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_list(&self.0)));
+    }
+}
+
+impl AcceptLanguage {
+    /// Returns an iterator over the language tags and their weights, in the
+    /// order they were sent.
+    ///
+    /// The `*` wildcard isn't a language tag, and is skipped.
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a LanguageTag, f32)> + 'a {
+        self.0.iter().filter_map(|range| match *range.value() {
+            LanguageRange::Tag(ref tag) => Some((tag, range.quality().as_f32())),
+            LanguageRange::Any => None,
+        })
+    }
+
+    /// Picks the best of the `available` languages for this request.
+    ///
+    /// This uses the "basic filtering" of
+    /// [RFC4647](https://tools.ietf.org/html/rfc4647#section-3.3.1), where a
+    /// range matches a tag if it is equal to it, or a prefix of it ending
+    /// at a `-`. So `en` matches `en-US`, but `en-US` doesn't match `en`.
+    /// `*` matches any tag.
+    ///
+    /// Each available language is weighted by the most specific range
+    /// that matches it, languages weighted `q=0` are never returned, and
+    /// ties go to the earlier entry in `available`.
+    pub fn matches(&self, available: &[LanguageTag]) -> Option<LanguageTag> {
+        let mut best: Option<(&LanguageTag, Quality)> = None;
+
+        for tag in available {
+            let weight = self
+                .0
+                .iter()
+                .filter_map(|range| range.value().specificity(tag).map(|s| (s, range.quality())))
+                .max_by_key(|&(s, _)| s);
+
+            let q = match weight {
+                Some((_, q)) if q > Quality::ZERO => q,
+                _ => continue,
+            };
+            let better = match best {
+                Some((_, best_q)) => q > best_q,
+                None => true,
+            };
+            if better {
+                best = Some((tag, q));
+            }
+        }
+
+        best.map(|(tag, _)| tag.clone())
+    }
+}
+
+impl FromIterator<LanguageTag> for AcceptLanguage {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = LanguageTag>,
+    {
+        AcceptLanguage(
+            iter.into_iter()
+                .map(|tag| QualityValue::from(LanguageRange::Tag(tag)))
+                .collect(),
+        )
+    }
+}
+
+impl LanguageRange {
+    // How specific this range is for `tag`, if it matches at all.
+    fn specificity(&self, tag: &LanguageTag) -> Option<usize> {
+        match *self {
+            LanguageRange::Any => Some(0),
+            LanguageRange::Tag(ref range) => {
+                let range = range.as_str();
+                let tag = tag.as_str();
+                if tag.len() < range.len() || !tag[..range.len()].eq_ignore_ascii_case(range) {
+                    return None;
+                }
+                if tag.len() == range.len() || tag.as_bytes()[range.len()] == b'-' {
+                    Some(range.len())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for LanguageRange {
+    type Err = ::Error;
+
+    fn from_str(s: &str) -> Result<LanguageRange, ::Error> {
+        if s == "*" {
+            Ok(LanguageRange::Any)
+        } else {
+            s.parse().map(LanguageRange::Tag)
+        }
+    }
+}
+
+impl fmt::Display for LanguageRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LanguageRange::Any => f.write_str("*"),
+            LanguageRange::Tag(ref tag) => fmt::Display::fmt(tag, f),
+        }
+    }
+}
+
+// ===== impl LanguageTag =====
+
+impl LanguageTag {
+    /// Returns the tag as it was given.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the primary language subtag, such as `en` for `en-US`.
+    pub fn primary_language(&self) -> &str {
+        self.0.split('-').next().unwrap_or("")
+    }
+}
+
+impl FromStr for LanguageTag {
+    type Err = ::Error;
+
+    fn from_str(s: &str) -> Result<LanguageTag, ::Error> {
+        let mut subtags = s.split('-');
+        let primary = subtags.next().unwrap_or("");
+        let valid_primary = is_subtag(primary) && primary.bytes().all(|b| b.is_ascii_alphabetic());
+        if valid_primary && subtags.all(is_subtag) {
+            Ok(LanguageTag(s.to_owned()))
+        } else {
+            Err(::Error::invalid())
+        }
+    }
+}
+
+fn is_subtag(s: &str) -> bool {
+    !s.is_empty() && s.len() <= 8 && s.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+impl fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq for LanguageTag {
+    fn eq(&self, other: &LanguageTag) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for LanguageTag {}
+
+impl Hash for LanguageTag {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for b in self.0.bytes() {
+            state.write_u8(b.to_ascii_lowercase());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    fn tags(strs: &[&str]) -> Vec<LanguageTag> {
+        strs.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn decode() {
+        let accept =
+            test_decode::<AcceptLanguage>(&["da, en-gb;q=0.8", "en;q=0.7, *;q=0.1"]).unwrap();
+        let parsed = accept
+            .iter()
+            .map(|(tag, q)| (tag.as_str(), q))
+            .collect::<Vec<_>>();
+        assert_eq!(parsed, vec![("da", 1.0), ("en-gb", 0.8), ("en", 0.7)]);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<AcceptLanguage>(&[""]), None);
+        assert_eq!(test_decode::<AcceptLanguage>(&["en_US"]), None);
+        assert_eq!(test_decode::<AcceptLanguage>(&["toolonglanguage"]), None);
+        assert_eq!(test_decode::<AcceptLanguage>(&["en-"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let accept = test_decode::<AcceptLanguage>(&["da, en-GB;q=0.80", "*;q=0"]).unwrap();
+        let headers = test_encode(accept);
+        assert_eq!(headers["accept-language"], "da, en-GB; q=0.8, *; q=0");
+    }
+
+    #[test]
+    fn matches_prefix() {
+        let accept = test_decode::<AcceptLanguage>(&["en"]).unwrap();
+        let available = tags(&["fr", "en-US"]);
+        assert_eq!(accept.matches(&available), Some(available[1].clone()));
+
+        let accept = test_decode::<AcceptLanguage>(&["en-US"]).unwrap();
+        assert_eq!(accept.matches(&tags(&["en"])), None);
+        assert_eq!(accept.matches(&tags(&["en-USA"])), None);
+    }
+
+    #[test]
+    fn matches_case_insensitive() {
+        let accept = test_decode::<AcceptLanguage>(&["EN-gb"]).unwrap();
+        let available = tags(&["en-GB"]);
+        assert_eq!(accept.matches(&available), Some(available[0].clone()));
+        assert_eq!(available[0], "en-gb".parse::<LanguageTag>().unwrap());
+    }
+
+    #[test]
+    fn matches_weights() {
+        let accept = test_decode::<AcceptLanguage>(&["da, en-gb;q=0.8, en;q=0.7"]).unwrap();
+        let available = tags(&["en-US", "en-GB"]);
+        assert_eq!(accept.matches(&available), Some(available[1].clone()));
+
+        let available = tags(&["en-US", "da"]);
+        assert_eq!(accept.matches(&available), Some(available[1].clone()));
+    }
+
+    #[test]
+    fn matches_wildcard_and_exclusion() {
+        let accept = test_decode::<AcceptLanguage>(&["fr, *;q=0.5, de;q=0"]).unwrap();
+        let available = tags(&["de", "es"]);
+        assert_eq!(accept.matches(&available), Some(available[1].clone()));
+        assert_eq!(accept.matches(&tags(&["de-AT"])), None);
+
+        let accept = test_decode::<AcceptLanguage>(&["en, en-GB;q=0"]).unwrap();
+        let available = tags(&["en-GB", "en-US"]);
+        assert_eq!(accept.matches(&available), Some(available[1].clone()));
+    }
+
+    #[test]
+    fn language_tag() {
+        let tag = "zh-Hant-TW".parse::<LanguageTag>().unwrap();
+        assert_eq!(tag.primary_language(), "zh");
+        assert_eq!(tag.to_string(), "zh-Hant-TW");
+        assert!("1en".parse::<LanguageTag>().is_err());
+        assert!("*".parse::<LanguageTag>().is_err());
+    }
+}
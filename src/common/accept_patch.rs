@@ -0,0 +1,147 @@
+use std::iter::FromIterator;
+
+use mime::Mime;
+
+use util::{FlatCsv, TryFromValues};
+use HeaderValue;
+
+static ACCEPT_PATCH: ::HeaderName = ::HeaderName::from_static("accept-patch");
+
+/// `Accept-Patch` header, defined in
+/// [RFC5789](https://tools.ietf.org/html/rfc5789#section-3.1)
+///
+/// The `Accept-Patch` response header lists the media types of patch
+/// documents the server accepts in a `PATCH` request to the resource.
+///
+/// # ABNF
+///
+/// ```text
+/// Accept-Patch = 1#media-type
+/// ```
+///
+/// # Example values
+///
+/// * `application/example, text/example`
+/// * `application/json-patch+json, application/merge-patch+json`
+/// * `text/example;charset=utf-8`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate mime;
+/// use headers::AcceptPatch;
+///
+/// let json_patch = "application/json-patch+json".parse::<mime::Mime>().unwrap();
+/// let accept_patch = vec![json_patch.clone()]
+///     .into_iter()
+///     .collect::<AcceptPatch>();
+///
+/// assert!(accept_patch.contains(&json_patch));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AcceptPatch(Vec<Mime>);
+
+impl ::Header for AcceptPatch {
+    fn name() -> &'static ::HeaderName {
+        &ACCEPT_PATCH
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        let mimes = csv
+            .iter()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<Mime>().map_err(|_| ::Error::invalid()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if mimes.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(AcceptPatch(mimes))
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let s = self
+            .0
+            .iter()
+            .map(|mime| mime.as_ref())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let value = HeaderValue::from_str(&s).expect("Mime is always a valid HeaderValue");
+        values.extend(::std::iter::once(value));
+    }
+}
+
+impl AcceptPatch {
+    /// Returns an iterator over the accepted media types, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Mime> {
+        self.0.iter()
+    }
+
+    /// Check if a media type is accepted.
+    ///
+    /// Types and parameter names are compared case-insensitively, as is
+    /// the `charset` parameter's value.
+    pub fn contains(&self, mime: &Mime) -> bool {
+        self.0.iter().any(|accepted| accepted == mime)
+    }
+}
+
+impl FromIterator<Mime> for AcceptPatch {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Mime>,
+    {
+        AcceptPatch(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_merges_lines() {
+        let accept_patch = test_decode::<AcceptPatch>(&[
+            "application/json-patch+json",
+            "application/merge-patch+json, text/example; charset=\"utf-8\"",
+        ])
+        .unwrap();
+
+        let mimes = accept_patch.iter().collect::<Vec<_>>();
+        assert_eq!(mimes.len(), 3);
+        assert_eq!(mimes[0].subtype(), "json-patch");
+        assert_eq!(mimes[0].suffix(), Some(mime::JSON));
+        assert_eq!(mimes[2].get_param(mime::CHARSET), Some(mime::UTF_8));
+
+        assert!(accept_patch.contains(&"Application/Merge-Patch+JSON".parse().unwrap()));
+        assert!(!accept_patch.contains(&mime::APPLICATION_JSON));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<AcceptPatch>(&[""]), None);
+        assert_eq!(
+            test_decode::<AcceptPatch>(&["application/json, json"]),
+            None
+        );
+    }
+
+    #[test]
+    fn encode() {
+        let accept_patch = vec![
+            "application/json-patch+json".parse().unwrap(),
+            mime::TEXT_PLAIN_UTF_8,
+        ]
+        .into_iter()
+        .collect::<AcceptPatch>();
+
+        let headers = test_encode(accept_patch);
+        assert_eq!(
+            headers["accept-patch"],
+            "application/json-patch+json, text/plain; charset=utf-8"
+        );
+    }
+}
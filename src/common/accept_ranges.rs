@@ -31,7 +31,8 @@ pub struct AcceptRanges(FlatCsv);
 
 derive_header! {
     AcceptRanges(_),
-    name: ACCEPT_RANGES
+    name: ACCEPT_RANGES,
+    list
 }
 
 impl AcceptRanges {
@@ -39,4 +40,68 @@ impl AcceptRanges {
     pub fn bytes() -> Self {
         AcceptRanges(::HeaderValue::from_static("bytes").into())
     }
+
+    /// A constructor for `Accept-Ranges: none`, saying that range requests
+    /// aren't supported.
+    pub fn none() -> Self {
+        AcceptRanges(::HeaderValue::from_static("none").into())
+    }
+
+    /// Returns true if `bytes` is one of the accepted range units.
+    pub fn is_bytes(&self) -> bool {
+        self.iter().any(|unit| unit.eq_ignore_ascii_case("bytes"))
+    }
+
+    /// Returns true if this is `Accept-Ranges: none`.
+    pub fn is_none(&self) -> bool {
+        let mut units = self.iter();
+        match (units.next(), units.next()) {
+            (Some(unit), None) => unit.eq_ignore_ascii_case("none"),
+            _ => false,
+        }
+    }
+
+    /// Returns an iterator over the range units, including ones this crate
+    /// doesn't know about.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter(|unit| !unit.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn bytes() {
+        let accept_ranges = test_decode::<AcceptRanges>(&["Bytes"]).unwrap();
+        assert!(accept_ranges.is_bytes());
+        assert!(!accept_ranges.is_none());
+        assert!(AcceptRanges::bytes().is_bytes());
+    }
+
+    #[test]
+    fn none() {
+        let accept_ranges = test_decode::<AcceptRanges>(&["none"]).unwrap();
+        assert!(accept_ranges.is_none());
+        assert!(!accept_ranges.is_bytes());
+        assert_eq!(accept_ranges, AcceptRanges::none());
+    }
+
+    #[test]
+    fn unknown_unit_passes_through() {
+        let accept_ranges = test_decode::<AcceptRanges>(&["pages, bytes"]).unwrap();
+        assert!(accept_ranges.is_bytes());
+        assert_eq!(
+            accept_ranges.iter().collect::<Vec<_>>(),
+            vec!["pages", "bytes"]
+        );
+
+        let accept_ranges = test_decode::<AcceptRanges>(&["pages"]).unwrap();
+        assert!(!accept_ranges.is_bytes());
+        assert!(!accept_ranges.is_none());
+        let headers = test_encode(accept_ranges);
+        assert_eq!(headers["accept-ranges"], "pages");
+    }
 }
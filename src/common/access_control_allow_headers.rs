@@ -30,24 +30,53 @@ use {HeaderName, HeaderValue};
 /// let allow_headers = vec![CACHE_CONTROL, CONTENT_TYPE]
 ///     .into_iter()
 ///     .collect::<AccessControlAllowHeaders>();
+///
+/// assert!(allow_headers.contains(&CONTENT_TYPE));
+/// assert!(!allow_headers.is_any());
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 pub struct AccessControlAllowHeaders(FlatCsv);
 
 derive_header! {
     AccessControlAllowHeaders(_),
-    name: ACCESS_CONTROL_ALLOW_HEADERS
+    name: ACCESS_CONTROL_ALLOW_HEADERS,
+    list
 }
 
 impl AccessControlAllowHeaders {
-    /// Returns an iterator over `HeaderName`s contained within.
+    /// Constructs an `Access-Control-Allow-Headers: *` header.
+    pub fn any() -> AccessControlAllowHeaders {
+        AccessControlAllowHeaders(HeaderValue::from_static("*").into())
+    }
+
+    /// Check if this includes the `*` wildcard.
+    pub fn is_any(&self) -> bool {
+        self.0.iter().any(|s| s == "*")
+    }
+
+    /// Returns an iterator over `HeaderName`s contained within, skipping
+    /// `*`.
     pub fn iter<'a>(&'a self) -> impl Iterator<Item = HeaderName> + 'a {
         self.0
             .iter()
+            .filter(|s| *s != "*")
             .map(|s| s.parse().ok())
             .take_while(|val| val.is_some())
             .filter_map(|val| val)
     }
+
+    /// Check if a header name is listed, ignoring case.
+    ///
+    /// This is false for a header that's only covered by `*`.
+    pub fn contains(&self, name: &HeaderName) -> bool {
+        self.0.iter().any(|s| s.eq_ignore_ascii_case(name.as_str()))
+    }
+
+    /// Check if both allow the same header names, ignoring case, order and
+    /// duplicates.
+    pub fn semantically_eq(&self, other: &AccessControlAllowHeaders) -> bool {
+        self.0.set_eq(&other.0, true)
+    }
 }
 
 impl FromIterator<HeaderName> for AccessControlAllowHeaders {
@@ -89,6 +118,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn merged_lines_and_contains() {
+        let allow_headers =
+            test_decode::<AccessControlAllowHeaders>(&[" Content-Type ,x-token", "*"]).unwrap();
+
+        assert!(allow_headers.is_any());
+        assert!(allow_headers.contains(&::http::header::CONTENT_TYPE));
+        assert!(allow_headers.contains(&HeaderName::from_static("x-token")));
+        assert!(!allow_headers.contains(&::http::header::AUTHORIZATION));
+        assert_eq!(allow_headers.iter().count(), 2);
+        assert!(AccessControlAllowHeaders::any().is_any());
+    }
+
+    #[test]
+    fn semantically_eq() {
+        let a = test_decode::<AccessControlAllowHeaders>(&["X-Token, Content-Type"]).unwrap();
+        let b = test_decode::<AccessControlAllowHeaders>(&["content-type", "x-token"]).unwrap();
+        assert_ne!(a, b);
+        assert!(a.semantically_eq(&b));
+        assert!(!a.semantically_eq(&AccessControlAllowHeaders::any()));
+    }
+
     #[test]
     fn test_with_invalid() {
         let allow_headers = test_decode::<AccessControlAllowHeaders>(&["foo foo, bar"]).unwrap();
@@ -2,6 +2,7 @@ use std::iter::FromIterator;
 
 use http::Method;
 
+use super::allow::canonical_methods;
 use util::FlatCsv;
 
 /// `Access-Control-Allow-Methods` header, part of
@@ -37,13 +38,44 @@ pub struct AccessControlAllowMethods(FlatCsv);
 
 derive_header! {
     AccessControlAllowMethods(_),
-    name: ACCESS_CONTROL_ALLOW_METHODS
+    name: ACCESS_CONTROL_ALLOW_METHODS,
+    list
 }
 
 impl AccessControlAllowMethods {
-    /// Returns an iterator over `Method`s contained within.
+    /// Constructs an `Access-Control-Allow-Methods: *` header.
+    pub fn any() -> AccessControlAllowMethods {
+        AccessControlAllowMethods(::HeaderValue::from_static("*").into())
+    }
+
+    /// Check if this includes the `*` wildcard.
+    pub fn is_any(&self) -> bool {
+        self.0.iter().any(|s| s == "*")
+    }
+
+    /// Returns an iterator over `Method`s contained within, skipping `*`.
     pub fn iter<'a>(&'a self) -> impl Iterator<Item = Method> + 'a {
-        self.0.iter().filter_map(|s| s.parse().ok())
+        self.0
+            .iter()
+            .filter(|s| *s != "*")
+            .filter_map(|s| s.parse().ok())
+    }
+
+    /// Check if a `Method` is listed.
+    ///
+    /// Methods are case-sensitive, and this is false for a method that's
+    /// only covered by `*`.
+    pub fn contains(&self, method: &Method) -> bool {
+        self.0.iter().any(|s| s == method.as_str())
+    }
+
+    /// Returns a copy with the methods in a fixed order and without
+    /// duplicates, as with [`Allow::canonical`](struct.Allow.html#method.canonical).
+    ///
+    /// A `*` is kept, and as it sorts before letters it comes right after
+    /// the well-known methods.
+    pub fn canonical(&self) -> AccessControlAllowMethods {
+        AccessControlAllowMethods(canonical_methods(&self.0))
     }
 }
 
@@ -81,6 +113,32 @@ mod tests {
         assert_eq!(as_vec[1], Method::PUT);
     }
 
+    #[test]
+    fn merged_lines_and_contains() {
+        let allowed = test_decode::<AccessControlAllowMethods>(&["GET , POST", " PATCH"]).unwrap();
+
+        assert!(allowed.contains(&Method::PATCH));
+        assert!(!allowed.contains(&Method::DELETE));
+        assert!(!allowed.is_any());
+        assert_eq!(allowed.iter().count(), 3);
+
+        let any = test_decode::<AccessControlAllowMethods>(&["*"]).unwrap();
+        assert!(any.is_any());
+        assert_eq!(any.iter().next(), None);
+        assert_eq!(any, AccessControlAllowMethods::any());
+    }
+
+    #[test]
+    fn canonical() {
+        let allowed =
+            test_decode::<AccessControlAllowMethods>(&["XMODIFY, PUT", "*, GET, PUT"]).unwrap();
+        let headers = test_encode(allowed.canonical());
+        assert_eq!(
+            headers["access-control-allow-methods"],
+            "GET, PUT, *, XMODIFY"
+        );
+    }
+
     #[test]
     fn from_iter() {
         let allow: AccessControlAllowMethods = vec![Method::GET, Method::PUT].into_iter().collect();
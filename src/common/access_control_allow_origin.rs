@@ -62,6 +62,25 @@ impl AccessControlAllowOrigin {
             _ => None,
         }
     }
+
+    /// Checks if this is `Access-Control-Allow-Origin: *`.
+    pub fn is_wildcard(&self) -> bool {
+        self.0 == OriginOrAny::Any
+    }
+
+    /// Checks if this allows a request's `Origin`.
+    ///
+    /// `*` allows every origin. Otherwise the origins must match, as with
+    /// [`Origin::matches`](struct.Origin.html#method.matches), except that
+    /// `null` allows a `null` request origin, as the literal comparison
+    /// of the CORS check does.
+    pub fn matches(&self, origin: &Origin) -> bool {
+        match self.0 {
+            OriginOrAny::Any => true,
+            OriginOrAny::Origin(ref allowed) if allowed.is_null() => origin.is_null(),
+            OriginOrAny::Origin(ref allowed) => allowed.matches(origin),
+        }
+    }
 }
 
 impl TryFrom<&str> for AccessControlAllowOrigin {
@@ -158,6 +177,25 @@ mod tests {
         assert_eq!(headers["access-control-allow-origin"], "*");
     }
 
+    #[test]
+    fn matches() {
+        let origin = test_decode::<Origin>(&["https://example.com"]).unwrap();
+        let other = test_decode::<Origin>(&["https://example.org"]).unwrap();
+
+        assert!(AccessControlAllowOrigin::ANY.is_wildcard());
+        assert!(AccessControlAllowOrigin::ANY.matches(&origin));
+        assert!(AccessControlAllowOrigin::ANY.matches(&Origin::NULL));
+
+        let allow_origin = AccessControlAllowOrigin::try_from("https://example.com").unwrap();
+        assert!(!allow_origin.is_wildcard());
+        assert!(allow_origin.matches(&origin));
+        assert!(!allow_origin.matches(&other));
+        assert!(!allow_origin.matches(&Origin::NULL));
+
+        assert!(AccessControlAllowOrigin::NULL.matches(&Origin::NULL));
+        assert!(!AccessControlAllowOrigin::NULL.matches(&origin));
+    }
+
     #[test]
     fn null() {
         let allow_origin = test_decode::<AccessControlAllowOrigin>(&["null"]).unwrap();
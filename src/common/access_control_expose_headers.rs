@@ -1,6 +1,6 @@
 use std::iter::FromIterator;
 
-use util::FlatCsv;
+use util::{FlatCsv, TryFromValues};
 use {HeaderName, HeaderValue};
 
 /// `Access-Control-Expose-Headers` header, part of
@@ -30,20 +30,76 @@ use {HeaderName, HeaderValue};
 /// let expose = vec![CONTENT_LENGTH, ETAG]
 ///     .into_iter()
 ///     .collect::<AccessControlExposeHeaders>();
+///
+/// assert!(expose.contains(&ETAG));
+/// assert!(!expose.is_any());
 /// # }
 /// ```
-#[derive(Clone, Debug)]
-pub struct AccessControlExposeHeaders(FlatCsv);
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessControlExposeHeaders {
+    any: bool,
+    names: Vec<HeaderName>,
+}
+
+impl ::Header for AccessControlExposeHeaders {
+    fn name() -> &'static HeaderName {
+        &::http::header::ACCESS_CONTROL_EXPOSE_HEADERS
+    }
 
-derive_header! {
-    AccessControlExposeHeaders(_),
-    name: ACCESS_CONTROL_EXPOSE_HEADERS
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        let mut any = false;
+        let mut names = Vec::new();
+        for s in csv.iter().filter(|s| !s.is_empty()) {
+            if s == "*" {
+                any = true;
+            } else {
+                names.push(s.parse().map_err(|_| ::Error::invalid())?);
+            }
+        }
+        Ok(AccessControlExposeHeaders { any, names })
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let mut elems = Vec::with_capacity(self.names.len() + 1);
+        if self.any {
+            elems.push("*");
+        }
+        elems.extend(self.names.iter().map(HeaderName::as_str));
+        let s = elems.join(", ");
+        let value = HeaderValue::from_str(&s).expect("HeaderNames are valid HeaderValues");
+        values.extend(::std::iter::once(value));
+    }
 }
 
 impl AccessControlExposeHeaders {
-    /// Returns an iterator over `HeaderName`s contained within.
-    pub fn iter<'a>(&'a self) -> impl Iterator<Item = HeaderName> + 'a {
-        self.0.iter().filter_map(|s| s.parse().ok())
+    /// Constructs an `Access-Control-Expose-Headers: *` header.
+    ///
+    /// Browsers only treat `*` as a wildcard for requests without
+    /// credentials.
+    pub fn any() -> AccessControlExposeHeaders {
+        AccessControlExposeHeaders {
+            any: true,
+            names: Vec::new(),
+        }
+    }
+
+    /// Check if this includes the `*` wildcard.
+    pub fn is_any(&self) -> bool {
+        self.any
+    }
+
+    /// Returns an iterator over `HeaderName`s contained within, skipping
+    /// `*`.
+    pub fn iter(&self) -> impl Iterator<Item = &HeaderName> {
+        self.names.iter()
+    }
+
+    /// Check if a header name is listed.
+    ///
+    /// This is false for a header that's only covered by `*`.
+    pub fn contains(&self, name: &HeaderName) -> bool {
+        self.names.contains(name)
     }
 }
 
@@ -52,8 +108,10 @@ impl FromIterator<HeaderName> for AccessControlExposeHeaders {
     where
         I: IntoIterator<Item = HeaderName>,
     {
-        let flat = iter.into_iter().map(HeaderValue::from).collect();
-        AccessControlExposeHeaders(flat)
+        AccessControlExposeHeaders {
+            any: false,
+            names: iter.into_iter().collect(),
+        }
     }
 }
 
@@ -85,4 +143,40 @@ mod tests {
             "cache-control, if-range"
         );
     }
+
+    #[test]
+    fn merged_lines_and_contains() {
+        let expose =
+            test_decode::<AccessControlExposeHeaders>(&[" ETag ,x-token", "", "*"]).unwrap();
+
+        assert!(expose.is_any());
+        assert!(expose.contains(&::http::header::ETAG));
+        assert!(expose.contains(&HeaderName::from_static("x-token")));
+        assert!(!expose.contains(&::http::header::CONTENT_LENGTH));
+        assert_eq!(expose.iter().count(), 2);
+
+        let headers = test_encode(expose);
+        assert_eq!(headers["access-control-expose-headers"], "*, etag, x-token");
+    }
+
+    #[test]
+    fn any() {
+        let headers = test_encode(AccessControlExposeHeaders::any());
+        assert_eq!(headers["access-control-expose-headers"], "*");
+        assert!(test_decode::<AccessControlExposeHeaders>(&["*"])
+            .unwrap()
+            .is_any());
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(
+            test_decode::<AccessControlExposeHeaders>(&["foo foo, bar"]),
+            None
+        );
+        assert_eq!(
+            test_decode::<AccessControlExposeHeaders>(&["*", "a:b"]),
+            None
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
-use util::Seconds;
+use util::IterExt;
+use HeaderValue;
 
 /// `Access-Control-Max-Age` header, part of
 /// [CORS](http://www.w3.org/TR/cors/#access-control-max-age-response-header)
@@ -8,6 +9,10 @@ use util::Seconds;
 /// The `Access-Control-Max-Age` header indicates how long the results of a
 /// preflight request can be cached in a preflight result cache.
 ///
+/// Browsers treat a negative value, usually `-1`, as disabling the cache
+/// altogether, so negative values are accepted and kept as they were
+/// sent.
+///
 /// # ABNF
 ///
 /// ```text
@@ -17,6 +22,7 @@ use util::Seconds;
 /// # Example values
 ///
 /// * `531`
+/// * `-1`
 ///
 /// # Examples
 ///
@@ -26,23 +32,100 @@ use util::Seconds;
 /// use headers::AccessControlMaxAge;
 ///
 /// let max_age = AccessControlMaxAge::from(Duration::from_secs(531));
+/// assert_eq!(max_age.duration(), Some(Duration::from_secs(531)));
+///
+/// let disabled = AccessControlMaxAge::disabled();
+/// assert_eq!(disabled.duration(), None);
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct AccessControlMaxAge(Seconds);
+pub struct AccessControlMaxAge(i64);
+
+impl ::Header for AccessControlMaxAge {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::ACCESS_CONTROL_MAX_AGE
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        values
+            .just_one()
+            .and_then(|value| value.to_str().ok()?.parse().ok())
+            .map(AccessControlMaxAge)
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(self.0.into()));
+    }
+}
+
+impl AccessControlMaxAge {
+    /// Creates an `Access-Control-Max-Age: -1` header, which asks the
+    /// browser not to cache the preflight result at all.
+    pub fn disabled() -> AccessControlMaxAge {
+        AccessControlMaxAge(-1)
+    }
+
+    /// Returns how long the preflight result can be cached, or `None` if
+    /// the value is negative and caching is disabled.
+    pub fn duration(&self) -> Option<Duration> {
+        if self.0 < 0 {
+            None
+        } else {
+            Some(Duration::from_secs(self.0 as u64))
+        }
+    }
 
-derive_header! {
-    AccessControlMaxAge(_),
-    name: ACCESS_CONTROL_MAX_AGE
+    /// Check if the value is negative, disabling the preflight cache.
+    pub fn is_disabled(&self) -> bool {
+        self.0 < 0
+    }
 }
 
+/// Durations longer than `i64::MAX` seconds are clamped.
 impl From<Duration> for AccessControlMaxAge {
     fn from(dur: Duration) -> AccessControlMaxAge {
-        AccessControlMaxAge(dur.into())
+        AccessControlMaxAge(dur.as_secs().min(i64::MAX as u64) as i64)
     }
 }
 
+/// A disabled cache converts to a zero `Duration`.
 impl From<AccessControlMaxAge> for Duration {
     fn from(acma: AccessControlMaxAge) -> Duration {
-        acma.0.into()
+        acma.duration().unwrap_or_else(|| Duration::from_secs(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let max_age = test_decode::<AccessControlMaxAge>(&["531"]).unwrap();
+        assert_eq!(max_age.duration(), Some(Duration::from_secs(531)));
+        assert!(!max_age.is_disabled());
+
+        let max_age = test_decode::<AccessControlMaxAge>(&["-1"]).unwrap();
+        assert_eq!(max_age.duration(), None);
+        assert!(max_age.is_disabled());
+        assert_eq!(Duration::from(max_age), Duration::from_secs(0));
+
+        assert_eq!(test_decode::<AccessControlMaxAge>(&["1.5"]), None);
+        assert_eq!(test_decode::<AccessControlMaxAge>(&["-"]), None);
+        assert_eq!(test_decode::<AccessControlMaxAge>(&["1", "2"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(AccessControlMaxAge::disabled());
+        assert_eq!(headers["access-control-max-age"], "-1");
+
+        let max_age = test_decode::<AccessControlMaxAge>(&["-30"]).unwrap();
+        let headers = test_encode(max_age);
+        assert_eq!(headers["access-control-max-age"], "-30");
+
+        let headers = test_encode(AccessControlMaxAge::from(Duration::from_secs(600)));
+        assert_eq!(headers["access-control-max-age"], "600");
     }
 }
@@ -1,5 +1,6 @@
 use std::iter::FromIterator;
 
+use super::AccessControlAllowHeaders;
 use util::FlatCsv;
 use {HeaderName, HeaderValue};
 
@@ -38,14 +39,54 @@ pub struct AccessControlRequestHeaders(FlatCsv);
 
 derive_header! {
     AccessControlRequestHeaders(_),
-    name: ACCESS_CONTROL_REQUEST_HEADERS
+    name: ACCESS_CONTROL_REQUEST_HEADERS,
+    list
 }
 
 impl AccessControlRequestHeaders {
     /// Returns an iterator over `HeaderName`s contained within.
+    ///
+    /// Like any `HeaderName`, these are lowercased, whatever the case the
+    /// browser sent them in.
     pub fn iter<'a>(&'a self) -> impl Iterator<Item = HeaderName> + 'a {
         self.0.iter().filter_map(|s| s.parse().ok())
     }
+
+    /// Check if every requested header is allowed, so the preflight can
+    /// be approved.
+    ///
+    /// Names are compared ignoring case. A `*` in `allowed` covers every
+    /// header except `Authorization`, which must be listed by name. A
+    /// requested name that isn't a valid `HeaderName` is never allowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// extern crate http;
+    /// use http::header::{CONTENT_TYPE, HeaderName};
+    /// use headers::{AccessControlAllowHeaders, AccessControlRequestHeaders};
+    ///
+    /// let requested = vec![HeaderName::from_static("x-token"), CONTENT_TYPE]
+    ///     .into_iter()
+    ///     .collect::<AccessControlRequestHeaders>();
+    /// let allowed = vec![CONTENT_TYPE]
+    ///     .into_iter()
+    ///     .collect::<AccessControlAllowHeaders>();
+    ///
+    /// assert!(!requested.is_subset_of(&allowed));
+    /// assert!(requested.is_subset_of(&AccessControlAllowHeaders::any()));
+    /// ```
+    pub fn is_subset_of(&self, allowed: &AccessControlAllowHeaders) -> bool {
+        self.0
+            .iter()
+            .filter(|s| !s.is_empty())
+            .all(|s| match s.parse::<HeaderName>() {
+                Ok(ref name) if allowed.contains(name) => true,
+                Ok(name) => allowed.is_any() && name != ::http::header::AUTHORIZATION,
+                Err(_) => false,
+            })
+    }
 }
 
 impl FromIterator<HeaderName> for AccessControlRequestHeaders {
@@ -86,4 +127,43 @@ mod tests {
             "cache-control, if-range"
         );
     }
+
+    #[test]
+    fn iter_lowercases() {
+        let req_headers =
+            test_decode::<AccessControlRequestHeaders>(&["X-Token, Content-TYPE"]).unwrap();
+
+        let as_vec = req_headers.iter().collect::<Vec<_>>();
+        assert_eq!(
+            as_vec,
+            [
+                HeaderName::from_static("x-token"),
+                ::http::header::CONTENT_TYPE
+            ]
+        );
+    }
+
+    #[test]
+    fn is_subset_of() {
+        let allowed = test_decode::<AccessControlAllowHeaders>(&["content-type, X-TOKEN"]).unwrap();
+        let requested = |s: &str| test_decode::<AccessControlRequestHeaders>(&[s]).unwrap();
+
+        assert!(requested("X-Token, Content-Type").is_subset_of(&allowed));
+        assert!(requested("content-type,,").is_subset_of(&allowed));
+        assert!(requested("").is_subset_of(&allowed));
+        assert!(!requested("x-token, x-other").is_subset_of(&allowed));
+        assert!(!requested("x-token, bad name").is_subset_of(&allowed));
+    }
+
+    #[test]
+    fn is_subset_of_any() {
+        let any = AccessControlAllowHeaders::any();
+        let requested = |s: &str| test_decode::<AccessControlRequestHeaders>(&[s]).unwrap();
+
+        assert!(requested("X-Anything, Content-Type").is_subset_of(&any));
+        assert!(!requested("x-token, Authorization").is_subset_of(&any));
+
+        let allowed = test_decode::<AccessControlAllowHeaders>(&["*, authorization"]).unwrap();
+        assert!(requested("x-token, Authorization").is_subset_of(&allowed));
+    }
 }
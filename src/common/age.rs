@@ -1,6 +1,7 @@
 use std::time::Duration;
 
-use util::Seconds;
+use util::{parse_u64, IterExt, Seconds};
+use HeaderValue;
 
 /// `Age` header, defined in [RFC7234](https://tools.ietf.org/html/rfc7234#section-5.1)
 ///
@@ -18,6 +19,9 @@ use util::Seconds;
 /// The Age field-value is a non-negative integer, representing time in
 /// seconds (see [Section 1.2.1](https://tools.ietf.org/html/rfc7234#section-1.2.1)).
 ///
+/// A value greater than 2147483648 seconds (2^31) is clamped to it, as
+/// that section allows.
+///
 /// The presence of an Age header field implies that the response was not
 /// generated or validated by the origin server for this request.
 /// However, lack of an Age header field does not imply the origin was
@@ -39,9 +43,32 @@ use util::Seconds;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Age(Seconds);
 
-derive_header! {
-  Age(_),
-  name: AGE
+impl ::Header for Age {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::AGE
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        values
+            .just_one()
+            .and_then(|value| parse_delta_seconds(value.as_bytes()))
+            .map(Age::from_secs)
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
+}
+
+// The largest value RFC7234 requires recipients to handle.
+const MAX_DELTA_SECONDS: u64 = 1 << 31;
+
+fn parse_delta_seconds(digits: &[u8]) -> Option<u64> {
+    if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    Some(parse_u64(digits).map_or(MAX_DELTA_SECONDS, |n| n.min(MAX_DELTA_SECONDS)))
 }
 
 impl Age {
@@ -56,9 +83,10 @@ impl Age {
     }
 }
 
+/// Any fraction of a second is dropped.
 impl From<Duration> for Age {
     fn from(dur: Duration) -> Self {
-        Age(Seconds::from(dur))
+        Age::from_secs(dur.as_secs())
     }
 }
 
@@ -67,3 +95,41 @@ impl From<Age> for Duration {
         age.0.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn from_duration_truncates() {
+        let age = Age::from(Duration::from_millis(3_999));
+        assert_eq!(age.as_secs(), 3);
+
+        let headers = test_encode(age);
+        assert_eq!(headers["age"], "3");
+    }
+
+    #[test]
+    fn decode_clamps_overflow() {
+        assert_eq!(test_decode::<Age>(&["3600"]), Some(Age::from_secs(3600)));
+        assert_eq!(
+            test_decode::<Age>(&["2147483649"]),
+            Some(Age::from_secs(2_147_483_648))
+        );
+        assert_eq!(
+            test_decode::<Age>(&["99999999999999999999999"]),
+            Some(Age::from_secs(2_147_483_648))
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Age>(&[""]), None);
+        assert_eq!(test_decode::<Age>(&["-1"]), None);
+        assert_eq!(test_decode::<Age>(&["1h"]), None);
+        assert_eq!(test_decode::<Age>(&["1", "2"]), None);
+    }
+
+    bench_header!(bench, Age, "3600");
+}
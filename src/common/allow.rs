@@ -2,7 +2,8 @@ use std::iter::FromIterator;
 
 use http::Method;
 
-use util::FlatCsv;
+use util::{FlatCsv, TryFromValues};
+use HeaderValue;
 
 /// `Allow` header, defined in [RFC7231](http://tools.ietf.org/html/rfc7231#section-7.4.1)
 ///
@@ -33,13 +34,37 @@ use util::FlatCsv;
 /// let allow = vec![Method::GET, Method::POST]
 ///     .into_iter()
 ///     .collect::<Allow>();
+///
+/// assert!(allow.contains(&Method::POST));
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 pub struct Allow(FlatCsv);
 
-derive_header! {
-    Allow(_),
-    name: ALLOW
+impl ::Header for Allow {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::ALLOW
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        let valid = csv
+            .iter()
+            .filter(|s| !s.is_empty())
+            .all(|s| s.parse::<Method>().is_ok());
+        if valid {
+            Ok(Allow(csv))
+        } else {
+            Err(::Error::invalid())
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
+
+    fn encode_to<W: Extend<HeaderValue>>(&self, values: &mut W) {
+        self.0.encode_to(values);
+    }
 }
 
 impl Allow {
@@ -47,6 +72,70 @@ impl Allow {
     pub fn iter<'a>(&'a self) -> impl Iterator<Item = Method> + 'a {
         self.0.iter().filter_map(|s| s.parse().ok())
     }
+
+    /// Check if a `Method` is allowed.
+    ///
+    /// Methods are case-sensitive, so `get` doesn't match `GET`.
+    pub fn contains(&self, method: &Method) -> bool {
+        self.0.iter().any(|s| s == method.as_str())
+    }
+
+    /// Check if both allow the same methods, ignoring order and
+    /// duplicates.
+    ///
+    /// Unlike the other token lists, case still matters, just as it does
+    /// for `contains`.
+    pub fn semantically_eq(&self, other: &Allow) -> bool {
+        self.0.set_eq(&other.0, false)
+    }
+
+    /// Returns a copy with the methods in a fixed order and without
+    /// duplicates, so it always encodes the same way.
+    ///
+    /// The order is `GET`, `HEAD`, `POST`, `PUT`, `DELETE`, `PATCH` and
+    /// `OPTIONS`, and then any other methods alphabetically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// extern crate http;
+    /// use headers::Allow;
+    /// use http::Method;
+    ///
+    /// let allow = vec![Method::OPTIONS, Method::TRACE, Method::GET, Method::OPTIONS]
+    ///     .into_iter()
+    ///     .collect::<Allow>()
+    ///     .canonical();
+    ///
+    /// let methods = allow.iter().collect::<Vec<_>>();
+    /// assert_eq!(methods, vec![Method::GET, Method::OPTIONS, Method::TRACE]);
+    /// ```
+    pub fn canonical(&self) -> Allow {
+        Allow(canonical_methods(&self.0))
+    }
+}
+
+// The order `canonical` puts well-known methods in, before any others.
+const METHOD_ORDER: [&str; 7] = ["GET", "HEAD", "POST", "PUT", "DELETE", "PATCH", "OPTIONS"];
+
+// Sorts and dedups a list of methods for `Allow::canonical` and
+// `AccessControlAllowMethods::canonical`. Methods are case-sensitive, so
+// `get` is kept apart from `GET`.
+pub(super) fn canonical_methods(methods: &FlatCsv) -> FlatCsv {
+    let mut methods = methods.iter().filter(|s| !s.is_empty()).collect::<Vec<_>>();
+    methods.sort_by_key(|&method| {
+        let rank = METHOD_ORDER
+            .iter()
+            .position(|&known| known == method)
+            .unwrap_or(METHOD_ORDER.len());
+        (rank, method)
+    });
+    methods.dedup();
+    methods
+        .into_iter()
+        .map(|method| HeaderValue::from_str(method).expect("checked to be a valid value"))
+        .collect()
 }
 
 impl FromIterator<Method> for Allow {
@@ -59,10 +148,75 @@ impl FromIterator<Method> for Allow {
             .map(|method| {
                 method
                     .as_str()
-                    .parse::<::HeaderValue>()
+                    .parse::<HeaderValue>()
                     .expect("Method is a valid HeaderValue")
             })
             .collect();
         Allow(flat)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn iter_and_contains() {
+        let allow = test_decode::<Allow>(&["GET, HEAD", "PURGE"]).unwrap();
+        assert_eq!(
+            allow.iter().collect::<Vec<_>>(),
+            vec![
+                Method::GET,
+                Method::HEAD,
+                Method::from_bytes(b"PURGE").unwrap()
+            ]
+        );
+        assert!(allow.contains(&Method::HEAD));
+        assert!(!allow.contains(&Method::POST));
+
+        let empty = test_decode::<Allow>(&[""]).unwrap();
+        assert_eq!(empty.iter().next(), None);
+    }
+
+    #[test]
+    fn semantically_eq() {
+        let a = test_decode::<Allow>(&["GET, POST, GET"]).unwrap();
+        let b = test_decode::<Allow>(&["POST", "GET"]).unwrap();
+        assert_ne!(a, b);
+        assert!(a.semantically_eq(&b));
+
+        let lower = test_decode::<Allow>(&["get, post"]).unwrap();
+        assert!(!a.semantically_eq(&lower));
+    }
+
+    #[test]
+    fn decode_invalid_method() {
+        assert_eq!(test_decode::<Allow>(&["GET, (PUT)"]), None);
+        assert_eq!(test_decode::<Allow>(&["GET PUT"]), None);
+    }
+
+    #[test]
+    fn canonical() {
+        let a =
+            test_decode::<Allow>(&["PURGE, OPTIONS, GET", "patch, PATCH, DELETE, GET"]).unwrap();
+        let b = test_decode::<Allow>(&["DELETE, PATCH", "patch, GET, PURGE, OPTIONS"]).unwrap();
+        assert_ne!(a, b);
+        assert_eq!(a.canonical(), b.canonical());
+
+        let headers = test_encode(a.canonical());
+        assert_eq!(
+            headers["allow"],
+            "GET, DELETE, PATCH, OPTIONS, PURGE, patch"
+        );
+    }
+
+    #[test]
+    fn encode_in_order() {
+        let allow = vec![Method::POST, Method::GET, Method::OPTIONS]
+            .into_iter()
+            .collect::<Allow>();
+        let headers = test_encode(allow);
+        assert_eq!(headers["allow"], "POST, GET, OPTIONS");
+    }
+}
@@ -0,0 +1,406 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::time::Duration;
+
+use util::csv::{encode_list, parse_list, split_token, trim_ows};
+use util::quoted_string::{self, Quoted};
+use util::{FlatCsv, Seconds, TryFromValues};
+use HeaderValue;
+
+/// `Alt-Svc` header, defined in [RFC7838](https://tools.ietf.org/html/rfc7838#section-3)
+///
+/// The `Alt-Svc` header field advertises alternative services, such as
+/// the same origin reachable over HTTP/3, that a client may use for future
+/// requests. The special value `clear` invalidates all alternatives the
+/// client has cached for the origin.
+///
+/// # ABNF
+///
+/// ```text
+/// Alt-Svc       = clear / 1#alt-value
+/// clear         = %s"clear"; "clear", case-sensitive
+/// alt-value     = alternative *( OWS ";" OWS parameter )
+/// alternative   = protocol-id "=" alt-authority
+/// protocol-id   = token ; percent-encoded ALPN protocol name
+/// alt-authority = quoted-string ; containing [ uri-host ] ":" port
+/// parameter     = token "=" ( token / quoted-string )
+/// ```
+///
+/// # Example values
+///
+/// * `h3=":443"; ma=86400`
+/// * `h2="alt.example.com:8000", h2=":443"; persist=1`
+/// * `clear`
+///
+/// # Examples
+///
+/// ```
+/// # extern crate headers;
+/// use std::time::Duration;
+/// use headers::{AltService, AltSvc};
+///
+/// let alt_svc = vec![
+///     AltService::new("h3", ":443").with_max_age(Duration::from_secs(3600)),
+/// ]
+/// .into_iter()
+/// .collect::<AltSvc>();
+///
+/// let h3 = alt_svc.alternatives().next().unwrap();
+/// assert_eq!(h3.protocol_id(), "h3");
+/// assert_eq!(h3.port(), 443);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AltSvc(AltSvc_);
+
+#[derive(Clone, Debug, PartialEq)]
+enum AltSvc_ {
+    Clear,
+    Alternatives(Vec<AltService>),
+}
+
+/// A single alternative service in an [`AltSvc`](struct.AltSvc.html)
+/// header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AltService {
+    protocol_id: String,
+    authority: String,
+    max_age: Option<Seconds>,
+    persist: bool,
+}
+
+impl ::Header for AltSvc {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::ALT_SVC
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        let value = csv.to_value();
+        let s = value.to_str().map_err(|_| ::Error::invalid())?;
+        if s.trim() == "clear" {
+            return Ok(AltSvc::clear());
+        }
+
+        parse_list(s, parse_alternative)
+            .map(|alternatives| AltSvc(AltSvc_::Alternatives(alternatives)))
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let value = match self.0 {
+            AltSvc_::Clear => HeaderValue::from_static("clear"),
+            AltSvc_::Alternatives(ref alternatives) => encode_list(alternatives),
+        };
+        values.extend(::std::iter::once(value));
+    }
+}
+
+impl AltSvc {
+    /// Creates `Alt-Svc: clear`, telling the client to forget all
+    /// alternatives for the origin.
+    pub fn clear() -> AltSvc {
+        AltSvc(AltSvc_::Clear)
+    }
+
+    /// Returns true if this is `Alt-Svc: clear`.
+    pub fn is_clear(&self) -> bool {
+        self.0 == AltSvc_::Clear
+    }
+
+    /// Returns an iterator over the alternative services, in order of
+    /// preference.
+    ///
+    /// This is empty for `clear`.
+    pub fn alternatives(&self) -> impl Iterator<Item = &AltService> {
+        let alternatives = match self.0 {
+            AltSvc_::Clear => &[][..],
+            AltSvc_::Alternatives(ref alternatives) => &alternatives[..],
+        };
+        alternatives.iter()
+    }
+}
+
+impl FromIterator<AltService> for AltSvc {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = AltService>,
+    {
+        AltSvc(AltSvc_::Alternatives(iter.into_iter().collect()))
+    }
+}
+
+// ===== impl AltService =====
+
+impl AltService {
+    /// Creates an alternative service for an ALPN protocol, such as `h3`,
+    /// at an authority like `alt.example.com:443`.
+    ///
+    /// The host may be left out, as in `:443`, to mean the origin's host.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `protocol_id` is empty, or `authority` doesn't end with a
+    /// port, or contains control characters.
+    pub fn new<P, A>(protocol_id: P, authority: A) -> AltService
+    where
+        P: Into<String>,
+        A: Into<String>,
+    {
+        let protocol_id = protocol_id.into();
+        let authority = authority.into();
+        assert!(
+            !protocol_id.is_empty(),
+            "invalid Alt-Svc protocol-id: {:?}",
+            protocol_id
+        );
+        assert!(
+            is_authority(&authority),
+            "invalid Alt-Svc alt-authority: {:?}",
+            authority
+        );
+        AltService {
+            protocol_id,
+            authority,
+            max_age: None,
+            persist: false,
+        }
+    }
+
+    /// Sets the `ma` parameter, how long the alternative may be cached.
+    ///
+    /// Sub-second precision is discarded.
+    pub fn with_max_age(mut self, max_age: Duration) -> AltService {
+        self.max_age = Some(Seconds::from_secs(max_age.as_secs()));
+        self
+    }
+
+    /// Sets `persist=1`, asking the client to keep the alternative even
+    /// when its network changes.
+    pub fn with_persist(mut self) -> AltService {
+        self.persist = true;
+        self
+    }
+
+    /// Returns the ALPN protocol name, with any percent-encoding decoded.
+    pub fn protocol_id(&self) -> &str {
+        &self.protocol_id
+    }
+
+    /// Returns the alternative authority, such as `alt.example.com:443`
+    /// or `:443`.
+    pub fn authority(&self) -> &str {
+        &self.authority
+    }
+
+    /// Returns the host of the alternative, or `None` if it's the same as
+    /// the origin's.
+    pub fn host(&self) -> Option<&str> {
+        let host = &self.authority[..self.colon()];
+        if host.is_empty() {
+            None
+        } else {
+            Some(host)
+        }
+    }
+
+    /// Returns the port of the alternative.
+    pub fn port(&self) -> u16 {
+        self.authority[self.colon() + 1..]
+            .parse()
+            .expect("authority is validated to end with a port")
+    }
+
+    /// Returns how long the alternative may be cached, which defaults to
+    /// 24 hours when the `ma` parameter isn't sent.
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+            .map(Duration::from)
+            .unwrap_or_else(|| Duration::from_secs(24 * 60 * 60))
+    }
+
+    /// Returns true if `persist=1` was sent.
+    pub fn persist(&self) -> bool {
+        self.persist
+    }
+
+    fn colon(&self) -> usize {
+        self.authority
+            .rfind(':')
+            .expect("authority is validated to contain a port")
+    }
+}
+
+impl fmt::Display for AltService {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &b in self.protocol_id.as_bytes() {
+            if quoted_string::is_tchar(b) && b != b'%' {
+                write!(f, "{}", b as char)?;
+            } else {
+                write!(f, "%{:02X}", b)?;
+            }
+        }
+        write!(f, "={}", Quoted(&self.authority))?;
+        if let Some(max_age) = self.max_age {
+            write!(f, "; ma={}", max_age)?;
+        }
+        if self.persist {
+            f.write_str("; persist=1")?;
+        }
+        Ok(())
+    }
+}
+
+fn is_authority(s: &str) -> bool {
+    match s.rfind(':') {
+        Some(colon) => {
+            s[colon + 1..].parse::<u16>().is_ok()
+                && s[..colon]
+                    .bytes()
+                    .all(|b| b.is_ascii_graphic() && b != b'"' && b != b'\\')
+        }
+        None => false,
+    }
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let mut out = Vec::with_capacity(s.len());
+    let mut bytes = s.bytes();
+    while let Some(b) = bytes.next() {
+        if b == b'%' {
+            let hi = (bytes.next()? as char).to_digit(16)?;
+            let lo = (bytes.next()? as char).to_digit(16)?;
+            out.push((hi << 4 | lo) as u8);
+        } else {
+            out.push(b);
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+// Parses one alt-value from the start of `s`.
+fn parse_alternative(s: &str) -> Option<(AltService, &str)> {
+    let (protocol_id, rest) = split_token(s)?;
+    let protocol_id = percent_decode(protocol_id)?;
+    if protocol_id.is_empty() || !rest.starts_with('=') {
+        return None;
+    }
+    let (authority, mut rest) = quoted_string::parse(&rest[1..])?;
+    if !is_authority(&authority) {
+        return None;
+    }
+
+    let mut alternative = AltService {
+        protocol_id,
+        authority,
+        max_age: None,
+        persist: false,
+    };
+    loop {
+        rest = trim_ows(rest);
+        if !rest.starts_with(';') {
+            break;
+        }
+        let (name, after) = split_token(trim_ows(&rest[1..]))?;
+        let after = trim_ows(after);
+        if !after.starts_with('=') {
+            return None;
+        }
+        let after = trim_ows(&after[1..]);
+        let (value, after) = if after.starts_with('"') {
+            quoted_string::parse(after)?
+        } else {
+            let (value, after) = split_token(after)?;
+            (value.to_owned(), after)
+        };
+
+        // Unknown parameters, and invalid values, are ignored.
+        if name.eq_ignore_ascii_case("ma") {
+            if let Ok(secs) = value.parse() {
+                alternative.max_age = Some(Seconds::from_secs(secs));
+            }
+        } else if name.eq_ignore_ascii_case("persist") {
+            alternative.persist = value == "1";
+        }
+        rest = after;
+    }
+
+    Some((alternative, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let alt_svc =
+            test_decode::<AltSvc>(&[r#"h3=":443"; ma=3600, h2="alt.example.com:8000"; persist=1"#])
+                .unwrap();
+        let alternatives = alt_svc.alternatives().collect::<Vec<_>>();
+        assert_eq!(alternatives.len(), 2);
+
+        assert_eq!(alternatives[0].protocol_id(), "h3");
+        assert_eq!(alternatives[0].host(), None);
+        assert_eq!(alternatives[0].port(), 443);
+        assert_eq!(alternatives[0].max_age(), Duration::from_secs(3600));
+        assert!(!alternatives[0].persist());
+
+        assert_eq!(alternatives[1].authority(), "alt.example.com:8000");
+        assert_eq!(alternatives[1].host(), Some("alt.example.com"));
+        assert_eq!(alternatives[1].max_age(), Duration::from_secs(86400));
+        assert!(alternatives[1].persist());
+    }
+
+    #[test]
+    fn decode_escapes() {
+        let alt_svc =
+            test_decode::<AltSvc>(&[r#"w%3Dx%25y="\[::1\]:443" ; ma="60"; foo=bar"#]).unwrap();
+        let alternative = alt_svc.alternatives().next().unwrap();
+        assert_eq!(alternative.protocol_id(), "w=x%y");
+        assert_eq!(alternative.authority(), "[::1]:443");
+        assert_eq!(alternative.host(), Some("[::1]"));
+        assert_eq!(alternative.max_age(), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn decode_clear() {
+        let alt_svc = test_decode::<AltSvc>(&["clear"]).unwrap();
+        assert!(alt_svc.is_clear());
+        assert_eq!(alt_svc.alternatives().count(), 0);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<AltSvc>(&[""]), None);
+        assert_eq!(test_decode::<AltSvc>(&["h3=:443"]), None);
+        assert_eq!(test_decode::<AltSvc>(&[r#"h3="example.com""#]), None);
+        assert_eq!(test_decode::<AltSvc>(&[r#"h3=":443" ma=60"#]), None);
+        assert_eq!(test_decode::<AltSvc>(&[r#"h3=":443"; ma"#]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let alt_svc = vec![
+            AltService::new("h3", ":443").with_max_age(Duration::from_secs(86400)),
+            AltService::new("w=x%y", "alt.example:8443").with_persist(),
+        ]
+        .into_iter()
+        .collect::<AltSvc>();
+
+        let headers = test_encode(alt_svc);
+        assert_eq!(
+            headers["alt-svc"],
+            r#"h3=":443"; ma=86400, w%3Dx%25y="alt.example:8443"; persist=1"#
+        );
+
+        let headers = test_encode(AltSvc::clear());
+        assert_eq!(headers["alt-svc"], "clear");
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_without_port() {
+        let _ = AltService::new("h3", "example.com");
+    }
+}
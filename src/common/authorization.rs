@@ -3,6 +3,7 @@
 use base64;
 use bytes::Bytes;
 
+use util::quoted_string::{self, Quoted};
 use util::HeaderValueString;
 use HeaderValue;
 
@@ -39,11 +40,19 @@ pub struct Authorization<C: Credentials>(pub C);
 
 impl Authorization<Basic> {
     /// Create a `Basic` authorization header.
+    ///
+    /// The username isn't checked, so one with a colon will be decoded
+    /// differently by the server. Use [`Basic::new`](struct.Basic.html#method.new)
+    /// to reject it.
     pub fn basic(username: &str, password: &str) -> Self {
         let colon_pos = username.len();
         let decoded = format!("{}:{}", username, password);
 
-        Authorization(Basic { decoded, colon_pos })
+        Authorization(Basic {
+            decoded,
+            colon_pos,
+            raw: None,
+        })
     }
 
     /// View the decoded username.
@@ -62,13 +71,20 @@ impl Authorization<Bearer> {
     pub fn bearer(token: &str) -> Result<Self, InvalidBearerToken> {
         HeaderValueString::from_string(format!("Bearer {}", token))
             .map(|val| Authorization(Bearer(val)))
-            .ok_or_else(|| InvalidBearerToken { _inner: () })
+            .ok_or(InvalidBearerToken { _inner: () })
     }
 
     /// View the token part as a `&str`.
     pub fn token(&self) -> &str {
         self.0.token()
     }
+
+    /// Split the token into the header, payload and signature of a JWT.
+    ///
+    /// See [`Bearer::jwt_parts`](struct.Bearer.html#method.jwt_parts).
+    pub fn jwt_parts(&self) -> Option<(&str, &str, &str)> {
+        self.0.jwt_parts()
+    }
 }
 
 impl<C: Credentials> ::Header for Authorization<C> {
@@ -80,11 +96,7 @@ impl<C: Credentials> ::Header for Authorization<C> {
         values
             .next()
             .and_then(|val| {
-                let slice = val.as_bytes();
-                if slice.starts_with(C::SCHEME.as_bytes())
-                    && slice.len() > C::SCHEME.len()
-                    && slice[C::SCHEME.len()] == b' '
-                {
+                if C::has_scheme(val) {
                     C::decode(val).map(Authorization)
                 } else {
                     None
@@ -114,6 +126,17 @@ pub trait Credentials: Sized {
     /// like `"Basic"` in basic authorization.
     const SCHEME: &'static str;
 
+    /// Check if the `HeaderValue` uses the scheme of these credentials.
+    ///
+    /// Only values passing this check are given to `decode`. By default,
+    /// this checks that the value starts with `SCHEME` and a space.
+    fn has_scheme(value: &HeaderValue) -> bool {
+        let slice = value.as_bytes();
+        slice.starts_with(Self::SCHEME.as_bytes())
+            && slice.len() > Self::SCHEME.len()
+            && slice[Self::SCHEME.len()] == b' '
+    }
+
     /// Try to decode the credentials from the `HeaderValue`.
     ///
     /// The `SCHEME` will be the first part of the `value`.
@@ -126,22 +149,92 @@ pub trait Credentials: Sized {
 }
 
 /// Credential holder for Basic Authentication
+///
+/// The credentials are split on the first colon, so a password can contain
+/// colons but a username can't. Credentials that aren't UTF-8, such as
+/// ones from a client using Latin-1, still decode, and their raw bytes are
+/// kept.
 #[derive(Clone, PartialEq, Debug)]
 pub struct Basic {
+    // Lossily converted if the credentials weren't UTF-8.
     decoded: String,
     colon_pos: usize,
+    // The credentials as received, only if they weren't UTF-8.
+    raw: Option<Vec<u8>>,
 }
 
 impl Basic {
+    /// Try to create `Basic` credentials.
+    ///
+    /// Following [RFC7617](https://tools.ietf.org/html/rfc7617#section-2),
+    /// this fails if `username` contains a colon, or either of them
+    /// contains a control character.
+    pub fn new(username: &str, password: &str) -> Result<Basic, InvalidBasicCredentials> {
+        let has_ctl = |s: &str| s.chars().any(char::is_control);
+        if username.contains(':') || has_ctl(username) || has_ctl(password) {
+            return Err(InvalidBasicCredentials { _inner: () });
+        }
+        Ok(Authorization::basic(username, password).0)
+    }
+
     /// View the decoded username.
+    ///
+    /// If the credentials weren't UTF-8, invalid sequences are replaced by
+    /// `U+FFFD`. Use [`decoded`](#method.decoded) to reject them instead.
     pub fn username(&self) -> &str {
         &self.decoded[..self.colon_pos]
     }
 
     /// View the decoded password.
+    ///
+    /// Like `username`, invalid UTF-8 has been replaced.
     pub fn password(&self) -> &str {
         &self.decoded[self.colon_pos + 1..]
     }
+
+    /// Get the username and password, or `None` if the credentials
+    /// weren't UTF-8.
+    ///
+    /// Credentials without a colon fail to decode at all, so they never
+    /// get this far.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::authorization::Basic;
+    ///
+    /// let basic = Basic::new("Aladdin", "open sesame").unwrap();
+    /// assert_eq!(
+    ///     basic.decoded(),
+    ///     Some(("Aladdin".to_owned(), "open sesame".to_owned()))
+    /// );
+    ///
+    /// assert!(Basic::new("Alad:din", "open sesame").is_err());
+    /// ```
+    pub fn decoded(&self) -> Option<(String, String)> {
+        if self.raw.is_some() {
+            None
+        } else {
+            Some((self.username().to_owned(), self.password().to_owned()))
+        }
+    }
+
+    /// Get the credentials exactly as they were base64-decoded, with the
+    /// colon, whether or not they're UTF-8.
+    ///
+    /// The base64 is decoded along with the header, and a `Basic` that
+    /// fails to decode isn't created, so this is always `Some`.
+    pub fn decoded_bytes(&self) -> Option<Vec<u8>> {
+        Some(self.bytes().to_vec())
+    }
+
+    fn bytes(&self) -> &[u8] {
+        match self.raw {
+            Some(ref raw) => raw,
+            None => self.decoded.as_bytes(),
+        }
+    }
 }
 
 impl Credentials for Basic {
@@ -159,16 +252,26 @@ impl Credentials for Basic {
         let bytes = &bytes[non_space_pos..];
         let bytes = base64::decode(bytes).ok()?;
 
-        let decoded = String::from_utf8(bytes).ok()?;
+        let (decoded, raw) = match String::from_utf8(bytes) {
+            Ok(decoded) => (decoded, None),
+            Err(err) => (
+                String::from_utf8_lossy(err.as_bytes()).into_owned(),
+                Some(err.into_bytes()),
+            ),
+        };
 
         let colon_pos = decoded.find(':')?;
 
-        Some(Basic { decoded, colon_pos })
+        Some(Basic {
+            decoded,
+            colon_pos,
+            raw,
+        })
     }
 
     fn encode(&self) -> HeaderValue {
         let mut encoded = String::from("Basic ");
-        base64::encode_config_buf(&self.decoded, base64::STANDARD, &mut encoded);
+        base64::encode_config_buf(self.bytes(), base64::STANDARD, &mut encoded);
 
         let bytes = Bytes::from(encoded);
         HeaderValue::from_maybe_shared(bytes).expect("base64 encoding is always a valid HeaderValue")
@@ -184,6 +287,32 @@ impl Bearer {
     pub fn token(&self) -> &str {
         &self.0.as_str()["Bearer ".len()..]
     }
+
+    /// Split the token into the header, payload and signature of a JWT,
+    /// without decoding them.
+    ///
+    /// Returns `None` unless the token is exactly three base64url segments
+    /// separated by dots. The signature may be empty, as in an unsecured
+    /// JWT, but the header and payload may not.
+    pub fn jwt_parts(&self) -> Option<(&str, &str, &str)> {
+        let mut parts = self.token().split('.');
+        let header = parts.next()?;
+        let payload = parts.next()?;
+        let signature = parts.next()?;
+
+        let is_base64url = |s: &str| {
+            s.bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+        };
+        if parts.next().is_some()
+            || header.is_empty()
+            || payload.is_empty()
+            || ![header, payload, signature].iter().all(|s| is_base64url(s))
+        {
+            return None;
+        }
+        Some((header, payload, signature))
+    }
 }
 
 impl Credentials for Bearer {
@@ -204,12 +333,247 @@ impl Credentials for Bearer {
     }
 }
 
+/// Credential holder for Digest Authentication, defined in
+/// [RFC7616](https://tools.ietf.org/html/rfc7616#section-3.4)
+///
+/// This only parses and encodes the credentials, computing or checking the
+/// `response` is left to the caller.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Digest {
+    params: Vec<(String, String)>,
+}
+
+impl Digest {
+    /// Create `Digest` credentials from the required fields.
+    ///
+    /// Optional fields, like `qop`, `nc` and `cnonce`, can be added with
+    /// `with_param`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any value contains control characters.
+    pub fn new(username: &str, realm: &str, nonce: &str, uri: &str, response: &str) -> Self {
+        Digest { params: Vec::new() }
+            .with_param("username", username)
+            .with_param("realm", realm)
+            .with_param("nonce", nonce)
+            .with_param("uri", uri)
+            .with_param("response", response)
+    }
+
+    /// Set a field, replacing an existing one with the same name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a `token`, or `value` contains control
+    /// characters.
+    pub fn with_param(mut self, name: &str, value: &str) -> Self {
+        assert!(
+            quoted_string::is_token(name),
+            "invalid Digest field name: {:?}",
+            name
+        );
+        assert!(
+            value.chars().all(|c| c == '\t' || !c.is_control()),
+            "invalid Digest field value: {:?}",
+            value
+        );
+        self.params.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+        self.params.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// View the unquoted value of any field.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// View the `username`.
+    pub fn username(&self) -> &str {
+        self.required("username")
+    }
+
+    /// View the `realm`.
+    pub fn realm(&self) -> &str {
+        self.required("realm")
+    }
+
+    /// View the `nonce` sent by the server.
+    pub fn nonce(&self) -> &str {
+        self.required("nonce")
+    }
+
+    /// View the `uri` the credentials were computed for.
+    pub fn uri(&self) -> &str {
+        self.required("uri")
+    }
+
+    /// View the `response`, the hex encoded digest proving the password.
+    pub fn response(&self) -> &str {
+        self.required("response")
+    }
+
+    /// View the `qop`, the quality of protection, if any.
+    pub fn qop(&self) -> Option<&str> {
+        self.param("qop")
+    }
+
+    /// View the `nc`, the hex nonce count, if any.
+    pub fn nc(&self) -> Option<&str> {
+        self.param("nc")
+    }
+
+    /// View the `cnonce` chosen by the client, if any.
+    pub fn cnonce(&self) -> Option<&str> {
+        self.param("cnonce")
+    }
+
+    /// View the `opaque` value echoed from the server, if any.
+    pub fn opaque(&self) -> Option<&str> {
+        self.param("opaque")
+    }
+
+    fn required(&self, name: &str) -> &str {
+        self.param(name)
+            .expect("Digest required fields are checked when created")
+    }
+}
+
+// Fields sent as tokens, everything else is a quoted-string.
+const DIGEST_UNQUOTED: &[&str] = &["algorithm", "nc", "qop", "userhash"];
+
+impl Credentials for Digest {
+    const SCHEME: &'static str = "Digest";
+
+    fn decode(value: &HeaderValue) -> Option<Self> {
+        debug_assert!(
+            value.as_bytes().starts_with(b"Digest "),
+            "HeaderValue to decode should start with \"Digest ..\", received = {:?}",
+            value,
+        );
+
+        let mut challenges = super::www_authenticate::parse_challenges(value.to_str().ok()?)?;
+        if challenges.len() != 1 || challenges[0].token68().is_some() {
+            return None;
+        }
+        let challenge = challenges.pop()?;
+        let params = challenge
+            .params()
+            .map(|(n, v)| (n.to_owned(), v.to_owned()))
+            .collect();
+        let digest = Digest { params };
+
+        let required = ["username", "realm", "nonce", "uri", "response"];
+        if required.iter().all(|name| digest.param(name).is_some()) {
+            Some(digest)
+        } else {
+            None
+        }
+    }
+
+    fn encode(&self) -> HeaderValue {
+        let mut encoded = String::from("Digest ");
+        for (i, (name, value)) in self.params.iter().enumerate() {
+            if i > 0 {
+                encoded.push_str(", ");
+            }
+            let unquoted = DIGEST_UNQUOTED.iter().any(|n| n.eq_ignore_ascii_case(name));
+            if unquoted && quoted_string::is_token(value) {
+                encoded.push_str(&format!("{}={}", name, value));
+            } else {
+                encoded.push_str(&format!("{}={}", name, Quoted(value)));
+            }
+        }
+
+        HeaderValue::from_str(&encoded).expect("Digest fields are checked when created")
+    }
+}
+
+/// Credential holder for any scheme, kept exactly as it was received.
+///
+/// This can be used for schemes this crate doesn't know about, like
+/// `AWS4-HMAC-SHA256 Credential=...`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Raw {
+    value: HeaderValueString,
+    scheme_len: usize,
+}
+
+impl Raw {
+    /// Try to create credentials from a scheme and the rest of the value.
+    ///
+    /// The `scheme` must be a `token`, and `credentials` may be empty.
+    pub fn new(scheme: &str, credentials: &str) -> Result<Self, InvalidRawCredentials> {
+        if !quoted_string::is_token(scheme) {
+            return Err(InvalidRawCredentials { _inner: () });
+        }
+        let value = if credentials.is_empty() {
+            scheme.to_owned()
+        } else {
+            format!("{} {}", scheme, credentials)
+        };
+        HeaderValueString::from_string(value)
+            .map(|value| Raw {
+                value,
+                scheme_len: scheme.len(),
+            })
+            .ok_or(InvalidRawCredentials { _inner: () })
+    }
+
+    /// View the scheme, such as `"Basic"`.
+    pub fn scheme(&self) -> &str {
+        &self.value.as_str()[..self.scheme_len]
+    }
+
+    /// View everything after the scheme, without the separating spaces.
+    pub fn credentials(&self) -> &str {
+        self.value.as_str()[self.scheme_len..].trim_start_matches(' ')
+    }
+
+    /// View the whole value, including the scheme.
+    pub fn as_str(&self) -> &str {
+        self.value.as_str()
+    }
+}
+
+impl Credentials for Raw {
+    /// `Raw` credentials accept any scheme, so this is empty.
+    const SCHEME: &'static str = "";
+
+    fn has_scheme(value: &HeaderValue) -> bool {
+        let slice = value.as_bytes();
+        let end = slice
+            .iter()
+            .position(|&b| !quoted_string::is_tchar(b))
+            .unwrap_or(slice.len());
+        end > 0 && (end == slice.len() || slice[end] == b' ')
+    }
+
+    fn decode(value: &HeaderValue) -> Option<Self> {
+        let value = HeaderValueString::from_val(value).ok()?;
+        let scheme_len = value
+            .as_str()
+            .find(' ')
+            .unwrap_or_else(|| value.as_str().len());
+        Some(Raw { value, scheme_len })
+    }
+
+    fn encode(&self) -> HeaderValue {
+        (&self.value).into()
+    }
+}
+
+error_type!(InvalidBasicCredentials);
 error_type!(InvalidBearerToken);
+error_type!(InvalidRawCredentials);
 
 #[cfg(test)]
 mod tests {
     use super::super::{test_decode, test_encode};
-    use super::{Authorization, Basic, Bearer};
+    use super::{Authorization, Basic, Bearer, Digest, Raw};
     use http::header::HeaderMap;
     use HeaderMapExt;
 
@@ -255,6 +619,57 @@ mod tests {
         assert_eq!(auth.0.password(), "");
     }
 
+    #[test]
+    fn basic_decoded() {
+        let auth: Authorization<Basic> =
+            test_decode(&["Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="]).unwrap();
+        assert_eq!(
+            auth.0.decoded(),
+            Some(("Aladdin".to_owned(), "open sesame".to_owned()))
+        );
+        assert_eq!(
+            auth.0.decoded_bytes(),
+            Some(b"Aladdin:open sesame".to_vec())
+        );
+
+        // "user:pass:word"
+        let auth: Authorization<Basic> = test_decode(&["Basic dXNlcjpwYXNzOndvcmQ="]).unwrap();
+        assert_eq!(auth.0.username(), "user");
+        assert_eq!(auth.0.password(), "pass:word");
+    }
+
+    #[test]
+    fn basic_decode_not_utf8() {
+        // "J\xf6rg:pass", a Latin-1 username
+        let auth: Authorization<Basic> = test_decode(&["Basic SvZyZzpwYXNz"]).unwrap();
+        assert_eq!(auth.0.decoded(), None);
+        assert_eq!(auth.0.decoded_bytes(), Some(b"J\xf6rg:pass".to_vec()));
+        assert_eq!(auth.0.username(), "J\u{fffd}rg");
+        assert_eq!(auth.0.password(), "pass");
+
+        let headers = test_encode(auth);
+        assert_eq!(headers["authorization"], "Basic SvZyZzpwYXNz");
+    }
+
+    #[test]
+    fn basic_decode_no_colon() {
+        // "Aladdin"
+        assert_eq!(
+            test_decode::<Authorization<Basic>>(&["Basic QWxhZGRpbg=="]),
+            None
+        );
+    }
+
+    #[test]
+    fn basic_new() {
+        let basic = Basic::new("Aladdin", "open:sesame").unwrap();
+        assert_eq!(basic, Authorization::basic("Aladdin", "open:sesame").0);
+
+        assert!(Basic::new("Alad:din", "open sesame").is_err());
+        assert!(Basic::new("Aladdin\n", "open sesame").is_err());
+        assert!(Basic::new("Aladdin", "open\0sesame").is_err());
+    }
+
     #[test]
     fn bearer_encode() {
         let auth = Authorization::bearer("fpKL54jvWmEGVoRdCNjG").unwrap();
@@ -269,6 +684,139 @@ mod tests {
         let auth: Authorization<Bearer> = test_decode(&["Bearer fpKL54jvWmEGVoRdCNjG"]).unwrap();
         assert_eq!(auth.0.token().as_bytes(), b"fpKL54jvWmEGVoRdCNjG");
     }
+
+    #[test]
+    fn bearer_jwt_parts() {
+        let auth = Authorization::bearer("eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiIxIn0.c2ln-_").unwrap();
+        assert_eq!(
+            auth.jwt_parts(),
+            Some(("eyJhbGciOiJIUzI1NiJ9", "eyJzdWIiOiIxIn0", "c2ln-_"))
+        );
+
+        let unsecured = Authorization::bearer("eyJhbGciOiJub25lIn0.e30.").unwrap();
+        assert_eq!(
+            unsecured.jwt_parts(),
+            Some(("eyJhbGciOiJub25lIn0", "e30", ""))
+        );
+
+        for token in &["fpKL54jvWmEGVoRdCNjG", "a.b", "a.b.c.d", ".b.c", "a.b=.c"] {
+            let auth = Authorization::bearer(token).unwrap();
+            assert_eq!(auth.jwt_parts(), None, "{:?}", token);
+        }
+    }
+
+    #[test]
+    fn raw_round_trip() {
+        let raw = concat!(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/iam/aws4_request, ",
+            "SignedHeaders=content-type;host;x-amz-date, Signature=5d672d79c15b13162d9279b0855cfba6789a8edb4c82c400e06b5924a6f2b5d7",
+        );
+        let auth: Authorization<Raw> = test_decode(&[raw]).unwrap();
+        assert_eq!(auth.0.scheme(), "AWS4-HMAC-SHA256");
+        assert!(auth.0.credentials().starts_with("Credential=AKIDEXAMPLE/"));
+
+        let headers = test_encode(auth);
+        assert_eq!(headers["authorization"], raw);
+    }
+
+    #[test]
+    fn raw_decode_any_scheme() {
+        let auth: Authorization<Raw> = test_decode(&["Basic  QWxhZGRpbjo="]).unwrap();
+        assert_eq!(auth.0.scheme(), "Basic");
+        assert_eq!(auth.0.credentials(), "QWxhZGRpbjo=");
+        assert_eq!(auth.0.as_str(), "Basic  QWxhZGRpbjo=");
+
+        let auth: Authorization<Raw> = test_decode(&["Negotiate"]).unwrap();
+        assert_eq!(auth.0.scheme(), "Negotiate");
+        assert_eq!(auth.0.credentials(), "");
+
+        assert_eq!(test_decode::<Authorization<Raw>>(&[" Basic"]), None);
+        assert_eq!(test_decode::<Authorization<Raw>>(&["Bad:scheme x"]), None);
+    }
+
+    #[test]
+    fn raw_new() {
+        let raw = Raw::new("Custom", "token=abc").unwrap();
+        assert_eq!(raw.scheme(), "Custom");
+        assert_eq!(raw.credentials(), "token=abc");
+        let headers = test_encode(Authorization(raw));
+        assert_eq!(headers["authorization"], "Custom token=abc");
+
+        assert!(Raw::new("Two words", "x").is_err());
+        assert!(Raw::new("Custom", "bad\nline").is_err());
+    }
+
+    #[test]
+    fn digest_decode() {
+        let auth: Authorization<Digest> = test_decode(&[concat!(
+            r#"Digest username="Mufasa", realm="http-auth@example.org", "#,
+            r#"uri="/dir/index.html", algorithm=MD5, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", "#,
+            r#"nc=00000001, cnonce="f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ", qop=auth, "#,
+            r#"response="8ca523f5e9506fed4657c9700eebdbec", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#,
+        )])
+        .unwrap();
+        assert_eq!(auth.0.username(), "Mufasa");
+        assert_eq!(auth.0.realm(), "http-auth@example.org");
+        assert_eq!(
+            auth.0.nonce(),
+            "7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v"
+        );
+        assert_eq!(auth.0.uri(), "/dir/index.html");
+        assert_eq!(auth.0.response(), "8ca523f5e9506fed4657c9700eebdbec");
+        assert_eq!(auth.0.qop(), Some("auth"));
+        assert_eq!(auth.0.nc(), Some("00000001"));
+        assert_eq!(
+            auth.0.cnonce(),
+            Some("f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ")
+        );
+        assert_eq!(
+            auth.0.opaque(),
+            Some("FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS")
+        );
+        assert_eq!(auth.0.param("algorithm"), Some("MD5"));
+    }
+
+    #[test]
+    fn digest_decode_missing_fields() {
+        assert_eq!(
+            test_decode::<Authorization<Digest>>(&[r#"Digest username="Mufasa", realm="x""#]),
+            None
+        );
+        assert_eq!(
+            test_decode::<Authorization<Digest>>(&["Digest dXNlcjpwYXNz"]),
+            None
+        );
+    }
+
+    #[test]
+    fn digest_encode() {
+        let digest = Digest::new("Mufasa", "x", "abc", "/", "123")
+            .with_param("qop", "auth")
+            .with_param("nc", "00000001")
+            .with_param("cnonce", "xyz");
+        let headers = test_encode(Authorization(digest));
+
+        assert_eq!(
+            headers["authorization"],
+            concat!(
+                r#"Digest username="Mufasa", realm="x", nonce="abc", uri="/", "#,
+                r#"response="123", qop=auth, nc=00000001, cnonce="xyz""#,
+            )
+        );
+    }
+
+    #[test]
+    fn digest_requotes() {
+        let auth: Authorization<Digest> = test_decode(&[
+            r#"Digest username=Mufasa, realm="x", nonce=abc, uri="/", response=123, qop="auth""#,
+        ])
+        .unwrap();
+        let headers = test_encode(auth);
+        assert_eq!(
+            headers["authorization"],
+            r#"Digest username="Mufasa", realm="x", nonce="abc", uri="/", response="123", qop=auth"#
+        );
+    }
 }
 
 //bench_header!(raw, Authorization<String>, { vec![b"foo bar baz".to_vec()] });
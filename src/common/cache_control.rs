@@ -3,7 +3,8 @@ use std::iter::FromIterator;
 use std::str::FromStr;
 use std::time::Duration;
 
-use util::{self, csv, Seconds};
+use util::quoted_string::{self, Quoted};
+use util::{self, csv, parse_u64, FlatCsv, Seconds, TryFromValues};
 use HeaderValue;
 
 /// `Cache-Control` header, defined in [RFC7234](https://tools.ietf.org/html/rfc7234#section-5.2)
@@ -25,22 +26,37 @@ use HeaderValue;
 /// * `no-cache`
 /// * `private, community="UCI"`
 /// * `max-age=30`
+/// * `no-cache="Set-Cookie, Set-Cookie2"`
 ///
 /// # Example
 ///
 /// ```
 /// # extern crate headers;
+/// use std::time::Duration;
 /// use headers::CacheControl;
 ///
 /// let cc = CacheControl::new();
+///
+/// let asset = CacheControl::immutable_asset(Duration::from_secs(31536000));
+/// assert!(asset.is_cacheable());
 /// ```
+///
+/// Directives that aren't otherwise understood are kept as extensions, and
+/// encoded back after the known directives.
 #[derive(PartialEq, Clone, Debug)]
 pub struct CacheControl {
     flags: Flags,
+    // `Some` for the qualified forms, which only apply to these fields.
+    no_cache_fields: Option<Vec<String>>,
+    private_fields: Option<Vec<String>>,
     max_age: Option<Seconds>,
-    max_stale: Option<Seconds>,
+    // `Some(None)` is a bare `max-stale`, accepting any staleness.
+    max_stale: Option<Option<Seconds>>,
     min_fresh: Option<Seconds>,
     s_max_age: Option<Seconds>,
+    stale_while_revalidate: Option<Seconds>,
+    stale_if_error: Option<Seconds>,
+    extensions: Vec<Extension>,
 }
 
 bitflags! {
@@ -53,6 +69,7 @@ bitflags! {
         const PUBLIC           = 0b00100000;
         const PRIVATE          = 0b01000000;
         const PROXY_REVALIDATE = 0b10000000;
+        const IMMUTABLE        = 0b100000000;
     }
 }
 
@@ -61,20 +78,95 @@ impl CacheControl {
     pub fn new() -> Self {
         CacheControl {
             flags: Flags::empty(),
+            no_cache_fields: None,
+            private_fields: None,
             max_age: None,
             max_stale: None,
             min_fresh: None,
             s_max_age: None,
+            stale_while_revalidate: None,
+            stale_if_error: None,
+            extensions: Vec::new(),
         }
     }
 
+    /// Construct a `Cache-Control: no-store` header, for responses that
+    /// must never be kept by any cache.
+    ///
+    /// This is named `uncacheable` since `no_store` already checks for
+    /// the directive.
+    pub fn uncacheable() -> Self {
+        CacheControl::new().with_no_store()
+    }
+
+    /// Construct a `Cache-Control: public, max-age=N, immutable` header,
+    /// for fingerprinted assets whose content never changes at their URL.
+    pub fn immutable_asset(max_age: Duration) -> Self {
+        CacheControl::new()
+            .with_public()
+            .with_max_age(max_age)
+            .with_immutable()
+    }
+
+    /// Construct a `Cache-Control: private, no-cache` header, for
+    /// per-user responses that a browser may keep but must revalidate
+    /// before every reuse.
+    pub fn private_revalidate() -> Self {
+        CacheControl::new().with_private().with_no_cache()
+    }
+
+    /// Check if a cache may reuse the response without revalidating it,
+    /// which is ruled out by `no-store` or `no-cache`.
+    ///
+    /// A `no-cache` with field names doesn't rule it out, as only those
+    /// fields need to be left out of a reused response.
+    pub fn is_cacheable(&self) -> bool {
+        !self.no_store() && (!self.no_cache() || self.no_cache_fields.is_some())
+    }
+
     // getters
 
-    /// Check if the `no-cache` directive is set.
+    /// Check if the `no-cache` directive is set, with or without field
+    /// names.
     pub fn no_cache(&self) -> bool {
         self.flags.contains(Flags::NO_CACHE)
     }
 
+    /// Get the field names of a qualified `no-cache="..."` directive.
+    ///
+    /// Returns `None` if `no-cache` isn't set, or was sent bare, in which
+    /// case the whole response must be revalidated. The names are
+    /// lowercase.
+    ///
+    /// A `no-cache` sent both bare and with field names is bare, and one
+    /// whose field names can't be parsed is treated as bare too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// # extern crate http;
+    /// use headers::{CacheControl, HeaderMapExt};
+    ///
+    /// let mut map = http::HeaderMap::new();
+    /// map.insert(
+    ///     "cache-control",
+    ///     "max-age=60, no-cache=\"Set-Cookie, X-Token\"".parse().unwrap(),
+    /// );
+    ///
+    /// let cc = map.typed_get::<CacheControl>().unwrap();
+    /// assert!(cc.no_cache());
+    /// assert!(cc.is_cacheable());
+    ///
+    /// let fields = cc.no_cache_fields().unwrap().collect::<Vec<_>>();
+    /// assert_eq!(fields, vec!["set-cookie", "x-token"]);
+    /// ```
+    pub fn no_cache_fields(&self) -> Option<impl Iterator<Item = &str>> {
+        self.no_cache_fields
+            .as_ref()
+            .map(|fields| fields.iter().map(String::as_str))
+    }
+
     /// Check if the `no-store` directive is set.
     pub fn no_store(&self) -> bool {
         self.flags.contains(Flags::NO_STORE)
@@ -95,19 +187,38 @@ impl CacheControl {
         self.flags.contains(Flags::PUBLIC)
     }
 
-    /// Check if the `private` directive is set.
+    /// Check if the `private` directive is set, with or without field
+    /// names.
     pub fn private(&self) -> bool {
         self.flags.contains(Flags::PRIVATE)
     }
 
+    /// Get the field names of a qualified `private="..."` directive, which
+    /// a shared cache must leave out when storing the response.
+    ///
+    /// This works just like [`no_cache_fields`](#method.no_cache_fields).
+    pub fn private_fields(&self) -> Option<impl Iterator<Item = &str>> {
+        self.private_fields
+            .as_ref()
+            .map(|fields| fields.iter().map(String::as_str))
+    }
+
+    /// Check if the `immutable` directive is set.
+    pub fn immutable(&self) -> bool {
+        self.flags.contains(Flags::IMMUTABLE)
+    }
+
     /// Get the value of the `max-age` directive if set.
     pub fn max_age(&self) -> Option<Duration> {
         self.max_age.map(Into::into)
     }
 
-    /// Get the value of the `max-stale` directive if set.
-    pub fn max_stale(&self) -> Option<Duration> {
-        self.max_stale.map(Into::into)
+    /// Get the value of the `max-stale` request directive if set.
+    ///
+    /// A bare `max-stale`, which accepts a stale response of any age, is
+    /// `Some(None)`, unlike `max-stale=0`.
+    pub fn max_stale(&self) -> Option<Option<Duration>> {
+        self.max_stale.map(|secs| secs.map(Into::into))
     }
 
     /// Get the value of the `min-fresh` directive if set.
@@ -120,11 +231,43 @@ impl CacheControl {
         self.s_max_age.map(Into::into)
     }
 
+    /// Get the value of the `stale-while-revalidate` directive if set.
+    pub fn stale_while_revalidate(&self) -> Option<Duration> {
+        self.stale_while_revalidate.map(Into::into)
+    }
+
+    /// Get the value of the `stale-if-error` directive if set.
+    pub fn stale_if_error(&self) -> Option<Duration> {
+        self.stale_if_error.map(Into::into)
+    }
+
+    /// Returns an iterator over the extension directives, as name and
+    /// optional (unquoted) value.
+    pub fn extensions(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.extensions
+            .iter()
+            .map(|ext| (ext.name.as_str(), ext.value.as_deref()))
+    }
+
     // setters
 
     /// Set the `no-cache` directive.
+    ///
+    /// This replaces any field names set by
+    /// [`with_no_cache_fields`](#method.with_no_cache_fields).
     pub fn with_no_cache(mut self) -> Self {
         self.flags.insert(Flags::NO_CACHE);
+        self.no_cache_fields = None;
+        self
+    }
+
+    /// Set the `no-cache` directive for just the given fields.
+    pub fn with_no_cache_fields<I>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = ::HeaderName>,
+    {
+        self.flags.insert(Flags::NO_CACHE);
+        self.no_cache_fields = Some(field_names(fields));
         self
     }
 
@@ -147,8 +290,22 @@ impl CacheControl {
     }
 
     /// Set the `private` directive.
+    ///
+    /// This replaces any field names set by
+    /// [`with_private_fields`](#method.with_private_fields).
     pub fn with_private(mut self) -> Self {
         self.flags.insert(Flags::PRIVATE);
+        self.private_fields = None;
+        self
+    }
+
+    /// Set the `private` directive for just the given fields.
+    pub fn with_private_fields<I>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = ::HeaderName>,
+    {
+        self.flags.insert(Flags::PRIVATE);
+        self.private_fields = Some(field_names(fields));
         self
     }
 
@@ -158,6 +315,12 @@ impl CacheControl {
         self
     }
 
+    /// Set the `immutable` directive.
+    pub fn with_immutable(mut self) -> Self {
+        self.flags.insert(Flags::IMMUTABLE);
+        self
+    }
+
     /// Set the `max-age` directive.
     pub fn with_max_age(mut self, seconds: Duration) -> Self {
         self.max_age = Some(seconds.into());
@@ -166,7 +329,14 @@ impl CacheControl {
 
     /// Set the `max-stale` directive.
     pub fn with_max_stale(mut self, seconds: Duration) -> Self {
-        self.max_stale = Some(seconds.into());
+        self.max_stale = Some(Some(seconds.into()));
+        self
+    }
+
+    /// Set the `max-stale` directive without a value, so a stale response
+    /// of any age is accepted.
+    pub fn with_max_stale_any(mut self) -> Self {
+        self.max_stale = Some(None);
         self
     }
 
@@ -181,6 +351,48 @@ impl CacheControl {
         self.s_max_age = Some(seconds.into());
         self
     }
+
+    /// Set the `stale-while-revalidate` directive.
+    pub fn with_stale_while_revalidate(mut self, seconds: Duration) -> Self {
+        self.stale_while_revalidate = Some(seconds.into());
+        self
+    }
+
+    /// Set the `stale-if-error` directive.
+    pub fn with_stale_if_error(mut self, seconds: Duration) -> Self {
+        self.stale_if_error = Some(seconds.into());
+        self
+    }
+
+    /// Set an extension directive, replacing one with the same name.
+    ///
+    /// The value is quoted when encoding if it isn't a `token`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a `token`, or `value` contains control
+    /// characters.
+    pub fn with_extension(mut self, name: &str, value: Option<&str>) -> Self {
+        assert!(
+            quoted_string::is_token(name),
+            "invalid Cache-Control extension name: {:?}",
+            name
+        );
+        if let Some(value) = value {
+            assert!(
+                value.chars().all(|c| c == '\t' || !c.is_control()),
+                "invalid Cache-Control extension value: {:?}",
+                value
+            );
+        }
+        self.extensions
+            .retain(|ext| !ext.name.eq_ignore_ascii_case(name));
+        self.extensions.push(Extension {
+            name: name.to_owned(),
+            value: value.map(ToOwned::to_owned),
+        });
+        self
+    }
 }
 
 impl ::Header for CacheControl {
@@ -189,7 +401,14 @@ impl ::Header for CacheControl {
     }
 
     fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
-        csv::from_comma_delimited(values).map(|FromIter(cc)| cc)
+        // Split while respecting quotes, since a list of field names is a
+        // quoted string with commas.
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        csv.iter()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse().map_err(|_| ::Error::invalid()))
+            .collect::<Result<FromIter, _>>()
+            .map(|FromIter(cc)| cc)
     }
 
     fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
@@ -207,15 +426,21 @@ impl FromIterator<KnownDirective> for FromIter {
     {
         let mut cc = CacheControl::new();
 
-        // ignore all unknown directives
-        let iter = iter.into_iter().filter_map(|dir| match dir {
-            KnownDirective::Known(dir) => Some(dir),
-            KnownDirective::Unknown => None,
-        });
-
         for directive in iter {
+            // keep extensions, and ignore directives with unusable values
+            let directive = match directive {
+                KnownDirective::Known(dir) => dir,
+                KnownDirective::Extension(ext) => {
+                    cc.extensions.push(ext);
+                    continue;
+                }
+                KnownDirective::Unknown => continue,
+            };
+
             match directive {
-                Directive::NoCache => {
+                Directive::NoCache(fields) => {
+                    let bare = cc.no_cache() && cc.no_cache_fields.is_none();
+                    merge_fields(&mut cc.no_cache_fields, bare, fields);
                     cc.flags.insert(Flags::NO_CACHE);
                 }
                 Directive::NoStore => {
@@ -233,23 +458,34 @@ impl FromIterator<KnownDirective> for FromIter {
                 Directive::Public => {
                     cc.flags.insert(Flags::PUBLIC);
                 }
-                Directive::Private => {
+                Directive::Private(fields) => {
+                    let bare = cc.private() && cc.private_fields.is_none();
+                    merge_fields(&mut cc.private_fields, bare, fields);
                     cc.flags.insert(Flags::PRIVATE);
                 }
                 Directive::ProxyRevalidate => {
                     cc.flags.insert(Flags::PROXY_REVALIDATE);
                 }
+                Directive::Immutable => {
+                    cc.flags.insert(Flags::IMMUTABLE);
+                }
                 Directive::MaxAge(secs) => {
-                    cc.max_age = Some(Duration::from_secs(secs.into()).into());
+                    cc.max_age = Some(Duration::from_secs(secs).into());
                 }
                 Directive::MaxStale(secs) => {
-                    cc.max_stale = Some(Duration::from_secs(secs.into()).into());
+                    cc.max_stale = Some(secs.map(|secs| Duration::from_secs(secs).into()));
                 }
                 Directive::MinFresh(secs) => {
-                    cc.min_fresh = Some(Duration::from_secs(secs.into()).into());
+                    cc.min_fresh = Some(Duration::from_secs(secs).into());
                 }
                 Directive::SMaxAge(secs) => {
-                    cc.s_max_age = Some(Duration::from_secs(secs.into()).into());
+                    cc.s_max_age = Some(Duration::from_secs(secs).into());
+                }
+                Directive::StaleWhileRevalidate(secs) => {
+                    cc.stale_while_revalidate = Some(Duration::from_secs(secs).into());
+                }
+                Directive::StaleIfError(secs) => {
+                    cc.stale_if_error = Some(Duration::from_secs(secs).into());
                 }
             }
         }
@@ -258,6 +494,57 @@ impl FromIterator<KnownDirective> for FromIter {
     }
 }
 
+// Adds the field names of a qualified `no-cache` or `private` that was
+// decoded, where a bare directive seen at any point wins.
+fn merge_fields(existing: &mut Option<Vec<String>>, bare: bool, fields: Option<Vec<String>>) {
+    match (existing.as_mut(), fields) {
+        (_, None) => *existing = None,
+        (_, Some(_)) if bare => (),
+        (Some(existing), Some(fields)) => existing.extend(fields),
+        (None, Some(fields)) => *existing = Some(fields),
+    }
+}
+
+fn field_names<I>(fields: I) -> Vec<String>
+where
+    I: IntoIterator<Item = ::HeaderName>,
+{
+    fields
+        .into_iter()
+        .map(|name| name.as_str().to_owned())
+        .collect()
+}
+
+// Parses the value of a qualified `no-cache` or `private`, which should be
+// a quoted list of field names but may be a single token.
+//
+// Returns `None` for an unusable list, so the directive is taken as bare.
+fn parse_field_names(value: &str) -> Option<Vec<String>> {
+    let value = value.trim();
+    let list = match quoted_string::parse(value) {
+        Some((list, rest)) if rest.trim().is_empty() => list,
+        Some(_) => return None,
+        None => value.to_owned(),
+    };
+    let fields = list
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            if quoted_string::is_token(field) {
+                Some(field.to_ascii_lowercase())
+            } else {
+                None
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if fields.is_empty() {
+        None
+    } else {
+        Some(fields)
+    }
+}
+
 struct Fmt<'a>(&'a CacheControl);
 
 impl<'a> fmt::Display for Fmt<'a> {
@@ -271,14 +558,21 @@ impl<'a> fmt::Display for Fmt<'a> {
         };
 
         let slice = &[
-            if_flag(Flags::NO_CACHE, Directive::NoCache),
+            if_flag(
+                Flags::NO_CACHE,
+                Directive::NoCache(self.0.no_cache_fields.clone()),
+            ),
             if_flag(Flags::NO_STORE, Directive::NoStore),
             if_flag(Flags::NO_TRANSFORM, Directive::NoTransform),
             if_flag(Flags::ONLY_IF_CACHED, Directive::OnlyIfCached),
             if_flag(Flags::MUST_REVALIDATE, Directive::MustRevalidate),
             if_flag(Flags::PUBLIC, Directive::Public),
-            if_flag(Flags::PRIVATE, Directive::Private),
+            if_flag(
+                Flags::PRIVATE,
+                Directive::Private(self.0.private_fields.clone()),
+            ),
             if_flag(Flags::PROXY_REVALIDATE, Directive::ProxyRevalidate),
+            if_flag(Flags::IMMUTABLE, Directive::Immutable),
             self.0
                 .max_age
                 .as_ref()
@@ -286,7 +580,7 @@ impl<'a> fmt::Display for Fmt<'a> {
             self.0
                 .max_stale
                 .as_ref()
-                .map(|s| Directive::MaxStale(s.as_u64())),
+                .map(|s| Directive::MaxStale(s.as_ref().map(Seconds::as_u64))),
             self.0
                 .min_fresh
                 .as_ref()
@@ -295,94 +589,202 @@ impl<'a> fmt::Display for Fmt<'a> {
                 .s_max_age
                 .as_ref()
                 .map(|s| Directive::SMaxAge(s.as_u64())),
+            self.0
+                .stale_while_revalidate
+                .as_ref()
+                .map(|s| Directive::StaleWhileRevalidate(s.as_u64())),
+            self.0
+                .stale_if_error
+                .as_ref()
+                .map(|s| Directive::StaleIfError(s.as_u64())),
         ];
 
-        let iter = slice.iter().filter_map(|o| *o);
+        let iter = slice
+            .iter()
+            .filter_map(|o| o.as_ref())
+            .map(|dir| dir as &dyn fmt::Display)
+            .chain(self.0.extensions.iter().map(|ext| ext as &dyn fmt::Display));
 
         csv::fmt_comma_delimited(f, iter)
     }
 }
 
-#[derive(Clone, Copy)]
 enum KnownDirective {
     Known(Directive),
+    Extension(Extension),
     Unknown,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Debug, PartialEq)]
+struct Extension {
+    name: String,
+    value: Option<String>,
+}
+
+impl fmt::Display for Extension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.value {
+            Some(ref value) if quoted_string::is_token(value) => {
+                write!(f, "{}={}", self.name, value)
+            }
+            Some(ref value) => write!(f, "{}={}", self.name, Quoted(value)),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+impl FromStr for Extension {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = match s.find('=') {
+            Some(idx) => {
+                let raw = s[idx + 1..].trim();
+                let value = match quoted_string::parse(raw) {
+                    Some((value, rest)) if rest.trim().is_empty() => value,
+                    _ => raw.to_owned(),
+                };
+                (s[..idx].trim(), Some(value))
+            }
+            None => (s, None),
+        };
+        if !quoted_string::is_token(name) {
+            return Err(());
+        }
+        Ok(Extension {
+            name: name.to_owned(),
+            value,
+        })
+    }
+}
+
+#[derive(Clone)]
 enum Directive {
-    NoCache,
+    NoCache(Option<Vec<String>>),
     NoStore,
     NoTransform,
     OnlyIfCached,
 
     // request directives
     MaxAge(u64),
-    MaxStale(u64),
+    MaxStale(Option<u64>),
     MinFresh(u64),
 
     // response directives
     MustRevalidate,
     Public,
-    Private,
+    Private(Option<Vec<String>>),
     ProxyRevalidate,
     SMaxAge(u64),
+
+    // RFC8246 extension
+    Immutable,
+
+    // RFC5861 extensions
+    StaleWhileRevalidate(u64),
+    StaleIfError(u64),
 }
 
 impl fmt::Display for Directive {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(
             match *self {
-                Directive::NoCache => "no-cache",
+                Directive::NoCache(None) => "no-cache",
+                Directive::NoCache(Some(ref fields)) => {
+                    return write!(f, "no-cache={}", Quoted(&fields.join(", ")))
+                }
                 Directive::NoStore => "no-store",
                 Directive::NoTransform => "no-transform",
                 Directive::OnlyIfCached => "only-if-cached",
 
                 Directive::MaxAge(secs) => return write!(f, "max-age={}", secs),
-                Directive::MaxStale(secs) => return write!(f, "max-stale={}", secs),
+                Directive::MaxStale(Some(secs)) => return write!(f, "max-stale={}", secs),
+                Directive::MaxStale(None) => "max-stale",
                 Directive::MinFresh(secs) => return write!(f, "min-fresh={}", secs),
 
                 Directive::MustRevalidate => "must-revalidate",
                 Directive::Public => "public",
-                Directive::Private => "private",
+                Directive::Private(None) => "private",
+                Directive::Private(Some(ref fields)) => {
+                    return write!(f, "private={}", Quoted(&fields.join(", ")))
+                }
                 Directive::ProxyRevalidate => "proxy-revalidate",
                 Directive::SMaxAge(secs) => return write!(f, "s-maxage={}", secs),
+
+                Directive::Immutable => "immutable",
+
+                Directive::StaleWhileRevalidate(secs) => {
+                    return write!(f, "stale-while-revalidate={}", secs)
+                }
+                Directive::StaleIfError(secs) => return write!(f, "stale-if-error={}", secs),
             },
             f,
         )
     }
 }
 
+impl KnownDirective {
+    fn extension(s: &str) -> KnownDirective {
+        s.parse()
+            .map(KnownDirective::Extension)
+            .unwrap_or(KnownDirective::Unknown)
+    }
+}
+
 impl FromStr for KnownDirective {
     type Err = ();
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(KnownDirective::Known(match s {
-            "no-cache" => Directive::NoCache,
+            "no-cache" => Directive::NoCache(None),
             "no-store" => Directive::NoStore,
             "no-transform" => Directive::NoTransform,
             "only-if-cached" => Directive::OnlyIfCached,
+            "max-stale" => Directive::MaxStale(None),
             "must-revalidate" => Directive::MustRevalidate,
             "public" => Directive::Public,
-            "private" => Directive::Private,
+            "private" => Directive::Private(None),
             "proxy-revalidate" => Directive::ProxyRevalidate,
+            "immutable" => Directive::Immutable,
             "" => return Err(()),
             _ => match s.find('=') {
                 Some(idx) if idx + 1 < s.len() => {
-                    match (&s[..idx], (&s[idx + 1..]).trim_matches('"')) {
-                        ("max-age", secs) => secs.parse().map(Directive::MaxAge).map_err(|_| ())?,
-                        ("max-stale", secs) => {
-                            secs.parse().map(Directive::MaxStale).map_err(|_| ())?
-                        }
-                        ("min-fresh", secs) => {
-                            secs.parse().map(Directive::MinFresh).map_err(|_| ())?
+                    match &s[..idx] {
+                        "no-cache" => {
+                            let fields = parse_field_names(&s[idx + 1..]);
+                            return Ok(KnownDirective::Known(Directive::NoCache(fields)));
                         }
-                        ("s-maxage", secs) => {
-                            secs.parse().map(Directive::SMaxAge).map_err(|_| ())?
+                        "private" => {
+                            let fields = parse_field_names(&s[idx + 1..]);
+                            return Ok(KnownDirective::Known(Directive::Private(fields)));
                         }
-                        _unknown => return Ok(KnownDirective::Unknown),
+                        _ => (),
+                    }
+                    match (&s[..idx], (&s[idx + 1..]).trim_matches('"')) {
+                        ("max-age", secs) => parse_u64(secs.as_bytes())
+                            .map(Directive::MaxAge)
+                            .ok_or(())?,
+                        ("max-stale", secs) => parse_u64(secs.as_bytes())
+                            .map(|secs| Directive::MaxStale(Some(secs)))
+                            .ok_or(())?,
+                        ("min-fresh", secs) => parse_u64(secs.as_bytes())
+                            .map(Directive::MinFresh)
+                            .ok_or(())?,
+                        ("s-maxage", secs) => parse_u64(secs.as_bytes())
+                            .map(Directive::SMaxAge)
+                            .ok_or(())?,
+                        // These are optional hints, so an invalid value is
+                        // ignored instead of failing the whole header.
+                        ("stale-while-revalidate", secs) => match parse_u64(secs.as_bytes()) {
+                            Some(secs) => Directive::StaleWhileRevalidate(secs),
+                            None => return Ok(KnownDirective::Unknown),
+                        },
+                        ("stale-if-error", secs) => match parse_u64(secs.as_bytes()) {
+                            Some(secs) => Directive::StaleIfError(secs),
+                            None => return Ok(KnownDirective::Unknown),
+                        },
+                        _unknown => return Ok(KnownDirective::extension(s)),
                     }
                 }
-                Some(_) | None => return Ok(KnownDirective::Unknown),
+                Some(_) | None => return Ok(KnownDirective::extension(s)),
             },
         }))
     }
@@ -423,11 +825,48 @@ mod tests {
     fn test_parse_extension() {
         assert_eq!(
             test_decode::<CacheControl>(&["foo, no-cache, bar=baz"]).unwrap(),
-            CacheControl::new().with_no_cache(),
-            "unknown extensions are ignored but shouldn't fail parsing",
+            CacheControl::new()
+                .with_extension("foo", None)
+                .with_no_cache()
+                .with_extension("bar", Some("baz")),
+            "unknown extensions are kept and shouldn't fail parsing",
+        );
+    }
+
+    #[test]
+    fn test_extension_round_trip() {
+        let cc = test_decode::<CacheControl>(&[
+            "private, x-internal-ttl=30, community=\"UCI\"",
+            "x-flag",
+        ])
+        .unwrap();
+        assert!(cc.private());
+        assert_eq!(
+            cc.extensions().collect::<Vec<_>>(),
+            vec![
+                ("x-internal-ttl", Some("30")),
+                ("community", Some("UCI")),
+                ("x-flag", None),
+            ]
+        );
+
+        let headers = test_encode(cc);
+        assert_eq!(
+            headers["cache-control"],
+            "private, x-internal-ttl=30, community=UCI, x-flag"
         );
     }
 
+    #[test]
+    fn encode_extension_quotes_non_token() {
+        let cc = CacheControl::new()
+            .with_no_store()
+            .with_extension("x-note", Some("a b"));
+
+        let headers = test_encode(cc);
+        assert_eq!(headers["cache-control"], "no-store, x-note=\"a b\"");
+    }
+
     #[test]
     fn test_parse_bad_syntax() {
         assert_eq!(test_decode::<CacheControl>(&["max-age=lolz"]), None,);
@@ -461,4 +900,200 @@ mod tests {
         );
         assert_eq!(headers["cache-control"], "no-cache, max-age=100");
     }
+
+    #[test]
+    fn test_immutable_round_trip() {
+        let cc = test_decode::<CacheControl>(&["public, max-age=31536000, immutable"]).unwrap();
+        assert!(cc.public());
+        assert!(cc.immutable());
+        assert_eq!(cc.max_age(), Some(Duration::from_secs(31536000)));
+
+        let headers = test_encode(cc);
+        assert_eq!(
+            headers["cache-control"],
+            "public, immutable, max-age=31536000"
+        );
+    }
+
+    #[test]
+    fn recipes() {
+        let cc = CacheControl::uncacheable();
+        assert!(!cc.is_cacheable());
+        assert_eq!(test_encode(cc)["cache-control"], "no-store");
+
+        let cc = CacheControl::immutable_asset(Duration::from_secs(31536000));
+        assert!(cc.is_cacheable());
+        assert_eq!(
+            test_encode(cc)["cache-control"],
+            "public, immutable, max-age=31536000"
+        );
+
+        let cc = CacheControl::private_revalidate();
+        assert!(!cc.is_cacheable());
+        assert_eq!(test_encode(cc)["cache-control"], "no-cache, private");
+    }
+
+    #[test]
+    fn is_cacheable() {
+        assert!(CacheControl::new().is_cacheable());
+        assert!(
+            test_decode::<CacheControl>(&["max-age=60, must-revalidate"])
+                .unwrap()
+                .is_cacheable()
+        );
+        assert!(!test_decode::<CacheControl>(&["max-age=60, no-cache"])
+            .unwrap()
+            .is_cacheable());
+    }
+
+    #[test]
+    fn test_parse_request_directives() {
+        let cc = test_decode::<CacheControl>(&["max-stale, min-fresh=60, only-if-cached"]).unwrap();
+        assert_eq!(cc.max_stale(), Some(None));
+        assert_eq!(cc.min_fresh(), Some(Duration::from_secs(60)));
+        assert!(cc.only_if_cached());
+
+        let cc = test_decode::<CacheControl>(&["max-stale=0"]).unwrap();
+        assert_eq!(cc.max_stale(), Some(Some(Duration::from_secs(0))));
+        assert_eq!(cc.min_fresh(), None);
+        assert!(!cc.only_if_cached());
+
+        assert_eq!(
+            test_decode::<CacheControl>(&["max-age=1"])
+                .unwrap()
+                .max_stale(),
+            None
+        );
+    }
+
+    #[test]
+    fn encode_max_stale() {
+        let headers = test_encode(CacheControl::new().with_max_stale_any());
+        assert_eq!(headers["cache-control"], "max-stale");
+
+        let headers = test_encode(CacheControl::new().with_max_stale(Duration::from_secs(0)));
+        assert_eq!(headers["cache-control"], "max-stale=0");
+    }
+
+    #[test]
+    fn test_parse_stale_directives() {
+        assert_eq!(
+            test_decode::<CacheControl>(&[
+                "stale-if-error=86400, max-age=600",
+                "stale-while-revalidate=30"
+            ])
+            .unwrap(),
+            CacheControl::new()
+                .with_max_age(Duration::from_secs(600))
+                .with_stale_while_revalidate(Duration::from_secs(30))
+                .with_stale_if_error(Duration::from_secs(86400)),
+        );
+    }
+
+    #[test]
+    fn test_parse_stale_directives_bad_value_ignored() {
+        let cc = test_decode::<CacheControl>(&[
+            "stale-while-revalidate=soon, stale-if-error=-1, public",
+        ])
+        .unwrap();
+        assert_eq!(cc, CacheControl::new().with_public());
+        assert_eq!(cc.stale_while_revalidate(), None);
+        assert_eq!(cc.stale_if_error(), None);
+    }
+
+    #[test]
+    fn encode_stale_directives_in_stable_order() {
+        let headers = test_encode(
+            CacheControl::new()
+                .with_stale_if_error(Duration::from_secs(60))
+                .with_stale_while_revalidate(Duration::from_secs(30))
+                .with_max_age(Duration::from_secs(10))
+                .with_public(),
+        );
+        assert_eq!(
+            headers["cache-control"],
+            "public, max-age=10, stale-while-revalidate=30, stale-if-error=60"
+        );
+    }
+
+    #[test]
+    fn test_parse_qualified_no_cache() {
+        let cc = test_decode::<CacheControl>(&[
+            "no-cache=\"Set-Cookie, Set-Cookie2\", max-age=60",
+            "private=X-Token",
+        ])
+        .unwrap();
+        assert!(cc.no_cache());
+        assert!(cc.is_cacheable());
+        assert_eq!(cc.max_age(), Some(Duration::from_secs(60)));
+        assert_eq!(
+            cc.no_cache_fields().unwrap().collect::<Vec<_>>(),
+            vec!["set-cookie", "set-cookie2"]
+        );
+        assert!(cc.private());
+        assert_eq!(
+            cc.private_fields().unwrap().collect::<Vec<_>>(),
+            vec!["x-token"]
+        );
+    }
+
+    #[test]
+    fn test_parse_bare_no_cache_distinct() {
+        let cc = test_decode::<CacheControl>(&["no-cache, private"]).unwrap();
+        assert!(cc.no_cache());
+        assert!(cc.no_cache_fields().is_none());
+        assert!(cc.private_fields().is_none());
+        assert!(!cc.is_cacheable());
+
+        // A bare directive anywhere wins over field names.
+        for value in &["no-cache=\"a\", no-cache", "no-cache, no-cache=\"a\""] {
+            let cc = test_decode::<CacheControl>(&[value]).unwrap();
+            assert!(cc.no_cache_fields().is_none(), "{:?}", value);
+        }
+
+        let cc = test_decode::<CacheControl>(&["no-cache=\"a\", no-cache=\"B\""]).unwrap();
+        assert_eq!(
+            cc.no_cache_fields().unwrap().collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+
+        // Unusable lists make the directive bare.
+        for value in &["no-cache=\"\"", "no-cache=\"a b\"", "no-cache=\"a\"b"] {
+            let cc = test_decode::<CacheControl>(&[value]).unwrap();
+            assert!(cc.no_cache(), "{:?}", value);
+            assert!(cc.no_cache_fields().is_none(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn encode_qualified_no_cache() {
+        use http::header::{SET_COOKIE, WWW_AUTHENTICATE};
+
+        let cc = CacheControl::new()
+            .with_no_cache_fields(vec![SET_COOKIE, WWW_AUTHENTICATE])
+            .with_private_fields(vec![SET_COOKIE]);
+        let headers = test_encode(cc.clone());
+        assert_eq!(
+            headers["cache-control"],
+            "no-cache=\"set-cookie, www-authenticate\", private=\"set-cookie\""
+        );
+        assert_eq!(
+            test_decode::<CacheControl>(&[headers["cache-control"].to_str().unwrap()]),
+            Some(cc.clone())
+        );
+
+        let headers = test_encode(cc.with_no_cache());
+        assert_eq!(headers["cache-control"], "no-cache, private=\"set-cookie\"");
+    }
+
+    bench_header!(
+        bench_typical,
+        CacheControl,
+        "public, max-age=31536000, immutable"
+    );
+    bench_header!(
+        bench_stale,
+        CacheControl,
+        "max-age=600, s-maxage=3600, stale-while-revalidate=30, stale-if-error=86400"
+    );
 }
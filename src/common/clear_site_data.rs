@@ -0,0 +1,236 @@
+use std::iter::FromIterator;
+
+use util::quoted_string;
+use util::{FlatCsv, TryFromValues};
+use HeaderValue;
+
+static CLEAR_SITE_DATA: ::HeaderName = ::HeaderName::from_static("clear-site-data");
+
+/// `Clear-Site-Data` header, defined in
+/// [Clear Site Data](https://www.w3.org/TR/clear-site-data/#header)
+///
+/// The `Clear-Site-Data` response header asks the browser to clear data
+/// it stores for the response's origin, such as when a user logs out.
+/// Each directive is a quoted string; `"*"` clears everything.
+///
+/// Unknown directives are ignored when decoding, as the spec requires.
+///
+/// A header built with `FromIterator` panics if the iterator is empty,
+/// since a header without directives can't be decoded.
+///
+/// # ABNF
+///
+/// ```text
+/// Clear-Site-Data = 1#( quoted-string )
+/// ```
+///
+/// # Example values
+///
+/// * `"cache", "cookies", "storage"`
+/// * `"*"`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{ClearSiteData, ClearSiteDataDirective};
+///
+/// let csd = ClearSiteData::new(ClearSiteDataDirective::Cache)
+///     .with_directive(ClearSiteDataDirective::Cookies);
+///
+/// assert!(csd.contains(ClearSiteDataDirective::Cookies));
+/// assert!(!csd.is_wildcard());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClearSiteData(Vec<ClearSiteDataDirective>);
+
+/// A directive of a [`ClearSiteData`](struct.ClearSiteData.html) header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClearSiteDataDirective {
+    /// `"cache"`: clear locally cached responses.
+    Cache,
+    /// `"cookies"`: clear cookies for the registrable domain.
+    Cookies,
+    /// `"storage"`: clear DOM storage, such as `localStorage` and
+    /// IndexedDB.
+    Storage,
+    /// `"executionContexts"`: reload every browsing context of the origin.
+    ExecutionContexts,
+    /// `"*"`: clear every type of data.
+    Wildcard,
+}
+
+impl ClearSiteDataDirective {
+    /// Returns the directive's name, without quotes.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            ClearSiteDataDirective::Cache => "cache",
+            ClearSiteDataDirective::Cookies => "cookies",
+            ClearSiteDataDirective::Storage => "storage",
+            ClearSiteDataDirective::ExecutionContexts => "executionContexts",
+            ClearSiteDataDirective::Wildcard => "*",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<ClearSiteDataDirective> {
+        match s {
+            "cache" => Some(ClearSiteDataDirective::Cache),
+            "cookies" => Some(ClearSiteDataDirective::Cookies),
+            "storage" => Some(ClearSiteDataDirective::Storage),
+            "executionContexts" => Some(ClearSiteDataDirective::ExecutionContexts),
+            "*" => Some(ClearSiteDataDirective::Wildcard),
+            _ => None,
+        }
+    }
+}
+
+impl ::Header for ClearSiteData {
+    fn name() -> &'static ::HeaderName {
+        &CLEAR_SITE_DATA
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+
+        let mut directives = Vec::new();
+        let mut seen = false;
+        for elem in csv.iter().filter(|elem| !elem.is_empty()) {
+            let (name, rest) = quoted_string::parse(elem).ok_or_else(::Error::invalid)?;
+            if !rest.trim().is_empty() {
+                return Err(::Error::invalid());
+            }
+            seen = true;
+            if let Some(directive) = ClearSiteDataDirective::from_str(&name) {
+                if !directives.contains(&directive) {
+                    directives.push(directive);
+                }
+            }
+        }
+
+        if !seen {
+            return Err(::Error::invalid());
+        }
+        Ok(ClearSiteData(directives))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let s = self
+            .0
+            .iter()
+            .map(|directive| format!("\"{}\"", directive.as_str()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        values.extend(::std::iter::once(
+            HeaderValue::from_str(&s).expect("directives are valid header values"),
+        ));
+    }
+}
+
+impl ClearSiteData {
+    /// Creates a `ClearSiteData` with its first directive, to add more to.
+    pub fn new(directive: ClearSiteDataDirective) -> ClearSiteData {
+        ClearSiteData(vec![directive])
+    }
+
+    /// Creates `Clear-Site-Data: "*"`, clearing every type of data.
+    pub fn wildcard() -> ClearSiteData {
+        ClearSiteData(vec![ClearSiteDataDirective::Wildcard])
+    }
+
+    /// Adds a directive, if it isn't already present.
+    pub fn with_directive(mut self, directive: ClearSiteDataDirective) -> Self {
+        if !self.0.contains(&directive) {
+            self.0.push(directive);
+        }
+        self
+    }
+
+    /// Returns true if the directive is listed.
+    ///
+    /// This only checks the listed directives, so it returns false for,
+    /// say, `Cache` when only `"*"` was sent.
+    pub fn contains(&self, directive: ClearSiteDataDirective) -> bool {
+        self.0.contains(&directive)
+    }
+
+    /// Returns true if `"*"` is listed.
+    pub fn is_wildcard(&self) -> bool {
+        self.contains(ClearSiteDataDirective::Wildcard)
+    }
+
+    /// Returns an iterator over the known directives, in order.
+    pub fn iter(&self) -> impl Iterator<Item = ClearSiteDataDirective> + '_ {
+        self.0.iter().cloned()
+    }
+}
+
+impl FromIterator<ClearSiteDataDirective> for ClearSiteData {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = ClearSiteDataDirective>,
+    {
+        let mut iter = iter.into_iter();
+        let first = iter
+            .next()
+            .expect("ClearSiteData needs at least one directive");
+        iter.fold(ClearSiteData::new(first), ClearSiteData::with_directive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::ClearSiteDataDirective::*;
+    use super::*;
+
+    #[test]
+    fn encode_logout() {
+        let csd = vec![Cache, Cookies, Storage]
+            .into_iter()
+            .collect::<ClearSiteData>();
+        let headers = test_encode(csd);
+        assert_eq!(
+            headers["clear-site-data"],
+            r#""cache", "cookies", "storage""#
+        );
+    }
+
+    #[test]
+    fn decode_merges_lines() {
+        let csd =
+            test_decode::<ClearSiteData>(&[r#""cache", "executionContexts""#, r#""cookies""#])
+                .unwrap();
+        assert_eq!(
+            csd.iter().collect::<Vec<_>>(),
+            vec![Cache, ExecutionContexts, Cookies]
+        );
+        assert!(!csd.contains(Storage));
+    }
+
+    #[test]
+    fn decode_wildcard() {
+        let csd = test_decode::<ClearSiteData>(&[r#""*""#]).unwrap();
+        assert!(csd.is_wildcard());
+        assert_eq!(csd, ClearSiteData::wildcard());
+    }
+
+    #[test]
+    fn decode_ignores_unknown() {
+        let csd = test_decode::<ClearSiteData>(&[r#""cookies", "clientHints""#]).unwrap();
+        assert_eq!(csd.iter().collect::<Vec<_>>(), vec![Cookies]);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<ClearSiteData>(&[""]), None);
+        assert_eq!(test_decode::<ClearSiteData>(&["cache"]), None);
+        assert_eq!(test_decode::<ClearSiteData>(&[r#""cache"x"#]), None);
+        assert_eq!(test_decode::<ClearSiteData>(&[r#""cache"#]), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_iter_empty() {
+        let _ = Vec::new().into_iter().collect::<ClearSiteData>();
+    }
+}
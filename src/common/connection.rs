@@ -39,7 +39,8 @@ pub struct Connection(FlatCsv);
 
 derive_header! {
     Connection(_),
-    name: CONNECTION
+    name: CONNECTION,
+    list
 }
 
 impl Connection {
@@ -92,6 +93,50 @@ impl Connection {
             .find(|&opt| opt.eq_ignore_ascii_case(s))
             .is_some()
     }
+
+    /// Check if this header contains `close`, ignoring case.
+    pub fn is_close(&self) -> bool {
+        self.contains("close")
+    }
+
+    /// Check if this header contains `keep-alive`, ignoring case.
+    pub fn is_keep_alive(&self) -> bool {
+        self.contains("keep-alive")
+    }
+
+    /// Check if both have the same connection options, ignoring case,
+    /// order and duplicates.
+    pub fn semantically_eq(&self, other: &Connection) -> bool {
+        self.0.set_eq(&other.0, true)
+    }
+
+    /// Returns an iterator over the connection options, in order.
+    ///
+    /// Besides `close` and `keep-alive`, options usually name hop-by-hop
+    /// headers that a proxy must remove before forwarding the message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// extern crate http;
+    ///
+    /// use headers::{Connection, HeaderMapExt};
+    ///
+    /// let mut headers = http::HeaderMap::new();
+    /// headers.insert("connection", "close, x-trace".parse().unwrap());
+    /// headers.insert("x-trace", "abc".parse().unwrap());
+    ///
+    /// let conn = headers.typed_get::<Connection>().unwrap();
+    /// for opt in conn.iter() {
+    ///     headers.remove(opt);
+    /// }
+    ///
+    /// assert!(headers.get("x-trace").is_none());
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter(|opt| !opt.is_empty())
+    }
 }
 
 impl FromIterator<HeaderName> for Connection {
@@ -134,3 +179,46 @@ mod sealed {
 
     impl Sealed for ::HeaderName {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_decode;
+    use super::*;
+
+    #[test]
+    fn close_and_keep_alive() {
+        assert!(Connection::close().is_close());
+        assert!(!Connection::close().is_keep_alive());
+        assert!(Connection::keep_alive().is_keep_alive());
+        assert!(!Connection::upgrade().is_close());
+    }
+
+    #[test]
+    fn semantically_eq() {
+        let a = test_decode::<Connection>(&["Upgrade, close"]).unwrap();
+        let b = test_decode::<Connection>(&["CLOSE", "upgrade,"]).unwrap();
+        assert!(a.semantically_eq(&b));
+        assert!(!a.semantically_eq(&Connection::close()));
+    }
+
+    #[test]
+    fn iter_merges_lines() {
+        let conn = test_decode::<Connection>(&["Keep-Alive, Upgrade", "x-hop"]).unwrap();
+        assert_eq!(
+            conn.iter().collect::<Vec<_>>(),
+            vec!["Keep-Alive", "Upgrade", "x-hop"]
+        );
+        assert!(conn.is_keep_alive());
+        assert!(conn.contains("upgrade"));
+    }
+
+    #[test]
+    fn encode_to_keeps_lines() {
+        use Header;
+
+        let conn = test_decode::<Connection>(&["Keep-Alive", "Upgrade"]).unwrap();
+        let mut values = Vec::new();
+        conn.encode_to(&mut values);
+        assert_eq!(values, ["Keep-Alive", "Upgrade"]);
+    }
+}
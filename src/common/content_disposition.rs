@@ -6,6 +6,11 @@
 // Browser conformance tests at: http://greenbytes.de/tech/tc2231/
 // IANA assignment: http://www.iana.org/assignments/cont-disp/cont-disp.xhtml
 
+use std::borrow::Cow;
+
+use util::extended_value::{ExtendedValue, Utf8};
+use util::quoted_string::{self, Quoted};
+
 /// A `Content-Disposition` header, (re)defined in [RFC6266](https://tools.ietf.org/html/rfc6266).
 ///
 /// The Content-Disposition response header field is used to convey
@@ -39,9 +44,25 @@
 ///
 /// ```
 /// # extern crate headers;
-/// use headers::ContentDisposition;
+/// use headers::{ContentDisposition, DispositionType};
 ///
 /// let cd = ContentDisposition::inline();
+/// assert_eq!(cd.disposition(), DispositionType::Inline);
+///
+/// let cd = ContentDisposition::attachment("report.pdf");
+/// assert!(cd.is_attachment());
+/// ```
+///
+/// Filenames that aren't plain ASCII tokens are sent as an RFC5987
+/// `filename*`, with an ASCII `filename` fallback for older recipients:
+///
+/// ```
+/// # extern crate headers;
+/// use headers::ContentDisposition;
+///
+/// let cd = ContentDisposition::inline().with_filename("\u{20AC} rates.txt");
+///
+/// assert_eq!(cd.filename().as_ref().map(String::as_str), Some("\u{20AC} rates.txt"));
 /// ```
 #[derive(Clone, Debug)]
 pub struct ContentDisposition(::HeaderValue);
@@ -52,31 +73,97 @@ impl ContentDisposition {
         ContentDisposition(::HeaderValue::from_static("inline"))
     }
 
-    /*
+    /// Construct a `Content-Disposition: attachment` header with the
+    /// given filename.
+    ///
+    /// The filename is encoded the same way as by
+    /// [`with_filename`](ContentDisposition::with_filename).
     pub fn attachment(filename: &str) -> ContentDisposition {
-        let full = Bytes::from(format!("attachment; filename={}", filename));
-        match ::HeaderValue::from_maybe_shared(full) {
-            Ok(val) => ContentDisposition(val),
-            Err(_) => {
-                unimplemented!("filename that isn't ASCII");
-            }
+        ContentDisposition(::HeaderValue::from_static("attachment")).with_filename(filename)
+    }
+
+    /// Get the disposition-type.
+    ///
+    /// The type is matched case-insensitively, and anything other than
+    /// `inline`, `attachment` or `form-data` is returned as
+    /// `DispositionType::Ext`.
+    pub fn disposition(&self) -> DispositionType {
+        let ty = self.get_type().trim();
+        if ty.eq_ignore_ascii_case("inline") {
+            DispositionType::Inline
+        } else if ty.eq_ignore_ascii_case("attachment") {
+            DispositionType::Attachment
+        } else if ty.eq_ignore_ascii_case("form-data") {
+            DispositionType::FormData
+        } else {
+            DispositionType::Ext(ty.to_owned())
         }
     }
-    */
 
     /// Check if the disposition-type is `inline`.
     pub fn is_inline(&self) -> bool {
-        self.get_type() == "inline"
+        self.disposition() == DispositionType::Inline
     }
 
     /// Check if the disposition-type is `attachment`.
     pub fn is_attachment(&self) -> bool {
-        self.get_type() == "attachment"
+        self.disposition() == DispositionType::Attachment
     }
 
     /// Check if the disposition-type is `form-data`.
     pub fn is_form_data(&self) -> bool {
-        self.get_type() == "form-data"
+        self.disposition() == DispositionType::FormData
+    }
+
+    /// Get the field name of a `form-data` part, from its `name`
+    /// parameter.
+    ///
+    /// Returns `None` for other disposition-types, where `name` has no
+    /// defined meaning.
+    pub fn name(&self) -> Option<String> {
+        self.name_param().map(Cow::into_owned)
+    }
+
+    /// Get the field name and filename of a `form-data` part, as when
+    /// reading a `multipart/form-data` body.
+    ///
+    /// Returns `None` for other disposition-types, or if the part has no
+    /// `name`, which [RFC7578](https://tools.ietf.org/html/rfc7578#section-4.2)
+    /// requires.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// # extern crate http;
+    /// use headers::{ContentDisposition, HeaderMapExt};
+    ///
+    /// let mut part = http::HeaderMap::new();
+    /// part.insert(
+    ///     "content-disposition",
+    ///     "form-data; name=avatar; filename=\"me.png\"".parse().unwrap(),
+    /// );
+    ///
+    /// let cd = part.typed_get::<ContentDisposition>().unwrap();
+    /// let field = cd.form_field().unwrap();
+    /// assert_eq!(field.name(), "avatar");
+    /// assert_eq!(field.filename(), Some("me.png"));
+    /// ```
+    pub fn form_field(&self) -> Option<FormField<'_>> {
+        Some(FormField {
+            name: self.name_param()?,
+            filename: self.filename_param(),
+        })
+    }
+
+    fn name_param(&self) -> Option<Cow<'_, str>> {
+        if !self.is_form_data() {
+            return None;
+        }
+        self.params()
+            .into_iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("name"))
+            .map(|(_, value)| value)
     }
 
     fn get_type(&self) -> &str {
@@ -87,6 +174,157 @@ impl ContentDisposition {
             .next()
             .expect("split always has at least 1 item")
     }
+
+    /// Get the filename, if any.
+    ///
+    /// An RFC5987 `filename*` parameter is decoded and preferred over a
+    /// plain `filename` parameter. A `filename*` in a charset other than
+    /// `UTF-8` or `ISO-8859-1` is skipped.
+    pub fn filename(&self) -> Option<String> {
+        self.filename_param().map(Cow::into_owned)
+    }
+
+    fn filename_param(&self) -> Option<Cow<'_, str>> {
+        let mut params = self.params();
+        let ext = params
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case("filename*"))
+            .filter_map(|(_, value)| ExtendedValue::parse(value)?.decode())
+            .next();
+        if let Some(filename) = ext {
+            return Some(Cow::Owned(filename));
+        }
+        let idx = params
+            .iter()
+            .position(|(name, _)| name.eq_ignore_ascii_case("filename"))?;
+        Some(params.swap_remove(idx).1)
+    }
+
+    /// Set the filename, replacing any existing `filename` and `filename*`
+    /// parameters.
+    ///
+    /// A filename that isn't a `token` is encoded as a `UTF-8` `filename*`,
+    /// along with a `filename` where characters outside of printable ASCII
+    /// are replaced by `_`.
+    pub fn with_filename(self, filename: &str) -> Self {
+        let mut s = self.get_type().trim().to_owned();
+        for (name, value) in self.params() {
+            if name.eq_ignore_ascii_case("filename") || name.eq_ignore_ascii_case("filename*") {
+                continue;
+            }
+            push_param(&mut s, name, &value);
+        }
+
+        if quoted_string::is_token(filename) {
+            push_param(&mut s, "filename", filename);
+        } else {
+            let fallback = filename
+                .chars()
+                .map(|c| match c {
+                    ' '..='~' => c,
+                    _ => '_',
+                })
+                .collect::<String>();
+            push_param(&mut s, "filename", &fallback);
+            s.push_str("; filename*=");
+            s.push_str(&Utf8(filename).to_string());
+        }
+
+        ContentDisposition(
+            ::HeaderValue::from_str(&s).expect("ContentDisposition is a valid HeaderValue"),
+        )
+    }
+
+    /// Parses the `disposition-parm`s, unquoting their values.
+    ///
+    /// A value is only copied if it had to be unescaped. Parsing stops at
+    /// the first malformed parameter.
+    fn params(&self) -> Vec<(&str, Cow<'_, str>)> {
+        let s = self.0.to_str().unwrap_or("");
+        let mut rest = match s.find(';') {
+            Some(idx) => &s[idx + 1..],
+            None => return Vec::new(),
+        };
+
+        let mut params = Vec::new();
+        loop {
+            rest = rest.trim_start_matches(&[' ', '\t', ';'][..]);
+            let eq = match rest.find('=') {
+                Some(eq) => eq,
+                None => break,
+            };
+            let name = rest[..eq].trim();
+            let value = rest[eq + 1..].trim_start();
+            let (value, after) = if value.starts_with('"') {
+                let (unquoted, after) = match quoted_string::parse(value) {
+                    Some(parsed) => parsed,
+                    None => break,
+                };
+                // Without escapes, the unquoted value is what's between the quotes.
+                let inner = &value[1..value.len() - after.len() - 1];
+                if inner == unquoted {
+                    (Cow::Borrowed(inner), after)
+                } else {
+                    (Cow::Owned(unquoted), after)
+                }
+            } else {
+                let end = value.find(';').unwrap_or(value.len());
+                (Cow::Borrowed(value[..end].trim_end()), &value[end..])
+            };
+            params.push((name, value));
+            rest = after;
+        }
+        params
+    }
+}
+
+/// The disposition-type of a `Content-Disposition` header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DispositionType {
+    /// `inline`, to be displayed as part of the page.
+    Inline,
+    /// `attachment`, to be downloaded and saved locally.
+    Attachment,
+    /// `form-data`, a part of a `multipart/form-data` body, as in
+    /// [RFC7578](https://tools.ietf.org/html/rfc7578#section-4.2).
+    FormData,
+    /// Any other type, as it was sent. Recipients should treat unknown
+    /// types as `attachment`.
+    Ext(String),
+}
+
+/// The field of a `form-data` part, returned by
+/// [`ContentDisposition::form_field`](struct.ContentDisposition.html#method.form_field).
+///
+/// The name and filename borrow from the header, unless they had to be
+/// unescaped or decoded from a `filename*`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormField<'a> {
+    name: Cow<'a, str>,
+    filename: Option<Cow<'a, str>>,
+}
+
+impl<'a> FormField<'a> {
+    /// The name of the form field.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The filename, if the part is a file upload.
+    ///
+    /// As with [`ContentDisposition::filename`](struct.ContentDisposition.html#method.filename),
+    /// a `filename*` is preferred.
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+}
+
+fn push_param(s: &mut String, name: &str, value: &str) {
+    if quoted_string::is_token(value) {
+        s.push_str(&format!("; {}={}", name, value));
+    } else {
+        s.push_str(&format!("; {}={}", name, Quoted(value)));
+    }
 }
 
 impl ::Header for ContentDisposition {
@@ -107,6 +345,165 @@ impl ::Header for ContentDisposition {
         values.extend(::std::iter::once(self.0.clone()));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn filename_plain() {
+        let cd =
+            test_decode::<ContentDisposition>(&["attachment; filename=\"image.jpg\""]).unwrap();
+        assert!(cd.is_attachment());
+        assert_eq!(cd.filename().unwrap(), "image.jpg");
+
+        let cd = test_decode::<ContentDisposition>(&["inline"]).unwrap();
+        assert_eq!(cd.filename(), None);
+    }
+
+    #[test]
+    fn filename_ext_preferred() {
+        let cd = test_decode::<ContentDisposition>(&[
+            "attachment; filename*=UTF-8''%E2%82%AC%20rates.txt; filename=\"EUR rates.txt\"",
+        ])
+        .unwrap();
+        assert_eq!(cd.filename().unwrap(), "\u{20AC} rates.txt");
+
+        let cd = test_decode::<ContentDisposition>(&[
+            "attachment; filename=\"EUR rates.txt\"; filename*=iso-8859-1'en'%A3%20rates.txt",
+        ])
+        .unwrap();
+        assert_eq!(cd.filename().unwrap(), "\u{A3} rates.txt");
+    }
+
+    #[test]
+    fn filename_ext_invalid_falls_back() {
+        let cd = test_decode::<ContentDisposition>(&[
+            "attachment; filename*=UTF-8''%E2%82; filename=fallback.txt",
+        ])
+        .unwrap();
+        assert_eq!(cd.filename().unwrap(), "fallback.txt");
+    }
+
+    #[test]
+    fn disposition_type() {
+        let ty = |s: &str| {
+            test_decode::<ContentDisposition>(&[s])
+                .unwrap()
+                .disposition()
+        };
+        assert_eq!(ty("inline"), DispositionType::Inline);
+        assert_eq!(ty("INLINE"), DispositionType::Inline);
+        assert_eq!(
+            ty("Attachment; filename=a.txt"),
+            DispositionType::Attachment
+        );
+        assert_eq!(ty("form-data ; name=upload"), DispositionType::FormData);
+        assert_eq!(ty("x-Custom"), DispositionType::Ext("x-Custom".to_owned()));
+
+        assert!(test_decode::<ContentDisposition>(&["ATTACHMENT"])
+            .unwrap()
+            .is_attachment());
+    }
+
+    #[test]
+    fn form_data_name() {
+        let cd = test_decode::<ContentDisposition>(&[
+            "form-data; name=\"user name\"; filename=\"me.png\"",
+        ])
+        .unwrap();
+        assert_eq!(cd.name().unwrap(), "user name");
+
+        let cd = test_decode::<ContentDisposition>(&["form-data"]).unwrap();
+        assert_eq!(cd.name(), None);
+
+        let cd = test_decode::<ContentDisposition>(&["attachment; name=x"]).unwrap();
+        assert_eq!(cd.name(), None);
+    }
+
+    #[test]
+    fn form_field() {
+        let cd = test_decode::<ContentDisposition>(&[
+            "form-data; name=\"upload\"; filename=\"EUR.txt\"; filename*=UTF-8''%E2%82%AC.txt",
+        ])
+        .unwrap();
+        let field = cd.form_field().unwrap();
+        assert_eq!(field.name(), "upload");
+        assert_eq!(field.filename(), Some("\u{20AC}.txt"));
+
+        let cd = test_decode::<ContentDisposition>(&["form-data; name=comment"]).unwrap();
+        let field = cd.form_field().unwrap();
+        assert_eq!(field.name(), "comment");
+        assert_eq!(field.filename(), None);
+
+        let cd = test_decode::<ContentDisposition>(&[
+            "form-data; name=\"a\\\"b\"; filename=\"report.pdf\"",
+        ])
+        .unwrap();
+        let field = cd.form_field().unwrap();
+        assert_eq!(field.name(), "a\"b");
+        assert!(matches!(field.name, Cow::Owned(_)));
+        assert!(matches!(field.filename, Some(Cow::Borrowed("report.pdf"))));
+
+        let cd = test_decode::<ContentDisposition>(&["form-data; filename=a.txt"]).unwrap();
+        assert_eq!(cd.form_field(), None);
+
+        let cd =
+            test_decode::<ContentDisposition>(&["attachment; name=x; filename=a.txt"]).unwrap();
+        assert_eq!(cd.form_field(), None);
+    }
+
+    #[test]
+    fn attachment() {
+        let headers = test_encode(ContentDisposition::attachment("report.pdf"));
+        assert_eq!(
+            headers["content-disposition"],
+            "attachment; filename=report.pdf"
+        );
+
+        let cd = ContentDisposition::attachment("\u{20AC}.txt");
+        assert!(cd.is_attachment());
+        assert_eq!(cd.filename().unwrap(), "\u{20AC}.txt");
+    }
+
+    #[test]
+    fn with_filename_token() {
+        let cd = ContentDisposition::inline().with_filename("colourful.csv");
+        let headers = test_encode(cd);
+        assert_eq!(
+            headers["content-disposition"],
+            "inline; filename=colourful.csv"
+        );
+    }
+
+    #[test]
+    fn with_filename_unicode() {
+        let cd = ContentDisposition::inline().with_filename("\u{20AC} and \"rates\".txt");
+        assert_eq!(cd.filename().unwrap(), "\u{20AC} and \"rates\".txt");
+
+        let headers = test_encode(cd);
+        assert_eq!(
+            headers["content-disposition"],
+            "inline; filename=\"_ and \\\"rates\\\".txt\"; \
+             filename*=UTF-8''%E2%82%AC%20and%20%22rates%22.txt"
+        );
+    }
+
+    #[test]
+    fn with_filename_replaces_existing() {
+        let cd = test_decode::<ContentDisposition>(&[
+            "form-data; name=upload; filename=\"old.png\"; filename*=UTF-8''old.png",
+        ])
+        .unwrap()
+        .with_filename("new.png");
+        let headers = test_encode(cd);
+        assert_eq!(
+            headers["content-disposition"],
+            "form-data; name=upload; filename=new.png"
+        );
+    }
+}
 /*
 use language_tags::LanguageTag;
 use std::fmt;
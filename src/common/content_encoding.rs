@@ -1,5 +1,7 @@
+use std::iter::FromIterator;
+
 use self::sealed::AsCoding;
-use util::FlatCsv;
+use util::{quoted_string, FlatCsv};
 use HeaderValue;
 
 /// `Content-Encoding` header, defined in
@@ -13,6 +15,9 @@ use HeaderValue;
 /// representation's data to be compressed without losing the identity of
 /// its underlying media type.
 ///
+/// Collecting a `ContentEncoding` from `&str`s panics if a coding isn't a
+/// `token`.
+///
 /// # ABNF
 ///
 /// ```text
@@ -22,6 +27,7 @@ use HeaderValue;
 /// # Example values
 ///
 /// * `gzip`
+/// * `gzip, br`
 ///
 /// # Examples
 ///
@@ -30,13 +36,18 @@ use HeaderValue;
 /// use headers::ContentEncoding;
 ///
 /// let content_enc = ContentEncoding::gzip();
+///
+/// // Codings are listed in the order they were applied.
+/// let layered = vec!["deflate", "br"].into_iter().collect::<ContentEncoding>();
+/// assert_eq!(layered.iter().collect::<Vec<_>>(), vec!["deflate", "br"]);
 /// ```
 #[derive(Clone, Debug)]
 pub struct ContentEncoding(FlatCsv);
 
 derive_header! {
     ContentEncoding(_),
-    name: CONTENT_ENCODING
+    name: CONTENT_ENCODING,
+    list
 }
 
 impl ContentEncoding {
@@ -46,8 +57,35 @@ impl ContentEncoding {
         ContentEncoding(HeaderValue::from_static("gzip").into())
     }
 
+    /// A constructor to easily create a `Content-Encoding: br` header.
+    #[inline]
+    pub fn br() -> ContentEncoding {
+        ContentEncoding(HeaderValue::from_static("br").into())
+    }
+
+    /// A constructor to easily create a `Content-Encoding: deflate` header.
+    #[inline]
+    pub fn deflate() -> ContentEncoding {
+        ContentEncoding(HeaderValue::from_static("deflate").into())
+    }
+
+    /// A constructor to easily create a `Content-Encoding: identity` header.
+    #[inline]
+    pub fn identity() -> ContentEncoding {
+        ContentEncoding(HeaderValue::from_static("identity").into())
+    }
+
+    /// Returns an iterator over the codings, in the order they were applied.
+    ///
+    /// To decode the content, undo them starting from the last one.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter(|coding| !coding.is_empty())
+    }
+
     /// Check if this header contains a given "coding".
     ///
+    /// Codings are compared case-insensitively.
+    ///
     /// This can be used with these argument types:
     ///
     /// - `&str`
@@ -65,7 +103,27 @@ impl ContentEncoding {
     /// ```
     pub fn contains(&self, coding: impl AsCoding) -> bool {
         let s = coding.as_coding();
-        self.0.iter().find(|&opt| opt == s).is_some()
+        self.iter().any(|opt| opt.eq_ignore_ascii_case(s))
+    }
+}
+
+impl<'a> FromIterator<&'a str> for ContentEncoding {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let values = iter
+            .into_iter()
+            .map(|coding| {
+                assert!(
+                    quoted_string::is_token(coding),
+                    "invalid content-coding: {:?}",
+                    coding
+                );
+                HeaderValue::from_str(coding).expect("a token is a valid HeaderValue")
+            })
+            .collect::<Vec<_>>();
+        ContentEncoding(values.iter().collect())
     }
 }
 
@@ -84,3 +142,43 @@ mod sealed {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_stacked() {
+        let content_enc = test_decode::<ContentEncoding>(&["gzip, BR", "identity"]).unwrap();
+        assert_eq!(
+            content_enc.iter().collect::<Vec<_>>(),
+            vec!["gzip", "BR", "identity"]
+        );
+        assert!(content_enc.contains("br"));
+        assert!(content_enc.contains("GZIP"));
+        assert!(!content_enc.contains("deflate"));
+    }
+
+    #[test]
+    fn encode_from_iter() {
+        let content_enc = vec!["deflate", "gzip"]
+            .into_iter()
+            .collect::<ContentEncoding>();
+        let headers = test_encode(content_enc);
+        assert_eq!(headers["content-encoding"], "deflate, gzip");
+    }
+
+    #[test]
+    fn constructors() {
+        assert!(ContentEncoding::br().contains("br"));
+        assert!(ContentEncoding::deflate().contains("deflate"));
+        assert!(ContentEncoding::identity().contains("identity"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_iter_invalid() {
+        let _ = vec!["gzip, br"].into_iter().collect::<ContentEncoding>();
+    }
+}
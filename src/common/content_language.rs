@@ -0,0 +1,119 @@
+use std::iter::FromIterator;
+
+use super::LanguageTag;
+use util::{FlatCsv, TryFromValues};
+use HeaderValue;
+
+/// `Content-Language` header, defined in
+/// [RFC7231](https://tools.ietf.org/html/rfc7231#section-3.1.3.2)
+///
+/// The `Content-Language` header field describes the natural language(s)
+/// of the intended audience for the representation.  Note that this
+/// might not be equivalent to all the languages used within the
+/// representation.
+///
+/// # ABNF
+///
+/// ```text
+/// Content-Language = 1#language-tag
+/// ```
+///
+/// # Example values
+///
+/// * `da`
+/// * `mi, en`
+///
+/// # Examples
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{ContentLanguage, LanguageTag};
+///
+/// let tags = vec!["en-US".parse::<LanguageTag>().unwrap()];
+/// let con_lang = tags.into_iter().collect::<ContentLanguage>();
+///
+/// assert!(con_lang.contains("en-us"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContentLanguage(Vec<LanguageTag>);
+
+impl ::Header for ContentLanguage {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::CONTENT_LANGUAGE
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        let tags = csv
+            .iter()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse())
+            .collect::<Result<Vec<LanguageTag>, ::Error>>()?;
+        if tags.is_empty() {
+            return Err(::Error::invalid());
+        }
+        Ok(ContentLanguage(tags))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let s = self.iter().collect::<Vec<_>>().join(", ");
+        let value = HeaderValue::from_str(&s).expect("LanguageTag is a valid HeaderValue");
+        values.extend(::std::iter::once(value));
+    }
+}
+
+impl ContentLanguage {
+    /// Returns an iterator over the language tags, as they were given.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(LanguageTag::as_str)
+    }
+
+    /// Returns true if the header lists `tag`.
+    ///
+    /// Language tags are compared case-insensitively.
+    pub fn contains(&self, tag: &str) -> bool {
+        self.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+}
+
+impl FromIterator<LanguageTag> for ContentLanguage {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = LanguageTag>,
+    {
+        ContentLanguage(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_merges_lines() {
+        let con_lang = test_decode::<ContentLanguage>(&[" mi ,en", "de-AT"]).unwrap();
+        assert_eq!(
+            con_lang.iter().collect::<Vec<_>>(),
+            vec!["mi", "en", "de-AT"]
+        );
+        assert!(con_lang.contains("de-at"));
+        assert!(!con_lang.contains("de"));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<ContentLanguage>(&[""]), None);
+        assert_eq!(test_decode::<ContentLanguage>(&["en, not a tag"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let con_lang = vec!["da".parse().unwrap(), "en-GB".parse().unwrap()]
+            .into_iter()
+            .collect::<ContentLanguage>();
+
+        let headers = test_encode(con_lang);
+        assert_eq!(headers["content-language"], "da, en-GB");
+    }
+}
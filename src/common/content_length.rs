@@ -51,11 +51,12 @@ impl Header for ContentLength {
         // correctly. If not, then it's an error.
         let mut len = None;
         for value in values {
-            let parsed = value
-                .to_str()
-                .map_err(|_| ::Error::invalid())?
-                .parse::<u64>()
-                .map_err(|_| ::Error::invalid())?;
+            let s = value.to_str().map_err(|_| ::Error::invalid())?;
+            // `u64::from_str` would also allow a leading `+`
+            if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(::Error::invalid());
+            }
+            let parsed = s.parse::<u64>().map_err(|_| ::Error::invalid())?;
 
             if let Some(prev) = len {
                 if prev != parsed {
@@ -74,6 +75,35 @@ impl Header for ContentLength {
     }
 }
 
+impl ContentLength {
+    /// Returns the length, in octets.
+    pub fn len(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns true if the length is zero.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Adds to the length, returning `None` if it would overflow.
+    pub fn checked_add(self, other: u64) -> Option<ContentLength> {
+        self.0.checked_add(other).map(ContentLength)
+    }
+}
+
+impl From<u64> for ContentLength {
+    fn from(len: u64) -> ContentLength {
+        ContentLength(len)
+    }
+}
+
+impl PartialEq<u64> for ContentLength {
+    fn eq(&self, other: &u64) -> bool {
+        self.0 == *other
+    }
+}
+
 /*
 __hyper__tm!(ContentLength, tests {
     // Testcase from RFC
@@ -93,3 +123,45 @@ __hyper__tm!(ContentLength, tests {
     test_header!(test_duplicates_vary, vec![b"5", b"6", b"5"], None);
 });
 */
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_decode;
+    use super::*;
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            test_decode::<ContentLength>(&["3495"]),
+            Some(ContentLength(3495))
+        );
+        assert_eq!(
+            test_decode::<ContentLength>(&["5", "5"]),
+            Some(ContentLength(5))
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<ContentLength>(&["34v95"]), None);
+        assert_eq!(test_decode::<ContentLength>(&["-1"]), None);
+        assert_eq!(test_decode::<ContentLength>(&["+5"]), None);
+        assert_eq!(test_decode::<ContentLength>(&[" 5"]), None);
+        assert_eq!(test_decode::<ContentLength>(&[""]), None);
+        assert_eq!(test_decode::<ContentLength>(&["5", "6", "5"]), None);
+        assert_eq!(
+            test_decode::<ContentLength>(&["18446744073709551616"]),
+            None
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let len = ContentLength::from(10);
+        assert_eq!(len, 10);
+        assert_eq!(len.len(), 10);
+        assert!(!len.is_empty());
+        assert_eq!(len.checked_add(5), Some(ContentLength(15)));
+        assert_eq!(ContentLength(u64::MAX).checked_add(1), None);
+    }
+}
@@ -1,3 +1,4 @@
+use super::location::has_scheme;
 use HeaderValue;
 
 /// `Content-Location` header, defined in
@@ -24,6 +25,16 @@ use HeaderValue;
 ///
 /// # Examples
 ///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{ContentLocation, Header, HeaderValue};
+///
+/// let value = HeaderValue::from_static("http://www.example.org/hypertext/Overview.html");
+/// let loc = ContentLocation::decode(&mut std::iter::once(&value)).unwrap();
+///
+/// assert!(loc.is_absolute());
+/// ```
 #[derive(Clone, Debug, PartialEq)]
 pub struct ContentLocation(HeaderValue);
 
@@ -32,6 +43,28 @@ derive_header! {
     name: CONTENT_LOCATION
 }
 
+impl ContentLocation {
+    /// Returns the location as a string, or `None` if it contains bytes
+    /// that aren't visible ASCII.
+    pub fn to_str(&self) -> Option<&str> {
+        self.0.to_str().ok()
+    }
+
+    /// Returns whether the location is an absolute URI rather than a
+    /// partial one.
+    pub fn is_absolute(&self) -> bool {
+        has_scheme(self.0.as_bytes())
+    }
+
+    /// Resolves the location against the effective request URL.
+    ///
+    /// Returns `None` if the location can't be parsed as a URL reference.
+    #[cfg(feature = "url")]
+    pub fn resolve(&self, base: &::url::Url) -> Option<::url::Url> {
+        base.join(self.to_str()?).ok()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::test_decode;
@@ -52,4 +85,22 @@ mod tests {
 
         assert_eq!(loc, ContentLocation(HeaderValue::from_static(s)));
     }
+
+    #[test]
+    fn is_absolute() {
+        let loc = |s| test_decode::<ContentLocation>(&[s]).unwrap();
+        assert!(loc("http://www.example.org/hypertext/Overview.html").is_absolute());
+        assert!(!loc("/hypertext/Overview.html").is_absolute());
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn resolve() {
+        let base = ::url::Url::parse("http://www.example.org/docs/").unwrap();
+        let loc = test_decode::<ContentLocation>(&["Overview.html"]).unwrap();
+        assert_eq!(
+            loc.resolve(&base).unwrap().as_str(),
+            "http://www.example.org/docs/Overview.html"
+        );
+    }
 }
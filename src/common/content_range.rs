@@ -1,6 +1,7 @@
 use std::fmt;
 use std::ops::{Bound, RangeBounds};
 
+use super::Range;
 use {util, HeaderValue};
 
 /// Content-Range, described in [RFC7233](https://tools.ietf.org/html/rfc7233#section-4.2)
@@ -48,6 +49,9 @@ error_type!(InvalidContentRange);
 
 impl ContentRange {
     /// Construct a new `Content-Range: bytes ..` header.
+    ///
+    /// Returns an error if the range is empty, or doesn't fit within the
+    /// complete length.
     pub fn bytes(
         range: impl RangeBounds<u64>,
         complete_length: impl Into<Option<u64>>,
@@ -55,19 +59,22 @@ impl ContentRange {
         let complete_length = complete_length.into();
 
         let start = match range.start_bound() {
-            Bound::Included(&s) => s,
-            Bound::Excluded(&s) => s + 1,
-            Bound::Unbounded => 0,
+            Bound::Included(&s) => Some(s),
+            Bound::Excluded(&s) => s.checked_add(1),
+            Bound::Unbounded => Some(0),
         };
+        let start = start.ok_or(InvalidContentRange { _inner: () })?;
 
         let end = match range.end_bound() {
-            Bound::Included(&e) => e,
-            Bound::Excluded(&e) => e - 1,
-            Bound::Unbounded => match complete_length {
-                Some(max) => max - 1,
-                None => return Err(InvalidContentRange { _inner: () }),
-            },
+            Bound::Included(&e) => Some(e),
+            Bound::Excluded(&e) => e.checked_sub(1),
+            Bound::Unbounded => complete_length.and_then(|max| max.checked_sub(1)),
         };
+        let end = end.ok_or(InvalidContentRange { _inner: () })?;
+
+        if start > end || !is_within(end, complete_length) {
+            return Err(InvalidContentRange { _inner: () });
+        }
 
         Ok(ContentRange {
             range: Some((start, end)),
@@ -75,6 +82,58 @@ impl ContentRange {
         })
     }
 
+    /// Construct a `Content-Range: bytes start-end/complete` header, where
+    /// `range_end` is the last byte included, as it's written in the
+    /// header.
+    ///
+    /// Fails under the same conditions as [`bytes`](#method.bytes).
+    pub fn from_range(
+        range_start: u64,
+        range_end: u64,
+        complete: u64,
+    ) -> Result<ContentRange, InvalidContentRange> {
+        ContentRange::bytes(range_start..=range_end, complete)
+    }
+
+    /// Works out the `Content-Range` of the response to a request with a
+    /// single range, for a representation of `complete_length` bytes.
+    ///
+    /// If the range is satisfiable, this is the range the server should
+    /// send with `206 Partial Content`. Otherwise it is
+    /// `bytes */complete_length`, for a `416 Range Not Satisfiable`
+    /// response, which `bytes_range` returning `None` tells apart.
+    ///
+    /// Returns `None` if the request has several ranges, which need a
+    /// `multipart/byteranges` response instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::{ContentRange, Range};
+    ///
+    /// let range = Range::bytes(100..200).unwrap();
+    ///
+    /// let cr = ContentRange::for_range(&range, 150).unwrap();
+    /// assert_eq!(cr.bytes_range(), Some((100, 149)));
+    ///
+    /// let cr = ContentRange::for_range(&range, 50).unwrap();
+    /// assert_eq!(cr.bytes_range(), None);
+    /// ```
+    pub fn for_range(range: &Range, complete_length: u64) -> Option<ContentRange> {
+        if range.iter().nth(1).is_some() {
+            return None;
+        }
+
+        Some(match range.resolve(complete_length).next() {
+            Some(bytes) => ContentRange {
+                range: Some((*bytes.start(), *bytes.end())),
+                complete_length: Some(complete_length),
+            },
+            None => ContentRange::unsatisfied_bytes(complete_length),
+        })
+    }
+
     /// Create a new `ContentRange` stating the range could not be satisfied.
     ///
     /// The passed argument is the complete length of the entity.
@@ -128,7 +187,7 @@ impl ::Header for ContentRange {
                     let (first_byte, last_byte) = split_in_two(range, '-')?;
                     let first_byte = first_byte.parse().ok()?;
                     let last_byte = last_byte.parse().ok()?;
-                    if last_byte < first_byte {
+                    if last_byte < first_byte || !is_within(last_byte, complete_length) {
                         return None;
                     }
                     Some((first_byte, last_byte))
@@ -169,6 +228,13 @@ impl ::Header for ContentRange {
     }
 }
 
+fn is_within(last_byte: u64, complete_length: Option<u64>) -> bool {
+    match complete_length {
+        Some(len) => last_byte < len,
+        None => true,
+    }
+}
+
 fn split_in_two(s: &str, separator: char) -> Option<(&str, &str)> {
     let mut iter = s.splitn(2, separator);
     match (iter.next(), iter.next()) {
@@ -177,6 +243,97 @@ fn split_in_two(s: &str, separator: char) -> Option<(&str, &str)> {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn bytes() {
+        let cr = ContentRange::bytes(200..=1023, 2048).unwrap();
+        assert_eq!(cr.bytes_range(), Some((200, 1023)));
+        assert_eq!(cr.bytes_len(), Some(2048));
+
+        let headers = test_encode(cr);
+        assert_eq!(headers["content-range"], "bytes 200-1023/2048");
+
+        let cr = ContentRange::bytes(0..500, None).unwrap();
+        let headers = test_encode(cr);
+        assert_eq!(headers["content-range"], "bytes 0-499/*");
+
+        let cr = ContentRange::bytes(100.., 200).unwrap();
+        assert_eq!(cr.bytes_range(), Some((100, 199)));
+    }
+
+    #[test]
+    fn bytes_invalid() {
+        assert!(ContentRange::bytes((Bound::Included(10), Bound::Included(5)), 100).is_err());
+        assert!(ContentRange::bytes((Bound::Included(0), Bound::Excluded(0)), 100).is_err());
+        assert!(ContentRange::bytes(0..=100, 100).is_err());
+        assert!(ContentRange::bytes(0.., 0).is_err());
+        assert!(ContentRange::bytes(0.., None).is_err());
+        assert!(ContentRange::bytes((Bound::Excluded(u64::MAX), Bound::Unbounded), None).is_err());
+    }
+
+    #[test]
+    fn unsatisfied_bytes() {
+        let cr = ContentRange::unsatisfied_bytes(2048);
+        assert_eq!(cr.bytes_range(), None);
+        assert_eq!(cr.bytes_len(), Some(2048));
+
+        let headers = test_encode(cr);
+        assert_eq!(headers["content-range"], "bytes */2048");
+    }
+
+    #[test]
+    fn from_range() {
+        let cr = ContentRange::from_range(0, 499, 1234).unwrap();
+        assert_eq!(cr.bytes_range(), Some((0, 499)));
+        assert_eq!(cr.bytes_len(), Some(1234));
+        assert_eq!(test_encode(cr)["content-range"], "bytes 0-499/1234");
+
+        assert!(ContentRange::from_range(5, 4, 10).is_err());
+        assert!(ContentRange::from_range(0, 10, 10).is_err());
+    }
+
+    #[test]
+    fn for_range() {
+        let for_range = |s: &str, len: u64| {
+            let range = test_decode::<Range>(&[s]).unwrap();
+            ContentRange::for_range(&range, len).map(|cr| test_encode(cr)["content-range"].clone())
+        };
+
+        assert_eq!(for_range("bytes=0-499", 1234).unwrap(), "bytes 0-499/1234");
+        assert_eq!(
+            for_range("bytes=1000-", 1234).unwrap(),
+            "bytes 1000-1233/1234"
+        );
+        assert_eq!(
+            for_range("bytes=-100", 1234).unwrap(),
+            "bytes 1134-1233/1234"
+        );
+        assert_eq!(
+            for_range("bytes=0-5000", 1234).unwrap(),
+            "bytes 0-1233/1234"
+        );
+        assert_eq!(for_range("bytes=2000-", 1234).unwrap(), "bytes */1234");
+        assert_eq!(for_range("bytes=0-0", 0).unwrap(), "bytes */0");
+        assert_eq!(for_range("bytes=0-1, 5-9", 1234), None);
+    }
+
+    #[test]
+    fn decode() {
+        let cr = test_decode::<ContentRange>(&["bytes 0-499/500"]).unwrap();
+        assert_eq!(cr, ContentRange::bytes(0..500, 500).unwrap());
+
+        let cr = test_decode::<ContentRange>(&["bytes */500"]).unwrap();
+        assert_eq!(cr, ContentRange::unsatisfied_bytes(500));
+
+        assert_eq!(test_decode::<ContentRange>(&["bytes 499-0/500"]), None);
+        assert_eq!(test_decode::<ContentRange>(&["bytes 0-500/500"]), None);
+    }
+}
+
 /*
         test_header!(test_bytes,
             vec![b"bytes 0-499/500"],
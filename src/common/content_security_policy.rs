@@ -0,0 +1,342 @@
+use std::fmt;
+
+use util::IterExt;
+use HeaderValue;
+
+/// `Content-Security-Policy` header, defined in
+/// [CSP Level 3](https://www.w3.org/TR/CSP3/#csp-header)
+///
+/// The `Content-Security-Policy` response header delivers a policy that
+/// restricts which resources the user agent may load for the page, and
+/// where from. A policy is a list of directives, each a name followed by
+/// a space-separated list of sources.
+///
+/// A response may carry several policies, one per field, and all of them
+/// are enforced. Use `HeaderMapExt::typed_get_all` to decode each one.
+///
+/// # ABNF
+///
+/// ```text
+/// Content-Security-Policy = 1#serialized-policy
+/// serialized-policy       = serialized-directive *( OWS ";" [ OWS serialized-directive ] )
+/// serialized-directive    = directive-name [ RWS directive-value ]
+/// directive-name          = 1*( ALPHA / DIGIT / "-" )
+/// directive-value         = *( %x09 / %x20-%x2B / %x2D-%x3A / %x3C-%x7E )
+/// ```
+///
+/// # Example values
+///
+/// * `default-src 'self'`
+/// * `default-src 'none'; script-src 'self' 'unsafe-inline'; img-src *`
+/// * `upgrade-insecure-requests`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::ContentSecurityPolicy;
+///
+/// let csp = ContentSecurityPolicy::new("default-src", &["'self'"])
+///     .with_directive("img-src", &["'self'", "https://images.example"]);
+///
+/// let img_src = csp.directive("img-src").unwrap().collect::<Vec<_>>();
+/// assert_eq!(img_src, vec!["'self'", "https://images.example"]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContentSecurityPolicy(pub(super) Policy);
+
+// A single serialized policy, shared with `Content-Security-Policy-Report-Only`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(super) struct Policy {
+    directives: Vec<Directive>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Directive {
+    name: String,
+    sources: Vec<String>,
+}
+
+impl ::Header for ContentSecurityPolicy {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::CONTENT_SECURITY_POLICY
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        Policy::decode(values).map(ContentSecurityPolicy)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(self.0.encode()));
+    }
+
+    fn separate_fields() -> bool {
+        true
+    }
+}
+
+impl ContentSecurityPolicy {
+    /// Creates a policy with its first directive, to add more to with
+    /// `with_directive`.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as `with_directive`.
+    pub fn new<I>(name: &str, sources: I) -> ContentSecurityPolicy
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        ContentSecurityPolicy(Policy::new(name, sources))
+    }
+
+    /// Adds a directive with its sources, replacing any existing directive
+    /// with the same name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't made of letters, digits and `-`, or a source
+    /// is empty or contains whitespace, `;` or `,`.
+    pub fn with_directive<I>(self, name: &str, sources: I) -> ContentSecurityPolicy
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        ContentSecurityPolicy(self.0.with_directive(name, sources))
+    }
+
+    /// Returns the sources of the named directive, if the policy has it.
+    ///
+    /// Directive names are compared case-insensitively.
+    pub fn directive(&self, name: &str) -> Option<impl Iterator<Item = &str>> {
+        self.0.get(name)
+    }
+
+    /// Returns an iterator over the directive names, in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.names()
+    }
+}
+
+impl Policy {
+    pub(super) fn decode<'i, I>(values: &mut I) -> Result<Policy, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let s = values
+            .just_one()
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(::Error::invalid)?;
+        if s.contains(',') {
+            // several policies in one field aren't supported
+            return Err(::Error::invalid());
+        }
+
+        let mut policy = Policy::default();
+        for directive in s.split(';') {
+            let mut tokens = directive.split_ascii_whitespace();
+            let name = match tokens.next() {
+                Some(name) => name,
+                None => continue,
+            };
+            if !is_directive_name(name) {
+                return Err(::Error::invalid());
+            }
+            policy.directives.push(Directive {
+                name: name.to_owned(),
+                sources: tokens.map(str::to_owned).collect(),
+            });
+        }
+
+        if policy.directives.is_empty() {
+            return Err(::Error::invalid());
+        }
+        Ok(policy)
+    }
+
+    // A policy always has a directive, since an empty one can't be decoded.
+    pub(super) fn new<I>(name: &str, sources: I) -> Policy
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        Policy::default().with_directive(name, sources)
+    }
+
+    pub(super) fn encode(&self) -> HeaderValue {
+        ::util::fmt(self)
+    }
+
+    pub(super) fn with_directive<I>(mut self, name: &str, sources: I) -> Policy
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        assert!(
+            is_directive_name(name),
+            "invalid directive name: {:?}",
+            name
+        );
+        let sources = sources
+            .into_iter()
+            .map(|source| {
+                let source = source.as_ref();
+                assert!(is_source(source), "invalid directive source: {:?}", source);
+                source.to_owned()
+            })
+            .collect();
+
+        let directive = Directive {
+            name: name.to_owned(),
+            sources,
+        };
+        match self
+            .directives
+            .iter_mut()
+            .find(|d| d.name.eq_ignore_ascii_case(name))
+        {
+            Some(existing) => *existing = directive,
+            None => self.directives.push(directive),
+        }
+        self
+    }
+
+    // Only the first directive with a name counts, later ones are ignored.
+    pub(super) fn get(&self, name: &str) -> Option<impl Iterator<Item = &str>> {
+        self.directives
+            .iter()
+            .find(|d| d.name.eq_ignore_ascii_case(name))
+            .map(|d| d.sources.iter().map(|s| s.as_str()))
+    }
+
+    pub(super) fn names(&self) -> impl Iterator<Item = &str> {
+        self.directives.iter().map(|d| d.name.as_str())
+    }
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, directive) in self.directives.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            f.write_str(&directive.name)?;
+            for source in &directive.sources {
+                write!(f, " {}", source)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_directive_name(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+fn is_source(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_graphic() && b != b';' && b != b',')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let csp = test_decode::<ContentSecurityPolicy>(&[
+            "default-src 'none';  script-src 'self' 'unsafe-inline' ; ; img-src *; upgrade-insecure-requests",
+        ])
+        .unwrap();
+        assert_eq!(
+            csp.names().collect::<Vec<_>>(),
+            vec![
+                "default-src",
+                "script-src",
+                "img-src",
+                "upgrade-insecure-requests"
+            ]
+        );
+        assert_eq!(
+            csp.directive("Script-Src").unwrap().collect::<Vec<_>>(),
+            vec!["'self'", "'unsafe-inline'"]
+        );
+        assert_eq!(
+            csp.directive("upgrade-insecure-requests").unwrap().count(),
+            0
+        );
+        assert!(csp.directive("style-src").is_none());
+    }
+
+    #[test]
+    fn duplicate_directive_first_wins() {
+        let csp = test_decode::<ContentSecurityPolicy>(&["img-src 'self'; img-src *"]).unwrap();
+        assert_eq!(
+            csp.directive("img-src").unwrap().collect::<Vec<_>>(),
+            vec!["'self'"]
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<ContentSecurityPolicy>(&[""]), None);
+        assert_eq!(test_decode::<ContentSecurityPolicy>(&[" ; "]), None);
+        assert_eq!(test_decode::<ContentSecurityPolicy>(&["img_src *"]), None);
+        assert_eq!(
+            test_decode::<ContentSecurityPolicy>(&["img-src *, script-src 'self'"]),
+            None
+        );
+    }
+
+    #[test]
+    fn multiple_policies() {
+        let mut map = ::HeaderMap::new();
+        map.append(
+            "content-security-policy",
+            "default-src 'self'".parse().unwrap(),
+        );
+        map.append("content-security-policy", "img-src *".parse().unwrap());
+
+        use HeaderMapExt;
+        let policies = map.typed_get_all::<ContentSecurityPolicy>();
+        assert_eq!(policies.len(), 2);
+        assert!(policies[1].directive("img-src").is_some());
+    }
+
+    #[test]
+    fn round_trip() {
+        let raw = "default-src 'self'; script-src 'self' 'unsafe-inline' 'nonce-abc123'; upgrade-insecure-requests";
+        let csp = test_decode::<ContentSecurityPolicy>(&[raw]).unwrap();
+        let headers = test_encode(csp);
+        assert_eq!(headers["content-security-policy"], raw);
+    }
+
+    #[test]
+    fn builder() {
+        let csp = ContentSecurityPolicy::new("default-src", &["'none'"])
+            .with_directive("img-src", &["'self'"])
+            .with_directive("DEFAULT-SRC", &["'self'"])
+            .with_directive("upgrade-insecure-requests", Vec::<String>::new());
+
+        let headers = test_encode(csp);
+        assert_eq!(
+            headers["content-security-policy"],
+            "DEFAULT-SRC 'self'; img-src 'self'; upgrade-insecure-requests"
+        );
+
+        let csp = ContentSecurityPolicy::new("upgrade-insecure-requests", Vec::<String>::new());
+        let headers = test_encode(csp);
+        assert_eq!(
+            headers["content-security-policy"],
+            "upgrade-insecure-requests"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn builder_invalid_source() {
+        let _ = ContentSecurityPolicy::new("img-src", &["a;b"]);
+    }
+}
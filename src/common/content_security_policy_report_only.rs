@@ -0,0 +1,108 @@
+use super::content_security_policy::Policy;
+use HeaderValue;
+
+/// `Content-Security-Policy-Report-Only` header, defined in
+/// [CSP Level 3](https://www.w3.org/TR/CSP3/#cspro-header)
+///
+/// The `Content-Security-Policy-Report-Only` response header delivers a
+/// policy that isn't enforced. Violations are only reported, so a new
+/// policy can be tried out before switching it to
+/// [`ContentSecurityPolicy`](struct.ContentSecurityPolicy.html), which
+/// has the same syntax.
+///
+/// # Example values
+///
+/// * `default-src 'self'; report-uri /csp-reports`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::ContentSecurityPolicyReportOnly;
+///
+/// let csp = ContentSecurityPolicyReportOnly::new("script-src", &["'self'"])
+///     .with_directive("report-uri", &["/csp-reports"]);
+///
+/// assert!(csp.directive("report-uri").is_some());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContentSecurityPolicyReportOnly(Policy);
+
+impl ::Header for ContentSecurityPolicyReportOnly {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::CONTENT_SECURITY_POLICY_REPORT_ONLY
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        Policy::decode(values).map(ContentSecurityPolicyReportOnly)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(self.0.encode()));
+    }
+
+    fn separate_fields() -> bool {
+        true
+    }
+}
+
+impl ContentSecurityPolicyReportOnly {
+    /// Creates a policy with its first directive, to add more to with
+    /// `with_directive`.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as
+    /// `ContentSecurityPolicy::with_directive`.
+    pub fn new<I>(name: &str, sources: I) -> ContentSecurityPolicyReportOnly
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        ContentSecurityPolicyReportOnly(Policy::new(name, sources))
+    }
+
+    /// Adds a directive with its sources, replacing any existing directive
+    /// with the same name.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as
+    /// `ContentSecurityPolicy::with_directive`.
+    pub fn with_directive<I>(self, name: &str, sources: I) -> ContentSecurityPolicyReportOnly
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        ContentSecurityPolicyReportOnly(self.0.with_directive(name, sources))
+    }
+
+    /// Returns the sources of the named directive, if the policy has it.
+    pub fn directive(&self, name: &str) -> Option<impl Iterator<Item = &str>> {
+        self.0.get(name)
+    }
+
+    /// Returns an iterator over the directive names, in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.names()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let raw = "default-src 'self'; report-uri /csp-reports";
+        let csp = test_decode::<ContentSecurityPolicyReportOnly>(&[raw]).unwrap();
+        assert_eq!(
+            csp.directive("report-uri").unwrap().collect::<Vec<_>>(),
+            vec!["/csp-reports"]
+        );
+
+        let headers = test_encode(csp);
+        assert_eq!(headers["content-security-policy-report-only"], raw);
+    }
+}
@@ -2,6 +2,10 @@ use std::fmt;
 
 use mime::{self, Mime};
 
+use util::quoted_string;
+#[cfg(feature = "getrandom")]
+use util::random_bytes;
+
 /// `Content-Type` header, defined in
 /// [RFC7231](http://tools.ietf.org/html/rfc7231#section-3.1.1.5)
 ///
@@ -92,6 +96,155 @@ impl ContentType {
     pub fn octet_stream() -> ContentType {
         ContentType(mime::APPLICATION_OCTET_STREAM)
     }
+
+    /// Create a `Content-Type: multipart/form-data` header with the given
+    /// boundary.
+    ///
+    /// The boundary is quoted if it isn't a token.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `boundary` isn't 1 to 70 `bchars`, or ends with a space.
+    pub fn multipart_form_data_with_boundary(boundary: &str) -> ContentType {
+        ContentType::multipart(mime::FORM_DATA.as_str(), boundary)
+    }
+
+    /// Create a `multipart` `Content-Type` with a new random boundary, such
+    /// as `multipart/mixed; boundary=3f6c0a1b9d2e4c7a8b5f0e1d2c3b4a59`.
+    ///
+    /// Returns the boundary too, to delimit the parts with. It's 32
+    /// lowercase hex digits, which are `bcharsnospace`, so it never needs
+    /// quoting.
+    ///
+    /// The boundary comes from the operating system's secure random
+    /// source, and this needs the `getrandom` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `subtype` isn't a token, or the operating system can't
+    /// provide random bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::ContentType;
+    ///
+    /// # #[cfg(feature = "getrandom")]
+    /// # fn main() {
+    /// let (ct, boundary) = ContentType::multipart_random_boundary("mixed");
+    ///
+    /// assert_eq!(ct.mime().subtype(), "mixed");
+    /// assert_eq!(ct.boundary(), Some(&*boundary));
+    /// # }
+    /// # #[cfg(not(feature = "getrandom"))]
+    /// # fn main() {}
+    /// ```
+    #[cfg(feature = "getrandom")]
+    pub fn multipart_random_boundary(subtype: &str) -> (ContentType, String) {
+        let boundary = random_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+        (ContentType::multipart(subtype, &boundary), boundary)
+    }
+
+    fn multipart(subtype: &str, boundary: &str) -> ContentType {
+        assert!(
+            quoted_string::is_token(subtype),
+            "invalid multipart subtype: {:?}",
+            subtype
+        );
+        assert!(is_boundary(boundary), "invalid boundary: {:?}", boundary);
+        let mime = if quoted_string::is_token(boundary) {
+            format!("multipart/{}; boundary={}", subtype, boundary)
+        } else {
+            format!("multipart/{}; boundary=\"{}\"", subtype, boundary)
+        };
+        ContentType(mime.parse().expect("multipart type is a valid Mime"))
+    }
+
+    /// Returns the parsed media type.
+    ///
+    /// The header is stored as a `Mime`, so this is only a borrow.
+    pub fn mime(&self) -> &Mime {
+        &self.0
+    }
+
+    /// Returns the `charset` parameter, if any, such as `utf-8`.
+    ///
+    /// Charsets are case-insensitive, and are always lowercased.
+    pub fn charset(&self) -> Option<&str> {
+        self.0.get_param(mime::CHARSET).map(|name| name.as_str())
+    }
+
+    /// Returns the `boundary` parameter of a multipart type, if any.
+    pub fn boundary(&self) -> Option<&str> {
+        self.0.get_param(mime::BOUNDARY).map(|name| name.as_str())
+    }
+
+    /// Check if both have the same type and subtype, such as `text/html`,
+    /// ignoring case and any parameters.
+    pub fn essence_eq(&self, other: &ContentType) -> bool {
+        self.0
+            .essence_str()
+            .eq_ignore_ascii_case(other.0.essence_str())
+    }
+
+    /// Check if both are the same media type, with the same parameters in
+    /// any order.
+    ///
+    /// Parameter names are compared ignoring case, as is the `charset`
+    /// value. Other values, like `boundary`, must match exactly. The
+    /// `PartialEq` impl compares just as `Mime` does instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// # extern crate mime;
+    /// use headers::ContentType;
+    ///
+    /// let parse = |s: &str| ContentType::from(s.parse::<mime::Mime>().unwrap());
+    ///
+    /// let a = parse("text/html; charset=utf-8; level=1");
+    /// let b = parse("Text/HTML; Level=1; charset=UTF-8");
+    /// assert!(a.semantically_eq(&b));
+    ///
+    /// assert!(a.essence_eq(&ContentType::html()));
+    /// assert!(!a.semantically_eq(&ContentType::html()));
+    /// ```
+    pub fn semantically_eq(&self, other: &ContentType) -> bool {
+        let params = |ct: &ContentType| {
+            let mut params =
+                ct.0.params()
+                    .map(|(name, value)| {
+                        let name = name.as_str().to_ascii_lowercase();
+                        let value = if name == "charset" {
+                            value.as_str().to_ascii_lowercase()
+                        } else {
+                            value.as_str().to_owned()
+                        };
+                        (name, value)
+                    })
+                    .collect::<Vec<_>>();
+            params.sort();
+            params
+        };
+        self.essence_eq(other) && params(self) == params(other)
+    }
+}
+
+// boundary := 0*69<bchars> bcharsnospace, from RFC 2046.
+fn is_boundary(s: &str) -> bool {
+    let is_bchar = |b: u8| {
+        matches!(
+            b,
+            b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b' ' | b'\'' | b'(' | b')' | b'+' | b'_'
+                | b',' | b'-' | b'.' | b'/' | b':' | b'=' | b'?'
+        )
+    };
+    (1..=70).contains(&s.len()) && !s.ends_with(' ') && s.bytes().all(is_bchar)
 }
 
 impl ::Header for ContentType {
@@ -129,6 +282,12 @@ impl From<ContentType> for mime::Mime {
     }
 }
 
+impl AsRef<mime::Mime> for ContentType {
+    fn as_ref(&self) -> &mime::Mime {
+        &self.0
+    }
+}
+
 impl fmt::Display for ContentType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(&self.0, f)
@@ -137,8 +296,9 @@ impl fmt::Display for ContentType {
 
 #[cfg(test)]
 mod tests {
-    use super::super::test_decode;
-    use super::ContentType;
+    use super::super::{test_decode, test_encode};
+    use super::{is_boundary, ContentType};
+    use mime::{self, Mime};
 
     #[test]
     fn json() {
@@ -148,6 +308,116 @@ mod tests {
         );
     }
 
+    #[test]
+    fn charset() {
+        let ct = test_decode::<ContentType>(&["text/html; charset=ISO-8859-1"]).unwrap();
+        assert_eq!(ct.charset(), Some("iso-8859-1"));
+        assert_eq!(ct.boundary(), None);
+
+        assert_eq!(ContentType::text_utf8().charset(), Some("utf-8"));
+        assert_eq!(ContentType::json().charset(), None);
+    }
+
+    #[test]
+    fn boundary() {
+        let ct = test_decode::<ContentType>(&[
+            "multipart/form-data; boundary=\"---abc\"; charset=utf-8",
+        ])
+        .unwrap();
+        assert_eq!(ct.boundary(), Some("---abc"));
+        assert_eq!(ct.charset(), Some("utf-8"));
+    }
+
+    #[test]
+    fn multipart_form_data_with_boundary() {
+        let ct = ContentType::multipart_form_data_with_boundary("---abc");
+        assert_eq!(ct.mime().essence_str(), "multipart/form-data");
+        assert_eq!(ct.boundary(), Some("---abc"));
+
+        let ct = ContentType::multipart_form_data_with_boundary("simple boundary");
+        assert_eq!(ct.boundary(), Some("simple boundary"));
+        let headers = test_encode(ct);
+        assert_eq!(
+            headers["content-type"],
+            "multipart/form-data; boundary=\"simple boundary\""
+        );
+    }
+
+    #[test]
+    fn multipart_invalid_boundary() {
+        assert!(is_boundary(&"a".repeat(70)));
+        assert!(!is_boundary(&"a".repeat(71)));
+        for boundary in &["", "trailing ", "semi;colon", "quo\"te", "caf\u{e9}"] {
+            assert!(!is_boundary(boundary), "{:?}", boundary);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn multipart_form_data_invalid_boundary_panics() {
+        let _ = ContentType::multipart_form_data_with_boundary("semi;colon");
+    }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn multipart_random_boundary() {
+        let (ct, boundary) = ContentType::multipart_random_boundary("byteranges");
+        assert_eq!(ct.mime().essence_str(), "multipart/byteranges");
+        assert_eq!(ct.boundary(), Some(boundary.as_str()));
+        assert_eq!(boundary.len(), 32);
+        assert!(boundary.bytes().all(|b| b.is_ascii_hexdigit()));
+
+        let (_, other) = ContentType::multipart_random_boundary("byteranges");
+        assert_ne!(boundary, other);
+    }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    #[should_panic]
+    fn multipart_random_boundary_invalid_subtype() {
+        let _ = ContentType::multipart_random_boundary("form data");
+    }
+
+    #[test]
+    fn essence_eq() {
+        let html = test_decode::<ContentType>(&["text/HTML; charset=UTF-8"]).unwrap();
+        assert!(html.essence_eq(&ContentType::html()));
+        assert!(!html.essence_eq(&ContentType::text()));
+
+        let svg = test_decode::<ContentType>(&["image/svg+xml"]).unwrap();
+        assert!(!svg.essence_eq(&test_decode::<ContentType>(&["image/svg"]).unwrap()));
+    }
+
+    #[test]
+    fn semantically_eq() {
+        let a = test_decode::<ContentType>(&["multipart/form-data; charset=utf-8; boundary=AbC"])
+            .unwrap();
+        let b =
+            test_decode::<ContentType>(&["Multipart/Form-Data; Boundary=AbC; CHARSET=\"UTF-8\""])
+                .unwrap();
+        assert!(a.semantically_eq(&b));
+        assert!(b.semantically_eq(&a));
+
+        let c = test_decode::<ContentType>(&["multipart/form-data; charset=utf-8; boundary=abc"])
+            .unwrap();
+        assert!(a.essence_eq(&c));
+        assert!(!a.semantically_eq(&c));
+
+        let d = test_decode::<ContentType>(&["multipart/form-data; boundary=AbC"]).unwrap();
+        assert!(!a.semantically_eq(&d));
+    }
+
+    #[test]
+    fn mime_conversions() {
+        let ct = ContentType::from(mime::TEXT_HTML_UTF_8);
+        assert_eq!(ct.mime(), &mime::TEXT_HTML_UTF_8);
+        assert_eq!(ct.mime().subtype(), mime::HTML);
+
+        let as_ref: &Mime = ct.as_ref();
+        assert_eq!(as_ref.type_(), mime::TEXT);
+        assert_eq!(Mime::from(ct), mime::TEXT_HTML_UTF_8);
+    }
+
     bench_header!(bench_plain, ContentType, "text/plain");
     bench_header!(bench_json, ContentType, "application/json");
     bench_header!(
@@ -1,4 +1,9 @@
+use std::iter::FromIterator;
+
+use super::set_cookie::{is_cookie_value, unquote};
+use util::quoted_string;
 use util::{FlatCsv, SemiColon};
+use HeaderValue;
 
 /// `Cookie` header, defined in [RFC6265](http://tools.ietf.org/html/rfc6265#section-5.4)
 ///
@@ -9,10 +14,28 @@ use util::{FlatCsv, SemiColon};
 /// When the user agent generates an HTTP request, the user agent MUST NOT
 /// attach more than one Cookie header field.
 ///
+/// Collecting a `Cookie` from name-value pairs panics if a name isn't a
+/// `token`, or a value isn't a valid `cookie-value`.
+///
 /// # Example values
 /// * `SID=31d4d96e407aad42`
 /// * `SID=31d4d96e407aad42; lang=en-US`
 ///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::Cookie;
+///
+/// let cookie = vec![
+///     ("SID".to_owned(), "31d4d96e407aad42".to_owned()),
+///     ("lang".to_owned(), "en-US".to_owned()),
+/// ]
+/// .into_iter()
+/// .collect::<Cookie>();
+///
+/// assert_eq!(cookie.get("lang"), Some("en-US"));
+/// ```
 #[derive(Clone, Debug)]
 pub struct Cookie(FlatCsv<SemiColon>);
 
@@ -54,14 +77,110 @@ impl Cookie {
     }
 
     /// Iterator the key-value pairs of this `Cookie` header.
+    ///
+    /// Values wrapped in double quotes are returned without them.
     pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
         self.0.iter().filter_map(|kv| {
             let mut iter = kv.splitn(2, '=');
             let key = iter.next()?.trim();
             let val = iter.next()?.trim();
-            Some((key, val))
+            Some((key, unquote(val)))
         })
     }
+
+    /// Add a cookie-pair, replacing any pairs that already have the name.
+    ///
+    /// Together with [`SetCookie::to_cookie_pair`] and [`remove`], this is
+    /// enough to fold received `SetCookie` headers into the `Cookie` sent
+    /// with the next request.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a `token`, or `value` isn't a valid
+    /// `cookie-value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use std::time::SystemTime;
+    /// use headers::{Cookie, SetCookie};
+    ///
+    /// let mut cookie = vec![("lang".to_owned(), "en-US".to_owned())]
+    ///     .into_iter()
+    ///     .collect::<Cookie>();
+    ///
+    /// let received = SystemTime::now();
+    /// let set_cookies = vec![
+    ///     SetCookie::build("SID", "31d4d96e407aad42").finish(),
+    ///     SetCookie::build("lang", "").max_age(Default::default()).finish(),
+    /// ];
+    /// for set_cookie in set_cookies {
+    ///     if set_cookie.is_expired(received, SystemTime::now()) {
+    ///         cookie.remove(set_cookie.name());
+    ///     } else {
+    ///         let (name, value) = set_cookie.to_cookie_pair();
+    ///         cookie.append(&name, &value);
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(cookie.get("SID"), Some("31d4d96e407aad42"));
+    /// assert_eq!(cookie.get("lang"), None);
+    /// ```
+    ///
+    /// [`SetCookie::to_cookie_pair`]: struct.SetCookie.html#method.to_cookie_pair
+    /// [`remove`]: #method.remove
+    pub fn append(&mut self, name: &str, value: &str) {
+        assert!(
+            quoted_string::is_token(name),
+            "invalid Cookie name: {:?}",
+            name
+        );
+        assert!(is_cookie_value(value), "invalid Cookie value: {:?}", value);
+        let pair = HeaderValue::from_str(&format!("{}={}", name, value))
+            .expect("cookie-pair is a valid HeaderValue");
+        self.retain_other_names(name, Some(pair));
+    }
+
+    /// Remove every cookie-pair with the name.
+    pub fn remove(&mut self, name: &str) {
+        self.retain_other_names(name, None);
+    }
+
+    fn retain_other_names(&mut self, name: &str, extra: Option<HeaderValue>) {
+        let values = self
+            .0
+            .iter()
+            .filter(|kv| !kv.is_empty())
+            .filter(|kv| kv.split('=').next().map(str::trim) != Some(name))
+            .map(|kv| HeaderValue::from_str(kv).expect("split from a valid HeaderValue"))
+            .chain(extra)
+            .collect::<Vec<_>>();
+        self.0 = values.iter().collect();
+    }
+}
+
+/// Assembles a `Cookie` from name-value pairs.
+impl FromIterator<(String, String)> for Cookie {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let values = iter
+            .into_iter()
+            .map(|(name, value)| {
+                assert!(
+                    quoted_string::is_token(&name),
+                    "invalid Cookie name: {:?}",
+                    name
+                );
+                assert!(is_cookie_value(&value), "invalid Cookie value: {:?}", value);
+                HeaderValue::from_str(&format!("{}={}", name, value))
+                    .expect("cookie-pair is a valid HeaderValue")
+            })
+            .collect::<Vec<_>>();
+        Cookie(values.iter().collect())
+    }
 }
 
 /*
@@ -83,7 +202,7 @@ impl PartialEq for Cookie {
 
 #[cfg(test)]
 mod tests {
-    use super::super::test_decode;
+    use super::super::{test_decode, test_encode};
     use super::Cookie;
 
     #[test]
@@ -109,6 +228,65 @@ mod tests {
         assert_eq!(cookie.get("lol"), Some("cat"));
     }
 
+    #[test]
+    fn test_iter() {
+        let cookie = test_decode::<Cookie>(&["foo=bar;baz=\"quoted\";  empty=; invalid"]).unwrap();
+
+        assert_eq!(
+            cookie.iter().collect::<Vec<_>>(),
+            vec![("foo", "bar"), ("baz", "quoted"), ("empty", "")]
+        );
+        assert_eq!(cookie.get("baz"), Some("quoted"));
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let cookie = vec![
+            ("foo".to_owned(), "bar".to_owned()),
+            ("baz".to_owned(), "\"quux\"".to_owned()),
+        ]
+        .into_iter()
+        .collect::<Cookie>();
+
+        assert_eq!(cookie.get("baz"), Some("quux"));
+
+        let headers = test_encode(cookie);
+        assert_eq!(headers["cookie"], "foo=bar; baz=\"quux\"");
+    }
+
+    #[test]
+    fn test_append() {
+        let mut cookie = test_decode::<Cookie>(&["foo=bar; lol=cat;; invalid"]).unwrap();
+
+        cookie.append("foo", "\"baz\"");
+        cookie.append("new", "");
+        assert_eq!(cookie.get("foo"), Some("baz"));
+        assert_eq!(cookie.len(), 3);
+
+        let headers = test_encode(cookie);
+        assert_eq!(headers["cookie"], "lol=cat; invalid; foo=\"baz\"; new=");
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut cookie = test_decode::<Cookie>(&["foo=bar", "lol=cat; foo=baz"]).unwrap();
+
+        cookie.remove("foo");
+        cookie.remove("missing");
+        assert_eq!(cookie.get("foo"), None);
+        assert_eq!(cookie.get("lol"), Some("cat"));
+
+        cookie.remove("lol");
+        assert_eq!(cookie.len(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_append_invalid() {
+        let mut cookie = test_decode::<Cookie>(&["foo=bar"]).unwrap();
+        cookie.append("foo", "a;b");
+    }
+
     /*
     #[test]
     fn test_set_and_get() {
@@ -0,0 +1,78 @@
+use HeaderName;
+
+static CROSS_ORIGIN_EMBEDDER_POLICY: HeaderName =
+    HeaderName::from_static("cross-origin-embedder-policy");
+
+token_enum_header! {
+    /// `Cross-Origin-Embedder-Policy` header, defined in the
+    /// [HTML standard](https://html.spec.whatwg.org/multipage/browsers.html#coep)
+    ///
+    /// The `Cross-Origin-Embedder-Policy` response header controls whether
+    /// a document may load cross-origin resources that haven't opted in,
+    /// with CORS or `Cross-Origin-Resource-Policy`.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Cross-Origin-Embedder-Policy = %s"unsafe-none" / %s"require-corp"
+    ///                                / %s"credentialless"
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `require-corp`
+    /// * `credentialless`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::{CrossOriginEmbedderPolicy, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.typed_insert(CrossOriginEmbedderPolicy::RequireCorp);
+    ///
+    /// assert_eq!(headers["cross-origin-embedder-policy"], "require-corp");
+    /// ```
+    pub enum CrossOriginEmbedderPolicy {
+        /// `unsafe-none`, the default, which allows any resource.
+        UnsafeNone => "unsafe-none",
+        /// `require-corp`, cross-origin resources must opt in to being
+        /// loaded.
+        RequireCorp => "require-corp",
+        /// `credentialless`, cross-origin `no-cors` requests are sent
+        /// without credentials instead.
+        Credentialless => "credentialless",
+    }
+
+    name: &CROSS_ORIGIN_EMBEDDER_POLICY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            test_decode::<CrossOriginEmbedderPolicy>(&["Require-Corp"]),
+            Some(CrossOriginEmbedderPolicy::RequireCorp)
+        );
+        assert_eq!(
+            test_decode::<CrossOriginEmbedderPolicy>(&["credentialless"]),
+            Some(CrossOriginEmbedderPolicy::Credentialless)
+        );
+        assert_eq!(test_decode::<CrossOriginEmbedderPolicy>(&["require"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(CrossOriginEmbedderPolicy::UnsafeNone);
+        assert_eq!(headers["cross-origin-embedder-policy"], "unsafe-none");
+        assert_eq!(
+            CrossOriginEmbedderPolicy::RequireCorp.as_str(),
+            "require-corp"
+        );
+    }
+}
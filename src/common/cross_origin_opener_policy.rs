@@ -0,0 +1,80 @@
+use HeaderName;
+
+static CROSS_ORIGIN_OPENER_POLICY: HeaderName =
+    HeaderName::from_static("cross-origin-opener-policy");
+
+token_enum_header! {
+    /// `Cross-Origin-Opener-Policy` header, defined in the
+    /// [HTML standard](https://html.spec.whatwg.org/multipage/browsers.html#cross-origin-opener-policies)
+    ///
+    /// The `Cross-Origin-Opener-Policy` response header decides whether a
+    /// document shares its browsing context group with cross-origin
+    /// documents it opens, or that open it. Together with
+    /// `Cross-Origin-Embedder-Policy` it makes a page cross-origin
+    /// isolated.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Cross-Origin-Opener-Policy = %s"unsafe-none" / %s"same-origin-allow-popups"
+    ///                              / %s"same-origin"
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `same-origin`
+    /// * `same-origin-allow-popups`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::{CrossOriginOpenerPolicy, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.typed_insert(CrossOriginOpenerPolicy::SameOrigin);
+    ///
+    /// assert_eq!(headers["cross-origin-opener-policy"], "same-origin");
+    /// ```
+    pub enum CrossOriginOpenerPolicy {
+        /// `unsafe-none`, the default, which shares the group with any
+        /// document.
+        UnsafeNone => "unsafe-none",
+        /// `same-origin-allow-popups`, which keeps references to popups
+        /// this document opens.
+        SameOriginAllowPopups => "same-origin-allow-popups",
+        /// `same-origin`, which isolates the document from cross-origin
+        /// documents.
+        SameOrigin => "same-origin",
+    }
+
+    name: &CROSS_ORIGIN_OPENER_POLICY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            test_decode::<CrossOriginOpenerPolicy>(&[" same-origin-ALLOW-popups "]),
+            Some(CrossOriginOpenerPolicy::SameOriginAllowPopups)
+        );
+        assert_eq!(
+            test_decode::<CrossOriginOpenerPolicy>(&["unsafe-none"]),
+            Some(CrossOriginOpenerPolicy::UnsafeNone)
+        );
+        assert_eq!(test_decode::<CrossOriginOpenerPolicy>(&["same-site"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(CrossOriginOpenerPolicy::SameOriginAllowPopups);
+        assert_eq!(
+            headers["cross-origin-opener-policy"],
+            "same-origin-allow-popups"
+        );
+    }
+}
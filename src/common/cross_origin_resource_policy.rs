@@ -0,0 +1,80 @@
+use HeaderName;
+
+static CROSS_ORIGIN_RESOURCE_POLICY: HeaderName =
+    HeaderName::from_static("cross-origin-resource-policy");
+
+token_enum_header! {
+    /// `Cross-Origin-Resource-Policy` header, defined in the
+    /// [Fetch standard](https://fetch.spec.whatwg.org/#cross-origin-resource-policy-header)
+    ///
+    /// The `Cross-Origin-Resource-Policy` response header lets a server
+    /// refuse `no-cors` requests for a resource from other origins or
+    /// sites, protecting it from being loaded by pages it wasn't meant
+    /// for.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// Cross-Origin-Resource-Policy = %s"same-origin" / %s"same-site" / %s"cross-origin"
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `same-origin`
+    /// * `cross-origin`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::{CrossOriginResourcePolicy, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.typed_insert(CrossOriginResourcePolicy::SameSite);
+    ///
+    /// assert_eq!(headers["cross-origin-resource-policy"], "same-site");
+    /// ```
+    pub enum CrossOriginResourcePolicy {
+        /// `same-origin`, only requests from the same origin may load it.
+        SameOrigin => "same-origin",
+        /// `same-site`, only requests from the same site may load it.
+        SameSite => "same-site",
+        /// `cross-origin`, any origin may load it.
+        CrossOrigin => "cross-origin",
+    }
+
+    name: &CROSS_ORIGIN_RESOURCE_POLICY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            test_decode::<CrossOriginResourcePolicy>(&["Same-Origin"]),
+            Some(CrossOriginResourcePolicy::SameOrigin)
+        );
+        assert_eq!(
+            test_decode::<CrossOriginResourcePolicy>(&["cross-origin"]),
+            Some(CrossOriginResourcePolicy::CrossOrigin)
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<CrossOriginResourcePolicy>(&["same"]), None);
+        assert_eq!(
+            test_decode::<CrossOriginResourcePolicy>(&["same-site", "same-site"]),
+            None
+        );
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(CrossOriginResourcePolicy::SameOrigin);
+        assert_eq!(headers["cross-origin-resource-policy"], "same-origin");
+    }
+}
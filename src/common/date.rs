@@ -33,6 +33,13 @@ derive_header! {
     name: DATE
 }
 
+impl Date {
+    /// Creates a `Date` for the current time.
+    pub fn now() -> Date {
+        Date::from(SystemTime::now())
+    }
+}
+
 impl From<SystemTime> for Date {
     fn from(time: SystemTime) -> Date {
         Date(time.into())
@@ -44,3 +51,31 @@ impl From<Date> for SystemTime {
         date.0.into()
     }
 }
+
+#[cfg(feature = "chrono")]
+impl<Tz: ::chrono::TimeZone> From<::chrono::DateTime<Tz>> for Date {
+    fn from(time: ::chrono::DateTime<Tz>) -> Date {
+        Date(time.into())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Date> for ::chrono::DateTime<::chrono::Utc> {
+    fn from(date: Date) -> ::chrono::DateTime<::chrono::Utc> {
+        date.0.into()
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<::time::OffsetDateTime> for Date {
+    fn from(time: ::time::OffsetDateTime) -> Date {
+        Date(time.into())
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<Date> for ::time::OffsetDateTime {
+    fn from(date: Date) -> ::time::OffsetDateTime {
+        date.0.into()
+    }
+}
@@ -0,0 +1,185 @@
+use util::quoted_string;
+use util::{FlatCsv, TryFromValues};
+use HeaderValue;
+
+static DIGEST: ::HeaderName = ::HeaderName::from_static("digest");
+
+/// `Digest` header, defined in
+/// [RFC3230](https://tools.ietf.org/html/rfc3230#section-4.3.2)
+///
+/// The `Digest` header carries one or more digests of the full
+/// representation, each tagged with the algorithm that computed it, so
+/// the recipient can check the payload's integrity.
+///
+/// Values are kept exactly as sent; hash algorithms such as `sha-256`
+/// use base64, but this doesn't decode them.
+///
+/// # ABNF
+///
+/// ```text
+/// Digest          = "Digest" ":" #(instance-digest)
+/// instance-digest = digest-algorithm "=" <encoded digest output>
+/// digest-algorithm = token
+/// ```
+///
+/// # Example values
+///
+/// * `sha-256=X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=`
+/// * `SHA=thvDyvhfIqlvFe+A9MYgxAfm1q5=, unixsum=30637`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::Digest;
+///
+/// let digest = Digest::new("sha-256", "X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=");
+///
+/// assert_eq!(
+///     digest.get("SHA-256"),
+///     Some("X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=")
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Digest(Vec<(String, String)>);
+
+impl ::Header for Digest {
+    fn name() -> &'static ::HeaderName {
+        &DIGEST
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        let digests = csv
+            .iter()
+            .filter(|elem| !elem.is_empty())
+            .map(|elem| parse_instance(elem).ok_or_else(::Error::invalid))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if digests.is_empty() {
+            return Err(::Error::invalid());
+        }
+        Ok(Digest(digests))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let s = self
+            .0
+            .iter()
+            .map(|(algo, value)| format!("{}={}", algo, value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        values.extend(::std::iter::once(
+            HeaderValue::from_str(&s).expect("digests are checked when added"),
+        ));
+    }
+}
+
+impl Digest {
+    /// Creates a `Digest` with a single algorithm and encoded value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the algorithm isn't a token, or the value is empty or
+    /// contains a comma or whitespace.
+    pub fn new(algorithm: &str, value: &str) -> Digest {
+        Digest(Vec::new()).with_digest(algorithm, value)
+    }
+
+    /// Adds another algorithm and encoded value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the algorithm isn't a token, or the value is empty or
+    /// contains a comma or whitespace.
+    pub fn with_digest(mut self, algorithm: &str, value: &str) -> Self {
+        assert!(
+            quoted_string::is_token(algorithm),
+            "invalid digest algorithm: {:?}",
+            algorithm
+        );
+        assert!(is_digest_value(value), "invalid digest value: {:?}", value);
+        self.0.push((algorithm.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Returns the encoded value for an algorithm, ignoring case.
+    pub fn get(&self, algorithm: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|&(algo, _)| algo.eq_ignore_ascii_case(algorithm))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns an iterator over the algorithms and their encoded values,
+    /// in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(algo, value)| (algo.as_str(), value.as_str()))
+    }
+}
+
+fn parse_instance(s: &str) -> Option<(String, String)> {
+    let eq = s.find('=')?;
+    let algo = s[..eq].trim();
+    let value = s[eq + 1..].trim();
+    if quoted_string::is_token(algo) && is_digest_value(value) {
+        Some((algo.to_owned(), value.to_owned()))
+    } else {
+        None
+    }
+}
+
+fn is_digest_value(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_graphic() && b != b',' && b != b'"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_keeps_raw_values() {
+        let digest = test_decode::<Digest>(&[
+            "SHA=thvDyvhfIqlvFe+A9MYgxAfm1q5=, unixsum=30637",
+            "sha-256=X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=",
+        ])
+        .unwrap();
+        assert_eq!(digest.get("sha"), Some("thvDyvhfIqlvFe+A9MYgxAfm1q5="));
+        assert_eq!(digest.get("UNIXSUM"), Some("30637"));
+        assert_eq!(
+            digest.get("sha-256"),
+            Some("X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=")
+        );
+        assert_eq!(digest.get("md5"), None);
+        assert_eq!(
+            digest.iter().map(|(algo, _)| algo).collect::<Vec<_>>(),
+            vec!["SHA", "unixsum", "sha-256"]
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Digest>(&[""]), None);
+        assert_eq!(test_decode::<Digest>(&["sha-256"]), None);
+        assert_eq!(test_decode::<Digest>(&["sha-256="]), None);
+        assert_eq!(test_decode::<Digest>(&["sha 256=abc"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let digest = Digest::new("sha-256", "abc=").with_digest("unixsum", "30637");
+        let headers = test_encode(digest);
+        assert_eq!(headers["digest"], "sha-256=abc=, unixsum=30637");
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_invalid_value() {
+        let _ = Digest::new("sha-256", "a b");
+    }
+}
@@ -39,6 +39,117 @@ derive_header! {
 }
 
 impl ETag {
+    /// Construct a strong entity-tag, quoting the opaque `tag`.
+    ///
+    /// Fails if `tag` has a character that isn't allowed in an entity-tag,
+    /// such as a double quote, whitespace or a control character.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::ETag;
+    ///
+    /// let etag = ETag::strong("33a64df5").unwrap();
+    /// assert_eq!(etag.as_str(), Some("\"33a64df5\""));
+    ///
+    /// assert!(ETag::strong("not \"valid\"").is_err());
+    /// ```
+    pub fn strong(tag: &str) -> Result<ETag, InvalidETag> {
+        EntityTag::from_tag(false, tag)
+            .map(ETag)
+            .ok_or(InvalidETag { _inner: () })
+    }
+
+    /// Construct a weak entity-tag, as `W/"tag"`.
+    ///
+    /// Fails under the same conditions as [`ETag::strong`].
+    pub fn weak(tag: &str) -> Result<ETag, InvalidETag> {
+        EntityTag::from_tag(true, tag)
+            .map(ETag)
+            .ok_or(InvalidETag { _inner: () })
+    }
+
+    /// Construct a strong entity-tag of the form `"<len>-<hash>"`, from the
+    /// length of the representation and a hash of it.
+    ///
+    /// Any hash can be used, as long as it's written with characters
+    /// allowed in an entity-tag, such as in hex or base64.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hash` is empty, or has a character that isn't allowed in
+    /// an entity-tag.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::ETag;
+    ///
+    /// let etag = ETag::from_parts(1024, "9f86d081884c7d65");
+    /// assert_eq!(etag.as_str(), Some("\"1024-9f86d081884c7d65\""));
+    /// assert_eq!(etag.parts(), Some((1024, "9f86d081884c7d65")));
+    /// ```
+    pub fn from_parts(len: u64, hash: &str) -> ETag {
+        assert!(!hash.is_empty(), "ETag hash is empty");
+        ETag::strong(&format!("{}-{}", len, hash))
+            .unwrap_or_else(|_| panic!("invalid ETag hash: {:?}", hash))
+    }
+
+    /// Get the length and hash from an entity-tag of the form
+    /// `"<len>-<hash>"`, as made by [`ETag::from_parts`].
+    ///
+    /// A weak entity-tag is split the same way, since intermediaries may
+    /// weaken a tag they've modified. Returns `None` if the tag doesn't
+    /// have this form.
+    pub fn parts(&self) -> Option<(u64, &str)> {
+        let tag = self.tag()?;
+        let dash = tag.find('-')?;
+        let (len, hash) = (&tag[..dash], &tag[dash + 1..]);
+        if len.is_empty() || !len.bytes().all(|b| b.is_ascii_digit()) || hash.is_empty() {
+            return None;
+        }
+        Some((len.parse().ok()?, hash))
+    }
+
+    /// Get the entity-tag as it's sent in the header, such as `W/"xyzzy"`.
+    ///
+    /// Returns `None` if the tag contains `obs-text` that isn't valid
+    /// UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        ::std::str::from_utf8(self.0.as_bytes()).ok()
+    }
+
+    /// Check if this is a weak entity-tag, prefixed by `W/`.
+    pub fn is_weak(&self) -> bool {
+        self.0.is_weak()
+    }
+
+    /// Get the opaque tag, without the `W/` prefix and quotes.
+    ///
+    /// Returns `None` if the tag contains `obs-text` that isn't valid
+    /// UTF-8.
+    pub fn tag(&self) -> Option<&str> {
+        ::std::str::from_utf8(self.0.tag()).ok()
+    }
+
+    /// Compare using the strong comparison function of
+    /// [RFC7232](https://tools.ietf.org/html/rfc7232#section-2.3.2).
+    ///
+    /// Both must be strong, and their opaque tags must match exactly.
+    pub fn strong_eq(&self, other: &ETag) -> bool {
+        self.0.strong_eq(&other.0)
+    }
+
+    /// Compare using the weak comparison function of
+    /// [RFC7232](https://tools.ietf.org/html/rfc7232#section-2.3.2).
+    ///
+    /// The opaque tags must match exactly, ignoring whether either is weak.
+    pub fn weak_eq(&self, other: &ETag) -> bool {
+        self.0.weak_eq(&other.0)
+    }
+
     #[cfg(test)]
     pub(crate) fn from_static(src: &'static str) -> ETag {
         ETag(EntityTag::from_static(src))
@@ -56,7 +167,113 @@ impl FromStr for ETag {
 
         EntityTag::from_owned(val)
             .map(ETag)
-            .ok_or_else(|| InvalidETag { _inner: () })
+            .ok_or(InvalidETag { _inner: () })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accessors() {
+        let strong = ETag::from_static("\"xyzzy\"");
+        assert!(!strong.is_weak());
+        assert_eq!(strong.tag(), Some("xyzzy"));
+
+        let weak = ETag::from_static("W/\"xyzzy\"");
+        assert!(weak.is_weak());
+        assert_eq!(weak.tag(), Some("xyzzy"));
+
+        assert_eq!(ETag::from_static("\"\"").tag(), Some(""));
+    }
+
+    #[test]
+    fn constructors() {
+        let strong = ETag::strong("xyzzy").unwrap();
+        assert!(!strong.is_weak());
+        assert_eq!(strong.tag(), Some("xyzzy"));
+        assert_eq!(strong.as_str(), Some("\"xyzzy\""));
+        assert_eq!(strong, ETag::from_static("\"xyzzy\""));
+
+        let weak = ETag::weak("xyzzy").unwrap();
+        assert!(weak.is_weak());
+        assert_eq!(weak.as_str(), Some("W/\"xyzzy\""));
+
+        assert_eq!(ETag::strong("").unwrap().as_str(), Some("\"\""));
+        assert_eq!(ETag::weak("caf\u{e9}").unwrap().tag(), Some("caf\u{e9}"));
+
+        for tag in &["a\"b", "a b", "a\tb", "a\nb", "a\x7fb"] {
+            assert!(ETag::strong(tag).is_err(), "{:?}", tag);
+            assert!(ETag::weak(tag).is_err(), "{:?}", tag);
+        }
+    }
+
+    #[test]
+    fn obs_text() {
+        let val = ::HeaderValue::from_bytes(b"\"caf\xe9\"").unwrap();
+        let etag = ETag(EntityTag::from_owned(val).unwrap());
+        assert_eq!(etag.as_str(), None);
+        assert_eq!(etag.tag(), None);
+        assert_eq!(etag.parts(), None);
+    }
+
+    #[test]
+    fn from_parts() {
+        let etag = ETag::from_parts(0, "e3b0c442-98fc");
+        assert!(!etag.is_weak());
+        assert_eq!(etag.as_str(), Some("\"0-e3b0c442-98fc\""));
+        assert_eq!(etag.parts(), Some((0, "e3b0c442-98fc")));
+
+        let weak = ETag::from_static("W/\"42-abc\"");
+        assert_eq!(weak.parts(), Some((42, "abc")));
+    }
+
+    #[test]
+    fn parts_invalid() {
+        for tag in &[
+            "\"xyzzy\"",
+            "\"-abc\"",
+            "\"42-\"",
+            "\"+42-abc\"",
+            "\"4.2-abc\"",
+            "\"99999999999999999999-abc\"",
+            "\"\"",
+        ] {
+            assert_eq!(ETag::from_static(tag).parts(), None, "{}", tag);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_parts_invalid_hash() {
+        let _ = ETag::from_parts(1, "not a hash");
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_parts_empty_hash() {
+        let _ = ETag::from_parts(1, "");
+    }
+
+    #[test]
+    fn comparison() {
+        // From RFC7232, Section 2.3.2
+        let w1 = ETag::from_static("W/\"1\"");
+        let w2 = ETag::from_static("W/\"2\"");
+        let s1 = ETag::from_static("\"1\"");
+
+        assert!(!w1.strong_eq(&w1));
+        assert!(w1.weak_eq(&w1));
+
+        assert!(!w1.strong_eq(&w2));
+        assert!(!w1.weak_eq(&w2));
+
+        assert!(!w1.strong_eq(&s1));
+        assert!(w1.weak_eq(&s1));
+
+        assert!(s1.strong_eq(&s1));
+        assert!(s1.weak_eq(&s1));
     }
 }
 
@@ -1,6 +1,7 @@
 use std::fmt;
 
 use util::IterExt;
+use HeaderValue;
 
 /// The `Expect` header.
 ///
@@ -11,6 +12,10 @@ use util::IterExt;
 /// >
 /// >    Expect  = "100-continue"
 ///
+/// Other expectations are kept as they were sent, so that a proxy can
+/// forward them, and an origin server can answer them with
+/// `417 Expectation Failed`.
+///
 /// # Example
 ///
 /// ```
@@ -18,13 +23,68 @@ use util::IterExt;
 /// use headers::Expect;
 ///
 /// let expect = Expect::CONTINUE;
+/// assert!(expect.is_continue());
 /// ```
 #[derive(Clone, PartialEq)]
-pub struct Expect(());
+pub struct Expect(Expectation);
+
+#[derive(Clone, PartialEq)]
+enum Expectation {
+    Continue,
+    Other(HeaderValue),
+}
 
 impl Expect {
     /// "100-continue"
-    pub const CONTINUE: Expect = Expect(());
+    pub const CONTINUE: Expect = Expect(Expectation::Continue);
+
+    /// Creates an `Expect: 100-continue` header.
+    pub fn continue_() -> Expect {
+        Expect::CONTINUE
+    }
+
+    /// Checks if this is `100-continue`, ignoring case.
+    pub fn is_continue(&self) -> bool {
+        self.0 == Expectation::Continue
+    }
+
+    /// Checks if the server should answer with `100 Continue` before
+    /// reading the request body.
+    ///
+    /// A server may also skip the interim response and just read the body,
+    /// or answer with a final status instead, and a request that isn't at
+    /// least HTTP/1.1 shouldn't be sent `100 Continue` at all.
+    pub fn should_send_continue(&self) -> bool {
+        self.is_continue()
+    }
+
+    /// Checks if this has an expectation other than `100-continue`, which
+    /// the server should answer with `417 Expectation Failed`.
+    ///
+    /// An `Expect` header that fails to decode, such as an empty one, is
+    /// unsupported too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// # extern crate http;
+    /// use headers::{Expect, HeaderMapExt};
+    ///
+    /// let mut map = http::HeaderMap::new();
+    /// map.insert("expect", "100-continue, sandwich".parse().unwrap());
+    ///
+    /// let status = match map.typed_try_get::<Expect>() {
+    ///     Ok(Some(ref expect)) if expect.should_send_continue() => Some(100),
+    ///     Ok(Some(ref expect)) if expect.is_unsupported() => Some(417),
+    ///     Ok(_) => None,
+    ///     Err(_) => Some(417),
+    /// };
+    /// assert_eq!(status, Some(417));
+    /// ```
+    pub fn is_unsupported(&self) -> bool {
+        !self.is_continue()
+    }
 }
 
 impl ::Header for Expect {
@@ -36,31 +96,38 @@ impl ::Header for Expect {
         values
             .just_one()
             .and_then(|value| {
-                if value == "100-continue" {
+                if value.as_bytes().eq_ignore_ascii_case(b"100-continue") {
                     Some(Expect::CONTINUE)
-                } else {
+                } else if value.is_empty() {
                     None
+                } else {
+                    Some(Expect(Expectation::Other(value.clone())))
                 }
             })
             .ok_or_else(::Error::invalid)
     }
 
     fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
-        values.extend(::std::iter::once(::HeaderValue::from_static(
-            "100-continue",
-        )));
+        let value = match self.0 {
+            Expectation::Continue => ::HeaderValue::from_static("100-continue"),
+            Expectation::Other(ref value) => value.clone(),
+        };
+        values.extend(::std::iter::once(value));
     }
 }
 
 impl fmt::Debug for Expect {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_tuple("Expect").field(&"100-continue").finish()
+        match self.0 {
+            Expectation::Continue => f.debug_tuple("Expect").field(&"100-continue").finish(),
+            Expectation::Other(ref value) => f.debug_tuple("Expect").field(value).finish(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::super::test_decode;
+    use super::super::{test_decode, test_encode};
     use super::Expect;
 
     #[test]
@@ -69,11 +136,31 @@ mod tests {
             test_decode::<Expect>(&["100-continue"]),
             Some(Expect::CONTINUE),
         );
+        assert!(test_decode::<Expect>(&["100-Continue"])
+            .unwrap()
+            .is_continue());
     }
 
     #[test]
-    fn expectation_failed() {
-        assert_eq!(test_decode::<Expect>(&["sandwich"]), None,);
+    fn unknown_expectation_is_kept() {
+        let expect = test_decode::<Expect>(&["sandwich"]).unwrap();
+        assert!(!expect.is_continue());
+
+        let headers = test_encode(expect);
+        assert_eq!(headers["expect"], "sandwich");
+    }
+
+    #[test]
+    fn send_continue_or_unsupported() {
+        let expect = test_decode::<Expect>(&["100-continue"]).unwrap();
+        assert!(expect.should_send_continue());
+        assert!(!expect.is_unsupported());
+
+        for value in &["sandwich", "100-continue, sandwich", "100-continue; x=1"] {
+            let expect = test_decode::<Expect>(&[value]).unwrap();
+            assert!(!expect.should_send_continue(), "{:?}", value);
+            assert!(expect.is_unsupported(), "{:?}", value);
+        }
     }
 
     #[test]
@@ -83,4 +170,9 @@ mod tests {
             None,
         );
     }
+
+    #[test]
+    fn empty() {
+        assert_eq!(test_decode::<Expect>(&[""]), None);
+    }
 }
@@ -1,5 +1,5 @@
-use std::time::SystemTime;
-use util::HttpDate;
+use std::time::{Duration, SystemTime};
+use util::{HttpDate, IterExt};
 
 /// `Expires` header, defined in [RFC7234](http://tools.ietf.org/html/rfc7234#section-5.3)
 ///
@@ -18,6 +18,11 @@ use util::HttpDate;
 ///
 /// # Example values
 /// * `Thu, 01 Dec 1994 16:00:00 GMT`
+/// * `0`
+///
+/// Following the RFC, a value that isn't a valid date, such as `0`, is
+/// decoded as a time in the past, so the response is already stale. It's
+/// encoded again as `0`, and converts to the Unix epoch.
 ///
 /// # Example
 ///
@@ -30,21 +35,129 @@ use util::HttpDate;
 /// let expires = Expires::from(time);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Expires(HttpDate);
+pub struct Expires(Option<HttpDate>);
+
+impl ::Header for Expires {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::EXPIRES
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i ::HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        values
+            .just_one()
+            .map(|value| Expires(HttpDate::from_val(value)))
+            .ok_or_else(::Error::invalid)
+    }
 
-derive_header! {
-    Expires(_),
-    name: EXPIRES
+    fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
+        let value = match self.0 {
+            Some(date) => date.into(),
+            None => ::HeaderValue::from_static("0"),
+        };
+        values.extend(::std::iter::once(value));
+    }
+}
+
+impl Expires {
+    /// Creates an `Expires` for the current time, so the response is
+    /// already stale.
+    pub fn now() -> Expires {
+        Expires::from(SystemTime::now())
+    }
+
+    /// Creates an `Expires` for `dur` from now.
+    pub fn after(dur: Duration) -> Expires {
+        Expires::from(SystemTime::now() + dur)
+    }
+
+    /// Checks if this time has been reached at `now`.
+    ///
+    /// HTTP dates only have whole seconds, so this is true from the
+    /// start of the second the response expires at.
+    ///
+    /// A value that wasn't a valid date is always in the past.
+    pub fn is_past(&self, now: SystemTime) -> bool {
+        SystemTime::from(*self) <= now
+    }
+
+    fn date(&self) -> HttpDate {
+        self.0
+            .unwrap_or_else(|| HttpDate::from(SystemTime::UNIX_EPOCH))
+    }
 }
 
 impl From<SystemTime> for Expires {
     fn from(time: SystemTime) -> Expires {
-        Expires(time.into())
+        Expires(Some(time.into()))
     }
 }
 
 impl From<Expires> for SystemTime {
     fn from(date: Expires) -> SystemTime {
-        date.0.into()
+        date.date().into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: ::chrono::TimeZone> From<::chrono::DateTime<Tz>> for Expires {
+    fn from(time: ::chrono::DateTime<Tz>) -> Expires {
+        Expires(Some(time.into()))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Expires> for ::chrono::DateTime<::chrono::Utc> {
+    fn from(date: Expires) -> ::chrono::DateTime<::chrono::Utc> {
+        date.date().into()
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<::time::OffsetDateTime> for Expires {
+    fn from(time: ::time::OffsetDateTime) -> Expires {
+        Expires(Some(time.into()))
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<Expires> for ::time::OffsetDateTime {
+    fn from(date: Expires) -> ::time::OffsetDateTime {
+        date.date().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn is_past() {
+        let now = SystemTime::now();
+        let expires = Expires::after(Duration::from_secs(3600));
+        assert!(!expires.is_past(now));
+        assert!(expires.is_past(now + Duration::from_secs(3601)));
+
+        assert!(Expires::now().is_past(SystemTime::now()));
+    }
+
+    #[test]
+    fn invalid_dates_are_past() {
+        let now = SystemTime::UNIX_EPOCH;
+        for value in &["0", "-1", "soon"] {
+            let expires = test_decode::<Expires>(&[value]).unwrap();
+            assert!(expires.is_past(now), "{:?}", value);
+            assert_eq!(test_encode(expires)["expires"], "0");
+        }
+
+        assert_eq!(test_decode::<Expires>(&["0", "0"]), None);
+    }
+
+    #[test]
+    fn valid_date_round_trips() {
+        let date = "Thu, 01 Dec 1994 16:00:00 GMT";
+        let expires = test_decode::<Expires>(&[date]).unwrap();
+        assert!(!expires.is_past(SystemTime::UNIX_EPOCH));
+        assert_eq!(test_encode(expires)["expires"], date);
     }
 }
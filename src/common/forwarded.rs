@@ -0,0 +1,337 @@
+use std::fmt;
+use std::iter::FromIterator;
+
+use util::csv::encode_list;
+use util::quoted_string::{self, Quoted};
+use util::{FlatCsv, TryFromValues};
+use HeaderValue;
+
+/// `Forwarded` header, defined in [RFC7239](https://tools.ietf.org/html/rfc7239#section-4)
+///
+/// The `Forwarded` header field contains information from the client-facing
+/// side of proxy servers that is altered or lost when a proxy is involved
+/// in the path of the request. Each proxy appends an element to the list.
+///
+/// # ABNF
+///
+/// ```text
+/// Forwarded         = 1#forwarded-element
+/// forwarded-element = [ forwarded-pair ] *( ";" [ forwarded-pair ] )
+/// forwarded-pair    = token "=" value
+/// value             = token / quoted-string
+/// ```
+///
+/// # Example values
+///
+/// * `for="_gazonk"`
+/// * `For="[2001:db8:cafe::17]:4711"`
+/// * `for=192.0.2.60;proto=http;by=203.0.113.43`
+/// * `for=192.0.2.43, for=198.51.100.17`
+///
+/// # Examples
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{Forwarded, ForwardedElement};
+///
+/// let forwarded = vec![
+///     ForwardedElement::new()
+///         .with_for("[2001:db8::1]:443")
+///         .with_proto("https"),
+/// ]
+/// .into_iter()
+/// .collect::<Forwarded>();
+///
+/// let first = forwarded.elements().next().unwrap();
+/// assert_eq!(first.for_(), Some("[2001:db8::1]:443"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Forwarded(Vec<ForwardedElement>);
+
+/// A single element of a [`Forwarded`](struct.Forwarded.html) header,
+/// usually added by one proxy.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ForwardedElement {
+    pairs: Vec<(String, String)>,
+}
+
+impl ::Header for Forwarded {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::FORWARDED
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        let value = csv.to_value();
+        let s = value.to_str().map_err(|_| ::Error::invalid())?;
+
+        let mut elements = Vec::new();
+        let mut element = ForwardedElement::new();
+        let mut rest = s.trim_start();
+        while !rest.is_empty() {
+            if rest.starts_with(',') {
+                if !element.pairs.is_empty() {
+                    elements.push(element);
+                    element = ForwardedElement::new();
+                }
+                rest = rest[1..].trim_start();
+                continue;
+            }
+            if rest.starts_with(';') {
+                rest = rest[1..].trim_start();
+                continue;
+            }
+
+            let (pair, after) = parse_pair(rest).ok_or_else(::Error::invalid)?;
+            element.pairs.push(pair);
+            rest = after.trim_start();
+        }
+        if !element.pairs.is_empty() {
+            elements.push(element);
+        }
+
+        if elements.is_empty() {
+            return Err(::Error::invalid());
+        }
+        Ok(Forwarded(elements))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_list(&self.0)));
+    }
+}
+
+impl Forwarded {
+    /// Returns an iterator over the elements, in the order the proxies
+    /// appended them.
+    pub fn elements(&self) -> impl Iterator<Item = &ForwardedElement> {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<ForwardedElement> for Forwarded {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = ForwardedElement>,
+    {
+        Forwarded(iter.into_iter().collect())
+    }
+}
+
+// ===== impl ForwardedElement =====
+
+impl ForwardedElement {
+    /// Creates an empty element, to be filled in with the `with_*` methods.
+    pub fn new() -> ForwardedElement {
+        ForwardedElement::default()
+    }
+
+    /// Sets the `by` parameter, the interface where the request came in.
+    pub fn with_by<V: Into<String>>(self, value: V) -> Self {
+        self.with_param("by", value)
+    }
+
+    /// Sets the `for` parameter, the client that made the request.
+    pub fn with_for<V: Into<String>>(self, value: V) -> Self {
+        self.with_param("for", value)
+    }
+
+    /// Sets the `host` parameter, the `Host` header the proxy received.
+    pub fn with_host<V: Into<String>>(self, value: V) -> Self {
+        self.with_param("host", value)
+    }
+
+    /// Sets the `proto` parameter, the protocol the proxy received.
+    pub fn with_proto<V: Into<String>>(self, value: V) -> Self {
+        self.with_param("proto", value)
+    }
+
+    /// Sets any parameter, replacing an existing one with the same name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a `token`, or `value` contains control
+    /// characters.
+    pub fn with_param<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        let name = name.into();
+        let value = value.into();
+        assert!(
+            quoted_string::is_token(&name),
+            "invalid Forwarded param name: {:?}",
+            name
+        );
+        assert!(
+            value.chars().all(|c| c == '\t' || !c.is_control()),
+            "invalid Forwarded param value: {:?}",
+            value
+        );
+        self.pairs.retain(|(n, _)| !n.eq_ignore_ascii_case(&name));
+        self.pairs.push((name, value));
+        self
+    }
+
+    /// Returns the `by` parameter.
+    pub fn by(&self) -> Option<&str> {
+        self.param("by")
+    }
+
+    /// Returns the `for` parameter.
+    pub fn for_(&self) -> Option<&str> {
+        self.param("for")
+    }
+
+    /// Returns the `host` parameter.
+    pub fn host(&self) -> Option<&str> {
+        self.param("host")
+    }
+
+    /// Returns the `proto` parameter.
+    pub fn proto(&self) -> Option<&str> {
+        self.param("proto")
+    }
+
+    /// Returns the unquoted value of any parameter, including extensions.
+    ///
+    /// Parameter names are case-insensitive.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns an iterator over all parameters, in order.
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs.iter().map(|(n, v)| (n.as_str(), v.as_str()))
+    }
+}
+
+impl fmt::Display for ForwardedElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (name, value)) in self.pairs.iter().enumerate() {
+            if i > 0 {
+                f.write_str(";")?;
+            }
+            if quoted_string::is_token(value) {
+                write!(f, "{}={}", name, value)?;
+            } else {
+                write!(f, "{}={}", name, Quoted(value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_pair(s: &str) -> Option<((String, String), &str)> {
+    let eq = s.find('=')?;
+    let name = s[..eq].trim();
+    if !quoted_string::is_token(name) {
+        return None;
+    }
+
+    let rest = s[eq + 1..].trim_start();
+    let (value, rest) = if rest.starts_with('"') {
+        quoted_string::parse(rest)?
+    } else {
+        let end = rest.find(&[';', ','][..]).unwrap_or(rest.len());
+        let value = rest[..end].trim_end();
+        if !quoted_string::is_token(value) {
+            return None;
+        }
+        (value.to_owned(), &rest[end..])
+    };
+
+    let after = rest.trim_start();
+    if !after.is_empty() && !after.starts_with(';') && !after.starts_with(',') {
+        return None;
+    }
+    Some(((name.to_owned(), value), after))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let forwarded = test_decode::<Forwarded>(&[
+            "for=192.0.2.60;proto=http;by=203.0.113.43;host=example.com",
+        ])
+        .unwrap();
+        let element = forwarded.elements().next().unwrap();
+        assert_eq!(element.for_(), Some("192.0.2.60"));
+        assert_eq!(element.proto(), Some("http"));
+        assert_eq!(element.by(), Some("203.0.113.43"));
+        assert_eq!(element.host(), Some("example.com"));
+    }
+
+    #[test]
+    fn decode_quoted_ipv6() {
+        let forwarded = test_decode::<Forwarded>(&[r#"For="[2001:db8:cafe::17]:4711""#]).unwrap();
+        let element = forwarded.elements().next().unwrap();
+        assert_eq!(element.for_(), Some("[2001:db8:cafe::17]:4711"));
+    }
+
+    #[test]
+    fn decode_multiple_elements() {
+        let forwarded =
+            test_decode::<Forwarded>(&["for=192.0.2.43, for=198.51.100.17", "for=\"_gazonk\""])
+                .unwrap();
+        let fors = forwarded
+            .elements()
+            .map(|element| element.for_().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(fors, vec!["192.0.2.43", "198.51.100.17", "_gazonk"]);
+    }
+
+    #[test]
+    fn decode_extensions() {
+        let forwarded = test_decode::<Forwarded>(&["for=unknown;secret=\"a b\";x=1"]).unwrap();
+        let element = forwarded.elements().next().unwrap();
+        assert_eq!(element.param("secret"), Some("a b"));
+        assert_eq!(
+            element.params().collect::<Vec<_>>(),
+            vec![("for", "unknown"), ("secret", "a b"), ("x", "1")]
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Forwarded>(&[""]), None);
+        assert_eq!(test_decode::<Forwarded>(&["for"]), None);
+        assert_eq!(test_decode::<Forwarded>(&["for=[::1]"]), None);
+        assert_eq!(test_decode::<Forwarded>(&["for=\"unterminated"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let forwarded = vec![
+            ForwardedElement::new()
+                .with_for("[2001:db8::1]:443")
+                .with_proto("https")
+                .with_param("x-trace", "abc"),
+            ForwardedElement::new().with_for("192.0.2.43"),
+        ]
+        .into_iter()
+        .collect::<Forwarded>();
+
+        let headers = test_encode(forwarded);
+        assert_eq!(
+            headers["forwarded"],
+            r#"for="[2001:db8::1]:443";proto=https;x-trace=abc, for=192.0.2.43"#
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let raw = r#"for=_gazonk;by=proxy;ext="with space", for=192.0.2.43"#;
+        let forwarded = test_decode::<Forwarded>(&[raw]).unwrap();
+        let headers = test_encode(forwarded);
+        assert_eq!(headers["forwarded"], raw);
+    }
+}
@@ -0,0 +1,185 @@
+//! Message framing checks, as described in
+//! [RFC7230](https://tools.ietf.org/html/rfc7230#section-3.3.3).
+//!
+//! The length of a message body is given by `Transfer-Encoding` or
+//! `Content-Length`. When a message has both, or its transfer codings
+//! don't end with `chunked`, recipients can disagree about where the body
+//! ends, which is how request smuggling works. A server should reject
+//! such a request with `400 Bad Request` and close the connection.
+//!
+//! # Example
+//!
+//! ```
+//! # extern crate headers;
+//! # extern crate http;
+//! use headers::{framing, ContentLength, HeaderMapExt, TransferEncoding};
+//!
+//! let mut map = http::HeaderMap::new();
+//! map.typed_insert(TransferEncoding::chunked());
+//! assert_eq!(framing::detect_conflict(&map), None);
+//!
+//! map.typed_insert(ContentLength(10));
+//! let err = framing::detect_conflict(&map).unwrap();
+//! assert_eq!(
+//!     err.to_string(),
+//!     "both transfer-encoding \"chunked\" and content-length 10 were sent"
+//! );
+//! ```
+
+use std::error::Error as StdError;
+use std::fmt;
+
+use http::header::{HeaderMap, CONTENT_LENGTH};
+
+use super::{ContentLength, TransferEncoding};
+use HeaderMapExt;
+
+/// A problem with how a message's body is framed, found by
+/// [`detect_conflict`](fn.detect_conflict.html).
+#[derive(Clone, Debug, PartialEq)]
+pub enum FramingError {
+    /// Both `Transfer-Encoding` and `Content-Length` were sent.
+    ///
+    /// `content_length` is `None` if the `Content-Length` couldn't be
+    /// decoded.
+    TransferEncodingAndContentLength {
+        /// The `Transfer-Encoding` that was sent.
+        transfer_encoding: TransferEncoding,
+        /// The `Content-Length` that was sent, if it was valid.
+        content_length: Option<ContentLength>,
+    },
+    /// The final transfer coding isn't `chunked`, so the body can't be
+    /// delimited.
+    NotChunked(TransferEncoding),
+}
+
+/// Checks the headers of a message for framing that recipients could
+/// disagree about.
+///
+/// A `Content-Length` is checked for just by name, so one that fails to
+/// decode still conflicts with a `Transfer-Encoding`. Returns `None` if
+/// the framing is fine, including when neither header was sent.
+///
+/// A response is allowed to end a body that isn't chunked by closing the
+/// connection, so a client may choose to ignore `NotChunked`.
+pub fn detect_conflict(map: &HeaderMap) -> Option<FramingError> {
+    let transfer_encoding = map.typed_get::<TransferEncoding>()?;
+    if map.contains_key(CONTENT_LENGTH) {
+        Some(FramingError::TransferEncodingAndContentLength {
+            transfer_encoding,
+            content_length: map.typed_get::<ContentLength>(),
+        })
+    } else if !transfer_encoding.is_chunked() {
+        Some(FramingError::NotChunked(transfer_encoding))
+    } else {
+        None
+    }
+}
+
+impl fmt::Display for FramingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FramingError::TransferEncodingAndContentLength {
+                ref transfer_encoding,
+                ref content_length,
+            } => {
+                write!(
+                    f,
+                    "both transfer-encoding {:?} ",
+                    Codings(transfer_encoding)
+                )?;
+                match *content_length {
+                    Some(ContentLength(len)) => write!(f, "and content-length {} were sent", len),
+                    None => f.write_str("and an invalid content-length were sent"),
+                }
+            }
+            FramingError::NotChunked(ref transfer_encoding) => write!(
+                f,
+                "transfer-encoding {:?} doesn't end with chunked",
+                Codings(transfer_encoding)
+            ),
+        }
+    }
+}
+
+impl StdError for FramingError {}
+
+struct Codings<'a>(&'a TransferEncoding);
+
+impl<'a> fmt::Debug for Codings<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0.iter().collect::<Vec<_>>().join(", "), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use HeaderValue;
+
+    fn map(headers: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for &(name, value) in headers {
+            map.append(name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    #[test]
+    fn fine() {
+        assert_eq!(detect_conflict(&map(&[])), None);
+        assert_eq!(detect_conflict(&map(&[("content-length", "10")])), None);
+        assert_eq!(
+            detect_conflict(&map(&[("transfer-encoding", "gzip, Chunked")])),
+            None
+        );
+    }
+
+    #[test]
+    fn transfer_encoding_and_content_length() {
+        let err = detect_conflict(&map(&[
+            ("transfer-encoding", "chunked"),
+            ("content-length", "10"),
+        ]))
+        .unwrap();
+        match err {
+            FramingError::TransferEncodingAndContentLength {
+                ref transfer_encoding,
+                content_length,
+            } => {
+                assert!(transfer_encoding.is_chunked());
+                assert_eq!(content_length, Some(ContentLength(10)));
+            }
+            _ => panic!("unexpected {:?}", err),
+        }
+
+        let err = detect_conflict(&map(&[
+            ("transfer-encoding", "gzip"),
+            ("transfer-encoding", "chunked"),
+            ("content-length", "10, 20"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "both transfer-encoding \"gzip, chunked\" and an invalid content-length were sent"
+        );
+    }
+
+    #[test]
+    fn not_chunked() {
+        let err = detect_conflict(&map(&[("transfer-encoding", "chunked, gzip")])).unwrap();
+        match err {
+            FramingError::NotChunked(ref transfer_encoding) => {
+                assert_eq!(
+                    transfer_encoding.iter().collect::<Vec<_>>(),
+                    ["chunked", "gzip"]
+                );
+            }
+            _ => panic!("unexpected {:?}", err),
+        }
+        assert_eq!(
+            err.to_string(),
+            "transfer-encoding \"chunked, gzip\" doesn't end with chunked"
+        );
+    }
+}
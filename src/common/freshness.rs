@@ -0,0 +1,234 @@
+//! Response freshness, as described in
+//! [RFC7234](https://tools.ietf.org/html/rfc7234#section-4.2).
+//!
+//! A cache can reuse a stored response without asking the origin server
+//! while the response is fresh, which is when its age hasn't reached its
+//! freshness lifetime. The lifetime is given by `Cache-Control` or
+//! `Expires`, and the age by `Age` and `Date`.
+//!
+//! # Example
+//!
+//! ```
+//! # extern crate headers;
+//! # extern crate http;
+//! use std::time::{Duration, SystemTime};
+//! use headers::{freshness, Age, CacheControl, Date, HeaderMapExt};
+//! use headers::freshness::Freshness;
+//!
+//! let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+//!
+//! let mut resp = http::HeaderMap::new();
+//! resp.typed_insert(CacheControl::new().with_max_age(Duration::from_secs(60)));
+//! resp.typed_insert(Date::from(now - Duration::from_secs(10)));
+//! resp.typed_insert(Age::from_secs(5));
+//!
+//! assert_eq!(
+//!     freshness::is_fresh(&resp, now),
+//!     Freshness::Fresh(Duration::from_secs(50))
+//! );
+//! ```
+
+use std::time::{Duration, SystemTime};
+
+use http::header::HeaderMap;
+
+use super::{Age, CacheControl, Date, Expires};
+use HeaderMapExt;
+
+/// Whether a stored response can be reused, returned by
+/// [`is_fresh`](fn.is_fresh.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Freshness {
+    /// The response is fresh for this much longer.
+    Fresh(Duration),
+    /// The response has been stale for this long. A zero duration means
+    /// it became stale just now.
+    Stale(Duration),
+    /// The response has a bare `Cache-Control: no-cache`, so it must be
+    /// revalidated with the origin server before every reuse.
+    MustRevalidate,
+}
+
+impl Freshness {
+    /// Check if the response can be reused without revalidating it.
+    pub fn is_fresh(&self) -> bool {
+        matches!(*self, Freshness::Fresh(_))
+    }
+}
+
+/// Computes the freshness of a response at `now`, from its headers.
+///
+/// The freshness lifetime is taken from `s-maxage`, then `max-age`, and
+/// otherwise from `Expires` minus `Date`. This is the lifetime a shared
+/// cache uses; a private cache should ignore `s-maxage`. With none of
+/// these, the lifetime is zero, so the response is stale. Heuristic
+/// freshness is left to the caller.
+///
+/// The age is the larger of the `Age` header and the time since `Date`. A
+/// response without a `Date` is taken to have been generated at `now`.
+/// Headers that fail to decode are ignored, except for an invalid
+/// `Expires`, which is always in the past.
+///
+/// A `no-cache` with field names doesn't force revalidation, but a fresh
+/// response must be reused without the fields in
+/// [`CacheControl::no_cache_fields`](../struct.CacheControl.html#method.no_cache_fields).
+pub fn is_fresh(resp: &HeaderMap, now: SystemTime) -> Freshness {
+    let cache_control = resp.typed_get::<CacheControl>();
+    if let Some(ref cache_control) = cache_control {
+        if cache_control.no_cache() && cache_control.no_cache_fields().is_none() {
+            return Freshness::MustRevalidate;
+        }
+    }
+
+    let date = resp
+        .typed_get::<Date>()
+        .map(SystemTime::from)
+        .unwrap_or(now);
+
+    let lifetime = cache_control
+        .and_then(|cc| cc.s_max_age().or_else(|| cc.max_age()))
+        .or_else(|| {
+            let expires = SystemTime::from(resp.typed_get::<Expires>()?);
+            Some(expires.duration_since(date).unwrap_or_default())
+        })
+        .unwrap_or_default();
+
+    let apparent_age = now.duration_since(date).unwrap_or_default();
+    let age = resp
+        .typed_get::<Age>()
+        .map(Duration::from)
+        .unwrap_or_default()
+        .max(apparent_age);
+
+    if age < lifetime {
+        Freshness::Fresh(lifetime - age)
+    } else {
+        Freshness::Stale(age - lifetime)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use HeaderValue;
+
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn map(headers: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for &(name, value) in headers {
+            map.append(name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    // 1994-11-06 08:49:37 GMT
+    const DATE: u64 = 784_111_777;
+
+    #[test]
+    fn max_age() {
+        let resp = map(&[
+            ("cache-control", "max-age=60"),
+            ("date", "Sun, 06 Nov 1994 08:49:37 GMT"),
+        ]);
+        assert_eq!(
+            is_fresh(&resp, at(DATE + 10)),
+            Freshness::Fresh(Duration::from_secs(50))
+        );
+        assert_eq!(
+            is_fresh(&resp, at(DATE + 70)),
+            Freshness::Stale(Duration::from_secs(10))
+        );
+    }
+
+    #[test]
+    fn s_max_age_preferred() {
+        let resp = map(&[("cache-control", "max-age=60, s-maxage=10")]);
+        assert_eq!(
+            is_fresh(&resp, at(DATE)),
+            Freshness::Fresh(Duration::from_secs(10))
+        );
+    }
+
+    #[test]
+    fn age_header() {
+        let resp = map(&[
+            ("cache-control", "max-age=60"),
+            ("date", "Sun, 06 Nov 1994 08:49:37 GMT"),
+            ("age", "30"),
+        ]);
+        assert_eq!(
+            is_fresh(&resp, at(DATE + 10)),
+            Freshness::Fresh(Duration::from_secs(30))
+        );
+
+        // Without a `Date`, the `Age` is all there is.
+        let resp = map(&[("cache-control", "max-age=60"), ("age", "60")]);
+        assert_eq!(
+            is_fresh(&resp, at(DATE)),
+            Freshness::Stale(Duration::from_secs(0))
+        );
+    }
+
+    #[test]
+    fn expires() {
+        let resp = map(&[
+            ("date", "Sun, 06 Nov 1994 08:49:37 GMT"),
+            ("expires", "Sun, 06 Nov 1994 08:50:37 GMT"),
+        ]);
+        assert_eq!(
+            is_fresh(&resp, at(DATE + 20)),
+            Freshness::Fresh(Duration::from_secs(40))
+        );
+
+        // Without a `Date`, the response is taken to be new.
+        let resp = map(&[("expires", "Sun, 06 Nov 1994 08:50:37 GMT")]);
+        assert_eq!(
+            is_fresh(&resp, at(DATE + 20)),
+            Freshness::Fresh(Duration::from_secs(40))
+        );
+
+        let resp = map(&[("expires", "0")]);
+        assert_eq!(
+            is_fresh(&resp, at(DATE)),
+            Freshness::Stale(Duration::from_secs(0))
+        );
+    }
+
+    #[test]
+    fn max_age_overrides_expires() {
+        let resp = map(&[
+            ("cache-control", "max-age=5"),
+            ("expires", "Sun, 06 Nov 1994 08:50:37 GMT"),
+        ]);
+        assert_eq!(
+            is_fresh(&resp, at(DATE)),
+            Freshness::Fresh(Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn no_cache() {
+        let resp = map(&[("cache-control", "no-cache, max-age=60")]);
+        assert_eq!(is_fresh(&resp, at(DATE)), Freshness::MustRevalidate);
+        assert!(!Freshness::MustRevalidate.is_fresh());
+
+        let resp = map(&[("cache-control", "no-cache=\"set-cookie\", max-age=60")]);
+        assert_eq!(
+            is_fresh(&resp, at(DATE)),
+            Freshness::Fresh(Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn no_lifetime() {
+        let resp = map(&[("date", "Sun, 06 Nov 1994 08:49:37 GMT")]);
+        assert_eq!(
+            is_fresh(&resp, at(DATE + 3)),
+            Freshness::Stale(Duration::from_secs(3))
+        );
+        assert!(!is_fresh(&map(&[]), at(DATE)).is_fresh());
+    }
+}
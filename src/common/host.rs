@@ -9,8 +9,16 @@ pub struct Host(Authority);
 
 impl Host {
     /// Get the hostname, such as example.domain.
+    ///
+    /// IPv6 addresses are returned without their brackets, so
+    /// `[2001:db8::1]:8080` has the hostname `2001:db8::1`.
     pub fn hostname(&self) -> &str {
-        self.0.host()
+        let host = self.0.host();
+        if host.starts_with('[') && host.ends_with(']') {
+            &host[1..host.len() - 1]
+        } else {
+            host
+        }
     }
 
     /// Get the optional port number.
@@ -29,6 +37,7 @@ impl ::Header for Host {
             .next()
             .cloned()
             .and_then(|val| Authority::try_from(val.as_bytes()).ok())
+            .filter(is_uri_host)
             .map(Host)
             .ok_or_else(::Error::invalid)
     }
@@ -41,6 +50,18 @@ impl ::Header for Host {
     }
 }
 
+// `Host = uri-host [ ":" port ]`, so no userinfo, and a port that fits.
+fn is_uri_host(auth: &Authority) -> bool {
+    if auth.as_str().contains('@') {
+        return false;
+    }
+    // `Authority::port` is `None` for a port that doesn't fit a `u16`
+    match auth.as_str()[auth.host().len()..].strip_prefix(':') {
+        Some(port) if !port.is_empty() => port.parse::<u16>().is_ok(),
+        _ => true,
+    }
+}
+
 impl From<Authority> for Host {
     fn from(auth: Authority) -> Host {
         Host(auth)
@@ -52,3 +73,42 @@ impl fmt::Display for Host {
         fmt::Display::fmt(&self.0, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn hostname_and_port() {
+        let host = test_decode::<Host>(&["example.com:443"]).unwrap();
+        assert_eq!(host.hostname(), "example.com");
+        assert_eq!(host.port(), Some(443));
+
+        let host = test_decode::<Host>(&["example.com"]).unwrap();
+        assert_eq!(host.hostname(), "example.com");
+        assert_eq!(host.port(), None);
+    }
+
+    #[test]
+    fn ipv6() {
+        let host = test_decode::<Host>(&["[2001:db8::1]:8080"]).unwrap();
+        assert_eq!(host.hostname(), "2001:db8::1");
+        assert_eq!(host.port(), Some(8080));
+
+        let host = test_decode::<Host>(&["[::1]"]).unwrap();
+        assert_eq!(host.hostname(), "::1");
+        assert_eq!(host.port(), None);
+
+        let headers = test_encode(host);
+        assert_eq!(headers["host"], "[::1]");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Host>(&["example.com:99999"]), None);
+        assert_eq!(test_decode::<Host>(&["example.com:http"]), None);
+        assert_eq!(test_decode::<Host>(&["user@example.com"]), None);
+        assert_eq!(test_decode::<Host>(&[""]), None);
+    }
+}
@@ -58,9 +58,25 @@ impl IfMatch {
         }
     }
 
-    /// Checks whether the `ETag` strongly matches.
-    pub fn precondition_passes(&self, etag: &ETag) -> bool {
-        self.0.matches_strong(&etag.0)
+    /// Checks whether the current `ETag` of the resource strongly matches.
+    ///
+    /// Pass `None` when there is no current representation, in which case
+    /// the precondition always fails, even for `*`.
+    pub fn precondition_passes<'a, E>(&self, current: E) -> bool
+    where
+        E: Into<Option<&'a ETag>>,
+    {
+        match current.into() {
+            Some(etag) => self.0.matches_strong(&etag.0),
+            None => false,
+        }
+    }
+
+    /// Returns an iterator over the listed entity tags.
+    ///
+    /// `If-Match: *` yields nothing; check `is_any` for that case.
+    pub fn iter(&self) -> impl Iterator<Item = ETag> + '_ {
+        self.0.tags().map(ETag)
     }
 }
 
@@ -72,6 +88,7 @@ impl From<ETag> for IfMatch {
 
 #[cfg(test)]
 mod tests {
+    use super::super::test_decode;
     use super::*;
 
     #[test]
@@ -108,4 +125,22 @@ mod tests {
 
         assert!(if_match.precondition_passes(&foo));
     }
+
+    #[test]
+    fn precondition_no_current() {
+        assert!(!IfMatch::any().precondition_passes(None));
+        assert!(!IfMatch::from(ETag::from_static("\"foo\"")).precondition_passes(None));
+    }
+
+    #[test]
+    fn iter() {
+        let if_match = test_decode::<IfMatch>(&["\"foo\", W/\"bar\"", "\"baz\""]).unwrap();
+        let tags = if_match.iter().collect::<Vec<_>>();
+        assert_eq!(tags.len(), 3);
+        assert_eq!(tags[0].tag(), Some("foo"));
+        assert!(tags[1].is_weak());
+        assert_eq!(tags[2].tag(), Some("baz"));
+
+        assert_eq!(IfMatch::any().iter().next(), None);
+    }
 }
@@ -39,6 +39,10 @@ derive_header! {
 
 impl IfModifiedSince {
     /// Check if the supplied time means the resource has been modified.
+    ///
+    /// HTTP dates only have one-second resolution, so `last_modified` is
+    /// truncated to whole seconds before comparing. A file modified
+    /// partway through the second this date names is not modified.
     pub fn is_modified(&self, last_modified: SystemTime) -> bool {
         self.0 < last_modified.into()
     }
@@ -56,11 +60,59 @@ impl From<IfModifiedSince> for SystemTime {
     }
 }
 
+#[cfg(feature = "chrono")]
+impl<Tz: ::chrono::TimeZone> From<::chrono::DateTime<Tz>> for IfModifiedSince {
+    fn from(time: ::chrono::DateTime<Tz>) -> IfModifiedSince {
+        IfModifiedSince(time.into())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<IfModifiedSince> for ::chrono::DateTime<::chrono::Utc> {
+    fn from(date: IfModifiedSince) -> ::chrono::DateTime<::chrono::Utc> {
+        date.0.into()
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<::time::OffsetDateTime> for IfModifiedSince {
+    fn from(time: ::time::OffsetDateTime) -> IfModifiedSince {
+        IfModifiedSince(time.into())
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<IfModifiedSince> for ::time::OffsetDateTime {
+    fn from(date: IfModifiedSince) -> ::time::OffsetDateTime {
+        date.0.into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::super::{test_decode, test_encode};
     use super::*;
     use std::time::Duration;
 
+    #[test]
+    fn decode_all_date_formats() {
+        let expected = SystemTime::UNIX_EPOCH + Duration::from_secs(784_111_777);
+        for s in &[
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+        ] {
+            let if_mod = test_decode::<IfModifiedSince>(&[s]).unwrap();
+            assert_eq!(SystemTime::from(if_mod), expected, "{:?}", s);
+
+            let headers = test_encode(if_mod);
+            assert_eq!(
+                headers["if-modified-since"],
+                "Sun, 06 Nov 1994 08:49:37 GMT"
+            );
+        }
+    }
+
     #[test]
     fn is_modified() {
         let newer = SystemTime::now();
@@ -72,4 +124,15 @@ mod tests {
         assert!(!if_mod.is_modified(exact));
         assert!(!if_mod.is_modified(older));
     }
+
+    #[test]
+    fn is_modified_ignores_subsec() {
+        let exact = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let if_mod = IfModifiedSince::from(exact);
+        assert!(!if_mod.is_modified(exact + Duration::from_millis(300)));
+        assert!(if_mod.is_modified(exact + Duration::from_secs(1)));
+
+        let if_mod = IfModifiedSince::from(exact + Duration::from_millis(700));
+        assert!(!if_mod.is_modified(exact + Duration::from_millis(300)));
+    }
 }
@@ -52,9 +52,26 @@ impl IfNoneMatch {
         IfNoneMatch(EntityTagRange::Any)
     }
 
-    /// Checks whether the ETag passes this precondition.
-    pub fn precondition_passes(&self, etag: &ETag) -> bool {
-        !self.0.matches_weak(&etag.0)
+    /// Checks whether the current `ETag` of the resource passes this
+    /// precondition, using weak comparison.
+    ///
+    /// Pass `None` when there is no current representation, in which case
+    /// the precondition always passes.
+    pub fn precondition_passes<'a, E>(&self, current: E) -> bool
+    where
+        E: Into<Option<&'a ETag>>,
+    {
+        match current.into() {
+            Some(etag) => !self.0.matches_weak(&etag.0),
+            None => true,
+        }
+    }
+
+    /// Returns an iterator over the listed entity tags.
+    ///
+    /// `If-None-Match: *` yields nothing.
+    pub fn iter(&self) -> impl Iterator<Item = ETag> + '_ {
+        self.0.tags().map(ETag)
     }
 }
 
@@ -76,6 +93,7 @@ test_if_none_match {
 
 #[cfg(test)]
 mod tests {
+    use super::super::test_decode;
     use super::*;
 
     #[test]
@@ -107,5 +125,24 @@ mod tests {
         let if_none = IfNoneMatch::any();
 
         assert!(!if_none.precondition_passes(&foo));
+        assert!(if_none.precondition_passes(None));
+    }
+
+    #[test]
+    fn precondition_no_current() {
+        let if_none = IfNoneMatch::from(ETag::from_static("\"foo\""));
+        assert!(if_none.precondition_passes(None));
+    }
+
+    #[test]
+    fn iter() {
+        let if_none = test_decode::<IfNoneMatch>(&["W/\"foo\", \"bar\""]).unwrap();
+        let tags = if_none
+            .iter()
+            .map(|tag| tag.tag().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(tags, vec!["foo", "bar"]);
+
+        assert_eq!(IfNoneMatch::any().iter().count(), 0);
     }
 }
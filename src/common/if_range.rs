@@ -51,7 +51,7 @@ derive_header! {
 impl IfRange {
     /// Create an `IfRange` header with an entity tag.
     pub fn etag(tag: ETag) -> IfRange {
-        IfRange(IfRange_::EntityTag(tag.0))
+        IfRange(IfRange_::EntityTag(tag))
     }
 
     /// Create an `IfRange` header with a date value.
@@ -61,18 +61,38 @@ impl IfRange {
 
     /// Checks if the resource has been modified, or if the range request
     /// can be served.
+    ///
+    /// A date is compared like [`IfModifiedSince`](struct.IfModifiedSince.html),
+    /// and an entity-tag with the strong comparison function.
     pub fn is_modified(&self, etag: Option<&ETag>, last_modified: Option<&LastModified>) -> bool {
         match self.0 {
             IfRange_::Date(since) => last_modified.map(|time| since < time.0).unwrap_or(true),
-            IfRange_::EntityTag(ref entity) => etag.map(|etag| !etag.0.strong_eq(entity)).unwrap_or(true),
+            IfRange_::EntityTag(ref entity) => etag.map(|etag| !etag.strong_eq(entity)).unwrap_or(true),
+        }
+    }
+
+    /// Get whether this holds a date or an entity-tag.
+    pub fn kind(&self) -> IfRangeKind<'_> {
+        match self.0 {
+            IfRange_::Date(date) => IfRangeKind::Date(date.into()),
+            IfRange_::EntityTag(ref tag) => IfRangeKind::EntityTag(tag),
         }
     }
 }
 
+/// The validator held by an [`IfRange`](struct.IfRange.html) header.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IfRangeKind<'a> {
+    /// The date when the client retrieved the resource.
+    Date(SystemTime),
+    /// The entity-tag the client has of the resource.
+    EntityTag(&'a ETag),
+}
+
 #[derive(Clone, Debug, PartialEq)]
 enum IfRange_ {
     /// The entity-tag the client has of the resource
-    EntityTag(EntityTag),
+    EntityTag(ETag),
     /// The date when the client retrieved the resource
     Date(HttpDate),
 }
@@ -86,7 +106,7 @@ impl ::util::TryFromValues for IfRange_ {
             .next()
             .and_then(|val| {
                 if let Some(tag) = EntityTag::from_val(val) {
-                    return Some(IfRange_::EntityTag(tag));
+                    return Some(IfRange_::EntityTag(ETag(tag)));
                 }
 
                 let date = HttpDate::from_val(val)?;
@@ -99,7 +119,7 @@ impl ::util::TryFromValues for IfRange_ {
 impl<'a> From<&'a IfRange_> for HeaderValue {
     fn from(if_range: &'a IfRange_) -> HeaderValue {
         match *if_range {
-            IfRange_::EntityTag(ref tag) => tag.into(),
+            IfRange_::EntityTag(ref tag) => (&tag.0).into(),
             IfRange_::Date(ref date) => date.into(),
         }
     }
@@ -119,7 +139,9 @@ mod tests {
 
 #[cfg(test)]
 mod tests {
+    use super::super::test_decode;
     use super::*;
+    use std::time::Duration;
 
     #[test]
     fn test_is_modified_etag() {
@@ -131,4 +153,28 @@ mod tests {
         let etag = ETag::from_static("W/\"xyzzy\"");
         assert!(if_range.is_modified(Some(&etag), None));
     }
+
+    #[test]
+    fn test_is_modified_date() {
+        let newer = SystemTime::now();
+        let exact = newer - Duration::from_secs(2);
+        let older = newer - Duration::from_secs(4);
+        let if_range = IfRange::date(exact);
+
+        assert!(if_range.is_modified(None, Some(&LastModified::from(newer))));
+        assert!(!if_range.is_modified(None, Some(&LastModified::from(exact))));
+        assert!(!if_range.is_modified(None, Some(&LastModified::from(older))));
+        assert!(if_range.is_modified(None, None));
+    }
+
+    #[test]
+    fn test_kind() {
+        let etag = ETag::from_static("\"xyzzy\"");
+        let if_range = test_decode::<IfRange>(&["\"xyzzy\""]).unwrap();
+        assert_eq!(if_range.kind(), IfRangeKind::EntityTag(&etag));
+
+        let if_range = test_decode::<IfRange>(&["Sat, 29 Oct 1994 19:43:31 GMT"]).unwrap();
+        let date = SystemTime::UNIX_EPOCH + Duration::from_secs(783_459_811);
+        assert_eq!(if_range.kind(), IfRangeKind::Date(date));
+    }
 }
@@ -39,7 +39,12 @@ derive_header! {
 }
 
 impl IfUnmodifiedSince {
-    /// Check if the supplied time passes the precondtion.
+    /// Check if the supplied time passes the precondition.
+    ///
+    /// This is the counterpart of `IfModifiedSince::is_modified`: it passes
+    /// when `last_modified` is at or before this date. HTTP dates only have
+    /// one-second resolution, so any fraction of a second in
+    /// `last_modified` is ignored.
     pub fn precondition_passes(&self, last_modified: SystemTime) -> bool {
         self.0 >= last_modified.into()
     }
@@ -57,6 +62,34 @@ impl From<IfUnmodifiedSince> for SystemTime {
     }
 }
 
+#[cfg(feature = "chrono")]
+impl<Tz: ::chrono::TimeZone> From<::chrono::DateTime<Tz>> for IfUnmodifiedSince {
+    fn from(time: ::chrono::DateTime<Tz>) -> IfUnmodifiedSince {
+        IfUnmodifiedSince(time.into())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<IfUnmodifiedSince> for ::chrono::DateTime<::chrono::Utc> {
+    fn from(date: IfUnmodifiedSince) -> ::chrono::DateTime<::chrono::Utc> {
+        date.0.into()
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<::time::OffsetDateTime> for IfUnmodifiedSince {
+    fn from(time: ::time::OffsetDateTime) -> IfUnmodifiedSince {
+        IfUnmodifiedSince(time.into())
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<IfUnmodifiedSince> for ::time::OffsetDateTime {
+    fn from(date: IfUnmodifiedSince) -> ::time::OffsetDateTime {
+        date.0.into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,4 +106,12 @@ mod tests {
         assert!(if_unmod.precondition_passes(exact));
         assert!(if_unmod.precondition_passes(older));
     }
+
+    #[test]
+    fn precondition_ignores_subsec() {
+        let exact = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let if_unmod = IfUnmodifiedSince::from(exact);
+        assert!(if_unmod.precondition_passes(exact + Duration::from_millis(500)));
+        assert!(!if_unmod.precondition_passes(exact + Duration::from_secs(1)));
+    }
 }
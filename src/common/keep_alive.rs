@@ -0,0 +1,190 @@
+use std::time::Duration;
+
+use util::quoted_string;
+use util::{FlatCsv, Seconds, TryFromValues};
+use HeaderValue;
+
+static KEEP_ALIVE: ::HeaderName = ::HeaderName::from_static("keep-alive");
+
+/// `Keep-Alive` header, defined in
+/// [RFC2068](https://tools.ietf.org/html/rfc2068#section-19.7.1.1) and
+/// [draft-thomson-hybi-http-timeout](https://tools.ietf.org/html/draft-thomson-hybi-http-timeout-03#section-2)
+///
+/// The `Keep-Alive` header lets a persistent HTTP/1.1 connection carry
+/// hints about how long it may stay idle (`timeout`), and how many more
+/// requests it may carry (`max`). It's only meaningful alongside
+/// `Connection: keep-alive`.
+///
+/// Unknown parameters are ignored when decoding.
+///
+/// # ABNF
+///
+/// ```text
+/// Keep-Alive = 1#keep-alive-info
+/// keep-alive-info = "timeout" "=" delta-seconds
+///                 / "max" "=" 1*DIGIT
+///                 / keep-alive-extension
+/// keep-alive-extension = token [ "=" ( token / quoted-string ) ]
+/// ```
+///
+/// # Example values
+///
+/// * `timeout=5, max=1000`
+/// * `timeout=30`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use std::time::Duration;
+/// use headers::KeepAlive;
+///
+/// let keep_alive = KeepAlive::from_timeout(Duration::from_secs(5)).with_max(1000);
+///
+/// assert_eq!(keep_alive.timeout(), Some(Duration::from_secs(5)));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeepAlive {
+    timeout: Option<Seconds>,
+    max: Option<u64>,
+}
+
+impl ::Header for KeepAlive {
+    fn name() -> &'static ::HeaderName {
+        &KEEP_ALIVE
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+
+        let mut keep_alive = KeepAlive {
+            timeout: None,
+            max: None,
+        };
+        let mut seen = false;
+        for param in csv.iter().filter(|param| !param.is_empty()) {
+            seen = true;
+            let (name, value) = match param.find('=') {
+                Some(eq) => (param[..eq].trim(), Some(param[eq + 1..].trim())),
+                None => (param, None),
+            };
+            if !quoted_string::is_token(name) {
+                return Err(::Error::invalid());
+            }
+
+            if name.eq_ignore_ascii_case("timeout") {
+                let secs = value.and_then(parse_digits).ok_or_else(::Error::invalid)?;
+                keep_alive.timeout = Some(Seconds::from_secs(secs));
+            } else if name.eq_ignore_ascii_case("max") {
+                let max = value.and_then(parse_digits).ok_or_else(::Error::invalid)?;
+                keep_alive.max = Some(max);
+            }
+        }
+
+        if !seen {
+            return Err(::Error::invalid());
+        }
+        Ok(keep_alive)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let mut params = Vec::new();
+        if let Some(timeout) = self.timeout {
+            params.push(format!("timeout={}", timeout));
+        }
+        if let Some(max) = self.max {
+            params.push(format!("max={}", max));
+        }
+
+        values.extend(::std::iter::once(
+            HeaderValue::from_str(&params.join(", ")).expect("params are valid header values"),
+        ));
+    }
+}
+
+impl KeepAlive {
+    /// Creates a `KeepAlive` with an idle `timeout`, dropping any fraction
+    /// of a second.
+    pub fn from_timeout(timeout: Duration) -> KeepAlive {
+        KeepAlive {
+            timeout: Some(Seconds::from_secs(timeout.as_secs())),
+            max: None,
+        }
+    }
+
+    /// Creates a `KeepAlive` with the `max` number of further requests.
+    pub fn from_max(max: u64) -> KeepAlive {
+        KeepAlive {
+            timeout: None,
+            max: Some(max),
+        }
+    }
+
+    /// Sets the idle `timeout`, dropping any fraction of a second.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(Seconds::from_secs(timeout.as_secs()));
+        self
+    }
+
+    /// Sets the `max` number of further requests.
+    pub fn with_max(mut self, max: u64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Returns how long the connection may stay idle, if sent.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from)
+    }
+
+    /// Returns how many more requests the connection may carry, if sent.
+    pub fn max(&self) -> Option<u64> {
+        self.max
+    }
+}
+
+fn parse_digits(s: &str) -> Option<u64> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let keep_alive = test_decode::<KeepAlive>(&["timeout=5, max=1000"]).unwrap();
+        assert_eq!(keep_alive.timeout(), Some(Duration::from_secs(5)));
+        assert_eq!(keep_alive.max(), Some(1000));
+    }
+
+    #[test]
+    fn decode_ignores_unknown() {
+        let keep_alive =
+            test_decode::<KeepAlive>(&["Timeout = 30, foo=\"a, b\", bar", "baz=1"]).unwrap();
+        assert_eq!(keep_alive.timeout(), Some(Duration::from_secs(30)));
+        assert_eq!(keep_alive.max(), None);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<KeepAlive>(&[""]), None);
+        assert_eq!(test_decode::<KeepAlive>(&["timeout=soon"]), None);
+        assert_eq!(test_decode::<KeepAlive>(&["max=-1"]), None);
+        assert_eq!(test_decode::<KeepAlive>(&["timeout"]), None);
+    }
+
+    #[test]
+    fn encode_stable_order() {
+        let keep_alive = KeepAlive::from_max(100).with_timeout(Duration::from_millis(5500));
+        let headers = test_encode(keep_alive);
+        assert_eq!(headers["keep-alive"], "timeout=5, max=100");
+
+        let headers = test_encode(KeepAlive::from_max(3));
+        assert_eq!(headers["keep-alive"], "max=3");
+    }
+}
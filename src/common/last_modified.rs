@@ -38,6 +38,13 @@ derive_header! {
     name: LAST_MODIFIED
 }
 
+impl LastModified {
+    /// Creates a `LastModified` for the current time.
+    pub fn now() -> LastModified {
+        LastModified::from(SystemTime::now())
+    }
+}
+
 impl From<SystemTime> for LastModified {
     fn from(time: SystemTime) -> LastModified {
         LastModified(time.into())
@@ -49,3 +56,31 @@ impl From<LastModified> for SystemTime {
         date.0.into()
     }
 }
+
+#[cfg(feature = "chrono")]
+impl<Tz: ::chrono::TimeZone> From<::chrono::DateTime<Tz>> for LastModified {
+    fn from(time: ::chrono::DateTime<Tz>) -> LastModified {
+        LastModified(time.into())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<LastModified> for ::chrono::DateTime<::chrono::Utc> {
+    fn from(date: LastModified) -> ::chrono::DateTime<::chrono::Utc> {
+        date.0.into()
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<::time::OffsetDateTime> for LastModified {
+    fn from(time: ::time::OffsetDateTime) -> LastModified {
+        LastModified(time.into())
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<LastModified> for ::time::OffsetDateTime {
+    fn from(date: LastModified) -> ::time::OffsetDateTime {
+        date.0.into()
+    }
+}
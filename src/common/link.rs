@@ -0,0 +1,335 @@
+use std::fmt;
+use std::iter::FromIterator;
+
+use util::csv::encode_list;
+use util::quoted_string::{self, Quoted};
+use util::{FlatCsv, TryFromValues};
+use HeaderValue;
+
+/// `Link` header, defined in [RFC8288](https://tools.ietf.org/html/rfc8288#section-3)
+///
+/// The `Link` header field provides a means for serialising one or more
+/// links into HTTP headers, each a target URI with parameters describing
+/// how it relates to the context of the response.
+///
+/// # ABNF
+///
+/// ```text
+/// Link       = #link-value
+/// link-value = "<" URI-Reference ">" *( OWS ";" OWS link-param )
+/// link-param = token BWS [ "=" BWS ( token / quoted-string ) ]
+/// ```
+///
+/// # Example values
+///
+/// * `<https://example.com/TheBook/chapter2>; rel="previous"; title="previous chapter"`
+/// * `</>; rel="http://example.net/foo"`
+/// * `</terms>; rel="copyright"; anchor="#foo"`
+///
+/// # Examples
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{Link, LinkValue};
+///
+/// let link = vec![
+///     LinkValue::new("/page/2").with_param("rel", "next"),
+///     LinkValue::new("/page/9").with_param("rel", "last"),
+/// ]
+/// .into_iter()
+/// .collect::<Link>();
+///
+/// let next = link.links().find(|l| l.rel().any(|rel| rel == "next"));
+/// assert_eq!(next.map(|l| l.uri()), Some("/page/2"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Link(Vec<LinkValue>);
+
+/// A single `link-value` of a [`Link`](struct.Link.html) header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkValue {
+    uri: String,
+    params: Vec<Param>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Param {
+    name: String,
+    value: Option<String>,
+    // Remembered so re-encoding keeps the value looking like it did.
+    quoted: bool,
+}
+
+impl ::Header for Link {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::LINK
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        let value = csv.to_value();
+        let s = value.to_str().map_err(|_| ::Error::invalid())?;
+
+        let mut links = Vec::new();
+        let mut rest = s.trim_start();
+        while !rest.is_empty() {
+            if rest.starts_with(',') {
+                rest = rest[1..].trim_start();
+                continue;
+            }
+            let (link, after) = parse_link_value(rest).ok_or_else(::Error::invalid)?;
+            links.push(link);
+            rest = after.trim_start();
+        }
+
+        if links.is_empty() {
+            return Err(::Error::invalid());
+        }
+        Ok(Link(links))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_list(&self.0)));
+    }
+}
+
+impl Link {
+    /// Returns an iterator over the `LinkValue`s, in order.
+    pub fn links(&self) -> impl Iterator<Item = &LinkValue> {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<LinkValue> for Link {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = LinkValue>,
+    {
+        Link(iter.into_iter().collect())
+    }
+}
+
+// ===== impl LinkValue =====
+
+impl LinkValue {
+    /// Creates a new `LinkValue` pointing at `uri`, with no parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `uri` contains whitespace, control characters or `>`.
+    pub fn new<U: Into<String>>(uri: U) -> LinkValue {
+        let uri = uri.into();
+        assert!(
+            uri.bytes().all(|b| b > b' ' && b != 0x7f && b != b'>'),
+            "invalid Link URI: {:?}",
+            uri
+        );
+        LinkValue {
+            uri,
+            params: Vec::new(),
+        }
+    }
+
+    /// Adds a parameter, quoting the value if it isn't a `token`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a `token`, or `value` contains control
+    /// characters.
+    pub fn with_param<N, V>(mut self, name: N, value: V) -> LinkValue
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        let name = name.into();
+        let value = value.into();
+        assert!(
+            quoted_string::is_token(&name),
+            "invalid Link param name: {:?}",
+            name
+        );
+        assert!(
+            value.chars().all(|c| c == '\t' || !c.is_control()),
+            "invalid Link param value: {:?}",
+            value
+        );
+        let quoted = !quoted_string::is_token(&value);
+        self.params.push(Param {
+            name,
+            value: Some(value),
+            quoted,
+        });
+        self
+    }
+
+    /// Returns the target URI, as it was written.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Returns an iterator over the relation types in the `rel` parameter.
+    pub fn rel(&self) -> impl Iterator<Item = &str> {
+        self.param("rel").unwrap_or("").split_whitespace()
+    }
+
+    /// Returns the value of the first parameter called `name`.
+    ///
+    /// Parameter names are case-insensitive. A parameter given without a
+    /// value returns an empty string.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|param| param.name.eq_ignore_ascii_case(name))
+            .map(|param| param.value.as_deref().unwrap_or(""))
+    }
+}
+
+impl fmt::Display for LinkValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{}>", self.uri)?;
+        for param in &self.params {
+            write!(f, "; {}", param.name)?;
+            match param.value {
+                Some(ref value) if param.quoted => write!(f, "={}", Quoted(value))?,
+                Some(ref value) => write!(f, "={}", value)?,
+                None => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_link_value(s: &str) -> Option<(LinkValue, &str)> {
+    if !s.starts_with('<') {
+        return None;
+    }
+    let end = s.find('>')?;
+    let uri = s[1..end].trim().to_owned();
+    let mut rest = s[end + 1..].trim_start();
+
+    let mut params = Vec::new();
+    while rest.starts_with(';') {
+        rest = rest[1..].trim_start();
+
+        let name_end = rest
+            .find(|c: char| c == '=' || c == ';' || c == ',' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let name = &rest[..name_end];
+        if !quoted_string::is_token(name) {
+            return None;
+        }
+        rest = rest[name_end..].trim_start();
+
+        let mut param = Param {
+            name: name.to_owned(),
+            value: None,
+            quoted: false,
+        };
+        if rest.starts_with('=') {
+            rest = rest[1..].trim_start();
+            if rest.starts_with('"') {
+                let (value, after) = quoted_string::parse(rest)?;
+                param.value = Some(value);
+                param.quoted = true;
+                rest = after;
+            } else {
+                let value_end = rest.find(&[';', ','][..]).unwrap_or(rest.len());
+                param.value = Some(rest[..value_end].trim_end().to_owned());
+                rest = &rest[value_end..];
+            }
+            rest = rest.trim_start();
+        }
+        params.push(param);
+    }
+
+    if !rest.is_empty() && !rest.starts_with(',') {
+        return None;
+    }
+    Some((LinkValue { uri, params }, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let link = test_decode::<Link>(&[
+            r#"<http://example.com/TheBook/chapter2>; rel="previous"; title="previous chapter""#,
+        ])
+        .unwrap();
+        let value = link.links().next().unwrap();
+        assert_eq!(value.uri(), "http://example.com/TheBook/chapter2");
+        assert_eq!(value.rel().collect::<Vec<_>>(), vec!["previous"]);
+        assert_eq!(value.param("TITLE"), Some("previous chapter"));
+        assert_eq!(value.param("anchor"), None);
+    }
+
+    #[test]
+    fn decode_multiple() {
+        let link = test_decode::<Link>(&[
+            r#"</a,b>; rel="start http://example.net/relation/other", </c>;rel=next;type=text/html"#,
+            "</d>; anchor=\"#foo\"",
+        ])
+        .unwrap();
+        let links = link.links().collect::<Vec<_>>();
+        assert_eq!(links.len(), 3);
+
+        assert_eq!(links[0].uri(), "/a,b");
+        assert_eq!(
+            links[0].rel().collect::<Vec<_>>(),
+            vec!["start", "http://example.net/relation/other"]
+        );
+        assert_eq!(links[1].uri(), "/c");
+        assert_eq!(links[1].rel().collect::<Vec<_>>(), vec!["next"]);
+        assert_eq!(links[1].param("type"), Some("text/html"));
+        assert_eq!(links[2].param("anchor"), Some("#foo"));
+    }
+
+    #[test]
+    fn decode_escapes_and_valueless_params() {
+        let link =
+            test_decode::<Link>(&[r#"</>; title="a \"quote\", really"; crossorigin"#]).unwrap();
+        let value = link.links().next().unwrap();
+        assert_eq!(value.param("title"), Some("a \"quote\", really"));
+        assert_eq!(value.param("crossorigin"), Some(""));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Link>(&[""]), None);
+        assert_eq!(test_decode::<Link>(&["/no-brackets; rel=next"]), None);
+        assert_eq!(test_decode::<Link>(&["</unclosed; rel=next"]), None);
+        assert_eq!(test_decode::<Link>(&[r#"</>; title="unterminated"#]), None);
+        assert_eq!(test_decode::<Link>(&["</> junk"]), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let raw = r#"</TheBook/chapter2>; rel="previous"; title="previous chapter", </>; rel=next; crossorigin"#;
+        let link = test_decode::<Link>(&[raw]).unwrap();
+        let headers = test_encode(link);
+        assert_eq!(headers["link"], raw);
+    }
+
+    #[test]
+    fn encode_builder() {
+        let link = vec![LinkValue::new("/page/2")
+            .with_param("rel", "next")
+            .with_param("title", "Next \"page\"")]
+        .into_iter()
+        .collect::<Link>();
+        let headers = test_encode(link);
+        assert_eq!(
+            headers["link"],
+            r#"</page/2>; rel=next; title="Next \"page\"""#
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_invalid_uri() {
+        LinkValue::new("/has space");
+    }
+}
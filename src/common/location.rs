@@ -20,6 +20,17 @@ use HeaderValue;
 ///
 /// # Examples
 ///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{Header, HeaderValue, Location};
+///
+/// let value = HeaderValue::from_static("/People.html#tim");
+/// let loc = Location::decode(&mut std::iter::once(&value)).unwrap();
+///
+/// assert_eq!(loc.to_str(), Some("/People.html#tim"));
+/// assert!(!loc.is_absolute());
+/// ```
 #[derive(Clone, Debug, PartialEq)]
 pub struct Location(HeaderValue);
 
@@ -28,10 +39,55 @@ derive_header! {
     name: LOCATION
 }
 
+impl Location {
+    /// Returns the location as a string.
+    ///
+    /// Any valid header value is accepted when decoding, so this is `None`
+    /// if it contains bytes that aren't visible ASCII.
+    pub fn to_str(&self) -> Option<&str> {
+        self.0.to_str().ok()
+    }
+
+    /// Returns whether the location is an absolute URI, starting with a
+    /// scheme such as `https:`.
+    ///
+    /// Anything else, including network-path references like
+    /// `//example.com/`, is relative to the request URI.
+    pub fn is_absolute(&self) -> bool {
+        has_scheme(self.0.as_bytes())
+    }
+
+    /// Resolves the location against a base URL, usually the URL the
+    /// request was sent to.
+    ///
+    /// Returns `None` if the location can't be parsed as a URL reference.
+    #[cfg(feature = "url")]
+    pub fn resolve(&self, base: &::url::Url) -> Option<::url::Url> {
+        base.join(self.to_str()?).ok()
+    }
+}
+
+/// Checks for `scheme ":"` at the start of a URI reference, where
+/// `scheme = ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )`.
+pub(super) fn has_scheme(uri: &[u8]) -> bool {
+    match uri.iter().position(|&b| b == b':') {
+        Some(colon) => {
+            let scheme = &uri[..colon];
+            !scheme.is_empty()
+                && scheme[0].is_ascii_alphabetic()
+                && scheme
+                    .iter()
+                    .all(|&b| b.is_ascii_alphanumeric() || b == b'+' || b == b'-' || b == b'.')
+        }
+        None => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::test_decode;
     use super::*;
+    use Header;
 
     #[test]
     fn absolute_uri() {
@@ -48,4 +104,47 @@ mod tests {
 
         assert_eq!(loc, Location(HeaderValue::from_static(s)));
     }
+
+    #[test]
+    fn is_absolute() {
+        let loc = |s| test_decode::<Location>(&[s]).unwrap();
+        assert!(loc("http://www.example.net/index.html").is_absolute());
+        assert!(loc("urn:isbn:0451450523").is_absolute());
+        assert!(!loc("/People.html#tim").is_absolute());
+        assert!(!loc("//www.example.net/").is_absolute());
+        assert!(!loc("People.html?at=10:00").is_absolute());
+        assert!(!loc("1http://").is_absolute());
+    }
+
+    #[test]
+    fn non_utf8_location() {
+        let value = HeaderValue::from_bytes(b"/caf\xe9").unwrap();
+        let loc = Location::decode(&mut ::std::iter::once(&value)).unwrap();
+        assert_eq!(loc.to_str(), None);
+        assert!(!loc.is_absolute());
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn resolve() {
+        let base = ::url::Url::parse("https://example.com/a/b?c").unwrap();
+        let loc = |s| test_decode::<Location>(&[s]).unwrap();
+
+        assert_eq!(
+            loc("/People.html#tim").resolve(&base).unwrap().as_str(),
+            "https://example.com/People.html#tim"
+        );
+        assert_eq!(
+            loc("d").resolve(&base).unwrap().as_str(),
+            "https://example.com/a/d"
+        );
+        assert_eq!(
+            loc("http://www.example.net/")
+                .resolve(&base)
+                .unwrap()
+                .as_str(),
+            "http://www.example.net/"
+        );
+        assert_eq!(loc("http://[::1").resolve(&base), None);
+    }
 }
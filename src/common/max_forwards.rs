@@ -0,0 +1,108 @@
+use util::IterExt;
+use HeaderValue;
+
+/// `Max-Forwards` header, defined in
+/// [RFC7231](https://tools.ietf.org/html/rfc7231#section-5.1.2)
+///
+/// The `Max-Forwards` header field provides a mechanism with the `TRACE`
+/// and `OPTIONS` request methods to limit the number of times that the
+/// request is forwarded by proxies. Each proxy that forwards the request
+/// decrements the count, and one that receives a count of zero must
+/// respond itself instead.
+///
+/// # ABNF
+///
+/// ```text
+/// Max-Forwards = 1*DIGIT
+/// ```
+///
+/// # Example values
+///
+/// * `10`
+/// * `0`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::MaxForwards;
+///
+/// let max_forwards = MaxForwards::from(1);
+/// let next = max_forwards.decrement().unwrap();
+///
+/// assert_eq!(next.count(), 0);
+/// assert_eq!(next.decrement(), None);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaxForwards(u64);
+
+impl ::Header for MaxForwards {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::MAX_FORWARDS
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        values
+            .just_one()
+            .and_then(|value| value.to_str().ok())
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|s| s.parse().ok())
+            .map(MaxForwards)
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(self.0.into()));
+    }
+}
+
+impl MaxForwards {
+    /// Returns how many more times the request may be forwarded.
+    pub fn count(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the value to forward the request with, or `None` if the
+    /// count is zero and the request must not be forwarded.
+    pub fn decrement(self) -> Option<MaxForwards> {
+        self.0.checked_sub(1).map(MaxForwards)
+    }
+}
+
+impl From<u64> for MaxForwards {
+    fn from(count: u64) -> MaxForwards {
+        MaxForwards(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        assert_eq!(test_decode::<MaxForwards>(&["10"]), Some(MaxForwards(10)));
+        assert_eq!(test_decode::<MaxForwards>(&["0"]), Some(MaxForwards(0)));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<MaxForwards>(&[""]), None);
+        assert_eq!(test_decode::<MaxForwards>(&["ten"]), None);
+        assert_eq!(test_decode::<MaxForwards>(&["+5"]), None);
+        assert_eq!(test_decode::<MaxForwards>(&["-1"]), None);
+        assert_eq!(test_decode::<MaxForwards>(&["5", "5"]), None);
+    }
+
+    #[test]
+    fn decrement() {
+        let max_forwards = MaxForwards::from(2);
+        assert_eq!(max_forwards.decrement(), Some(MaxForwards(1)));
+        assert_eq!(MaxForwards::from(0).decrement(), None);
+
+        let headers = test_encode(max_forwards.decrement().unwrap());
+        assert_eq!(headers["max-forwards"], "1");
+    }
+}
@@ -7,10 +7,11 @@
 //! is used, such as `ContentType(pub Mime)`.
 
 //pub use self::accept_charset::AcceptCharset;
-//pub use self::accept_encoding::AcceptEncoding;
-//pub use self::accept_language::AcceptLanguage;
+pub use self::accept_encoding::{AcceptEncoding, ContentCoding};
+pub use self::accept_language::{AcceptLanguage, LanguageTag};
+pub use self::accept_patch::AcceptPatch;
 pub use self::accept_ranges::AcceptRanges;
-//pub use self::accept::Accept;
+pub use self::accept::Accept;
 pub use self::access_control_allow_credentials::AccessControlAllowCredentials;
 pub use self::access_control_allow_headers::AccessControlAllowHeaders;
 pub use self::access_control_allow_methods::AccessControlAllowMethods;
@@ -21,53 +22,80 @@ pub use self::access_control_request_headers::AccessControlRequestHeaders;
 pub use self::access_control_request_method::AccessControlRequestMethod;
 pub use self::age::Age;
 pub use self::allow::Allow;
+pub use self::alt_svc::{AltService, AltSvc};
 pub use self::authorization::Authorization;
 pub use self::cache_control::CacheControl;
+pub use self::clear_site_data::{ClearSiteData, ClearSiteDataDirective};
 pub use self::connection::Connection;
-pub use self::content_disposition::ContentDisposition;
+pub use self::content_disposition::{ContentDisposition, DispositionType, FormField};
 pub use self::content_encoding::ContentEncoding;
-//pub use self::content_language::ContentLanguage;
+pub use self::content_language::ContentLanguage;
 pub use self::content_length::ContentLength;
 pub use self::content_location::ContentLocation;
 pub use self::content_range::ContentRange;
+pub use self::content_security_policy::ContentSecurityPolicy;
+pub use self::content_security_policy_report_only::ContentSecurityPolicyReportOnly;
 pub use self::content_type::ContentType;
 pub use self::cookie::Cookie;
+pub use self::cross_origin_embedder_policy::CrossOriginEmbedderPolicy;
+pub use self::cross_origin_opener_policy::CrossOriginOpenerPolicy;
+pub use self::cross_origin_resource_policy::CrossOriginResourcePolicy;
 pub use self::date::Date;
+pub use self::digest::Digest;
 pub use self::etag::ETag;
 pub use self::expect::Expect;
 pub use self::expires::Expires;
+pub use self::forwarded::{Forwarded, ForwardedElement};
 //pub use self::from::From;
 pub use self::host::Host;
 pub use self::if_match::IfMatch;
 pub use self::if_modified_since::IfModifiedSince;
 pub use self::if_none_match::IfNoneMatch;
-pub use self::if_range::IfRange;
+pub use self::if_range::{IfRange, IfRangeKind};
 pub use self::if_unmodified_since::IfUnmodifiedSince;
 //pub use self::last_event_id::LastEventId;
+pub use self::keep_alive::KeepAlive;
 pub use self::last_modified::LastModified;
-//pub use self::link::{Link, LinkValue, RelationType, MediaDesc};
+pub use self::link::{Link, LinkValue};
 pub use self::location::Location;
+pub use self::max_forwards::MaxForwards;
+#[cfg(feature = "serde_json")]
+pub use self::nel::Nel;
 pub use self::origin::Origin;
+pub use self::permissions_policy::PermissionsPolicy;
 pub use self::pragma::Pragma;
-//pub use self::prefer::{Prefer, Preference};
-//pub use self::preference_applied::PreferenceApplied;
+pub use self::prefer::{Prefer, Preference};
+pub use self::preference_applied::PreferenceApplied;
+pub use self::proxy_authenticate::ProxyAuthenticate;
 pub use self::proxy_authorization::ProxyAuthorization;
 pub use self::range::Range;
 pub use self::referer::Referer;
-pub use self::referrer_policy::ReferrerPolicy;
+pub use self::referrer_policy::{ReferrerPolicy, ReferrerPolicyDirective};
+pub use self::refresh::Refresh;
+#[cfg(feature = "serde_json")]
+pub use self::report_to::{ReportEndpoint, ReportGroup, ReportTo};
 pub use self::retry_after::RetryAfter;
 pub use self::sec_websocket_accept::SecWebsocketAccept;
 pub use self::sec_websocket_key::SecWebsocketKey;
 pub use self::sec_websocket_version::SecWebsocketVersion;
 pub use self::server::Server;
-pub use self::set_cookie::SetCookie;
+pub use self::server_timing::{ServerTiming, ServerTimingMetric};
+pub use self::set_cookie::{SameSite, SetCookie, SetCookieBuilder};
 pub use self::strict_transport_security::StrictTransportSecurity;
 pub use self::te::Te;
+pub use self::timing_allow_origin::TimingAllowOrigin;
+pub use self::trailer::Trailer;
 pub use self::transfer_encoding::TransferEncoding;
 pub use self::upgrade::Upgrade;
-pub use self::user_agent::UserAgent;
+pub use self::user_agent::{Product, UserAgent};
 pub use self::vary::Vary;
-//pub use self::warning::Warning;
+pub use self::via::{Via, ViaEntry};
+pub use self::want_digest::WantDigest;
+pub use self::warning::{Warning, WarningValue};
+pub use self::www_authenticate::{Challenge, WwwAuthenticate};
+pub use self::x_content_type_options::XContentTypeOptions;
+pub use self::x_forwarded_for::XForwardedFor;
+pub use self::x_frame_options::XFrameOptions;
 
 #[cfg(test)]
 fn test_decode<T: ::Header>(values: &[&str]) -> Option<T> {
@@ -89,20 +117,27 @@ fn test_encode<T: ::Header>(header: T) -> ::http::HeaderMap {
 
 #[cfg(test)]
 macro_rules! bench_header {
-    ($mod:ident, $ty:ident, $value:expr) => {
+    ($mod:ident, $ty:ident, [$($value:expr),+]) => {
         #[cfg(feature = "nightly")]
         mod $mod {
             use super::$ty;
             use HeaderMapExt;
 
+            fn map() -> ::http::HeaderMap {
+                let mut map = ::http::HeaderMap::new();
+                $(
+                    map.append(
+                        <$ty as ::Header>::name(),
+                        $value.parse().expect("HeaderValue::from_str($value)"),
+                    );
+                )+
+                map
+            }
+
             #[bench]
             fn bench_decode(b: &mut ::test::Bencher) {
-                let mut map = ::http::HeaderMap::new();
-                map.append(
-                    <$ty as ::Header>::name(),
-                    $value.parse().expect("HeaderValue::from_str($value)"),
-                );
-                b.bytes = $value.len() as u64;
+                let map = map();
+                b.bytes = 0 $(+ $value.len() as u64)+;
                 b.iter(|| {
                     map.typed_get::<$ty>().unwrap();
                 });
@@ -110,13 +145,9 @@ macro_rules! bench_header {
 
             #[bench]
             fn bench_encode(b: &mut ::test::Bencher) {
-                let mut map = ::http::HeaderMap::new();
-                map.append(
-                    <$ty as ::Header>::name(),
-                    $value.parse().expect("HeaderValue::from_str($value)"),
-                );
+                let mut map = map();
                 let typed = map.typed_get::<$ty>().unwrap();
-                b.bytes = $value.len() as u64;
+                b.bytes = 0 $(+ $value.len() as u64)+;
                 b.iter(|| {
                     map.typed_insert(typed.clone());
                     map.clear();
@@ -124,12 +155,16 @@ macro_rules! bench_header {
             }
         }
     };
+    ($mod:ident, $ty:ident, $value:expr) => {
+        bench_header!($mod, $ty, [$value]);
+    };
 }
 
-//mod accept;
+mod accept;
 //mod accept_charset;
-//mod accept_encoding;
-//mod accept_language;
+mod accept_encoding;
+mod accept_language;
+mod accept_patch;
 mod accept_ranges;
 mod access_control_allow_credentials;
 mod access_control_allow_headers;
@@ -141,21 +176,32 @@ mod access_control_request_headers;
 mod access_control_request_method;
 mod age;
 mod allow;
+mod alt_svc;
 pub mod authorization;
 mod cache_control;
+mod clear_site_data;
 mod connection;
 mod content_disposition;
 mod content_encoding;
-//mod content_language;
+mod content_language;
 mod content_length;
 mod content_location;
 mod content_range;
+mod content_security_policy;
+mod content_security_policy_report_only;
 mod content_type;
 mod cookie;
+mod cross_origin_embedder_policy;
+mod cross_origin_opener_policy;
+mod cross_origin_resource_policy;
 mod date;
+mod digest;
 mod etag;
 mod expect;
 mod expires;
+mod forwarded;
+pub mod framing;
+pub mod freshness;
 //mod from;
 mod host;
 mod if_match;
@@ -164,27 +210,46 @@ mod if_none_match;
 mod if_range;
 mod if_unmodified_since;
 //mod last_event_id;
+mod keep_alive;
 mod last_modified;
-//mod link;
+mod link;
 mod location;
+mod max_forwards;
+pub mod negotiate;
+#[cfg(feature = "serde_json")]
+mod nel;
 mod origin;
+mod permissions_policy;
 mod pragma;
-//mod prefer;
-//mod preference_applied;
+mod prefer;
+mod preference_applied;
+mod proxy_authenticate;
 mod proxy_authorization;
 mod range;
 mod referer;
 mod referrer_policy;
+mod refresh;
+#[cfg(feature = "serde_json")]
+mod report_to;
 mod retry_after;
 mod sec_websocket_accept;
 mod sec_websocket_key;
 mod sec_websocket_version;
 mod server;
+mod server_timing;
 mod set_cookie;
 mod strict_transport_security;
 mod te;
+mod timing_allow_origin;
+mod trailer;
 mod transfer_encoding;
 mod upgrade;
 mod user_agent;
 mod vary;
-//mod warning;
+mod via;
+mod want_digest;
+mod warning;
+mod www_authenticate;
+mod x_content_type_options;
+mod x_forwarded_for;
+mod x_frame_options;
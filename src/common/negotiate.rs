@@ -0,0 +1,175 @@
+//! Proactive content negotiation, as described in
+//! [RFC7231](https://tools.ietf.org/html/rfc7231#section-3.4.1).
+//!
+//! Each function picks the representation a server should send from the
+//! ones it has available, given the matching request header, if any. The
+//! [`Negotiation`](struct.Negotiation.html) result says what was chosen,
+//! whether to respond with `406 Not Acceptable` instead, and which request
+//! header the response should list in `Vary`.
+//!
+//! When the request header is missing, every representation is
+//! acceptable, so the first available one is chosen.
+//!
+//! # Example
+//!
+//! ```
+//! # extern crate headers;
+//! # extern crate mime;
+//! use headers::{negotiate, Accept, Header, HeaderValue, Vary};
+//!
+//! let value = HeaderValue::from_static("text/html;q=0.8, application/json");
+//! let accept = Accept::decode(&mut Some(&value).into_iter()).unwrap();
+//! let available = [mime::TEXT_HTML, mime::APPLICATION_JSON];
+//!
+//! let negotiation = negotiate::content_type(Some(&accept), &available);
+//! assert_eq!(negotiation.chosen(), Some(&mime::APPLICATION_JSON));
+//! assert!(!negotiation.is_not_acceptable());
+//!
+//! let vary = vec![negotiation.vary()].into_iter().collect::<Vary>();
+//! ```
+
+use http::header::{self, HeaderName};
+use mime::Mime;
+
+use super::{Accept, AcceptEncoding, AcceptLanguage, ContentCoding, LanguageTag};
+
+/// The outcome of negotiating one dimension of a response.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Negotiation<T = Mime> {
+    chosen: Option<T>,
+    vary: HeaderName,
+}
+
+impl<T> Negotiation<T> {
+    /// The representation to send, or `None` if nothing available was
+    /// acceptable.
+    pub fn chosen(&self) -> Option<&T> {
+        self.chosen.as_ref()
+    }
+
+    /// Consumes the result, returning the representation to send.
+    pub fn into_chosen(self) -> Option<T> {
+        self.chosen
+    }
+
+    /// Check if nothing available was acceptable, so the server should
+    /// respond with `406 Not Acceptable`, or ignore the request header and
+    /// send a default.
+    pub fn is_not_acceptable(&self) -> bool {
+        self.chosen.is_none()
+    }
+
+    /// The request header the choice depended on, to be listed in the
+    /// response's `Vary` header.
+    ///
+    /// This is given even when the request didn't have the header, since
+    /// a request that did might get a different response.
+    pub fn vary(&self) -> HeaderName {
+        self.vary.clone()
+    }
+}
+
+/// Picks a media type for the response, using
+/// [`Accept::negotiate`](struct.Accept.html#method.negotiate).
+pub fn content_type(accept: Option<&Accept>, available: &[Mime]) -> Negotiation {
+    let chosen = match accept {
+        Some(accept) => accept.negotiate(available),
+        None => available.first(),
+    };
+    Negotiation {
+        chosen: chosen.cloned(),
+        vary: header::ACCEPT,
+    }
+}
+
+/// Picks a content coding for the response, using
+/// [`AcceptEncoding::best`](struct.AcceptEncoding.html#method.best).
+pub fn content_encoding(
+    accept_encoding: Option<&AcceptEncoding>,
+    available: &[ContentCoding],
+) -> Negotiation<ContentCoding> {
+    let chosen = match accept_encoding {
+        Some(accept_encoding) => accept_encoding.best(available),
+        None => available.first().cloned(),
+    };
+    Negotiation {
+        chosen,
+        vary: header::ACCEPT_ENCODING,
+    }
+}
+
+/// Picks a language for the response, using
+/// [`AcceptLanguage::matches`](struct.AcceptLanguage.html#method.matches).
+pub fn language(
+    accept_language: Option<&AcceptLanguage>,
+    available: &[LanguageTag],
+) -> Negotiation<LanguageTag> {
+    let chosen = match accept_language {
+        Some(accept_language) => accept_language.matches(available),
+        None => available.first().cloned(),
+    };
+    Negotiation {
+        chosen,
+        vary: header::ACCEPT_LANGUAGE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_decode;
+    use super::*;
+
+    #[test]
+    fn content_type_picks_best() {
+        let accept = test_decode::<Accept>(&["text/*, application/json;q=0.5"]).unwrap();
+        let available = [mime::APPLICATION_JSON, mime::TEXT_PLAIN];
+
+        let negotiation = content_type(Some(&accept), &available);
+        assert_eq!(negotiation.chosen(), Some(&mime::TEXT_PLAIN));
+        assert_eq!(negotiation.vary(), header::ACCEPT);
+    }
+
+    #[test]
+    fn content_type_not_acceptable() {
+        let accept = test_decode::<Accept>(&["image/*"]).unwrap();
+        let negotiation = content_type(Some(&accept), &[mime::TEXT_HTML]);
+        assert!(negotiation.is_not_acceptable());
+        assert_eq!(negotiation.into_chosen(), None);
+
+        assert!(content_type(None, &[]).is_not_acceptable());
+    }
+
+    #[test]
+    fn missing_header_picks_first() {
+        let negotiation = content_type(None, &[mime::TEXT_HTML, mime::APPLICATION_JSON]);
+        assert_eq!(negotiation.chosen(), Some(&mime::TEXT_HTML));
+        assert_eq!(negotiation.vary(), header::ACCEPT);
+
+        let negotiation = content_encoding(None, &[ContentCoding::Gzip, ContentCoding::Identity]);
+        assert_eq!(negotiation.chosen(), Some(&ContentCoding::Gzip));
+        assert_eq!(negotiation.vary(), header::ACCEPT_ENCODING);
+
+        let available = ["en".parse::<LanguageTag>().unwrap()];
+        let negotiation = language(None, &available);
+        assert_eq!(negotiation.chosen(), Some(&available[0]));
+        assert_eq!(negotiation.vary(), header::ACCEPT_LANGUAGE);
+    }
+
+    #[test]
+    fn encoding_and_language() {
+        let accept_encoding = test_decode::<AcceptEncoding>(&["br;q=0.5, gzip"]).unwrap();
+        let negotiation = content_encoding(
+            Some(&accept_encoding),
+            &[ContentCoding::Brotli, ContentCoding::Gzip],
+        );
+        assert_eq!(negotiation.chosen(), Some(&ContentCoding::Gzip));
+
+        let accept_language = test_decode::<AcceptLanguage>(&["fr, en;q=0.5"]).unwrap();
+        let available = ["en-US".parse().unwrap(), "de".parse().unwrap()];
+        let negotiation = language(Some(&accept_language), &available);
+        assert_eq!(negotiation.chosen(), Some(&available[0]));
+
+        let accept_language = test_decode::<AcceptLanguage>(&["fr"]).unwrap();
+        assert!(language(Some(&accept_language), &available).is_not_acceptable());
+    }
+}
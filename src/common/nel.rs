@@ -0,0 +1,243 @@
+use std::time::Duration;
+
+use serde_json::{self, Map, Value};
+
+use super::report_to::encode_json;
+use HeaderValue;
+
+static NEL: ::HeaderName = ::HeaderName::from_static("nel");
+
+/// `NEL` header, defined in
+/// [Network Error Logging](https://www.w3.org/TR/network-error-logging/#nel-response-header)
+///
+/// The `NEL` response header sets a policy for reporting network errors,
+/// and optionally successful requests, to an endpoint group configured by
+/// [`ReportTo`](struct.ReportTo.html). The policy is a JSON object.
+///
+/// Unknown JSON members are ignored when decoding. Sampling fractions
+/// that are missing, or aren't between `0.0` and `1.0`, use their
+/// defaults.
+///
+/// This header is only available with the `serde_json` feature.
+///
+/// # Example values
+///
+/// * `{"report_to": "nel", "max_age": 2592000}`
+/// * `{"report_to": "nel", "max_age": 86400, "include_subdomains": true, "success_fraction": 0.01}`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use std::time::Duration;
+/// use headers::Nel;
+///
+/// let nel = Nel::new("nel", Duration::from_secs(86400)).with_success_fraction(0.1);
+///
+/// assert_eq!(nel.report_to(), "nel");
+/// assert_eq!(nel.failure_fraction(), 1.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Nel {
+    report_to: String,
+    max_age: u64,
+    include_subdomains: bool,
+    success_fraction: f64,
+    failure_fraction: f64,
+}
+
+impl ::Header for Nel {
+    fn name() -> &'static ::HeaderName {
+        &NEL
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        values
+            .next()
+            .and_then(|value| serde_json::from_slice::<Value>(value.as_bytes()).ok())
+            .and_then(|json| Nel::from_json(&json))
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let json = encode_json(&self.to_json());
+        let value = HeaderValue::from_str(&json).expect("Nel is a valid HeaderValue");
+        values.extend(::std::iter::once(value));
+    }
+}
+
+impl Nel {
+    /// Creates a policy reporting to the named endpoint group, which is
+    /// cached for `max_age`.
+    ///
+    /// Every failed request and no successful ones are reported, until
+    /// changed with `with_failure_fraction` and `with_success_fraction`. A
+    /// `max_age` of zero asks the browser to remove its policy.
+    pub fn new<S: Into<String>>(report_to: S, max_age: Duration) -> Nel {
+        Nel {
+            report_to: report_to.into(),
+            max_age: max_age.as_secs(),
+            include_subdomains: false,
+            success_fraction: 0.0,
+            failure_fraction: 1.0,
+        }
+    }
+
+    /// Sets whether the policy also applies to subdomains of the origin.
+    pub fn with_include_subdomains(mut self, include_subdomains: bool) -> Nel {
+        self.include_subdomains = include_subdomains;
+        self
+    }
+
+    /// Sets the fraction of successful requests to report.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fraction` isn't between `0.0` and `1.0`.
+    pub fn with_success_fraction(mut self, fraction: f64) -> Nel {
+        assert!(is_fraction(fraction), "invalid NEL success_fraction");
+        self.success_fraction = fraction;
+        self
+    }
+
+    /// Sets the fraction of failed requests to report.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fraction` isn't between `0.0` and `1.0`.
+    pub fn with_failure_fraction(mut self, fraction: f64) -> Nel {
+        assert!(is_fraction(fraction), "invalid NEL failure_fraction");
+        self.failure_fraction = fraction;
+        self
+    }
+
+    /// The name of the endpoint group to send reports to.
+    pub fn report_to(&self) -> &str {
+        &self.report_to
+    }
+
+    /// How long the browser should remember the policy.
+    pub fn max_age(&self) -> Duration {
+        Duration::from_secs(self.max_age)
+    }
+
+    /// Whether the policy also applies to subdomains of the origin.
+    pub fn include_subdomains(&self) -> bool {
+        self.include_subdomains
+    }
+
+    /// The fraction of successful requests to report, `0.0` by default.
+    pub fn success_fraction(&self) -> f64 {
+        self.success_fraction
+    }
+
+    /// The fraction of failed requests to report, `1.0` by default.
+    pub fn failure_fraction(&self) -> f64 {
+        self.failure_fraction
+    }
+
+    fn from_json(json: &Value) -> Option<Nel> {
+        let fraction = |name: &str, default: f64| {
+            json.get(name)
+                .and_then(Value::as_f64)
+                .filter(|&f| is_fraction(f))
+                .unwrap_or(default)
+        };
+
+        Some(Nel {
+            report_to: json.get("report_to")?.as_str()?.to_owned(),
+            max_age: json.get("max_age")?.as_u64()?,
+            include_subdomains: json.get("include_subdomains") == Some(&Value::Bool(true)),
+            success_fraction: fraction("success_fraction", 0.0),
+            failure_fraction: fraction("failure_fraction", 1.0),
+        })
+    }
+
+    fn to_json(&self) -> Value {
+        let mut json = Map::new();
+        json.insert("report_to".into(), self.report_to.clone().into());
+        json.insert("max_age".into(), self.max_age.into());
+        if self.include_subdomains {
+            json.insert("include_subdomains".into(), true.into());
+        }
+        if self.success_fraction != 0.0 {
+            json.insert("success_fraction".into(), self.success_fraction.into());
+        }
+        if self.failure_fraction != 1.0 {
+            json.insert("failure_fraction".into(), self.failure_fraction.into());
+        }
+        Value::Object(json)
+    }
+}
+
+fn is_fraction(f: f64) -> bool {
+    (0.0..=1.0).contains(&f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let nel = test_decode::<Nel>(&[
+            r#"{"report_to": "nel", "max_age": 86400, "include_subdomains": true, "success_fraction": 0.25, "request_headers": ["If-Match"]}"#,
+        ])
+        .unwrap();
+        assert_eq!(nel.report_to(), "nel");
+        assert_eq!(nel.max_age(), Duration::from_secs(86400));
+        assert!(nel.include_subdomains());
+        assert_eq!(nel.success_fraction(), 0.25);
+        assert_eq!(nel.failure_fraction(), 1.0);
+    }
+
+    #[test]
+    fn decode_fraction_out_of_range() {
+        let nel = test_decode::<Nel>(&[
+            r#"{"report_to": "nel", "max_age": 1, "success_fraction": 2, "failure_fraction": "half"}"#,
+        ])
+        .unwrap();
+        assert_eq!(nel.success_fraction(), 0.0);
+        assert_eq!(nel.failure_fraction(), 1.0);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Nel>(&[""]), None);
+        assert_eq!(test_decode::<Nel>(&[r#"{"max_age": 1}"#]), None);
+        assert_eq!(test_decode::<Nel>(&[r#"{"report_to": "nel"}"#]), None);
+        assert_eq!(
+            test_decode::<Nel>(&[r#"{"report_to": "nel", "max_age": 1.5}"#]),
+            None
+        );
+        assert_eq!(test_decode::<Nel>(&[r#"["nel", 1]"#]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let nel = Nel::new("nel", Duration::from_secs(60));
+        let headers = test_encode(nel.clone());
+        assert_eq!(headers["nel"], r#"{"max_age":60,"report_to":"nel"}"#);
+
+        let nel = nel
+            .with_include_subdomains(true)
+            .with_success_fraction(0.5)
+            .with_failure_fraction(0.75);
+        let headers = test_encode(nel.clone());
+        assert_eq!(
+            headers["nel"],
+            r#"{"failure_fraction":0.75,"include_subdomains":true,"max_age":60,"report_to":"nel","success_fraction":0.5}"#
+        );
+        assert_eq!(
+            test_decode::<Nel>(&[headers["nel"].to_str().unwrap()]),
+            Some(nel)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_fraction_panics() {
+        let _ = Nel::new("nel", Duration::from_secs(1)).with_success_fraction(1.5);
+    }
+}
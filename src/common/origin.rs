@@ -43,6 +43,12 @@ impl Origin {
     /// The literal `null` Origin header.
     pub const NULL: Origin = Origin(OriginOrNull::Null);
 
+    /// The `null` origin, sent for an opaque origin such as a sandboxed
+    /// document. This is the same as [`Origin::NULL`](#associatedconstant.NULL).
+    pub fn null() -> Origin {
+        Origin::NULL
+    }
+
     /// Checks if `Origin` is `null`.
     #[inline]
     pub fn is_null(&self) -> bool {
@@ -79,7 +85,47 @@ impl Origin {
         }
     }
 
+    /// Checks if two origins are the same origin, for CORS checks.
+    ///
+    /// Schemes and hosts are compared ignoring case, and a missing port
+    /// matches the scheme's default port. A `null` origin is opaque, so
+    /// it never matches anything, not even another `null`.
+    pub fn matches(&self, other: &Origin) -> bool {
+        !self.is_null()
+            && !other.is_null()
+            && self.scheme().eq_ignore_ascii_case(other.scheme())
+            && self.hostname().eq_ignore_ascii_case(other.hostname())
+            && self.effective_port() == other.effective_port()
+    }
+
+    fn effective_port(&self) -> Option<u16> {
+        self.port().or_else(|| default_port(self.scheme()))
+    }
+
     /// Tries to build a `Origin` from three parts, the scheme, the host and an optional port.
+    ///
+    /// The port is left out when it's the scheme's default, so `https` and
+    /// port `443` make `https://example.com`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the scheme isn't a valid URI scheme, or the host is empty
+    /// or isn't just a host, such as if it has userinfo or a path.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::Origin;
+    ///
+    /// let origin = Origin::try_from_parts("https", "example.com", 443).unwrap();
+    /// assert_eq!(origin.to_string(), "https://example.com");
+    ///
+    /// let origin = Origin::try_from_parts("http", "example.com", 8080).unwrap();
+    /// assert_eq!(origin.to_string(), "http://example.com:8080");
+    ///
+    /// assert!(Origin::try_from_parts("https", "user@example.com", None).is_err());
+    /// ```
     pub fn try_from_parts(
         scheme: &str,
         host: &str,
@@ -97,7 +143,27 @@ impl Origin {
             }
         }
 
-        let bytes = Bytes::from(format!("{}://{}{}", scheme, host, MaybePort(port.into())));
+        let valid_scheme = match scheme.as_bytes().split_first() {
+            Some((first, rest)) => {
+                first.is_ascii_alphabetic()
+                    && rest
+                        .iter()
+                        .all(|&b| b.is_ascii_alphanumeric() || b == b'+' || b == b'-' || b == b'.')
+            }
+            None => false,
+        };
+        // An IPv6 literal is the only host with a colon.
+        let valid_host = !host.is_empty()
+            && !host.contains('@')
+            && (host.starts_with('[') || !host.contains(':'));
+        if !valid_scheme || !valid_host {
+            return Err(InvalidOrigin { _inner: () });
+        }
+
+        let port = port
+            .into()
+            .filter(|&port| default_port(scheme) != Some(port));
+        let bytes = Bytes::from(format!("{}://{}{}", scheme, host, MaybePort(port)));
         HeaderValue::from_maybe_shared(bytes)
             .ok()
             .and_then(|val| Self::try_from_value(&val))
@@ -114,6 +180,14 @@ impl Origin {
     }
 }
 
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        s if s.eq_ignore_ascii_case("http") || s.eq_ignore_ascii_case("ws") => Some(80),
+        s if s.eq_ignore_ascii_case("https") || s.eq_ignore_ascii_case("wss") => Some(443),
+        _ => None,
+    }
+}
+
 impl fmt::Display for Origin {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.0 {
@@ -207,4 +281,65 @@ mod tests {
         let headers = test_encode(Origin::NULL);
         assert_eq!(headers["origin"], "null");
     }
+
+    #[test]
+    fn try_from_parts() {
+        let origin = Origin::try_from_parts("https", "example.com", 443).unwrap();
+        assert_eq!(origin.port(), None);
+        assert_eq!(test_encode(origin)["origin"], "https://example.com");
+
+        let origin = Origin::try_from_parts("http", "example.com", Some(80)).unwrap();
+        assert_eq!(test_encode(origin)["origin"], "http://example.com");
+
+        let origin = Origin::try_from_parts("http", "example.com", 8080).unwrap();
+        assert_eq!(origin.port(), Some(8080));
+        assert_eq!(test_encode(origin)["origin"], "http://example.com:8080");
+
+        let origin = Origin::try_from_parts("https", "example.com", 80).unwrap();
+        assert_eq!(test_encode(origin)["origin"], "https://example.com:80");
+
+        let origin = Origin::try_from_parts("http", "[::1]", 3000).unwrap();
+        assert_eq!(origin.hostname(), "[::1]");
+        assert_eq!(test_encode(origin)["origin"], "http://[::1]:3000");
+    }
+
+    #[test]
+    fn try_from_parts_invalid() {
+        assert!(Origin::try_from_parts("", "example.com", None).is_err());
+        assert!(Origin::try_from_parts("1http", "example.com", None).is_err());
+        assert!(Origin::try_from_parts("ht tp", "example.com", None).is_err());
+        assert!(Origin::try_from_parts("https", "", None).is_err());
+        assert!(Origin::try_from_parts("https", "user@example.com", None).is_err());
+        assert!(Origin::try_from_parts("https", "example.com:443", None).is_err());
+        assert!(Origin::try_from_parts("https", "example.com/path", None).is_err());
+        assert!(Origin::try_from_parts("https", "[::1]@example.com", None).is_err());
+    }
+
+    #[test]
+    fn null_constructor() {
+        assert!(Origin::null().is_null());
+        assert_eq!(Origin::null(), Origin::NULL);
+    }
+
+    #[test]
+    fn matches() {
+        let origin = test_decode::<Origin>(&["https://Example.com"]).unwrap();
+        let same = Origin::try_from_parts("https", "example.com", 443).unwrap();
+        assert!(origin.matches(&same));
+        assert!(same.matches(&origin));
+
+        let other_port = Origin::try_from_parts("https", "example.com", 8443).unwrap();
+        assert!(!origin.matches(&other_port));
+
+        let other_scheme = Origin::try_from_parts("http", "example.com", None).unwrap();
+        assert!(!origin.matches(&other_scheme));
+    }
+
+    #[test]
+    fn null_never_matches() {
+        let origin = test_decode::<Origin>(&["https://example.com"]).unwrap();
+        assert!(!Origin::NULL.matches(&origin));
+        assert!(!origin.matches(&Origin::NULL));
+        assert!(!Origin::NULL.matches(&Origin::NULL));
+    }
 }
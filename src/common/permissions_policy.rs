@@ -0,0 +1,376 @@
+use std::fmt;
+
+use util::csv::{decode_list, trim_ows};
+use util::quoted_string::{self, Quoted};
+use HeaderValue;
+
+static PERMISSIONS_POLICY: ::HeaderName = ::HeaderName::from_static("permissions-policy");
+
+/// `Permissions-Policy` header, defined in
+/// [Permissions Policy](https://www.w3.org/TR/permissions-policy-1/#permissions-policy-http-header-field)
+///
+/// The `Permissions-Policy` response header controls which origins may use
+/// powerful browser features, such as geolocation or the camera, in the
+/// document and the frames it embeds. Each feature has an allowlist of
+/// origins, where `self` is the document's own origin, `*` is any origin,
+/// and an empty list denies the feature everywhere.
+///
+/// # ABNF
+///
+/// ```text
+/// Permissions-Policy = sf-dictionary
+/// ; each member is  feature=( *( "self" / "*" / sf-string ) )
+/// ;             or  feature=( "self" / "*" / sf-string )
+/// ```
+///
+/// # Example values
+///
+/// * `geolocation=(self "https://maps.example"), camera=()`
+/// * `fullscreen=*`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::PermissionsPolicy;
+///
+/// let policy = PermissionsPolicy::new()
+///     .with_feature("geolocation", &["self", "https://maps.example"])
+///     .with_feature("camera", Vec::<String>::new());
+///
+/// assert_eq!(policy.allowlist("camera").unwrap().count(), 0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PermissionsPolicy {
+    features: Vec<Feature>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Feature {
+    name: String,
+    allowlist: Vec<Item>,
+    // `feature=*` rather than `feature=(*)`, kept for round-tripping.
+    bare: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Item {
+    Token(String),
+    Origin(String),
+}
+
+impl ::Header for PermissionsPolicy {
+    fn name() -> &'static ::HeaderName {
+        &PERMISSIONS_POLICY
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let mut policy = PermissionsPolicy::default();
+        for feature in decode_list(values, parse_feature)? {
+            policy.insert(feature);
+        }
+        Ok(policy)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(::util::fmt(self)));
+    }
+}
+
+impl PermissionsPolicy {
+    /// Creates an empty policy, to be filled in with `with_feature`.
+    pub fn new() -> PermissionsPolicy {
+        PermissionsPolicy::default()
+    }
+
+    /// Sets the allowlist of a feature, replacing any it already had.
+    ///
+    /// Each entry is `self`, `*`, or an origin such as
+    /// `https://maps.example`. An empty allowlist denies the feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `feature` isn't a lowercase structured-field key, or an
+    /// origin contains `"`, `\`, or control characters.
+    pub fn with_feature<I>(mut self, feature: &str, allowlist: I) -> PermissionsPolicy
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        assert!(
+            is_key(feature),
+            "invalid Permissions-Policy feature: {:?}",
+            feature
+        );
+        let allowlist = allowlist
+            .into_iter()
+            .map(|entry| {
+                let entry = entry.as_ref();
+                if entry == "self" || entry == "*" {
+                    return Item::Token(entry.to_owned());
+                }
+                assert!(
+                    entry
+                        .bytes()
+                        .all(|b| (b' '..=b'~').contains(&b) && b != b'"' && b != b'\\'),
+                    "invalid Permissions-Policy origin: {:?}",
+                    entry
+                );
+                Item::Origin(entry.to_owned())
+            })
+            .collect();
+        self.insert(Feature {
+            name: feature.to_owned(),
+            allowlist,
+            bare: false,
+        });
+        self
+    }
+
+    /// Returns the allowlist of a feature, if the policy mentions it.
+    ///
+    /// The entries are `self`, `*`, or unquoted origins. An empty
+    /// allowlist means the feature is denied everywhere.
+    pub fn allowlist(&self, feature: &str) -> Option<impl Iterator<Item = &str>> {
+        self.features.iter().find(|f| f.name == feature).map(|f| {
+            f.allowlist.iter().map(|item| match *item {
+                Item::Token(ref s) | Item::Origin(ref s) => s.as_str(),
+            })
+        })
+    }
+
+    /// Returns an iterator over the features, in order.
+    pub fn features(&self) -> impl Iterator<Item = &str> {
+        self.features.iter().map(|f| f.name.as_str())
+    }
+
+    // As in any structured-field dictionary, a repeated key overrides the
+    // earlier value, but keeps its position.
+    fn insert(&mut self, feature: Feature) {
+        match self.features.iter_mut().find(|f| f.name == feature.name) {
+            Some(existing) => *existing = feature,
+            None => self.features.push(feature),
+        }
+    }
+}
+
+impl fmt::Display for PermissionsPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, feature) in self.features.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}=", feature.name)?;
+            if feature.bare && feature.allowlist.len() == 1 {
+                fmt::Display::fmt(&feature.allowlist[0], f)?;
+                continue;
+            }
+            f.write_str("(")?;
+            for (j, item) in feature.allowlist.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(" ")?;
+                }
+                fmt::Display::fmt(item, f)?;
+            }
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Item::Token(ref token) => f.write_str(token),
+            Item::Origin(ref origin) => fmt::Display::fmt(&Quoted(origin), f),
+        }
+    }
+}
+
+// key = ( lcalpha / "*" ) *( lcalpha / DIGIT / "_" / "-" / "." / "*" )
+fn is_key(s: &str) -> bool {
+    let mut bytes = s.bytes();
+    match bytes.next() {
+        Some(b) if b.is_ascii_lowercase() || b == b'*' => (),
+        _ => return false,
+    }
+    bytes.all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"_-.*".contains(&b))
+}
+
+fn split_key(s: &str) -> Option<(&str, &str)> {
+    let end = s
+        .bytes()
+        .position(|b| !(b.is_ascii_lowercase() || b.is_ascii_digit() || b"_-.*".contains(&b)))
+        .unwrap_or(s.len());
+    if is_key(&s[..end]) {
+        Some(s.split_at(end))
+    } else {
+        None
+    }
+}
+
+// An sf-token, such as `self` or `*`.
+fn split_sf_token(s: &str) -> Option<(&str, &str)> {
+    match s.bytes().next() {
+        Some(b) if b.is_ascii_alphabetic() || b == b'*' => (),
+        _ => return None,
+    }
+    let end = s
+        .bytes()
+        .position(|b| !(quoted_string::is_tchar(b) || b == b':' || b == b'/'))
+        .unwrap_or(s.len());
+    Some(s.split_at(end))
+}
+
+fn parse_item(s: &str) -> Option<(Item, &str)> {
+    let (item, rest) = if s.starts_with('"') {
+        let (origin, rest) = quoted_string::parse(s)?;
+        (Item::Origin(origin), rest)
+    } else {
+        let (token, rest) = split_sf_token(s)?;
+        (Item::Token(token.to_owned()), rest)
+    };
+    Some((item, skip_parameters(rest)?))
+}
+
+// Parameters, such as `;report-to=main`, don't change the allowlist and
+// are skipped.
+fn skip_parameters(mut s: &str) -> Option<&str> {
+    while s.starts_with(';') {
+        let (_, rest) = split_key(trim_ows(&s[1..]))?;
+        s = rest;
+        if s.starts_with('=') {
+            let value = &s[1..];
+            s = if value.starts_with('"') {
+                quoted_string::parse(value)?.1
+            } else {
+                let end = value.find(&[';', ',', ' ', ')'][..]).unwrap_or(value.len());
+                &value[end..]
+            };
+        }
+    }
+    Some(s)
+}
+
+fn parse_feature(s: &str) -> Option<(Feature, &str)> {
+    let (name, rest) = split_key(s)?;
+    if !rest.starts_with('=') {
+        return None;
+    }
+    let rest = &rest[1..];
+
+    if !rest.starts_with('(') {
+        let (item, rest) = parse_item(rest)?;
+        let feature = Feature {
+            name: name.to_owned(),
+            allowlist: vec![item],
+            bare: true,
+        };
+        return Some((feature, rest));
+    }
+
+    let mut allowlist = Vec::new();
+    let mut rest = &rest[1..];
+    loop {
+        rest = rest.trim_start_matches(' ');
+        if rest.starts_with(')') {
+            rest = skip_parameters(&rest[1..])?;
+            break;
+        }
+        let (item, after) = parse_item(rest)?;
+        if !after.starts_with(' ') && !after.starts_with(')') {
+            return None;
+        }
+        allowlist.push(item);
+        rest = after;
+    }
+
+    let feature = Feature {
+        name: name.to_owned(),
+        allowlist,
+        bare: false,
+    };
+    Some((feature, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let policy = test_decode::<PermissionsPolicy>(&[
+            r#"geolocation=(self "https://maps.example"), camera=()"#,
+            "fullscreen=*",
+        ])
+        .unwrap();
+        assert_eq!(
+            policy.features().collect::<Vec<_>>(),
+            vec!["geolocation", "camera", "fullscreen"]
+        );
+        assert_eq!(
+            policy.allowlist("geolocation").unwrap().collect::<Vec<_>>(),
+            vec!["self", "https://maps.example"]
+        );
+        assert_eq!(policy.allowlist("camera").unwrap().count(), 0);
+        assert_eq!(
+            policy.allowlist("fullscreen").unwrap().collect::<Vec<_>>(),
+            vec!["*"]
+        );
+        assert!(policy.allowlist("microphone").is_none());
+    }
+
+    #[test]
+    fn decode_parameters_and_duplicates() {
+        let policy = test_decode::<PermissionsPolicy>(&[
+            r#"camera=(self);report-to=main, geolocation=("https://a.example";x=1), camera=()"#,
+        ])
+        .unwrap();
+        assert_eq!(policy.allowlist("camera").unwrap().count(), 0);
+        assert_eq!(
+            policy.allowlist("geolocation").unwrap().collect::<Vec<_>>(),
+            vec!["https://a.example"]
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<PermissionsPolicy>(&[""]), None);
+        assert_eq!(test_decode::<PermissionsPolicy>(&["Camera=()"]), None);
+        assert_eq!(test_decode::<PermissionsPolicy>(&["camera"]), None);
+        assert_eq!(test_decode::<PermissionsPolicy>(&["camera=(self"]), None);
+        assert_eq!(
+            test_decode::<PermissionsPolicy>(&[r#"camera=("a""b")"#]),
+            None
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let raw = r#"geolocation=(self "https://maps.example"), camera=(), fullscreen=*"#;
+        let policy = test_decode::<PermissionsPolicy>(&[raw]).unwrap();
+        let headers = test_encode(policy);
+        assert_eq!(headers["permissions-policy"], raw);
+    }
+
+    #[test]
+    fn builder() {
+        let policy = PermissionsPolicy::new()
+            .with_feature("geolocation", &["self", "https://maps.example"])
+            .with_feature("camera", Vec::<String>::new())
+            .with_feature("fullscreen", &["*"]);
+
+        let headers = test_encode(policy);
+        assert_eq!(
+            headers["permissions-policy"],
+            r#"geolocation=(self "https://maps.example"), camera=(), fullscreen=(*)"#
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn builder_invalid_feature() {
+        let _ = PermissionsPolicy::new().with_feature("Camera", &["self"]);
+    }
+}
@@ -1,3 +1,4 @@
+use util::FlatCsv;
 use HeaderValue;
 
 /// The `Pragma` header defined by HTTP/1.0.
@@ -24,22 +25,30 @@ use HeaderValue;
 /// let pragma = Pragma::no_cache();
 /// ```
 #[derive(Clone, Debug, PartialEq)]
-pub struct Pragma(HeaderValue);
+pub struct Pragma(FlatCsv);
 
 derive_header! {
     Pragma(_),
-    name: PRAGMA
+    name: PRAGMA,
+    list
 }
 
 impl Pragma {
     /// Construct the literal `no-cache` Pragma header.
     pub fn no_cache() -> Pragma {
-        Pragma(HeaderValue::from_static("no-cache"))
+        Pragma(HeaderValue::from_static("no-cache").into())
     }
 
-    /// Return whether this pragma is `no-cache`.
+    /// Return whether this pragma includes `no-cache`, ignoring case.
     pub fn is_no_cache(&self) -> bool {
-        self.0 == "no-cache"
+        self.iter()
+            .any(|directive| directive.eq_ignore_ascii_case("no-cache"))
+    }
+
+    /// Returns an iterator over the pragma directives, including any
+    /// extensions such as `foo=bar`.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter(|directive| !directive.is_empty())
     }
 }
 
@@ -58,4 +67,14 @@ mod tests {
         let ext = test_decode::<Pragma>(&["dexter"]).unwrap();
         assert!(!ext.is_no_cache());
     }
+
+    #[test]
+    fn no_cache_among_extensions() {
+        let pragma = test_decode::<Pragma>(&["x-foo=bar, No-Cache", "dexter"]).unwrap();
+        assert!(pragma.is_no_cache());
+        assert_eq!(
+            pragma.iter().collect::<Vec<_>>(),
+            vec!["x-foo=bar", "No-Cache", "dexter"]
+        );
+    }
 }
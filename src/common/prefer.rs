@@ -0,0 +1,348 @@
+use std::fmt;
+use std::iter::FromIterator;
+
+use util::csv::{decode_list, encode_list, split_token, trim_ows};
+use util::quoted_string::{self, Quoted};
+use HeaderValue;
+
+static PREFER: ::HeaderName = ::HeaderName::from_static("prefer");
+
+/// `Prefer` header, defined in
+/// [RFC7240](https://tools.ietf.org/html/rfc7240#section-2)
+///
+/// The `Prefer` request header asks the server to handle the request in
+/// a particular way, such as returning a minimal response or processing
+/// it asynchronously. Servers are free to ignore preferences, and say
+/// which ones they honored with `Preference-Applied`.
+///
+/// Preference names are case-insensitive. If a preference is repeated,
+/// only the first one counts.
+///
+/// # ABNF
+///
+/// ```text
+/// Prefer     = 1#preference
+/// preference = token [ BWS "=" BWS word ]
+///              *( OWS ";" [ OWS parameter ] )
+/// parameter  = token [ BWS "=" BWS word ]
+/// word       = token / quoted-string
+/// ```
+///
+/// # Example values
+///
+/// * `respond-async, wait=10`
+/// * `return=minimal`
+/// * `handling=lenient; foo="bar, baz"`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{Prefer, Preference};
+///
+/// let prefer = vec![
+///     Preference::new("return").with_value("minimal"),
+///     Preference::new("wait").with_value("10"),
+/// ]
+/// .into_iter()
+/// .collect::<Prefer>();
+///
+/// assert!(prefer.return_minimal());
+/// assert_eq!(prefer.preference("wait"), Some("10"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Prefer(Vec<Preference>);
+
+/// A single preference of a [`Prefer`](struct.Prefer.html) or
+/// [`PreferenceApplied`](struct.PreferenceApplied.html) header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Preference {
+    name: String,
+    value: Option<String>,
+    params: Vec<(String, Option<String>)>,
+}
+
+impl ::Header for Prefer {
+    fn name() -> &'static ::HeaderName {
+        &PREFER
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        decode_preferences(values).map(Prefer)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_preferences(&self.0)));
+    }
+}
+
+impl Prefer {
+    /// Returns the value of a preference, ignoring case.
+    ///
+    /// A preference without a value, such as `respond-async`, has an
+    /// empty value.
+    pub fn preference(&self, name: &str) -> Option<&str> {
+        find(&self.0, name).map(|pref| pref.value().unwrap_or(""))
+    }
+
+    /// Returns true if the `respond-async` preference is present.
+    pub fn respond_async(&self) -> bool {
+        find(&self.0, "respond-async").is_some()
+    }
+
+    /// Returns true if `return=minimal` is the `return` preference.
+    pub fn return_minimal(&self) -> bool {
+        find(&self.0, "return")
+            .and_then(Preference::value)
+            .map(|value| value.eq_ignore_ascii_case("minimal"))
+            .unwrap_or(false)
+    }
+
+    /// Returns an iterator over the preferences, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Preference> {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<Preference> for Prefer {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Preference>,
+    {
+        Prefer(iter.into_iter().collect())
+    }
+}
+
+// ===== impl Preference =====
+
+impl Preference {
+    /// Creates a preference with no value or parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a `token`.
+    pub fn new<S: Into<String>>(name: S) -> Preference {
+        let name = name.into();
+        assert!(
+            quoted_string::is_token(&name),
+            "invalid preference name: {:?}",
+            name
+        );
+        Preference {
+            name,
+            value: None,
+            params: Vec::new(),
+        }
+    }
+
+    /// Sets the value, which is quoted if it isn't a `token`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` contains control characters.
+    pub fn with_value<S: Into<String>>(mut self, value: S) -> Preference {
+        let value = value.into();
+        assert!(is_word(&value), "invalid preference value: {:?}", value);
+        self.value = Some(value);
+        self
+    }
+
+    /// Adds a parameter, which is quoted if it isn't a `token`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a `token`, or `value` contains control
+    /// characters.
+    pub fn with_param<N, V>(mut self, name: N, value: V) -> Preference
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        let name = name.into();
+        let value = value.into();
+        assert!(
+            quoted_string::is_token(&name),
+            "invalid preference parameter name: {:?}",
+            name
+        );
+        assert!(
+            is_word(&value),
+            "invalid preference parameter value: {:?}",
+            value
+        );
+        self.params.push((name, Some(value)));
+        self
+    }
+
+    /// Returns the preference's name, as it was written.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the unquoted value, if any.
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// Returns the unquoted value of a parameter, ignoring case.
+    ///
+    /// A parameter without a value has an empty value.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_deref().unwrap_or(""))
+    }
+}
+
+impl fmt::Display for Preference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if let Some(ref value) = self.value {
+            write!(f, "={}", Word(value))?;
+        }
+        for (name, value) in &self.params {
+            write!(f, "; {}", name)?;
+            if let Some(ref value) = *value {
+                write!(f, "={}", Word(value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// A `word`, quoted only when it needs to be.
+struct Word<'a>(&'a str);
+
+impl<'a> fmt::Display for Word<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if quoted_string::is_token(self.0) {
+            f.write_str(self.0)
+        } else {
+            fmt::Display::fmt(&Quoted(self.0), f)
+        }
+    }
+}
+
+fn is_word(s: &str) -> bool {
+    s.chars().all(|c| c == '\t' || !c.is_control())
+}
+
+// ===== parsing =====
+
+pub(super) fn find<'a>(prefs: &'a [Preference], name: &str) -> Option<&'a Preference> {
+    prefs
+        .iter()
+        .find(|pref| pref.name.eq_ignore_ascii_case(name))
+}
+
+pub(super) fn decode_preferences<'i, I>(values: &mut I) -> Result<Vec<Preference>, ::Error>
+where
+    I: Iterator<Item = &'i HeaderValue>,
+{
+    decode_list(values, parse_preference)
+}
+
+pub(super) fn encode_preferences(prefs: &[Preference]) -> HeaderValue {
+    encode_list(prefs)
+}
+
+fn parse_preference(s: &str) -> Option<(Preference, &str)> {
+    let (name, value, mut rest) = parse_pair(s)?;
+    let mut pref = Preference {
+        name,
+        value,
+        params: Vec::new(),
+    };
+
+    loop {
+        rest = trim_ows(rest);
+        if !rest.starts_with(';') {
+            return Some((pref, rest));
+        }
+        rest = trim_ows(&rest[1..]);
+        // parameters are optional after a `;`
+        if rest.is_empty() || rest.starts_with(&[';', ','][..]) {
+            continue;
+        }
+        let (name, value, after) = parse_pair(rest)?;
+        pref.params.push((name, value));
+        rest = after;
+    }
+}
+
+// token [ BWS "=" BWS word ]
+fn parse_pair(s: &str) -> Option<(String, Option<String>, &str)> {
+    let (name, rest) = split_token(s)?;
+    let after_ws = trim_ows(rest);
+    if !after_ws.starts_with('=') {
+        return Some((name.to_owned(), None, rest));
+    }
+
+    let rest = trim_ows(&after_ws[1..]);
+    let (value, rest) = if rest.starts_with('"') {
+        quoted_string::parse(rest)?
+    } else {
+        let (token, rest) = split_token(rest)?;
+        (token.to_owned(), rest)
+    };
+    Some((name.to_owned(), Some(value), rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_values_and_params() {
+        let prefer = test_decode::<Prefer>(&[
+            "respond-async, wait = 10",
+            "Return=minimal; foo=\"bar, baz\";; flag",
+        ])
+        .unwrap();
+        assert!(prefer.respond_async());
+        assert!(prefer.return_minimal());
+        assert_eq!(prefer.preference("respond-async"), Some(""));
+        assert_eq!(prefer.preference("WAIT"), Some("10"));
+        assert_eq!(prefer.preference("handling"), None);
+
+        let ret = prefer.iter().nth(2).unwrap();
+        assert_eq!(ret.name(), "Return");
+        assert_eq!(ret.param("foo"), Some("bar, baz"));
+        assert_eq!(ret.param("flag"), Some(""));
+    }
+
+    #[test]
+    fn decode_first_wins() {
+        let prefer = test_decode::<Prefer>(&["return=representation, return=minimal"]).unwrap();
+        assert!(!prefer.return_minimal());
+        assert_eq!(prefer.preference("return"), Some("representation"));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Prefer>(&[""]), None);
+        assert_eq!(test_decode::<Prefer>(&["wait="]), None);
+        assert_eq!(test_decode::<Prefer>(&["wait=\"10"]), None);
+        assert_eq!(test_decode::<Prefer>(&["return minimal"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let prefer = vec![
+            Preference::new("return").with_value("minimal"),
+            Preference::new("handling")
+                .with_value("lenient")
+                .with_param("foo", "bar baz"),
+            Preference::new("respond-async"),
+        ]
+        .into_iter()
+        .collect::<Prefer>();
+        let headers = test_encode(prefer);
+        assert_eq!(
+            headers["prefer"],
+            r#"return=minimal, handling=lenient; foo="bar baz", respond-async"#
+        );
+    }
+}
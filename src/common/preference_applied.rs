@@ -0,0 +1,105 @@
+use std::iter::FromIterator;
+
+use super::prefer::{decode_preferences, encode_preferences, find, Preference};
+use HeaderValue;
+
+static PREFERENCE_APPLIED: ::HeaderName = ::HeaderName::from_static("preference-applied");
+
+/// `Preference-Applied` header, defined in
+/// [RFC7240](https://tools.ietf.org/html/rfc7240#section-3)
+///
+/// The `Preference-Applied` response header lists the preferences from
+/// the request's `Prefer` header that the server honored.
+///
+/// # ABNF
+///
+/// ```text
+/// Preference-Applied = 1#applied-pref
+/// applied-pref       = token [ BWS "=" BWS word ]
+/// ```
+///
+/// # Example values
+///
+/// * `return=minimal`
+/// * `respond-async`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{Preference, PreferenceApplied};
+///
+/// let applied = vec![Preference::new("return").with_value("minimal")]
+///     .into_iter()
+///     .collect::<PreferenceApplied>();
+///
+/// assert_eq!(applied.preference("return"), Some("minimal"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreferenceApplied(Vec<Preference>);
+
+impl ::Header for PreferenceApplied {
+    fn name() -> &'static ::HeaderName {
+        &PREFERENCE_APPLIED
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        decode_preferences(values).map(PreferenceApplied)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_preferences(&self.0)));
+    }
+}
+
+impl PreferenceApplied {
+    /// Returns the value of an applied preference, ignoring case.
+    ///
+    /// A preference without a value, such as `respond-async`, has an
+    /// empty value.
+    pub fn preference(&self, name: &str) -> Option<&str> {
+        find(&self.0, name).map(|pref| pref.value().unwrap_or(""))
+    }
+
+    /// Returns an iterator over the applied preferences, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Preference> {
+        self.0.iter()
+    }
+}
+
+/// Parameters aren't part of `Preference-Applied`, so a preference
+/// copied from a `Prefer` header should be rebuilt without them.
+impl FromIterator<Preference> for PreferenceApplied {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Preference>,
+    {
+        PreferenceApplied(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let applied =
+            test_decode::<PreferenceApplied>(&["return=minimal", "respond-async"]).unwrap();
+        assert_eq!(applied.preference("Return"), Some("minimal"));
+        assert_eq!(applied.preference("respond-async"), Some(""));
+
+        let headers = test_encode(applied);
+        assert_eq!(
+            headers["preference-applied"],
+            "return=minimal, respond-async"
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<PreferenceApplied>(&[""]), None);
+        assert_eq!(test_decode::<PreferenceApplied>(&["=minimal"]), None);
+    }
+}
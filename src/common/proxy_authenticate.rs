@@ -0,0 +1,110 @@
+use std::iter::FromIterator;
+
+use super::www_authenticate::{encode_challenges, Challenge, WwwAuthenticate};
+use HeaderValue;
+
+/// `Proxy-Authenticate` header, defined in
+/// [RFC7235](https://tools.ietf.org/html/rfc7235#section-4.3)
+///
+/// The `Proxy-Authenticate` header field consists of at least one
+/// challenge that indicates the authentication scheme(s) and parameters
+/// applicable to the proxy for this request. It is sent in
+/// `407 Proxy Authentication Required` responses, and answered by the
+/// client with a `Proxy-Authorization` header.
+///
+/// The challenges are the same as for `WWW-Authenticate`, and parsed the
+/// same way.
+///
+/// # ABNF
+///
+/// ```text
+/// Proxy-Authenticate = 1#challenge
+/// ```
+///
+/// # Example values
+///
+/// * `Basic realm="proxy"`
+/// * `Digest realm="proxy", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", qop="auth"`
+///
+/// # Examples
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{Challenge, ProxyAuthenticate};
+///
+/// let proxy_auth = vec![Challenge::new("Basic").with_param("realm", "proxy")]
+///     .into_iter()
+///     .collect::<ProxyAuthenticate>();
+///
+/// let basic = proxy_auth.challenges().next().unwrap();
+/// assert_eq!(basic.scheme(), "Basic");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProxyAuthenticate(Vec<Challenge>);
+
+impl ::Header for ProxyAuthenticate {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::PROXY_AUTHENTICATE
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        WwwAuthenticate::decode(values).map(|www_auth| ProxyAuthenticate(www_auth.0))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_challenges(&self.0)));
+    }
+}
+
+impl ProxyAuthenticate {
+    /// Returns an iterator over the challenges, in order.
+    pub fn challenges(&self) -> impl Iterator<Item = &Challenge> {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<Challenge> for ProxyAuthenticate {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Challenge>,
+    {
+        ProxyAuthenticate(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn encode_basic() {
+        let proxy_auth = vec![Challenge::new("Basic").with_param("realm", "proxy")]
+            .into_iter()
+            .collect::<ProxyAuthenticate>();
+
+        let headers = test_encode(proxy_auth);
+        assert_eq!(headers["proxy-authenticate"], r#"Basic realm="proxy""#);
+    }
+
+    #[test]
+    fn decode_upstream() {
+        let proxy_auth = test_decode::<ProxyAuthenticate>(&[
+            r#"Digest realm="upstream", qop="auth", nonce="abc""#,
+            r#"Basic realm="proxy, inc""#,
+        ])
+        .unwrap();
+        let challenges = proxy_auth.challenges().collect::<Vec<_>>();
+        assert_eq!(challenges.len(), 2);
+        assert_eq!(challenges[0].scheme(), "Digest");
+        assert_eq!(challenges[0].param("nonce"), Some("abc"));
+        assert_eq!(challenges[1].scheme(), "Basic");
+        assert_eq!(challenges[1].param("realm"), Some("proxy, inc"));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<ProxyAuthenticate>(&[""]), None);
+        assert_eq!(test_decode::<ProxyAuthenticate>(&[r#"Basic realm="proxy"#]), None);
+    }
+}
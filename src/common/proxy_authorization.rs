@@ -1,4 +1,4 @@
-use super::authorization::{Authorization, Credentials};
+use super::authorization::{Authorization, Basic, Bearer, Credentials, InvalidBearerToken};
 
 /// `Proxy-Authorization` header, defined in [RFC7235](https://tools.ietf.org/html/rfc7235#section-4.4)
 ///
@@ -18,12 +18,53 @@ use super::authorization::{Authorization, Credentials};
 /// # Example values
 /// * `Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==`
 /// * `Bearer fpKL54jvWmEGVoRdCNjG`
+/// * `Digest username="Mufasa", realm="proxy", nonce="abc", uri="/", response="123"`
 ///
 /// # Examples
 ///
+/// It accepts the same [`Credentials`](authorization/trait.Credentials.html)
+/// as `Authorization`, including `Digest`:
+///
+/// ```
+/// # extern crate headers;
+/// use headers::authorization::Digest;
+/// use headers::ProxyAuthorization;
+///
+/// let basic = ProxyAuthorization::basic("Aladdin", "open sesame");
+/// let digest = ProxyAuthorization(Digest::new("Mufasa", "proxy", "abc", "/", "123"));
+/// ```
 #[derive(Clone, PartialEq, Debug)]
 pub struct ProxyAuthorization<C: Credentials>(pub C);
 
+impl ProxyAuthorization<Basic> {
+    /// Create a `Basic` proxy authorization header.
+    pub fn basic(username: &str, password: &str) -> Self {
+        ProxyAuthorization(Authorization::basic(username, password).0)
+    }
+
+    /// View the decoded username.
+    pub fn username(&self) -> &str {
+        self.0.username()
+    }
+
+    /// View the decoded password.
+    pub fn password(&self) -> &str {
+        self.0.password()
+    }
+}
+
+impl ProxyAuthorization<Bearer> {
+    /// Try to create a `Bearer` proxy authorization header.
+    pub fn bearer(token: &str) -> Result<Self, InvalidBearerToken> {
+        Authorization::bearer(token).map(|auth| ProxyAuthorization(auth.0))
+    }
+
+    /// View the token part as a `&str`.
+    pub fn token(&self) -> &str {
+        self.0.token()
+    }
+}
+
 impl<C: Credentials> ::Header for ProxyAuthorization<C> {
     fn name() -> &'static ::HeaderName {
         &::http::header::PROXY_AUTHORIZATION
@@ -45,3 +86,50 @@ impl<C: Credentials> ::Header for ProxyAuthorization<C> {
         values.extend(::std::iter::once(value));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::authorization::Digest;
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn basic_round_trip() {
+        let auth = ProxyAuthorization::basic("Aladdin", "open sesame");
+        let headers = test_encode(auth.clone());
+        assert_eq!(
+            headers["proxy-authorization"],
+            "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="
+        );
+
+        let decoded: ProxyAuthorization<Basic> =
+            test_decode(&["Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="]).unwrap();
+        assert_eq!(decoded, auth);
+        assert_eq!(decoded.username(), "Aladdin");
+    }
+
+    #[test]
+    fn bearer() {
+        let auth: ProxyAuthorization<Bearer> =
+            test_decode(&["Bearer fpKL54jvWmEGVoRdCNjG"]).unwrap();
+        assert_eq!(auth.token(), "fpKL54jvWmEGVoRdCNjG");
+    }
+
+    #[test]
+    fn digest_round_trip() {
+        let digest = Digest::new("Mufasa", "proxy", "abc", "/", "123").with_param("qop", "auth");
+        let headers = test_encode(ProxyAuthorization(digest));
+        assert_eq!(
+            headers["proxy-authorization"],
+            concat!(
+                r#"Digest username="Mufasa", realm="proxy", nonce="abc", uri="/", "#,
+                r#"response="123", qop=auth"#,
+            )
+        );
+
+        let value = headers["proxy-authorization"].to_str().unwrap();
+        let auth: ProxyAuthorization<Digest> = test_decode(&[value]).unwrap();
+        assert_eq!(auth.0.username(), "Mufasa");
+        assert_eq!(auth.0.param("qop"), Some("auth"));
+    }
+}
@@ -1,4 +1,5 @@
-use std::ops::{Bound, RangeBounds};
+use std::fmt::Write;
+use std::ops::{Bound, RangeBounds, RangeInclusive};
 
 /// `Range` header, defined in [RFC7233](https://tools.ietf.org/html/rfc7233#section-3.1)
 ///
@@ -38,6 +39,9 @@ use std::ops::{Bound, RangeBounds};
 ///
 ///
 /// let range = Range::bytes(0..1234).unwrap();
+///
+/// let chunks = Range::byte_ranges(vec![0..500, 1000..1500]).unwrap();
+/// let tail = Range::suffix(500);
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 pub struct Range(::HeaderValue);
@@ -46,21 +50,81 @@ error_type!(InvalidRange);
 
 impl Range {
     /// Creates a `Range` header from bounds.
+    ///
+    /// Fails if the range is empty, such as `5..5` or `5..=4`.
     pub fn bytes(bounds: impl RangeBounds<u64>) -> Result<Self, InvalidRange> {
+        let invalid = || InvalidRange { _inner: () };
         let v = match (bounds.start_bound(), bounds.end_bound()) {
             (Bound::Unbounded, Bound::Included(end)) => format!("bytes=-{}", end),
-            (Bound::Unbounded, Bound::Excluded(&end)) => format!("bytes=-{}", end - 1),
-            (Bound::Included(start), Bound::Included(end)) => format!("bytes={}-{}", start, end),
-            (Bound::Included(start), Bound::Excluded(&end)) => {
+            (Bound::Unbounded, Bound::Excluded(&end)) => {
+                format!("bytes=-{}", end.checked_sub(1).ok_or_else(invalid)?)
+            }
+            (Bound::Included(&start), Bound::Included(&end)) if start <= end => {
+                format!("bytes={}-{}", start, end)
+            }
+            (Bound::Included(&start), Bound::Excluded(&end)) if start < end => {
                 format!("bytes={}-{}", start, end - 1)
             }
             (Bound::Included(start), Bound::Unbounded) => format!("bytes={}-", start),
-            _ => return Err(InvalidRange { _inner: () }),
+            _ => return Err(invalid()),
         };
 
         Ok(Range(::HeaderValue::from_str(&v).unwrap()))
     }
 
+    /// Creates a `Range` header with one or more byte ranges, such as
+    /// `bytes=0-499, 1000-`.
+    ///
+    /// Each range covers the same offsets it would as a Rust range, so
+    /// `..500` is the first 500 bytes. Note that `bytes` treats `..500`
+    /// as a suffix instead; use [`suffix`](#method.suffix) to ask for the
+    /// last bytes of a representation.
+    ///
+    /// Fails if there are no ranges, or if a range is empty, such as
+    /// `5..5` or `5..=4`.
+    pub fn byte_ranges<I, R>(ranges: I) -> Result<Self, InvalidRange>
+    where
+        I: IntoIterator<Item = R>,
+        R: RangeBounds<u64>,
+    {
+        let invalid = || InvalidRange { _inner: () };
+        let mut v = String::from("bytes=");
+        for (i, range) in ranges.into_iter().enumerate() {
+            if i > 0 {
+                v.push_str(", ");
+            }
+            let start = match range.start_bound() {
+                Bound::Included(&start) => start,
+                Bound::Excluded(&start) => start.checked_add(1).ok_or_else(invalid)?,
+                Bound::Unbounded => 0,
+            };
+            let end = match range.end_bound() {
+                Bound::Included(&end) => Some(end),
+                Bound::Excluded(&end) => Some(end.checked_sub(1).ok_or_else(invalid)?),
+                Bound::Unbounded => None,
+            };
+            match end {
+                Some(end) if start > end => return Err(invalid()),
+                Some(end) => write!(v, "{}-{}", start, end),
+                None => write!(v, "{}-", start),
+            }
+            .expect("writing to a String can't fail");
+        }
+
+        if v.len() == "bytes=".len() {
+            return Err(invalid());
+        }
+        Ok(Range(::HeaderValue::from_str(&v).unwrap()))
+    }
+
+    /// Creates a `Range: bytes=-len` header, for the last `len` bytes of
+    /// the representation.
+    ///
+    /// A `len` of zero is allowed, but is never satisfiable.
+    pub fn suffix(len: u64) -> Self {
+        Range(::HeaderValue::from_str(&format!("bytes=-{}", len)).unwrap())
+    }
+
     /// Iterate the range sets as a tuple of bounds.
     pub fn iter<'a>(&'a self) -> impl Iterator<Item = (Bound<u64>, Bound<u64>)> + 'a {
         let s = self
@@ -73,6 +137,102 @@ impl Range {
             Some((parse_bound(iter.next()?)?, parse_bound(iter.next()?)?))
         })
     }
+
+    /// Iterate the range sets that are satisfiable for a representation of
+    /// `len` bytes, as a tuple of inclusive bounds.
+    ///
+    /// See [`resolve`](#method.resolve) for how each range is resolved.
+    pub fn satisfiable_ranges<'a>(
+        &'a self,
+        len: u64,
+    ) -> impl Iterator<Item = (Bound<u64>, Bound<u64>)> + 'a {
+        self.resolve(len).map(|range| {
+            (
+                Bound::Included(*range.start()),
+                Bound::Included(*range.end()),
+            )
+        })
+    }
+
+    /// Resolve the range sets against a representation of `len` bytes, as
+    /// described in [RFC7233](https://tools.ietf.org/html/rfc7233#section-2.1).
+    ///
+    /// A suffix range (`-500`) becomes the last 500 bytes, or the whole
+    /// representation if it's shorter. An open-ended range (`500-`), or one
+    /// whose end is past `len`, ends at the last byte. Ranges that start
+    /// at or past `len` are unsatisfiable, and skipped.
+    pub fn resolve<'a>(&'a self, len: u64) -> impl Iterator<Item = RangeInclusive<u64>> + 'a {
+        self.iter().filter_map(move |bounds| {
+            if len == 0 {
+                return None;
+            }
+            let last = len - 1;
+            match bounds {
+                (Bound::Included(start), Bound::Included(end)) if start <= end && start < len => {
+                    Some(start..=end.min(last))
+                }
+                (Bound::Included(start), Bound::Unbounded) if start < len => Some(start..=last),
+                (Bound::Unbounded, Bound::Included(suffix)) if suffix > 0 => {
+                    Some(len.saturating_sub(suffix)..=last)
+                }
+                _ => None,
+            }
+        })
+    }
+
+    /// Check if any of the range sets are satisfiable for a representation
+    /// of `len` bytes.
+    ///
+    /// If not, the server should respond with `416 Range Not Satisfiable`.
+    pub fn is_satisfiable(&self, len: u64) -> bool {
+        self.resolve(len).next().is_some()
+    }
+}
+
+// Checks for `bytes=` and at least one well-formed range, so `iter` can
+// trust the value.
+fn validate(val: &::HeaderValue) -> Result<(), &'static str> {
+    let s = val
+        .to_str()
+        .map_err(|_| "range contains non-visible characters")?;
+    let specs = match s.find('=') {
+        Some(eq) if s[..eq].eq_ignore_ascii_case("bytes") => &s[eq + 1..],
+        Some(_) => return Err("only the bytes range unit is supported"),
+        None => return Err("missing range unit"),
+    };
+
+    let mut empty = true;
+    for spec in specs
+        .split(',')
+        .map(str::trim)
+        .filter(|spec| !spec.is_empty())
+    {
+        empty = false;
+        let dash = spec.find('-').ok_or("range is missing a '-'")?;
+        match (parse_pos(&spec[..dash])?, parse_pos(&spec[dash + 1..])?) {
+            (Some(start), Some(end)) if start > end => {
+                return Err("range starts after it ends");
+            }
+            (None, None) => return Err("range has no positions"),
+            _ => (),
+        }
+    }
+    if empty {
+        return Err("empty range set");
+    }
+    Ok(())
+}
+
+fn parse_pos(s: &str) -> Result<Option<u64>, &'static str> {
+    if s.is_empty() {
+        return Ok(None);
+    }
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err("range position isn't a number");
+    }
+    s.parse()
+        .map(Some)
+        .map_err(|_| "range position is too large")
 }
 
 fn parse_bound(s: &str) -> Option<Bound<u64>> {
@@ -89,16 +249,9 @@ impl ::Header for Range {
     }
 
     fn decode<'i, I: Iterator<Item = &'i ::HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
-        values
-            .next()
-            .and_then(|val| {
-                if val.to_str().ok()?.starts_with("bytes=") {
-                    Some(Range(val.clone()))
-                } else {
-                    None
-                }
-            })
-            .ok_or_else(::Error::invalid)
+        let val = values.next().ok_or_else(::Error::missing)?;
+        validate(val).map_err(|message| ::Error::invalid().with_message(message))?;
+        Ok(Range(val.clone()))
     }
 
     fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
@@ -106,6 +259,165 @@ impl ::Header for Range {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+    use {Header, HeaderMapExt};
+
+    fn resolve(s: &str, len: u64) -> Vec<RangeInclusive<u64>> {
+        test_decode::<Range>(&[s]).unwrap().resolve(len).collect()
+    }
+
+    #[test]
+    fn resolve_ranges() {
+        assert_eq!(resolve("bytes=0-0", 3), vec![0..=0]);
+        assert_eq!(resolve("bytes=1-2", 3), vec![1..=2]);
+        assert_eq!(resolve("bytes=1-5", 3), vec![1..=2]);
+        assert_eq!(resolve("bytes=3-3", 3), vec![]);
+        assert_eq!(resolve("bytes=0-0", 0), vec![]);
+    }
+
+    #[test]
+    fn resolve_open_ended() {
+        assert_eq!(resolve("bytes=0-", 3), vec![0..=2]);
+        assert_eq!(resolve("bytes=2-", 3), vec![2..=2]);
+        assert_eq!(resolve("bytes=3-", 3), vec![]);
+        assert_eq!(resolve("bytes=0-", 0), vec![]);
+    }
+
+    #[test]
+    fn resolve_suffix() {
+        assert_eq!(resolve("bytes=-2", 3), vec![1..=2]);
+        assert_eq!(resolve("bytes=-1", 3), vec![2..=2]);
+        assert_eq!(resolve("bytes=-5", 3), vec![0..=2]);
+        assert_eq!(resolve("bytes=-0", 3), vec![]);
+        assert_eq!(resolve("bytes=-2", 0), vec![]);
+    }
+
+    #[test]
+    fn resolve_multiple() {
+        assert_eq!(
+            resolve("bytes=0-10, 2000-3000, 20-, -100", 1000),
+            vec![0..=10, 20..=999, 900..=999]
+        );
+
+        let range = test_decode::<Range>(&["bytes=0-10,-5"]).unwrap();
+        assert_eq!(
+            range.satisfiable_ranges(100).collect::<Vec<_>>(),
+            vec![
+                (Bound::Included(0), Bound::Included(10)),
+                (Bound::Included(95), Bound::Included(99)),
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_edge_cases() {
+        assert!(test_decode::<Range>(&["bytes=0-0"]).is_some());
+        assert!(test_decode::<Range>(&["bytes=-0"]).is_some());
+        assert!(test_decode::<Range>(&["bytes=100-"]).is_some());
+        assert!(test_decode::<Range>(&["Bytes=0-1, , 5-"]).is_some());
+    }
+
+    #[test]
+    fn decode_invalid() {
+        let err = |s: &str| {
+            let mut map = ::http::HeaderMap::new();
+            map.insert(::http::header::RANGE, s.parse().unwrap());
+            let err = map.typed_try_get::<Range>().unwrap_err();
+            assert!(err.is_invalid());
+            err.message().unwrap().to_owned()
+        };
+        assert_eq!(err("bytes=abc-def"), "range position isn't a number");
+        assert_eq!(err("bytes=2-1"), "range starts after it ends");
+        assert_eq!(err("bytes=0-1, 5-3"), "range starts after it ends");
+        assert_eq!(err("bytes="), "empty range set");
+        assert_eq!(err("bytes= , "), "empty range set");
+        assert_eq!(err("bytes=-"), "range has no positions");
+        assert_eq!(err("bytes=5"), "range is missing a '-'");
+        assert_eq!(err("bytes=+1-2"), "range position isn't a number");
+        assert_eq!(err("items=0-5"), "only the bytes range unit is supported");
+        assert_eq!(err("0-5"), "missing range unit");
+        assert_eq!(
+            err("bytes=0-99999999999999999999"),
+            "range position is too large"
+        );
+    }
+
+    #[test]
+    fn decode_missing() {
+        let map = ::http::HeaderMap::new();
+        assert!(map.typed_try_get::<Range>().unwrap().is_none());
+
+        let err = Range::decode(&mut ::std::iter::empty()).unwrap_err();
+        assert!(err.is_missing());
+    }
+
+    #[test]
+    fn bytes_invalid() {
+        let (start, end) = (5, 4);
+        assert!(Range::bytes(start..=end).is_err());
+        assert!(Range::bytes(5..5).is_err());
+        assert!(Range::bytes(..0).is_err());
+        assert!(Range::bytes(5..=5).is_ok());
+    }
+
+    #[test]
+    fn byte_ranges() {
+        let encoded = |range: Range| test_encode(range)["range"].to_str().unwrap().to_owned();
+
+        assert_eq!(
+            encoded(Range::byte_ranges(vec![0..500, 1000..1001]).unwrap()),
+            "bytes=0-499, 1000-1000"
+        );
+        assert_eq!(
+            encoded(Range::byte_ranges(vec![(Bound::Included(1000), Bound::Unbounded)]).unwrap()),
+            "bytes=1000-"
+        );
+        assert_eq!(
+            encoded(Range::byte_ranges(Some(..=9)).unwrap()),
+            "bytes=0-9"
+        );
+        assert_eq!(
+            encoded(Range::byte_ranges(Some((Bound::Excluded(4), Bound::Included(5)))).unwrap()),
+            "bytes=5-5"
+        );
+
+        let range = Range::byte_ranges(vec![0..=9, 20..=29]).unwrap();
+        assert_eq!(range.resolve(25).collect::<Vec<_>>(), vec![0..=9, 20..=24]);
+    }
+
+    #[test]
+    fn byte_ranges_invalid() {
+        assert!(Range::byte_ranges(Vec::<::std::ops::Range<u64>>::new()).is_err());
+        let (start, end) = (6, 5);
+        assert!(Range::byte_ranges(Some(5..5)).is_err());
+        assert!(Range::byte_ranges(Some(start..=end)).is_err());
+        assert!(Range::byte_ranges(vec![0..1, start..end]).is_err());
+        assert!(Range::byte_ranges(Some(..0)).is_err());
+        assert!(Range::byte_ranges(Some((Bound::Excluded(u64::MAX), Bound::Unbounded))).is_err());
+    }
+
+    #[test]
+    fn suffix() {
+        let headers = test_encode(Range::suffix(500));
+        assert_eq!(headers["range"], "bytes=-500");
+
+        let range = Range::suffix(500);
+        assert_eq!(range.resolve(2000).collect::<Vec<_>>(), vec![1500..=1999]);
+        assert!(!Range::suffix(0).is_satisfiable(10));
+    }
+
+    #[test]
+    fn is_satisfiable() {
+        let range = test_decode::<Range>(&["bytes=500-600,1000-"]).unwrap();
+        assert!(range.is_satisfiable(501));
+        assert!(!range.is_satisfiable(500));
+        assert!(!range.is_satisfiable(0));
+    }
+}
+
 /*
 
 impl ByteRangeSpec {
@@ -1,3 +1,5 @@
+use std::iter::FromIterator;
+
 use HeaderValue;
 
 /// `Referrer-Policy` header, part of
@@ -9,6 +11,13 @@ use HeaderValue;
 /// and with browsing contexts created from the context of the
 /// protected resource.
 ///
+/// The header may list several policies, so that a browser which
+/// doesn't know a newer policy can fall back to an older one. The
+/// last recognized policy is the one that applies, and decoding keeps
+/// just that one, so a decoded header equals the constant for its
+/// policy. A list built with `FromIterator` is encoded in full, and
+/// collecting from an empty iterator panics.
+///
 /// # ABNF
 ///
 /// ```text
@@ -21,100 +30,222 @@ use HeaderValue;
 /// # Example values
 ///
 /// * `no-referrer`
+/// * `no-referrer, strict-origin-when-cross-origin`
 ///
 /// # Example
 ///
 /// ```
 /// # extern crate headers;
-/// use headers::ReferrerPolicy;
+/// use headers::{ReferrerPolicy, ReferrerPolicyDirective};
 ///
 /// let rp = ReferrerPolicy::NO_REFERRER;
+///
+/// let with_fallback = vec![
+///     ReferrerPolicyDirective::NoReferrer,
+///     ReferrerPolicyDirective::StrictOriginWhenCrossOrigin,
+/// ]
+/// .into_iter()
+/// .collect::<ReferrerPolicy>();
+///
+/// assert_eq!(
+///     with_fallback.effective(),
+///     ReferrerPolicyDirective::StrictOriginWhenCrossOrigin
+/// );
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct ReferrerPolicy(Policy);
+pub struct ReferrerPolicy(Policies);
 
 derive_header! {
     ReferrerPolicy(_),
     name: REFERRER_POLICY
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-enum Policy {
+/// A single policy of a [`ReferrerPolicy`](struct.ReferrerPolicy.html)
+/// header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReferrerPolicyDirective {
+    /// `no-referrer`
     NoReferrer,
+    /// `no-referrer-when-downgrade`
     NoReferrerWhenDowngrade,
+    /// `same-origin`
     SameOrigin,
+    /// `origin`
     Origin,
+    /// `origin-when-cross-origin`
     OriginWhenCrossOrigin,
+    /// `unsafe-url`
     UnsafeUrl,
+    /// `strict-origin`
     StrictOrigin,
+    /// `strict-origin-when-cross-origin`
     StrictOriginWhenCrossOrigin,
 }
 
+// The known policies, in order, without repeats.
+//
+// This is an array rather than a `Vec` so that the `ReferrerPolicy`
+// constants can still be used as patterns. There are only 8 policies, so
+// without repeats they always fit.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Policies([Option<ReferrerPolicyDirective>; 8]);
+
 impl ReferrerPolicy {
     /// `no-referrer`
-    pub const NO_REFERRER: Self = ReferrerPolicy(Policy::NoReferrer);
+    pub const NO_REFERRER: Self = ReferrerPolicy::one(ReferrerPolicyDirective::NoReferrer);
 
     /// `no-referrer-when-downgrade`
-    pub const NO_REFERRER_WHEN_DOWNGRADE: Self = ReferrerPolicy(Policy::NoReferrerWhenDowngrade);
+    pub const NO_REFERRER_WHEN_DOWNGRADE: Self =
+        ReferrerPolicy::one(ReferrerPolicyDirective::NoReferrerWhenDowngrade);
 
     /// `same-origin`
-    pub const SAME_ORIGIN: Self = ReferrerPolicy(Policy::SameOrigin);
+    pub const SAME_ORIGIN: Self = ReferrerPolicy::one(ReferrerPolicyDirective::SameOrigin);
 
     /// `origin`
-    pub const ORIGIN: Self = ReferrerPolicy(Policy::Origin);
+    pub const ORIGIN: Self = ReferrerPolicy::one(ReferrerPolicyDirective::Origin);
 
     /// `origin-when-cross-origin`
-    pub const ORIGIN_WHEN_CROSS_ORIGIN: Self = ReferrerPolicy(Policy::OriginWhenCrossOrigin);
+    pub const ORIGIN_WHEN_CROSS_ORIGIN: Self =
+        ReferrerPolicy::one(ReferrerPolicyDirective::OriginWhenCrossOrigin);
 
     /// `unsafe-url`
-    pub const UNSAFE_URL: Self = ReferrerPolicy(Policy::UnsafeUrl);
+    pub const UNSAFE_URL: Self = ReferrerPolicy::one(ReferrerPolicyDirective::UnsafeUrl);
 
     /// `strict-origin`
-    pub const STRICT_ORIGIN: Self = ReferrerPolicy(Policy::StrictOrigin);
+    pub const STRICT_ORIGIN: Self = ReferrerPolicy::one(ReferrerPolicyDirective::StrictOrigin);
 
     ///`strict-origin-when-cross-origin`
     pub const STRICT_ORIGIN_WHEN_CROSS_ORIGIN: Self =
-        ReferrerPolicy(Policy::StrictOriginWhenCrossOrigin);
+        ReferrerPolicy::one(ReferrerPolicyDirective::StrictOriginWhenCrossOrigin);
+
+    const fn one(policy: ReferrerPolicyDirective) -> Self {
+        ReferrerPolicy(Policies([
+            Some(policy),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ]))
+    }
+
+    /// Returns the policy that applies, which is the last one listed.
+    ///
+    /// For a decoded header, this is the only policy.
+    pub fn effective(&self) -> ReferrerPolicyDirective {
+        self.iter()
+            .last()
+            .expect("ReferrerPolicy always has a policy")
+    }
+
+    /// Returns an iterator over the listed policies, in order.
+    pub fn iter(&self) -> impl Iterator<Item = ReferrerPolicyDirective> + '_ {
+        (self.0).0.iter().filter_map(|policy| *policy)
+    }
+}
+
+impl From<ReferrerPolicyDirective> for ReferrerPolicy {
+    fn from(policy: ReferrerPolicyDirective) -> ReferrerPolicy {
+        ReferrerPolicy::one(policy)
+    }
 }
 
-impl ::util::TryFromValues for Policy {
+/// A repeated policy only counts where it was last listed.
+impl FromIterator<ReferrerPolicyDirective> for ReferrerPolicy {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = ReferrerPolicyDirective>,
+    {
+        let policies = Policies::from_iter(iter);
+        assert!(policies.0[0].is_some(), "ReferrerPolicy needs a policy");
+        ReferrerPolicy(policies)
+    }
+}
+
+impl ReferrerPolicyDirective {
+    /// Returns the policy's token.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            ReferrerPolicyDirective::NoReferrer => "no-referrer",
+            ReferrerPolicyDirective::NoReferrerWhenDowngrade => "no-referrer-when-downgrade",
+            ReferrerPolicyDirective::SameOrigin => "same-origin",
+            ReferrerPolicyDirective::Origin => "origin",
+            ReferrerPolicyDirective::OriginWhenCrossOrigin => "origin-when-cross-origin",
+            ReferrerPolicyDirective::StrictOrigin => "strict-origin",
+            ReferrerPolicyDirective::StrictOriginWhenCrossOrigin => {
+                "strict-origin-when-cross-origin"
+            }
+            ReferrerPolicyDirective::UnsafeUrl => "unsafe-url",
+        }
+    }
+
+    fn from_token(s: &str) -> Option<ReferrerPolicyDirective> {
+        Some(match s {
+            "no-referrer" | "never" => ReferrerPolicyDirective::NoReferrer,
+            "no-referrer-when-downgrade" | "default" => {
+                ReferrerPolicyDirective::NoReferrerWhenDowngrade
+            }
+            "same-origin" => ReferrerPolicyDirective::SameOrigin,
+            "origin" => ReferrerPolicyDirective::Origin,
+            "origin-when-cross-origin" => ReferrerPolicyDirective::OriginWhenCrossOrigin,
+            "strict-origin" => ReferrerPolicyDirective::StrictOrigin,
+            "strict-origin-when-cross-origin" => {
+                ReferrerPolicyDirective::StrictOriginWhenCrossOrigin
+            }
+            "unsafe-url" | "always" => ReferrerPolicyDirective::UnsafeUrl,
+            _ => return None,
+        })
+    }
+}
+
+impl FromIterator<ReferrerPolicyDirective> for Policies {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = ReferrerPolicyDirective>,
+    {
+        let mut policies = [None; 8];
+        let mut len = 0;
+        for policy in iter {
+            // move a repeated policy to the end
+            if let Some(i) = policies[..len].iter().position(|p| *p == Some(policy)) {
+                policies[i..len].rotate_left(1);
+                len -= 1;
+            }
+            policies[len] = Some(policy);
+            len += 1;
+        }
+        Policies(policies)
+    }
+}
+
+impl ::util::TryFromValues for Policies {
     fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
     where
         I: Iterator<Item = &'i HeaderValue>,
     {
         // See https://www.w3.org/TR/referrer-policy/#determine-policy-for-token
         // tl;dr - Pick *last* known policy in the list
-        let mut known = None;
-        for s in csv(values) {
-            known = Some(match s {
-                "no-referrer" | "never" => Policy::NoReferrer,
-                "no-referrer-when-downgrade" | "default" => Policy::NoReferrerWhenDowngrade,
-                "same-origin" => Policy::SameOrigin,
-                "origin" => Policy::Origin,
-                "origin-when-cross-origin" => Policy::OriginWhenCrossOrigin,
-                "strict-origin" => Policy::StrictOrigin,
-                "strict-origin-when-cross-origin" => Policy::StrictOriginWhenCrossOrigin,
-                "unsafe-url" | "always" => Policy::UnsafeUrl,
-                _ => continue,
-            });
-        }
-
-        known.ok_or_else(::Error::invalid)
+        csv(values)
+            .filter_map(ReferrerPolicyDirective::from_token)
+            .last()
+            .map(|policy| ReferrerPolicy::one(policy).0)
+            .ok_or_else(::Error::invalid)
     }
 }
 
-impl<'a> From<&'a Policy> for HeaderValue {
-    fn from(policy: &'a Policy) -> HeaderValue {
-        HeaderValue::from_static(match *policy {
-            Policy::NoReferrer => "no-referrer",
-            Policy::NoReferrerWhenDowngrade => "no-referrer-when-downgrade",
-            Policy::SameOrigin => "same-origin",
-            Policy::Origin => "origin",
-            Policy::OriginWhenCrossOrigin => "origin-when-cross-origin",
-            Policy::StrictOrigin => "strict-origin",
-            Policy::StrictOriginWhenCrossOrigin => "strict-origin-when-cross-origin",
-            Policy::UnsafeUrl => "unsafe-url",
-        })
+impl<'a> From<&'a Policies> for HeaderValue {
+    fn from(policies: &'a Policies) -> HeaderValue {
+        let list = policies
+            .0
+            .iter()
+            .filter_map(|policy| policy.map(|policy| policy.as_str()))
+            .collect::<Vec<_>>();
+        match list.len() {
+            1 => HeaderValue::from_static(list[0]),
+            _ => HeaderValue::from_str(&list.join(", ")).expect("policies are tokens"),
+        }
     }
 }
 
@@ -134,8 +265,8 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::super::test_decode;
-    use super::ReferrerPolicy;
+    use super::super::{test_decode, test_encode};
+    use super::{ReferrerPolicy, ReferrerPolicyDirective};
 
     #[test]
     fn decode_as_last_policy() {
@@ -148,6 +279,48 @@ mod tests {
             test_decode::<ReferrerPolicy>(&["origin", "same-origin"]),
             Some(ReferrerPolicy::SAME_ORIGIN),
         );
+
+        assert_eq!(
+            test_decode::<ReferrerPolicy>(&["no-referrer, strict-origin-when-cross-origin"])
+                .map(|rp| rp.effective()),
+            Some(ReferrerPolicyDirective::StrictOriginWhenCrossOrigin),
+        );
+    }
+
+    #[test]
+    fn encode_fallbacks() {
+        let rp = vec![
+            ReferrerPolicyDirective::NoReferrer,
+            ReferrerPolicyDirective::StrictOrigin,
+        ]
+        .into_iter()
+        .collect::<ReferrerPolicy>();
+
+        let headers = test_encode(rp);
+        assert_eq!(headers["referrer-policy"], "no-referrer, strict-origin");
+        assert_eq!(
+            test_decode::<ReferrerPolicy>(&["no-referrer, strict-origin"]),
+            Some(ReferrerPolicy::STRICT_ORIGIN),
+        );
+    }
+
+    #[test]
+    fn repeats_count_where_last_listed() {
+        let rp = vec![
+            ReferrerPolicyDirective::Origin,
+            ReferrerPolicyDirective::NoReferrer,
+            ReferrerPolicyDirective::Origin,
+        ]
+        .into_iter()
+        .collect::<ReferrerPolicy>();
+        assert_eq!(
+            rp.iter().collect::<Vec<_>>(),
+            vec![
+                ReferrerPolicyDirective::NoReferrer,
+                ReferrerPolicyDirective::Origin
+            ]
+        );
+        assert_eq!(rp.effective(), ReferrerPolicyDirective::Origin);
     }
 
     #[test]
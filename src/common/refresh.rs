@@ -0,0 +1,203 @@
+use std::time::Duration;
+
+use util::{IterExt, Seconds};
+use HeaderValue;
+
+static REFRESH: ::HeaderName = ::HeaderName::from_static("refresh");
+
+/// `Refresh` header, as implemented by browsers and described by the
+/// [HTML standard](https://html.spec.whatwg.org/multipage/semantics.html#shared-declarative-refresh-steps)
+///
+/// The non-standard but widely supported `Refresh` response header tells
+/// the browser to reload the page, or to navigate to another URL, after
+/// a delay.
+///
+/// When decoding, the `url=` prefix and quotes around the URL are
+/// optional, and any fraction of the delay is ignored.
+///
+/// # Example values
+///
+/// * `5`
+/// * `5; url=https://example.com`
+/// * `0;URL='/login'`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use std::time::Duration;
+/// use headers::Refresh;
+///
+/// let refresh = Refresh::after(Duration::from_secs(5)).with_url("https://example.com");
+///
+/// assert_eq!(refresh.url(), Some("https://example.com"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Refresh {
+    delay: Seconds,
+    url: Option<String>,
+}
+
+impl ::Header for Refresh {
+    fn name() -> &'static ::HeaderName {
+        &REFRESH
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        values
+            .just_one()
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse)
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let value = match self.url {
+            Some(ref url) => HeaderValue::from_str(&format!("{}; url={}", self.delay, url))
+                .expect("url is checked by with_url"),
+            None => HeaderValue::from(&self.delay),
+        };
+        values.extend(::std::iter::once(value));
+    }
+}
+
+impl Refresh {
+    /// Creates a `Refresh` that reloads the page after a delay.
+    ///
+    /// Any fraction of a second is dropped.
+    pub fn after(delay: Duration) -> Refresh {
+        Refresh {
+            delay: Seconds::from_secs(delay.as_secs()),
+            url: None,
+        }
+    }
+
+    /// Navigates to a URL instead of reloading the page.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the URL is empty, or isn't a valid `HeaderValue`.
+    pub fn with_url(mut self, url: &str) -> Self {
+        assert!(
+            !url.is_empty() && HeaderValue::from_str(url).is_ok(),
+            "invalid Refresh url: {:?}",
+            url
+        );
+        self.url = Some(url.to_owned());
+        self
+    }
+
+    /// Returns the delay before refreshing.
+    pub fn delay(&self) -> Duration {
+        self.delay.into()
+    }
+
+    /// Returns the URL to navigate to, if any.
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+}
+
+fn parse(s: &str) -> Option<Refresh> {
+    let s = s.trim_start_matches(&[' ', '\t'][..]);
+
+    let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+    let secs = s[..digits].parse().ok()?;
+    // browsers skip a fraction, such as the `.5` of `5.5`
+    let rest = s[digits..].trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+
+    let rest = rest.trim_matches(&[' ', '\t'][..]);
+    if rest.is_empty() {
+        return Some(Refresh::after(Duration::from_secs(secs)));
+    }
+
+    let rest = rest
+        .strip_prefix(&[';', ','][..])?
+        .trim_start_matches(&[' ', '\t'][..]);
+    let rest = match rest.get(..3) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("url") => {
+            let after = rest[3..].trim_start_matches(&[' ', '\t'][..]);
+            match after.strip_prefix('=') {
+                Some(after) => after.trim_start_matches(&[' ', '\t'][..]),
+                // a URL which just starts with `url`
+                None => rest,
+            }
+        }
+        _ => rest,
+    };
+
+    let url = match rest.chars().next() {
+        Some(quote @ '"') | Some(quote @ '\'') => {
+            let inner = &rest[1..];
+            // an unterminated quote runs to the end
+            match inner.find(quote) {
+                Some(end) => &inner[..end],
+                None => inner,
+            }
+        }
+        _ => rest,
+    };
+
+    let refresh = Refresh::after(Duration::from_secs(secs));
+    if url.is_empty() {
+        Some(refresh)
+    } else {
+        Some(Refresh {
+            url: Some(url.to_owned()),
+            ..refresh
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_seconds() {
+        let refresh = test_decode::<Refresh>(&["5"]).unwrap();
+        assert_eq!(refresh.delay(), Duration::from_secs(5));
+        assert_eq!(refresh.url(), None);
+
+        let refresh = test_decode::<Refresh>(&["2.5"]).unwrap();
+        assert_eq!(refresh.delay(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn decode_url() {
+        let refresh = test_decode::<Refresh>(&["5; url=https://example.com"]).unwrap();
+        assert_eq!(refresh.delay(), Duration::from_secs(5));
+        assert_eq!(refresh.url(), Some("https://example.com"));
+
+        let refresh = test_decode::<Refresh>(&["0;URL='/login'"]).unwrap();
+        assert_eq!(refresh.url(), Some("/login"));
+
+        let refresh = test_decode::<Refresh>(&["0; \"/a b\""]).unwrap();
+        assert_eq!(refresh.url(), Some("/a b"));
+
+        let refresh = test_decode::<Refresh>(&["1, /next"]).unwrap();
+        assert_eq!(refresh.url(), Some("/next"));
+
+        let refresh = test_decode::<Refresh>(&["1; urls.html"]).unwrap();
+        assert_eq!(refresh.url(), Some("urls.html"));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Refresh>(&[""]), None);
+        assert_eq!(test_decode::<Refresh>(&["soon"]), None);
+        assert_eq!(test_decode::<Refresh>(&["5 url=/next"]), None);
+        assert_eq!(test_decode::<Refresh>(&["5", "6"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(Refresh::after(Duration::from_secs(3)));
+        assert_eq!(headers["refresh"], "3");
+
+        let refresh = Refresh::after(Duration::from_secs(5)).with_url("https://example.com");
+        let headers = test_encode(refresh);
+        assert_eq!(headers["refresh"], "5; url=https://example.com");
+    }
+}
@@ -0,0 +1,377 @@
+use std::iter::FromIterator;
+use std::time::Duration;
+
+use serde_json::{self, Map, Value};
+
+use HeaderValue;
+
+static REPORT_TO: ::HeaderName = ::HeaderName::from_static("report-to");
+
+/// `Report-To` header, defined in the
+/// [Reporting API](https://www.w3.org/TR/2018/WD-reporting-1-20180925/#header)
+///
+/// The `Report-To` response header configures named groups of endpoints
+/// that the browser sends reports to, such as for
+/// [`Nel`](struct.Nel.html). Each group is a JSON object, and several
+/// groups are separated by commas.
+///
+/// Groups and endpoints that don't follow the spec are skipped when
+/// decoding, as are unknown JSON members.
+///
+/// This header is only available with the `serde_json` feature.
+///
+/// # Example values
+///
+/// * `{"group": "nel", "max_age": 10886400, "endpoints": [{"url": "https://example.com/reports"}]}`
+/// * `{"max_age": 3600, "endpoints": [{"url": "https://a.example"}]}, {"group": "csp", "max_age": 0, "endpoints": []}`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use std::time::Duration;
+/// use headers::{ReportEndpoint, ReportGroup, ReportTo};
+///
+/// let report_to = vec![
+///     ReportGroup::new("nel", Duration::from_secs(86400))
+///         .with_endpoint(ReportEndpoint::new("https://example.com/reports")),
+/// ]
+/// .into_iter()
+/// .collect::<ReportTo>();
+///
+/// let group = report_to.group("nel").unwrap();
+/// assert_eq!(group.endpoints().count(), 1);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportTo(Vec<ReportGroup>);
+
+/// An endpoint group of a [`ReportTo`](struct.ReportTo.html) header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportGroup {
+    name: String,
+    max_age: u64,
+    endpoints: Vec<ReportEndpoint>,
+    include_subdomains: bool,
+}
+
+/// An endpoint of a [`ReportGroup`](struct.ReportGroup.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportEndpoint {
+    url: String,
+    priority: u64,
+    weight: u64,
+}
+
+impl ::Header for ReportTo {
+    fn name() -> &'static ::HeaderName {
+        &REPORT_TO
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        // The groups are a comma-separated list of JSON objects, so every
+        // field together makes a JSON array once it's wrapped in brackets.
+        let mut json = b"[".to_vec();
+        for (i, value) in values.enumerate() {
+            if i > 0 {
+                json.push(b',');
+            }
+            json.extend_from_slice(value.as_bytes());
+        }
+        json.push(b']');
+
+        let groups = match serde_json::from_slice(&json) {
+            Ok(Value::Array(groups)) => groups,
+            _ => return Err(::Error::invalid()),
+        };
+        let groups = groups
+            .iter()
+            .filter_map(ReportGroup::from_json)
+            .collect::<Vec<_>>();
+
+        if groups.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(ReportTo(groups))
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let json = self
+            .0
+            .iter()
+            .map(|group| encode_json(&group.to_json()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let value = HeaderValue::from_str(&json).expect("ReportTo is a valid HeaderValue");
+        values.extend(::std::iter::once(value));
+    }
+}
+
+impl ReportTo {
+    /// Returns an iterator over the endpoint groups, in order.
+    pub fn groups(&self) -> impl Iterator<Item = &ReportGroup> {
+        self.0.iter()
+    }
+
+    /// Returns the first group with the given name.
+    pub fn group(&self, name: &str) -> Option<&ReportGroup> {
+        self.0.iter().find(|group| group.name == name)
+    }
+}
+
+impl FromIterator<ReportGroup> for ReportTo {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = ReportGroup>,
+    {
+        ReportTo(iter.into_iter().collect())
+    }
+}
+
+impl ReportGroup {
+    /// Creates a group with no endpoints, which is cached for `max_age`.
+    ///
+    /// A `max_age` of zero asks the browser to remove the group.
+    pub fn new<S: Into<String>>(name: S, max_age: Duration) -> ReportGroup {
+        ReportGroup {
+            name: name.into(),
+            max_age: max_age.as_secs(),
+            endpoints: Vec::new(),
+            include_subdomains: false,
+        }
+    }
+
+    /// Adds an endpoint to the group.
+    pub fn with_endpoint(mut self, endpoint: ReportEndpoint) -> ReportGroup {
+        self.endpoints.push(endpoint);
+        self
+    }
+
+    /// Sets whether the group also applies to subdomains of the origin.
+    pub fn with_include_subdomains(mut self, include_subdomains: bool) -> ReportGroup {
+        self.include_subdomains = include_subdomains;
+        self
+    }
+
+    /// The name of the group, `default` if the header didn't give one.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// How long the browser should remember the group.
+    pub fn max_age(&self) -> Duration {
+        Duration::from_secs(self.max_age)
+    }
+
+    /// Returns an iterator over the endpoints, in order.
+    pub fn endpoints(&self) -> impl Iterator<Item = &ReportEndpoint> {
+        self.endpoints.iter()
+    }
+
+    /// Whether the group also applies to subdomains of the origin.
+    pub fn include_subdomains(&self) -> bool {
+        self.include_subdomains
+    }
+
+    fn from_json(json: &Value) -> Option<ReportGroup> {
+        let name = match json.get("group") {
+            None => "default",
+            Some(name) => name.as_str()?,
+        };
+        let endpoints = json
+            .get("endpoints")?
+            .as_array()?
+            .iter()
+            .filter_map(ReportEndpoint::from_json)
+            .collect();
+
+        Some(ReportGroup {
+            name: name.to_owned(),
+            max_age: json.get("max_age")?.as_u64()?,
+            endpoints,
+            include_subdomains: json.get("include_subdomains") == Some(&Value::Bool(true)),
+        })
+    }
+
+    fn to_json(&self) -> Value {
+        let mut json = Map::new();
+        json.insert("group".into(), self.name.clone().into());
+        json.insert("max_age".into(), self.max_age.into());
+        json.insert(
+            "endpoints".into(),
+            self.endpoints.iter().map(ReportEndpoint::to_json).collect(),
+        );
+        if self.include_subdomains {
+            json.insert("include_subdomains".into(), true.into());
+        }
+        Value::Object(json)
+    }
+}
+
+impl ReportEndpoint {
+    /// Creates an endpoint for the given URL, with the default priority
+    /// and weight of `1`.
+    pub fn new<S: Into<String>>(url: S) -> ReportEndpoint {
+        ReportEndpoint {
+            url: url.into(),
+            priority: 1,
+            weight: 1,
+        }
+    }
+
+    /// Sets the failover priority. Endpoints with a lower number are
+    /// tried first.
+    pub fn with_priority(mut self, priority: u64) -> ReportEndpoint {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the weight used to balance load between endpoints of the same
+    /// priority.
+    pub fn with_weight(mut self, weight: u64) -> ReportEndpoint {
+        self.weight = weight;
+        self
+    }
+
+    /// The URL reports are sent to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The failover priority of the endpoint.
+    pub fn priority(&self) -> u64 {
+        self.priority
+    }
+
+    /// The load balancing weight of the endpoint.
+    pub fn weight(&self) -> u64 {
+        self.weight
+    }
+
+    fn from_json(json: &Value) -> Option<ReportEndpoint> {
+        let number = |name: &str| match json.get(name) {
+            None => Some(1),
+            Some(n) => n.as_u64(),
+        };
+
+        Some(ReportEndpoint {
+            url: json.get("url")?.as_str()?.to_owned(),
+            priority: number("priority")?,
+            weight: number("weight")?,
+        })
+    }
+
+    fn to_json(&self) -> Value {
+        let mut json = Map::new();
+        json.insert("url".into(), self.url.clone().into());
+        if self.priority != 1 {
+            json.insert("priority".into(), self.priority.into());
+        }
+        if self.weight != 1 {
+            json.insert("weight".into(), self.weight.into());
+        }
+        Value::Object(json)
+    }
+}
+
+/// Serializes JSON for a header value.
+///
+/// `serde_json` leaves DEL unescaped inside strings, but it isn't allowed
+/// in a `HeaderValue`. It can't appear outside of a string, so it's always
+/// safe to escape.
+pub(super) fn encode_json(json: &Value) -> String {
+    json.to_string().replace('\u{7f}', "\\u007f")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_groups() {
+        let report_to = test_decode::<ReportTo>(&[
+            r#"{"group": "nel", "max_age": 10886400, "include_subdomains": true, "endpoints": [{"url": "https://a.example/r", "priority": 2}, {"url": "https://b.example/r", "weight": 3}]}"#,
+            r#"{"max_age": 3600, "endpoints": [{"url": "https://c.example/r"}], "extra": {"ignored": null}}"#,
+        ])
+        .unwrap();
+
+        let groups = report_to.groups().collect::<Vec<_>>();
+        assert_eq!(groups.len(), 2);
+
+        assert_eq!(groups[0].name(), "nel");
+        assert_eq!(groups[0].max_age(), Duration::from_secs(10886400));
+        assert!(groups[0].include_subdomains());
+        let endpoints = groups[0].endpoints().collect::<Vec<_>>();
+        assert_eq!(endpoints[0].url(), "https://a.example/r");
+        assert_eq!(endpoints[0].priority(), 2);
+        assert_eq!(endpoints[0].weight(), 1);
+        assert_eq!(endpoints[1].priority(), 1);
+        assert_eq!(endpoints[1].weight(), 3);
+
+        assert_eq!(groups[1].name(), "default");
+        assert!(!groups[1].include_subdomains());
+        assert_eq!(report_to.group("default"), Some(groups[1]));
+    }
+
+    #[test]
+    fn decode_skips_invalid_groups() {
+        let report_to = test_decode::<ReportTo>(&[
+            r#"{"group": "no-max-age", "endpoints": []}, {"group": "ok", "max_age": 1, "endpoints": [{"url": 1}, {"url": "https://a.example"}]}"#,
+        ])
+        .unwrap();
+        let groups = report_to.groups().collect::<Vec<_>>();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name(), "ok");
+        assert_eq!(groups[0].endpoints().count(), 1);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<ReportTo>(&[""]), None);
+        assert_eq!(test_decode::<ReportTo>(&["{"]), None);
+        assert_eq!(test_decode::<ReportTo>(&["[]"]), None);
+        assert_eq!(
+            test_decode::<ReportTo>(&[r#"{"max_age": -1, "endpoints": []}"#]),
+            None
+        );
+    }
+
+    #[test]
+    fn encode() {
+        let report_to = vec![
+            ReportGroup::new("nel", Duration::from_secs(60))
+                .with_include_subdomains(true)
+                .with_endpoint(ReportEndpoint::new("https://a.example/r"))
+                .with_endpoint(
+                    ReportEndpoint::new("https://b.example/r")
+                        .with_priority(2)
+                        .with_weight(5),
+                ),
+            ReportGroup::new("csp", Duration::from_secs(0)),
+        ]
+        .into_iter()
+        .collect::<ReportTo>();
+
+        let headers = test_encode(report_to.clone());
+        assert_eq!(
+            headers["report-to"],
+            r#"{"endpoints":[{"url":"https://a.example/r"},{"priority":2,"url":"https://b.example/r","weight":5}],"group":"nel","include_subdomains":true,"max_age":60}, {"endpoints":[],"group":"csp","max_age":0}"#
+        );
+
+        let decoded = test_decode::<ReportTo>(&[headers["report-to"].to_str().unwrap()]);
+        assert_eq!(decoded, Some(report_to));
+    }
+
+    #[test]
+    fn encode_escapes_del() {
+        let report_to = vec![ReportGroup::new("a\u{7f}b", Duration::from_secs(1))]
+            .into_iter()
+            .collect::<ReportTo>();
+        let headers = test_encode(report_to);
+        assert_eq!(
+            headers["report-to"],
+            r#"{"endpoints":[],"group":"a\u007fb","max_age":1}"#
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::time::{Duration, SystemTime};
 
-use util::{HttpDate, Seconds, TryFromValues};
+use util::{parse_u64, HttpDate, Seconds, TryFromValues};
 use HeaderValue;
 
 /// The `Retry-After` header.
@@ -13,6 +13,9 @@ use HeaderValue;
 /// can be either an HTTP-date or an integer number of seconds (in decimal)
 /// after the time of the response.
 ///
+/// A number of seconds greater than 2147483648 (2^31, about 68 years) is
+/// clamped to it, so that the delay can always be added to a `SystemTime`.
+///
 /// # Examples
 /// ```
 /// # extern crate headers;
@@ -46,10 +49,24 @@ impl RetryAfter {
         RetryAfter(After::DateTime(time.into()))
     }
 
-    /// Create an `RetryAfter` header with a date value.
+    /// Create an `RetryAfter` header with a delay value.
     pub fn delay(dur: Duration) -> RetryAfter {
         RetryAfter(After::Delay(dur.into()))
     }
+
+    /// Get how long to wait, starting at `now`.
+    ///
+    /// A date in the past results in a zero `Duration`. This never
+    /// overflows, since a date is at most in the year 9999 and a delay
+    /// doesn't depend on `now`.
+    pub fn duration_until(&self, now: SystemTime) -> Duration {
+        match self.0 {
+            After::Delay(delay) => delay.into(),
+            After::DateTime(date) => SystemTime::from(date)
+                .duration_since(now)
+                .unwrap_or_default(),
+        }
+    }
 }
 
 impl TryFromValues for After {
@@ -60,7 +77,7 @@ impl TryFromValues for After {
         values
             .next()
             .and_then(|val| {
-                if let Some(delay) = Seconds::from_val(val) {
+                if let Some(delay) = parse_delay(val) {
                     return Some(After::Delay(delay));
                 }
 
@@ -71,6 +88,20 @@ impl TryFromValues for After {
     }
 }
 
+const MAX_DELAY_SECONDS: u64 = 1 << 31;
+
+fn parse_delay(val: &HeaderValue) -> Option<Seconds> {
+    let bytes = val.as_bytes();
+    // `Seconds` accepts a leading `+`, so oversized values with one clamp too.
+    let digits = bytes.strip_prefix(b"+").unwrap_or(bytes);
+    let secs = if !digits.is_empty() && digits.iter().all(u8::is_ascii_digit) {
+        parse_u64(digits).unwrap_or(MAX_DELAY_SECONDS)
+    } else {
+        Seconds::from_val(val)?.as_u64()
+    };
+    Some(Seconds::from_secs(secs.min(MAX_DELAY_SECONDS)))
+}
+
 impl<'a> From<&'a After> for HeaderValue {
     fn from(after: &'a After) -> HeaderValue {
         match *after {
@@ -84,7 +115,7 @@ impl<'a> From<&'a After> for HeaderValue {
 mod tests {
     use super::super::test_decode;
     use super::RetryAfter;
-    use std::time::Duration;
+    use std::time::{Duration, SystemTime};
     use util::HttpDate;
 
     #[test]
@@ -93,6 +124,60 @@ mod tests {
         assert_eq!(r, RetryAfter::delay(Duration::from_secs(1234)),);
     }
 
+    #[test]
+    fn delay_decode_clamps() {
+        let max = Duration::from_secs(1 << 31);
+        for s in &[
+            "99999999999999999999",
+            "+99999999999999999999",
+            "2147483649",
+            "+2147483649",
+        ] {
+            let r: RetryAfter = test_decode(&[s]).unwrap();
+            assert_eq!(r, RetryAfter::delay(max), "{}", s);
+            let _ = SystemTime::now() + r.duration_until(SystemTime::now());
+        }
+        let r: RetryAfter = test_decode(&["2147483648"]).unwrap();
+        assert_eq!(r, RetryAfter::delay(max));
+
+        assert_eq!(test_decode::<RetryAfter>(&["-1"]), None);
+        assert_eq!(test_decode::<RetryAfter>(&["1.5"]), None);
+    }
+
+    #[test]
+    fn duration_until_delay() {
+        let r = RetryAfter::delay(Duration::from_secs(120));
+        assert_eq!(
+            r.duration_until(SystemTime::now()),
+            Duration::from_secs(120)
+        );
+    }
+
+    #[test]
+    fn duration_until_date() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let r = RetryAfter::date(now + Duration::from_secs(30));
+        assert_eq!(r.duration_until(now), Duration::from_secs(30));
+
+        let r = RetryAfter::date(now - Duration::from_secs(30));
+        assert_eq!(r.duration_until(now), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn duration_until_far_future_date() {
+        let r: RetryAfter = test_decode(&["Wed, 01 Jan 3000 00:00:00 GMT"]).unwrap();
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        // 3000-01-01 is 32503680000 seconds after the epoch.
+        assert_eq!(
+            r.duration_until(now),
+            Duration::from_secs(32_503_680_000 - 1_700_000_000)
+        );
+        assert_eq!(
+            r.duration_until(SystemTime::UNIX_EPOCH),
+            Duration::from_secs(32_503_680_000)
+        );
+    }
+
     macro_rules! test_retry_after_datetime {
         ($name:ident, $s:expr) => {
             #[test]
@@ -19,7 +19,7 @@ use super::SecWebsocketKey;
 /// let sec_key: SecWebsocketKey = /* from request headers */
 /// #    unimplemented!();
 ///
-/// let sec_accept = SecWebsocketAccept::from(sec_key);
+/// let sec_accept = SecWebsocketAccept::from_key(&sec_key);
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct SecWebsocketAccept(::HeaderValue);
@@ -29,9 +29,19 @@ derive_header! {
     name: SEC_WEBSOCKET_ACCEPT
 }
 
+impl SecWebsocketAccept {
+    /// Computes the accept value the server must send for a key.
+    ///
+    /// A client can check the server's response against this to verify
+    /// the handshake.
+    pub fn from_key(key: &SecWebsocketKey) -> SecWebsocketAccept {
+        sign(key.0.as_bytes())
+    }
+}
+
 impl From<SecWebsocketKey> for SecWebsocketAccept {
     fn from(key: SecWebsocketKey) -> SecWebsocketAccept {
-        sign(key.0.as_bytes())
+        SecWebsocketAccept::from_key(&key)
     }
 }
 
@@ -63,4 +73,15 @@ mod tests {
             "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
         );
     }
+
+    #[test]
+    fn verify_handshake() {
+        let key = test_decode::<SecWebsocketKey>(&["dGhlIHNhbXBsZSBub25jZQ=="]).expect("key");
+        let accept =
+            test_decode::<SecWebsocketAccept>(&["s3pPLMBiTxaQ9kYGzzhZRbK+xOo="]).expect("accept");
+        assert_eq!(accept, SecWebsocketAccept::from_key(&key));
+
+        let other = test_decode::<SecWebsocketKey>(&["AQIDBAUGBwgJCgsMDQ4PEA=="]).expect("key");
+        assert_ne!(accept, SecWebsocketAccept::from_key(&other));
+    }
 }
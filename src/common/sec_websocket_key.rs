@@ -1,4 +1,31 @@
+#[cfg(feature = "getrandom")]
+use base64;
+
+#[cfg(feature = "getrandom")]
+use util::random_bytes;
+
 /// The `Sec-Websocket-Key` header.
+///
+/// Sent by the client in the Websocket opening handshake, it holds a
+/// base64-encoded 16-byte nonce that the server signs in its
+/// `Sec-Websocket-Accept` header.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{SecWebsocketAccept, SecWebsocketKey};
+///
+/// # #[cfg(feature = "getrandom")]
+/// # fn main() {
+/// let key = SecWebsocketKey::random();
+/// let accept = SecWebsocketAccept::from_key(&key);
+///
+/// assert_eq!(accept, SecWebsocketAccept::from(key));
+/// # }
+/// # #[cfg(not(feature = "getrandom"))]
+/// # fn main() {}
+/// ```
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct SecWebsocketKey(pub(super) ::HeaderValue);
 
@@ -6,3 +33,33 @@ derive_header! {
     SecWebsocketKey(_),
     name: SEC_WEBSOCKET_KEY
 }
+
+impl SecWebsocketKey {
+    /// Generates a new key from 16 random bytes.
+    ///
+    /// The bytes come from the operating system's secure random source,
+    /// and this needs the `getrandom` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system can't provide random bytes.
+    #[cfg(feature = "getrandom")]
+    pub fn random() -> SecWebsocketKey {
+        let val = ::HeaderValue::from_str(&base64::encode(random_bytes()))
+            .expect("base64 is a valid value");
+        SecWebsocketKey(val)
+    }
+}
+
+#[cfg(all(test, feature = "getrandom"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_is_16_bytes() {
+        let key = SecWebsocketKey::random();
+        let bytes = base64::decode(key.0.as_bytes()).expect("base64");
+        assert_eq!(bytes.len(), 16);
+        assert_ne!(key, SecWebsocketKey::random());
+    }
+}
@@ -1,10 +1,24 @@
 /// The `Sec-Websocket-Version` header.
+///
+/// The version of the Websocket protocol, from 0 to 255. Only version 13,
+/// from RFC 6455, is in use today.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct SecWebsocketVersion(u8);
 
 impl SecWebsocketVersion {
     /// `Sec-Websocket-Version: 13`
     pub const V13: SecWebsocketVersion = SecWebsocketVersion(13);
+
+    /// Returns the version number.
+    pub fn as_u8(&self) -> u8 {
+        self.0
+    }
+}
+
+impl From<u8> for SecWebsocketVersion {
+    fn from(version: u8) -> SecWebsocketVersion {
+        SecWebsocketVersion(version)
+    }
 }
 
 impl ::Header for SecWebsocketVersion {
@@ -15,20 +29,21 @@ impl ::Header for SecWebsocketVersion {
     fn decode<'i, I: Iterator<Item = &'i ::HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
         values
             .next()
-            .and_then(|value| {
-                if value == "13" {
-                    Some(SecWebsocketVersion::V13)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|s| {
+                // 1*DIGIT, without the sign `u8::from_str` would allow
+                if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+                    s.parse::<u8>().ok()
                 } else {
                     None
                 }
             })
+            .map(SecWebsocketVersion)
             .ok_or_else(::Error::invalid)
     }
 
     fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
-        debug_assert_eq!(self.0, 13);
-
-        values.extend(::std::iter::once(::HeaderValue::from_static("13")));
+        values.extend(::std::iter::once(::HeaderValue::from(u16::from(self.0))));
     }
 }
 
@@ -45,9 +60,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_other_version() {
+        let version = test_decode::<SecWebsocketVersion>(&["8"]).unwrap();
+        assert_eq!(version.as_u8(), 8);
+    }
+
     #[test]
     fn decode_fail() {
-        assert_eq!(test_decode::<SecWebsocketVersion>(&["1"]), None,);
+        assert_eq!(test_decode::<SecWebsocketVersion>(&["256"]), None,);
+        assert_eq!(test_decode::<SecWebsocketVersion>(&["+13"]), None,);
+        assert_eq!(test_decode::<SecWebsocketVersion>(&["v13"]), None,);
     }
 
     #[test]
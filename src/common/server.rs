@@ -1,6 +1,9 @@
 use std::fmt;
 use std::str::FromStr;
 
+use super::user_agent::{
+    build_products, push_comment, push_product, strip_comments, Product, Products,
+};
 use util::HeaderValueString;
 
 /// `Server` header, defined in [RFC7231](http://tools.ietf.org/html/rfc7231#section-7.4.2)
@@ -29,6 +32,9 @@ use util::HeaderValueString;
 /// use headers::Server;
 ///
 /// let server = Server::from_static("hyper/0.12.2");
+///
+/// let server = Server::from_product("myproxy", Some("1.2")).with_comment("linux");
+/// assert_eq!(server.as_str(), "myproxy/1.2 (linux)");
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Server(HeaderValueString);
@@ -48,10 +54,57 @@ impl Server {
         Server(HeaderValueString::from_static(s))
     }
 
+    /// Construct a `Server` with a single product, such as `nginx/1.25.3`.
+    ///
+    /// This works just like
+    /// [`UserAgent::from_product`](struct.UserAgent.html#method.from_product).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` or `version` isn't a `token`.
+    pub fn from_product(name: &str, version: Option<&str>) -> Server {
+        Server(build_products("", |s| push_product(s, name, version)))
+    }
+
+    /// Add a product after the existing ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` or `version` isn't a `token`.
+    pub fn with_product(self, name: &str, version: Option<&str>) -> Server {
+        Server(build_products(self.as_str(), |s| {
+            push_product(s, name, version)
+        }))
+    }
+
+    /// Add a comment after the existing products, wrapped in parentheses
+    /// and with parentheses and backslashes in it escaped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `comment` has characters other than visible ASCII, spaces
+    /// and tabs.
+    pub fn with_comment(self, comment: &str) -> Server {
+        Server(build_products(self.as_str(), |s| push_comment(s, comment)))
+    }
+
+    /// Returns a copy with every comment removed, as with
+    /// [`UserAgent::without_comments`](struct.UserAgent.html#method.without_comments).
+    pub fn without_comments(&self) -> Server {
+        Server(strip_comments(self.as_str()))
+    }
+
     /// View this `Server` as a `&str`.
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
+
+    /// Returns an iterator over the product tokens, each with the comments
+    /// that follow it, as with
+    /// [`UserAgent::products`](struct.UserAgent.html#method.products).
+    pub fn products(&self) -> impl Iterator<Item = Product<'_>> {
+        Products(self.as_str())
+    }
 }
 
 error_type!(InvalidServer);
@@ -70,3 +123,43 @@ impl fmt::Display for Server {
         fmt::Display::fmt(&self.0, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_encode;
+    use super::*;
+
+    #[test]
+    fn products() {
+        let server = Server::from_static("Apache/2.4.1 (Unix) mod_ssl/2.4.1");
+        let products = server
+            .products()
+            .map(|p| (p.name(), p.version(), p.comment()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            products,
+            vec![
+                ("Apache", Some("2.4.1"), Some("Unix")),
+                ("mod_ssl", Some("2.4.1"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn from_product() {
+        let server = Server::from_product("myproxy", Some("1.2"))
+            .with_comment("linux")
+            .with_product("openssl", Some("3.0"));
+        assert_eq!(server.as_str(), "myproxy/1.2 (linux) openssl/3.0");
+
+        let headers = test_encode(server);
+        assert_eq!(headers["server"], "myproxy/1.2 (linux) openssl/3.0");
+    }
+
+    #[test]
+    fn without_comments() {
+        let server = Server::from_static("Apache/2.4.1 (Unix (x86_64)) mod_ssl/2.4.1");
+        let headers = test_encode(server.without_comments());
+        assert_eq!(headers["server"], "Apache/2.4.1 mod_ssl/2.4.1");
+    }
+}
@@ -0,0 +1,287 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::time::Duration;
+
+use util::csv::{decode_list, encode_list, split_token, trim_ows};
+use util::quoted_string::{self, Quoted};
+use util::Millis;
+use HeaderValue;
+
+static SERVER_TIMING: ::HeaderName = ::HeaderName::from_static("server-timing");
+
+/// `Server-Timing` header, defined in
+/// [Server Timing](https://www.w3.org/TR/server-timing/#the-server-timing-header-field)
+///
+/// The `Server-Timing` response header communicates metrics about how the
+/// server handled the request, such as time spent in the database, which
+/// browsers show in their developer tools.
+///
+/// # ABNF
+///
+/// ```text
+/// Server-Timing             = #server-timing-metric
+/// server-timing-metric      = metric-name *( OWS ";" OWS server-timing-param )
+/// metric-name               = token
+/// server-timing-param       = server-timing-param-name OWS "=" OWS server-timing-param-value
+/// server-timing-param-name  = token
+/// server-timing-param-value = token / quoted-string
+/// ```
+///
+/// # Example values
+///
+/// * `miss, db;dur=53, app;dur=47.2`
+/// * `cache;desc="Cache Read";dur=23.2`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use std::time::Duration;
+/// use headers::ServerTiming;
+///
+/// let timing = vec![
+///     ServerTiming::metric("db").dur(Duration::from_millis(53)),
+///     ServerTiming::metric("cache").desc("Cache Read"),
+/// ]
+/// .into_iter()
+/// .collect::<ServerTiming>();
+///
+/// let db = timing.iter().next().unwrap();
+/// assert_eq!(db.duration(), Some(Duration::from_millis(53)));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServerTiming(Vec<ServerTimingMetric>);
+
+/// A single metric in a [`ServerTiming`](struct.ServerTiming.html) header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServerTimingMetric {
+    name: String,
+    dur: Option<Millis>,
+    desc: Option<String>,
+}
+
+impl ::Header for ServerTiming {
+    fn name() -> &'static ::HeaderName {
+        &SERVER_TIMING
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        decode_list(values, parse_metric).map(ServerTiming)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_list(&self.0)));
+    }
+}
+
+impl ServerTiming {
+    /// Starts a metric with just a name, such as `db` or `cache`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a `token`.
+    pub fn metric<N: Into<String>>(name: N) -> ServerTimingMetric {
+        let name = name.into();
+        assert!(
+            quoted_string::is_token(&name),
+            "invalid Server-Timing metric name: {:?}",
+            name
+        );
+        ServerTimingMetric {
+            name,
+            dur: None,
+            desc: None,
+        }
+    }
+
+    /// Returns an iterator over the metrics, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &ServerTimingMetric> {
+        self.0.iter()
+    }
+}
+
+impl From<ServerTimingMetric> for ServerTiming {
+    fn from(metric: ServerTimingMetric) -> ServerTiming {
+        ServerTiming(vec![metric])
+    }
+}
+
+impl FromIterator<ServerTimingMetric> for ServerTiming {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = ServerTimingMetric>,
+    {
+        ServerTiming(iter.into_iter().collect())
+    }
+}
+
+// ===== impl ServerTimingMetric =====
+
+impl ServerTimingMetric {
+    /// Sets the `dur` parameter, how long the metric took.
+    pub fn dur(mut self, dur: Duration) -> ServerTimingMetric {
+        self.dur = Some(dur.into());
+        self
+    }
+
+    /// Sets the `desc` parameter, a human-readable description.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `desc` contains control characters.
+    pub fn desc<D: Into<String>>(mut self, desc: D) -> ServerTimingMetric {
+        let desc = desc.into();
+        assert!(
+            desc.chars().all(|c| c == '\t' || !c.is_control()),
+            "invalid Server-Timing description: {:?}",
+            desc
+        );
+        self.desc = Some(desc);
+        self
+    }
+
+    /// Returns the metric name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the `dur` parameter as a `Duration`.
+    pub fn duration(&self) -> Option<Duration> {
+        self.dur.map(Duration::from)
+    }
+
+    /// Returns the `dur` parameter in milliseconds, as it was sent.
+    pub fn duration_millis(&self) -> Option<f64> {
+        self.dur.as_ref().map(Millis::as_f64)
+    }
+
+    /// Returns the unquoted `desc` parameter.
+    pub fn description(&self) -> Option<&str> {
+        self.desc.as_deref()
+    }
+}
+
+impl fmt::Display for ServerTimingMetric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if let Some(dur) = self.dur {
+            write!(f, ";dur={}", dur)?;
+        }
+        if let Some(ref desc) = self.desc {
+            if quoted_string::is_token(desc) {
+                write!(f, ";desc={}", desc)?;
+            } else {
+                write!(f, ";desc={}", Quoted(desc))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_metric(s: &str) -> Option<(ServerTimingMetric, &str)> {
+    let (name, mut rest) = split_token(s)?;
+    let mut metric = ServerTimingMetric {
+        name: name.to_owned(),
+        dur: None,
+        desc: None,
+    };
+
+    loop {
+        rest = trim_ows(rest);
+        if !rest.starts_with(';') {
+            break;
+        }
+        let (param, after) = split_token(trim_ows(&rest[1..]))?;
+        let after = trim_ows(after);
+        if !after.starts_with('=') {
+            return None;
+        }
+        let after = trim_ows(&after[1..]);
+        let (value, after) = if after.starts_with('"') {
+            quoted_string::parse(after)?
+        } else {
+            let (value, after) = split_token(after)?;
+            (value.to_owned(), after)
+        };
+
+        // Only the first of each parameter counts, and unknown ones, or a
+        // `dur` that isn't a number, are ignored.
+        if param.eq_ignore_ascii_case("dur") && metric.dur.is_none() {
+            metric.dur = value.parse().ok();
+        } else if param.eq_ignore_ascii_case("desc") && metric.desc.is_none() {
+            metric.desc = Some(value);
+        }
+        rest = after;
+    }
+
+    Some((metric, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let timing = test_decode::<ServerTiming>(&[
+            "miss, db;dur=53, app ; dur = 47.2",
+            r#"cache;desc="Cache, Read";dur=23.2;dur=1;x=y"#,
+        ])
+        .unwrap();
+        let metrics = timing.iter().collect::<Vec<_>>();
+        assert_eq!(metrics.len(), 4);
+
+        assert_eq!(metrics[0].name(), "miss");
+        assert_eq!(metrics[0].duration(), None);
+        assert_eq!(metrics[0].description(), None);
+
+        assert_eq!(metrics[1].duration(), Some(Duration::from_millis(53)));
+        assert_eq!(metrics[2].duration_millis(), Some(47.2));
+
+        assert_eq!(metrics[3].name(), "cache");
+        assert_eq!(metrics[3].description(), Some("Cache, Read"));
+        assert_eq!(metrics[3].duration_millis(), Some(23.2));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<ServerTiming>(&[""]), None);
+        assert_eq!(test_decode::<ServerTiming>(&["db;dur"]), None);
+        assert_eq!(test_decode::<ServerTiming>(&["db dur=1"]), None);
+        assert_eq!(test_decode::<ServerTiming>(&[r#"db;desc="open"#]), None);
+    }
+
+    #[test]
+    fn invalid_dur_is_ignored() {
+        let timing = test_decode::<ServerTiming>(&["db;dur=fast"]).unwrap();
+        assert_eq!(timing.iter().next().unwrap().duration(), None);
+    }
+
+    #[test]
+    fn encode() {
+        let timing = vec![
+            ServerTiming::metric("miss"),
+            ServerTiming::metric("db").dur(Duration::from_micros(53_500)),
+            ServerTiming::metric("cache")
+                .desc("Cache Read")
+                .dur(Duration::from_millis(23)),
+        ]
+        .into_iter()
+        .collect::<ServerTiming>();
+
+        let headers = test_encode(timing);
+        assert_eq!(
+            headers["server-timing"],
+            r#"miss, db;dur=53.5, cache;dur=23;desc="Cache Read""#
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let raw = r#"db;dur=53.2, cache;dur=0.1;desc="Cache Read", total"#;
+        let timing = test_decode::<ServerTiming>(&[raw]).unwrap();
+        let headers = test_encode(timing);
+        assert_eq!(headers["server-timing"], raw);
+    }
+}
@@ -1,3 +1,9 @@
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+use util::quoted_string;
+use util::{self, HttpDate};
+
 /// `Set-Cookie` header, defined [RFC6265](http://tools.ietf.org/html/rfc6265#section-4.1)
 ///
 /// The Set-Cookie HTTP response header is used to send cookies from the
@@ -7,6 +13,13 @@
 /// "Set-Cookie" followed by a ":" and a cookie.  Each cookie begins with
 /// a name-value-pair, followed by zero or more attribute-value pairs.
 ///
+/// A `SetCookie` holds every `Set-Cookie` field of a message, one cookie
+/// per field. The single-cookie accessors, such as `name` and `max_age`,
+/// read the first cookie; use [`iter`](#method.iter) or
+/// `HeaderMapExt::typed_get_all` to look at each one. As user agents do,
+/// decoding takes a cookie's value as everything up to the first `;`, and
+/// only fails if a field has no `=` or an empty cookie name.
+///
 /// # ABNF
 ///
 /// ```text
@@ -51,9 +64,279 @@
 /// * `lang=en-US; Path=/; Domain=example.com`
 ///
 /// # Example
-#[derive(Clone, Debug)]
+///
+/// ```
+/// # extern crate headers;
+/// use std::time::Duration;
+/// use headers::{SameSite, SetCookie};
+///
+/// let cookie = SetCookie::build("SID", "31d4d96e407aad42")
+///     .path("/")
+///     .max_age(Duration::from_secs(3600))
+///     .secure()
+///     .http_only()
+///     .same_site(SameSite::Lax)
+///     .finish();
+///
+/// assert_eq!(cookie.name(), "SID");
+/// assert!(cookie.http_only());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
 pub struct SetCookie(Vec<::HeaderValue>);
 
+/// The `SameSite` attribute of a [`SetCookie`](struct.SetCookie.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SameSite {
+    /// Only sent with same-site requests.
+    Strict,
+    /// Also sent with top-level navigations from other sites.
+    Lax,
+    /// Sent with cross-site requests. Requires `Secure`.
+    None,
+}
+
+/// A builder for a [`SetCookie`](struct.SetCookie.html), created by
+/// [`SetCookie::build`](struct.SetCookie.html#method.build).
+#[derive(Clone, Debug)]
+pub struct SetCookieBuilder {
+    name: String,
+    value: String,
+    expires: Option<HttpDate>,
+    max_age: Option<i64>,
+    domain: Option<String>,
+    path: Option<String>,
+    secure: bool,
+    http_only: bool,
+    same_site: Option<SameSite>,
+}
+
+// A set-cookie-string, borrowed from a field of a `SetCookie`.
+struct Parsed<'a> {
+    name: &'a str,
+    // raw cookie-value, possibly in DQUOTEs
+    value: &'a str,
+    expires: Option<HttpDate>,
+    max_age: Option<i64>,
+    domain: Option<&'a str>,
+    path: Option<&'a str>,
+    secure: bool,
+    http_only: bool,
+    same_site: Option<SameSite>,
+}
+
+impl SetCookie {
+    /// Start building a `SetCookie` with a name and value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a `token`, or `value` isn't a valid
+    /// `cookie-value`.
+    pub fn build(name: &str, value: &str) -> SetCookieBuilder {
+        assert!(
+            quoted_string::is_token(name),
+            "invalid SetCookie name: {:?}",
+            name
+        );
+        assert!(
+            is_cookie_value(value),
+            "invalid SetCookie value: {:?}",
+            value
+        );
+        SetCookieBuilder {
+            name: name.to_owned(),
+            value: value.to_owned(),
+            expires: None,
+            max_age: None,
+            domain: None,
+            path: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+        }
+    }
+
+    /// Iterate the cookies, each as a `SetCookie` of its own.
+    pub fn iter(&self) -> impl Iterator<Item = SetCookie> + '_ {
+        self.0.iter().map(|value| SetCookie(vec![value.clone()]))
+    }
+
+    // Every field is checked when decoding or building, so this can't fail.
+    fn first(&self) -> Parsed<'_> {
+        self.0
+            .first()
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse)
+            .expect("SetCookie fields are valid")
+    }
+
+    /// Get the cookie name.
+    pub fn name(&self) -> &str {
+        self.first().name
+    }
+
+    /// Get the cookie value, without surrounding double quotes.
+    pub fn value(&self) -> &str {
+        unquote(self.first().value)
+    }
+
+    /// Get the `Expires` attribute, if set.
+    pub fn expires(&self) -> Option<SystemTime> {
+        self.first().expires.map(SystemTime::from)
+    }
+
+    /// Get the `Max-Age` attribute, if set.
+    ///
+    /// A zero or negative `Max-Age` is returned as a zero `Duration`.
+    pub fn max_age(&self) -> Option<Duration> {
+        self.first()
+            .max_age
+            .map(|secs| Duration::from_secs(secs.max(0) as u64))
+    }
+
+    /// Work out when the cookie expires, given when it was received.
+    ///
+    /// Following [RFC6265](https://tools.ietf.org/html/rfc6265#section-5.3),
+    /// `Max-Age` takes precedence over `Expires`. A zero or negative
+    /// `Max-Age` gives the Unix epoch, so the cookie is already expired and
+    /// should be removed. Returns `None` for a session cookie, which has
+    /// neither attribute.
+    ///
+    /// `Max-Age` is capped at 2<sup>31</sup> seconds, roughly 68 years, so
+    /// the result always fits in a `SystemTime`.
+    pub fn expires_at(&self, received: SystemTime) -> Option<SystemTime> {
+        match self.first().max_age {
+            Some(secs) if secs <= 0 => Some(SystemTime::UNIX_EPOCH),
+            Some(secs) => {
+                let secs = (secs as u64).min(1 << 31);
+                Some(received + Duration::from_secs(secs))
+            }
+            None => self.expires(),
+        }
+    }
+
+    /// Check whether the cookie has expired by `now`, given when it was
+    /// received.
+    ///
+    /// A session cookie never expires. See [`expires_at`](#method.expires_at).
+    pub fn is_expired(&self, received: SystemTime, now: SystemTime) -> bool {
+        match self.expires_at(received) {
+            Some(at) => at <= now,
+            None => false,
+        }
+    }
+
+    /// Get the name and value to send back in a [`Cookie`](struct.Cookie.html).
+    ///
+    /// The value is kept exactly as received, including any surrounding
+    /// double quotes.
+    pub fn to_cookie_pair(&self) -> (String, String) {
+        let cookie = self.first();
+        (cookie.name.to_owned(), cookie.value.to_owned())
+    }
+
+    /// Get the `Domain` attribute, if set.
+    pub fn domain(&self) -> Option<&str> {
+        self.first().domain
+    }
+
+    /// Get the `Path` attribute, if set.
+    pub fn path(&self) -> Option<&str> {
+        self.first().path
+    }
+
+    /// Check if the `Secure` attribute is set.
+    pub fn secure(&self) -> bool {
+        self.first().secure
+    }
+
+    /// Check if the `HttpOnly` attribute is set.
+    pub fn http_only(&self) -> bool {
+        self.first().http_only
+    }
+
+    /// Get the `SameSite` attribute, if set.
+    pub fn same_site(&self) -> Option<SameSite> {
+        self.first().same_site
+    }
+}
+
+impl SetCookieBuilder {
+    /// Set the `Max-Age` attribute.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        let secs = max_age.as_secs();
+        self.max_age = Some(if secs > i64::MAX as u64 {
+            i64::MAX
+        } else {
+            secs as i64
+        });
+        self
+    }
+
+    /// Set the `Expires` attribute.
+    pub fn expires(mut self, time: SystemTime) -> Self {
+        self.expires = Some(time.into());
+        self
+    }
+
+    /// Set the `Domain` attribute.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `domain` contains control characters or `;`.
+    pub fn domain(mut self, domain: &str) -> Self {
+        assert!(
+            is_attr_value(domain),
+            "invalid SetCookie domain: {:?}",
+            domain
+        );
+        self.domain = Some(domain.to_owned());
+        self
+    }
+
+    /// Set the `Path` attribute.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` contains control characters or `;`.
+    pub fn path(mut self, path: &str) -> Self {
+        assert!(is_attr_value(path), "invalid SetCookie path: {:?}", path);
+        self.path = Some(path.to_owned());
+        self
+    }
+
+    /// Set the `Secure` attribute.
+    pub fn secure(mut self) -> Self {
+        self.secure = true;
+        self
+    }
+
+    /// Set the `HttpOnly` attribute.
+    pub fn http_only(mut self) -> Self {
+        self.http_only = true;
+        self
+    }
+
+    /// Set the `SameSite` attribute.
+    ///
+    /// `SameSite=None` is only encoded if the `Secure` attribute is also
+    /// set, since user agents reject it otherwise.
+    pub fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        self
+    }
+
+    /// Finish building the `SetCookie`.
+    pub fn finish(self) -> SetCookie {
+        SetCookie(vec![util::fmt(Fmt(&self))])
+    }
+}
+
+impl From<SetCookieBuilder> for SetCookie {
+    fn from(builder: SetCookieBuilder) -> SetCookie {
+        builder.finish()
+    }
+}
+
 impl ::Header for SetCookie {
     fn name() -> &'static ::HeaderName {
         &::http::header::SET_COOKIE
@@ -62,7 +345,10 @@ impl ::Header for SetCookie {
     fn decode<'i, I: Iterator<Item = &'i ::HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
         let vec = values.cloned().collect::<Vec<_>>();
 
-        if !vec.is_empty() {
+        let valid = vec
+            .iter()
+            .all(|value| value.to_str().ok().and_then(parse).is_some());
+        if !vec.is_empty() && valid {
             Ok(SetCookie(vec))
         } else {
             Err(::Error::invalid())
@@ -72,12 +358,144 @@ impl ::Header for SetCookie {
     fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
         values.extend(self.0.iter().cloned());
     }
+
+    fn separate_fields() -> bool {
+        true
+    }
+}
+
+// Parses a set-cookie-string, following the user agent algorithm of
+// RFC6265 section 5.2, where invalid attributes are ignored.
+//
+// The name and value aren't checked against `token` and `cookie-value`,
+// which only constrain what servers send; the builder checks those.
+fn parse(s: &str) -> Option<Parsed<'_>> {
+    let mut parts = s.split(';');
+    let pair = parts.next()?;
+    let eq = pair.find('=')?;
+    let name = pair[..eq].trim();
+    let value = pair[eq + 1..].trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut cookie = Parsed {
+        name,
+        value,
+        expires: None,
+        max_age: None,
+        domain: None,
+        path: None,
+        secure: false,
+        http_only: false,
+        same_site: None,
+    };
+    for attr in parts {
+        let attr = attr.trim();
+        if attr.is_empty() {
+            continue;
+        }
+        let (key, val) = match attr.find('=') {
+            Some(eq) => (attr[..eq].trim(), attr[eq + 1..].trim()),
+            None => (attr, ""),
+        };
+
+        if key.eq_ignore_ascii_case("expires") {
+            if let Ok(date) = val.parse() {
+                cookie.expires = Some(date);
+            }
+        } else if key.eq_ignore_ascii_case("max-age") {
+            if let Ok(secs) = val.parse() {
+                cookie.max_age = Some(secs);
+            }
+        } else if key.eq_ignore_ascii_case("domain") {
+            let domain = val.trim_start_matches('.');
+            if !domain.is_empty() {
+                cookie.domain = Some(domain);
+            }
+        } else if key.eq_ignore_ascii_case("path") {
+            if val.starts_with('/') {
+                cookie.path = Some(val);
+            }
+        } else if key.eq_ignore_ascii_case("secure") {
+            cookie.secure = true;
+        } else if key.eq_ignore_ascii_case("httponly") {
+            cookie.http_only = true;
+        } else if key.eq_ignore_ascii_case("samesite") {
+            if val.eq_ignore_ascii_case("strict") {
+                cookie.same_site = Some(SameSite::Strict);
+            } else if val.eq_ignore_ascii_case("lax") {
+                cookie.same_site = Some(SameSite::Lax);
+            } else if val.eq_ignore_ascii_case("none") {
+                cookie.same_site = Some(SameSite::None);
+            }
+        }
+    }
+    Some(cookie)
+}
+
+struct Fmt<'a>(&'a SetCookieBuilder);
+
+impl<'a> fmt::Display for Fmt<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cookie = self.0;
+        write!(f, "{}={}", cookie.name, cookie.value)?;
+        if let Some(ref expires) = cookie.expires {
+            write!(f, "; Expires={}", expires)?;
+        }
+        if let Some(max_age) = cookie.max_age {
+            write!(f, "; Max-Age={}", max_age)?;
+        }
+        if let Some(ref domain) = cookie.domain {
+            write!(f, "; Domain={}", domain)?;
+        }
+        if let Some(ref path) = cookie.path {
+            write!(f, "; Path={}", path)?;
+        }
+        if cookie.secure {
+            f.write_str("; Secure")?;
+        }
+        if cookie.http_only {
+            f.write_str("; HttpOnly")?;
+        }
+        match cookie.same_site {
+            Some(SameSite::Strict) => f.write_str("; SameSite=Strict")?,
+            Some(SameSite::Lax) => f.write_str("; SameSite=Lax")?,
+            Some(SameSite::None) if cookie.secure => f.write_str("; SameSite=None")?,
+            Some(SameSite::None) | None => (),
+        }
+        Ok(())
+    }
+}
+
+pub(super) fn unquote(value: &str) -> &str {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        &value[1..value.len() - 1]
+    } else {
+        value
+    }
+}
+
+pub(super) fn is_cookie_value(value: &str) -> bool {
+    unquote(value).bytes().all(|b| {
+        matches!(
+            b,
+            b'\x21' | b'\x23'..=b'\x2B' | b'\x2D'..=b'\x3A' | b'\x3C'..=b'\x5B' | b'\x5D'..=b'\x7E'
+        )
+    })
+}
+
+fn is_attr_value(value: &str) -> bool {
+    value
+        .bytes()
+        .all(|b| (0x20..0x7F).contains(&b) && b != b';')
 }
 
 #[cfg(test)]
 mod tests {
     use super::super::{test_decode, test_encode};
     use super::*;
+    use {HeaderMap, HeaderMapExt};
 
     #[test]
     fn decode() {
@@ -100,4 +518,183 @@ mod tests {
         assert_eq!(vals.next().unwrap(), "baz=quux");
         assert_eq!(vals.next(), None);
     }
+
+    #[test]
+    fn decode_accessors() {
+        let cookie = test_decode::<SetCookie>(&["foo=bar"]).unwrap();
+        assert_eq!(cookie.name(), "foo");
+        assert_eq!(cookie.value(), "bar");
+        assert_eq!(cookie.expires(), None);
+        assert_eq!(cookie.max_age(), None);
+        assert!(!cookie.secure());
+    }
+
+    #[test]
+    fn decode_attributes() {
+        let cookie = test_decode::<SetCookie>(&[
+            "lang=\"en-US\"; expires=Sun, 06 Nov 1994 08:49:37 GMT; max-age=60; \
+             domain=.example.com; path=/docs; secure; httponly; samesite=strict; Partitioned",
+        ])
+        .unwrap();
+        assert_eq!(cookie.name(), "lang");
+        assert_eq!(cookie.value(), "en-US");
+        assert_eq!(
+            cookie.expires(),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(784_111_777))
+        );
+        assert_eq!(cookie.max_age(), Some(Duration::from_secs(60)));
+        assert_eq!(cookie.domain(), Some("example.com"));
+        assert_eq!(cookie.path(), Some("/docs"));
+        assert!(cookie.secure());
+        assert!(cookie.http_only());
+        assert_eq!(cookie.same_site(), Some(SameSite::Strict));
+    }
+
+    #[test]
+    fn decode_ignores_invalid_attributes() {
+        let cookie =
+            test_decode::<SetCookie>(&["a=b; Expires=soon; Max-Age=x; Path=rel; SameSite=maybe"])
+                .unwrap();
+        assert_eq!(cookie.expires(), None);
+        assert_eq!(cookie.max_age(), None);
+        assert_eq!(cookie.path(), None);
+        assert_eq!(cookie.same_site(), None);
+
+        let cookie = test_decode::<SetCookie>(&["a=b; Max-Age=-1"]).unwrap();
+        assert_eq!(cookie.max_age(), Some(Duration::from_secs(0)));
+    }
+
+    #[test]
+    fn expires_at() {
+        let received = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        let expires = "Sun, 06 Nov 1994 08:49:37 GMT";
+        let at = |s: &str| test_decode::<SetCookie>(&[s]).unwrap().expires_at(received);
+
+        assert_eq!(at("a=b"), None);
+        assert_eq!(
+            at(&format!("a=b; Expires={}", expires)),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(784_111_777))
+        );
+        assert_eq!(
+            at(&format!("a=b; Expires={}; Max-Age=60", expires)),
+            Some(received + Duration::from_secs(60))
+        );
+        assert_eq!(at("a=b; Max-Age=0"), Some(SystemTime::UNIX_EPOCH));
+        assert_eq!(at("a=b; Max-Age=-5"), Some(SystemTime::UNIX_EPOCH));
+        assert_eq!(
+            at("a=b; Max-Age=9223372036854775807"),
+            Some(received + Duration::from_secs(1 << 31))
+        );
+    }
+
+    #[test]
+    fn is_expired() {
+        let received = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        let later = received + Duration::from_secs(60);
+        let cookie = |s: &str| test_decode::<SetCookie>(&[s]).unwrap();
+
+        assert!(!cookie("a=b").is_expired(received, later));
+        assert!(cookie("a=b; Max-Age=0").is_expired(received, received));
+        assert!(!cookie("a=b; Max-Age=61").is_expired(received, later));
+        assert!(cookie("a=b; Max-Age=60").is_expired(received, later));
+    }
+
+    #[test]
+    fn to_cookie_pair() {
+        let cookie = test_decode::<SetCookie>(&["SID=\"31d4\"; Path=/"]).unwrap();
+        assert_eq!(
+            cookie.to_cookie_pair(),
+            ("SID".to_owned(), "\"31d4\"".to_owned())
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<SetCookie>(&["no-equals"]), None);
+        assert_eq!(test_decode::<SetCookie>(&["=value"]), None);
+        assert_eq!(test_decode::<SetCookie>(&["foo=bar", "=value"]), None);
+    }
+
+    #[test]
+    fn decode_lenient_value() {
+        let set_cookie = test_decode::<SetCookie>(&["foo=bar", "a=b c, d\\e; Path=/"]).unwrap();
+        let cookies = set_cookie.iter().collect::<Vec<_>>();
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].value(), "bar");
+        assert_eq!(cookies[1].name(), "a");
+        assert_eq!(cookies[1].value(), "b c, d\\e");
+        assert_eq!(cookies[1].path(), Some("/"));
+    }
+
+    #[test]
+    fn iter() {
+        let set_cookie = test_decode::<SetCookie>(&["foo=bar", "baz=quux; Path=/"]).unwrap();
+        assert_eq!(set_cookie.name(), "foo");
+
+        let cookies = set_cookie.iter().collect::<Vec<_>>();
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].name(), "foo");
+        assert_eq!(cookies[0].path(), None);
+        assert_eq!(cookies[1].name(), "baz");
+        assert_eq!(cookies[1].path(), Some("/"));
+    }
+
+    #[test]
+    fn encode_attributes() {
+        let cookie = SetCookie::build("SID", "31d4d96e407aad42")
+            .same_site(SameSite::Lax)
+            .http_only()
+            .secure()
+            .path("/")
+            .domain("example.com")
+            .max_age(Duration::from_secs(3600))
+            .expires(SystemTime::UNIX_EPOCH + Duration::from_secs(784_111_777))
+            .finish();
+
+        let headers = test_encode(cookie);
+        assert_eq!(
+            headers["set-cookie"],
+            "SID=31d4d96e407aad42; Expires=Sun, 06 Nov 1994 08:49:37 GMT; Max-Age=3600; \
+             Domain=example.com; Path=/; Secure; HttpOnly; SameSite=Lax"
+        );
+    }
+
+    #[test]
+    fn encode_same_site_none_requires_secure() {
+        let cookie = SetCookie::build("a", "b")
+            .same_site(SameSite::None)
+            .finish();
+        let headers = test_encode(cookie);
+        assert_eq!(headers["set-cookie"], "a=b");
+
+        let cookie = SetCookie::build("a", "b")
+            .same_site(SameSite::None)
+            .secure()
+            .finish();
+        let headers = test_encode(cookie);
+        assert_eq!(headers["set-cookie"], "a=b; Secure; SameSite=None");
+    }
+
+    #[test]
+    fn round_trip() {
+        let cookie = SetCookie::build("lang", "en-US")
+            .path("/")
+            .max_age(Duration::from_secs(60))
+            .expires(SystemTime::UNIX_EPOCH + Duration::from_secs(784_111_777))
+            .domain("example.com")
+            .secure()
+            .http_only()
+            .same_site(SameSite::Strict)
+            .finish();
+
+        let mut headers = HeaderMap::new();
+        headers.typed_insert(cookie.clone());
+        assert_eq!(headers.typed_get::<SetCookie>(), Some(cookie));
+    }
+
+    #[test]
+    #[should_panic]
+    fn build_invalid_value() {
+        SetCookie::build("a", "b;c");
+    }
 }
@@ -28,6 +28,7 @@ use util::{self, IterExt, Seconds};
 ///
 /// * `max-age=31536000`
 /// * `max-age=15768000 ; includeSubdomains`
+/// * `max-age=63072000; includeSubDomains; preload`
 ///
 /// # Example
 ///
@@ -48,6 +49,10 @@ pub struct StrictTransportSecurity {
     /// field, during which the UA regards the host (from whom the message was
     /// received) as a Known HSTS Host.
     max_age: Seconds,
+
+    /// Signals consent to have the host included in browsers' HSTS preload
+    /// lists. Not part of RFC6797, see <https://hstspreload.org>.
+    preload: bool,
 }
 
 impl StrictTransportSecurity {
@@ -60,6 +65,7 @@ impl StrictTransportSecurity {
         StrictTransportSecurity {
             max_age: max_age.into(),
             include_subdomains: true,
+            preload: false,
         }
     }
 
@@ -68,9 +74,16 @@ impl StrictTransportSecurity {
         StrictTransportSecurity {
             max_age: max_age.into(),
             include_subdomains: false,
+            preload: false,
         }
     }
 
+    /// Add the `preload` directive.
+    pub fn with_preload(mut self) -> Self {
+        self.preload = true;
+        self
+    }
+
     // getters
 
     /// Get whether this should include subdomains.
@@ -78,15 +91,35 @@ impl StrictTransportSecurity {
         self.include_subdomains
     }
 
-    /// Get the max-age.
+    /// Get the max-age, how long the browser should only use HTTPS for
+    /// the host.
+    ///
+    /// Decoding fails without a valid `max-age`, so this is always set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use std::time::Duration;
+    /// use headers::StrictTransportSecurity;
+    ///
+    /// let hsts = StrictTransportSecurity::including_subdomains(Duration::from_secs(31536000));
+    /// assert!(hsts.max_age() >= Duration::from_secs(365 * 24 * 60 * 60));
+    /// ```
     pub fn max_age(&self) -> Duration {
         self.max_age.into()
     }
+
+    /// Get whether the `preload` directive is set.
+    pub fn preload(&self) -> bool {
+        self.preload
+    }
 }
 
 enum Directive {
     MaxAge(u64),
     IncludeSubdomains,
+    Preload,
     Unknown,
 }
 
@@ -96,33 +129,35 @@ fn from_str(s: &str) -> Result<StrictTransportSecurity, ::Error> {
         .map(|sub| {
             if sub.eq_ignore_ascii_case("includeSubdomains") {
                 Some(Directive::IncludeSubdomains)
+            } else if sub.eq_ignore_ascii_case("preload") {
+                Some(Directive::Preload)
             } else {
                 let mut sub = sub.splitn(2, '=');
                 match (sub.next(), sub.next()) {
                     (Some(left), Some(right)) if left.trim().eq_ignore_ascii_case("max-age") => {
-                        right
-                            .trim()
-                            .trim_matches('"')
-                            .parse()
-                            .ok()
+                        util::parse_u64(right.trim().trim_matches('"').as_bytes())
                             .map(Directive::MaxAge)
                     }
                     _ => Some(Directive::Unknown),
                 }
             }
         })
-        .fold(Some((None, None)), |res, dir| match (res, dir) {
-            (Some((None, sub)), Some(Directive::MaxAge(age))) => Some((Some(age), sub)),
-            (Some((age, None)), Some(Directive::IncludeSubdomains)) => Some((age, Some(()))),
-            (Some((Some(_), _)), Some(Directive::MaxAge(_)))
-            | (Some((_, Some(_))), Some(Directive::IncludeSubdomains))
-            | (_, None) => None,
-            (res, _) => res,
+        .try_fold((None, None, false), |(age, sub, pre), dir| {
+            match (age, sub, dir?) {
+                (None, sub, Directive::MaxAge(age)) => Some((Some(age), sub, pre)),
+                (age, None, Directive::IncludeSubdomains) => Some((age, Some(()), pre)),
+                (age, sub, Directive::Preload) => Some((age, sub, true)),
+                (Some(_), _, Directive::MaxAge(_)) | (_, Some(_), Directive::IncludeSubdomains) => {
+                    None
+                }
+                (age, sub, Directive::Unknown) => Some((age, sub, pre)),
+            }
         })
         .and_then(|res| match res {
-            (Some(age), sub) => Some(StrictTransportSecurity {
+            (Some(age), sub, preload) => Some(StrictTransportSecurity {
                 max_age: Duration::from_secs(age).into(),
                 include_subdomains: sub.is_some(),
+                preload,
             }),
             _ => None,
         })
@@ -147,11 +182,14 @@ impl ::Header for StrictTransportSecurity {
 
         impl<'a> fmt::Display for Adapter<'a> {
             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "max-age={}", self.0.max_age)?;
                 if self.0.include_subdomains {
-                    write!(f, "max-age={}; includeSubdomains", self.0.max_age)
-                } else {
-                    write!(f, "max-age={}", self.0.max_age)
+                    f.write_str("; includeSubdomains")?;
                 }
+                if self.0.preload {
+                    f.write_str("; preload")?;
+                }
+                Ok(())
             }
         }
 
@@ -161,7 +199,7 @@ impl ::Header for StrictTransportSecurity {
 
 #[cfg(test)]
 mod tests {
-    use super::super::test_decode;
+    use super::super::{test_decode, test_encode};
     use super::StrictTransportSecurity;
     use std::time::Duration;
 
@@ -173,10 +211,30 @@ mod tests {
             StrictTransportSecurity {
                 include_subdomains: false,
                 max_age: Duration::from_secs(31536000).into(),
+                preload: false,
             }
         );
     }
 
+    #[test]
+    fn test_max_age_accessor() {
+        let h = test_decode::<StrictTransportSecurity>(&["includeSubDomains; max-age=63072000"])
+            .unwrap();
+        assert_eq!(h.max_age(), Duration::from_secs(63072000));
+
+        let h = test_decode::<StrictTransportSecurity>(&["max-age=0"]).unwrap();
+        assert_eq!(h.max_age(), Duration::from_secs(0));
+
+        assert_eq!(
+            test_decode::<StrictTransportSecurity>(&["max-age=-1"]),
+            None
+        );
+        assert_eq!(
+            test_decode::<StrictTransportSecurity>(&["max-age=1.5"]),
+            None
+        );
+    }
+
     #[test]
     fn test_parse_max_age_no_value() {
         assert_eq!(test_decode::<StrictTransportSecurity>(&["max-age"]), None,);
@@ -190,6 +248,7 @@ mod tests {
             StrictTransportSecurity {
                 include_subdomains: false,
                 max_age: Duration::from_secs(31536000).into(),
+                preload: false,
             }
         );
     }
@@ -202,6 +261,7 @@ mod tests {
             StrictTransportSecurity {
                 include_subdomains: false,
                 max_age: Duration::from_secs(31536000).into(),
+                preload: false,
             }
         );
     }
@@ -215,10 +275,33 @@ mod tests {
             StrictTransportSecurity {
                 include_subdomains: true,
                 max_age: Duration::from_secs(15768000).into(),
+                preload: false,
             }
         );
     }
 
+    #[test]
+    fn test_preload_round_trip() {
+        let h = test_decode::<StrictTransportSecurity>(&[
+            "max-age=63072000; includeSubDomains; PRELOAD; unknown=1",
+        ])
+        .unwrap();
+        assert!(h.include_subdomains());
+        assert!(h.preload());
+        assert_eq!(h.max_age(), Duration::from_secs(63072000));
+        assert_eq!(
+            h,
+            StrictTransportSecurity::including_subdomains(Duration::from_secs(63072000))
+                .with_preload()
+        );
+
+        let headers = test_encode(h);
+        assert_eq!(
+            headers["strict-transport-security"],
+            "max-age=63072000; includeSubdomains; preload"
+        );
+    }
+
     #[test]
     fn test_parse_no_max_age() {
         assert_eq!(
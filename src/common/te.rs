@@ -1,4 +1,9 @@
-use util::FlatCsv;
+use std::fmt;
+use std::str::FromStr;
+
+use util::csv::encode_list;
+use util::{quoted_string, QualityValue};
+use HeaderValue;
 
 /// `TE` header, defined in
 /// [RFC7230](http://tools.ietf.org/html/rfc7230#section-4.3)
@@ -25,17 +30,122 @@ use util::FlatCsv;
 ///
 /// # Examples
 ///
+/// ```
+/// # extern crate headers;
+/// use headers::Te;
+///
+/// let te = Te::trailers();
+/// assert!(te.accepts_trailers());
+/// ```
 #[derive(Clone, Debug, PartialEq)]
-pub struct Te(FlatCsv);
+pub struct Te(Vec<QualityValue<TCoding>>);
 
-derive_header! {
-    Te(_),
-    name: TE
+#[derive(Clone, Debug, PartialEq)]
+enum TCoding {
+    Trailers,
+    // the transfer-extension, with any parameters before the weight
+    Coding(String),
+}
+
+impl ::Header for Te {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::TE
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        QualityValue::decode_list(values).map(Te)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_list(&self.0)));
+    }
 }
 
 impl Te {
     /// Create a `TE: trailers` header.
     pub fn trailers() -> Self {
-        Te(::HeaderValue::from_static("trailers").into())
+        Te(vec![QualityValue::from(TCoding::Trailers)])
+    }
+
+    /// Checks if the client accepts trailer fields in a chunked response.
+    pub fn accepts_trailers(&self) -> bool {
+        self.0
+            .iter()
+            .any(|coding| *coding.value() == TCoding::Trailers)
+    }
+
+    /// Returns an iterator over the accepted transfer codings and their
+    /// weights, in order, leaving out `trailers`.
+    ///
+    /// Any parameters of a coding are kept, such as in `foo;bar=1`.
+    pub fn codings(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.0.iter().filter_map(|coding| match *coding.value() {
+            TCoding::Coding(ref name) => Some((name.as_str(), coding.quality().as_f32())),
+            TCoding::Trailers => None,
+        })
+    }
+}
+
+impl FromStr for TCoding {
+    type Err = ::Error;
+
+    fn from_str(s: &str) -> Result<TCoding, ::Error> {
+        if s.eq_ignore_ascii_case("trailers") {
+            return Ok(TCoding::Trailers);
+        }
+        let name = s.split(';').next().unwrap_or("").trim();
+        if quoted_string::is_token(name) {
+            Ok(TCoding::Coding(s.to_owned()))
+        } else {
+            Err(::Error::invalid())
+        }
+    }
+}
+
+impl fmt::Display for TCoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TCoding::Trailers => f.write_str("trailers"),
+            TCoding::Coding(ref coding) => f.write_str(coding),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_trailers_and_codings() {
+        let te = test_decode::<Te>(&["trailers, deflate;q=0.5", "gzip"]).unwrap();
+        assert!(te.accepts_trailers());
+        assert_eq!(
+            te.codings().collect::<Vec<_>>(),
+            vec![("deflate", 0.5), ("gzip", 1.0)]
+        );
+    }
+
+    #[test]
+    fn decode_empty() {
+        let te = test_decode::<Te>(&[""]).unwrap();
+        assert!(!te.accepts_trailers());
+        assert_eq!(te.codings().next(), None);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Te>(&["deflate;q=high"]), None);
+        assert_eq!(test_decode::<Te>(&["(deflate)"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(Te::trailers());
+        assert_eq!(headers["te"], "trailers");
+
+        let te = test_decode::<Te>(&["Trailers, deflate; q=0.5"]).unwrap();
+        let headers = test_encode(te);
+        assert_eq!(headers["te"], "trailers, deflate; q=0.5");
     }
 }
@@ -0,0 +1,144 @@
+use std::iter::FromIterator;
+
+use util::{FlatCsv, TryFromValues};
+use HeaderValue;
+
+static TIMING_ALLOW_ORIGIN: ::HeaderName = ::HeaderName::from_static("timing-allow-origin");
+
+/// `Timing-Allow-Origin` header, defined in
+/// [Resource Timing](https://www.w3.org/TR/resource-timing-2/#sec-timing-allow-origin)
+///
+/// The `Timing-Allow-Origin` response header lists the origins allowed to
+/// see the detailed timing information of a resource, which is otherwise
+/// hidden from cross-origin pages. The wildcard `*` allows every origin.
+///
+/// Collecting a `TimingAllowOrigin` from `&str`s panics if an origin isn't
+/// a valid `HeaderValue`, or contains a comma.
+///
+/// # ABNF
+///
+/// ```text
+/// Timing-Allow-Origin = 1#( origin-or-null / wildcard )
+/// ```
+///
+/// # Example values
+///
+/// * `*`
+/// * `https://example.com, https://cdn.example`
+///
+/// # Examples
+///
+/// ```
+/// # extern crate headers;
+/// use headers::TimingAllowOrigin;
+///
+/// let tao = vec!["https://example.com"]
+///     .into_iter()
+///     .collect::<TimingAllowOrigin>();
+///
+/// assert!(!tao.is_wildcard());
+/// assert!(TimingAllowOrigin::any().is_wildcard());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingAllowOrigin(FlatCsv);
+
+impl ::Header for TimingAllowOrigin {
+    fn name() -> &'static ::HeaderName {
+        &TIMING_ALLOW_ORIGIN
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        let tao = TimingAllowOrigin(csv);
+        if tao.iter().next().is_none() {
+            return Err(::Error::invalid());
+        }
+        Ok(tao)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
+
+    fn encode_to<W: Extend<HeaderValue>>(&self, values: &mut W) {
+        self.0.encode_to(values);
+    }
+}
+
+impl TimingAllowOrigin {
+    /// Creates `Timing-Allow-Origin: *`, allowing every origin.
+    pub fn any() -> TimingAllowOrigin {
+        TimingAllowOrigin(HeaderValue::from_static("*").into())
+    }
+
+    /// Returns true if every origin is allowed.
+    pub fn is_wildcard(&self) -> bool {
+        self.iter().any(|origin| origin == "*")
+    }
+
+    /// Returns an iterator over the listed origins, including `*`.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter(|origin| !origin.is_empty())
+    }
+}
+
+impl<'a> FromIterator<&'a str> for TimingAllowOrigin {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let values = iter
+            .into_iter()
+            .map(|origin| {
+                assert!(!origin.contains(','), "invalid origin: {:?}", origin);
+                HeaderValue::from_str(origin).expect("invalid origin")
+            })
+            .collect::<Vec<_>>();
+        TimingAllowOrigin(values.iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_merges_lines() {
+        let tao = test_decode::<TimingAllowOrigin>(&[
+            "https://example.com , https://cdn.example",
+            "null",
+        ])
+        .unwrap();
+        assert_eq!(
+            tao.iter().collect::<Vec<_>>(),
+            vec!["https://example.com", "https://cdn.example", "null"]
+        );
+        assert!(!tao.is_wildcard());
+    }
+
+    #[test]
+    fn decode_wildcard() {
+        let tao = test_decode::<TimingAllowOrigin>(&["*"]).unwrap();
+        assert!(tao.is_wildcard());
+        assert_eq!(tao, TimingAllowOrigin::any());
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<TimingAllowOrigin>(&[""]), None);
+        assert_eq!(test_decode::<TimingAllowOrigin>(&[" , "]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let tao = vec!["https://a.example", "https://b.example"]
+            .into_iter()
+            .collect::<TimingAllowOrigin>();
+        let headers = test_encode(tao);
+        assert_eq!(
+            headers["timing-allow-origin"],
+            "https://a.example, https://b.example"
+        );
+    }
+}
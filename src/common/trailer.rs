@@ -0,0 +1,129 @@
+use std::iter::FromIterator;
+
+use util::{FlatCsv, TryFromValues};
+use {HeaderName, HeaderValue};
+
+/// `Trailer` header, defined in
+/// [RFC7230](https://tools.ietf.org/html/rfc7230#section-4.4)
+///
+/// The `Trailer` header field announces which header fields the sender
+/// will send in the trailer of a chunked message, so the recipient can
+/// get ready to process them before the body finishes.
+///
+/// # ABNF
+///
+/// ```text
+/// Trailer = 1#field-name
+/// ```
+///
+/// # Example values
+///
+/// * `Server-Timing`
+/// * `Digest, Expires`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// extern crate http;
+/// use headers::Trailer;
+///
+/// let trailer = vec![http::header::EXPIRES]
+///     .into_iter()
+///     .collect::<Trailer>();
+///
+/// assert!(trailer.contains("Expires"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trailer(Vec<HeaderName>);
+
+impl ::Header for Trailer {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::TRAILER
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        let names = csv
+            .iter()
+            .filter(|name| !name.is_empty())
+            .map(|name| name.parse::<HeaderName>().map_err(|_| ::Error::invalid()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if names.is_empty() {
+            return Err(::Error::invalid());
+        }
+        Ok(Trailer(names))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let csv = self
+            .0
+            .iter()
+            .cloned()
+            .map(HeaderValue::from)
+            .collect::<FlatCsv>();
+        values.extend(::std::iter::once((&csv).into()));
+    }
+}
+
+impl Trailer {
+    /// Returns an iterator over the announced header names, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &HeaderName> {
+        self.0.iter()
+    }
+
+    /// Check if a header name is announced, ignoring case.
+    pub fn contains(&self, name: impl AsRef<str>) -> bool {
+        let name = name.as_ref();
+        self.0
+            .iter()
+            .any(|announced| announced.as_str().eq_ignore_ascii_case(name))
+    }
+}
+
+impl FromIterator<HeaderName> for Trailer {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = HeaderName>,
+    {
+        Trailer(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_merges_lines() {
+        let trailer = test_decode::<Trailer>(&["Server-Timing, Digest", "expires"]).unwrap();
+        assert_eq!(
+            trailer.iter().map(HeaderName::as_str).collect::<Vec<_>>(),
+            vec!["server-timing", "digest", "expires"]
+        );
+        assert!(trailer.contains("digest"));
+        assert!(trailer.contains(::http::header::EXPIRES));
+        assert!(!trailer.contains("etag"));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Trailer>(&[""]), None);
+        assert_eq!(test_decode::<Trailer>(&["server timing"]), None);
+        assert_eq!(test_decode::<Trailer>(&["digest, (expires)"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let trailer = vec![
+            HeaderName::from_static("server-timing"),
+            HeaderName::from_static("digest"),
+        ]
+        .into_iter()
+        .collect::<Trailer>();
+        let headers = test_encode(trailer);
+        assert_eq!(headers["trailer"], "server-timing, digest");
+    }
+}
@@ -38,12 +38,13 @@ use HeaderValue;
 // This currently is just a `HeaderValue`, instead of a `Vec<Encoding>`, since
 // the most common by far instance is simply the string `chunked`. It'd be a
 // waste to need to allocate just for that.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TransferEncoding(FlatCsv);
 
 derive_header! {
     TransferEncoding(_),
-    name: TRANSFER_ENCODING
+    name: TRANSFER_ENCODING,
+    list
 }
 
 impl TransferEncoding {
@@ -53,19 +54,23 @@ impl TransferEncoding {
     }
 
     /// Returns whether this ends with the `chunked` encoding.
+    ///
+    /// Only the last coding decides how the message is framed, so
+    /// `chunked, gzip` is not chunked.
     pub fn is_chunked(&self) -> bool {
-        self.0
-            .value
-            //TODO(perf): use split and trim (not an actual method) on &[u8]
-            .to_str()
-            .map(|s| {
-                s.split(',')
-                    .next_back()
-                    .map(|encoding| encoding.trim() == "chunked")
-                    .expect("split always has at least 1 item")
-            })
+        self.iter()
+            .last()
+            .map(|encoding| encoding.eq_ignore_ascii_case("chunked"))
             .unwrap_or(false)
     }
+
+    /// Returns an iterator over the transfer codings, in the order they
+    /// were applied.
+    ///
+    /// Parameters, such as the `q` in `gzip;q=0.5`, are kept.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter(|encoding| !encoding.is_empty())
+    }
 }
 
 #[cfg(test)]
@@ -101,4 +106,19 @@ mod tests {
         let te = test_decode::<TransferEncoding>(&["gzip", "chunked"]).unwrap();
         assert!(te.is_chunked());
     }
+
+    #[test]
+    fn decode_chunked_ignores_case() {
+        let te = test_decode::<TransferEncoding>(&["gzip, Chunked"]).unwrap();
+        assert!(te.is_chunked());
+    }
+
+    #[test]
+    fn iter_preserves_order() {
+        let te = test_decode::<TransferEncoding>(&["gzip, deflate", "chunked"]).unwrap();
+        assert_eq!(
+            te.iter().collect::<Vec<_>>(),
+            vec!["gzip", "deflate", "chunked"]
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use util::FlatCsv;
 use HeaderValue;
 
 /// `Upgrade` header, defined in [RFC7230](http://tools.ietf.org/html/rfc7230#section-6.7)
@@ -38,18 +39,75 @@ use HeaderValue;
 /// use headers::Upgrade;
 ///
 /// let ws = Upgrade::websocket();
+/// assert!(ws.is_websocket());
 /// ```
 #[derive(Clone, Debug, PartialEq)]
-pub struct Upgrade(HeaderValue);
+pub struct Upgrade(FlatCsv);
 
 derive_header! {
     Upgrade(_),
-    name: UPGRADE
+    name: UPGRADE,
+    list
 }
 
 impl Upgrade {
     /// Constructs an `Upgrade: websocket` header.
     pub fn websocket() -> Upgrade {
-        Upgrade(HeaderValue::from_static("websocket"))
+        Upgrade(HeaderValue::from_static("websocket").into())
+    }
+
+    /// Check if this header offers a protocol, ignoring case.
+    ///
+    /// A protocol without a version, such as `"h2c"`, matches any version
+    /// of it; one with a version, such as `"HTTP/2.0"`, must match exactly.
+    pub fn contains(&self, proto: &str) -> bool {
+        let versioned = proto.contains('/');
+        self.iter().any(|offered| {
+            if versioned {
+                offered.eq_ignore_ascii_case(proto)
+            } else {
+                let name = offered.split('/').next().unwrap_or(offered);
+                name.eq_ignore_ascii_case(proto)
+            }
+        })
+    }
+
+    /// Check if this header offers `websocket`.
+    pub fn is_websocket(&self) -> bool {
+        self.contains("websocket")
+    }
+
+    /// Returns an iterator over the offered protocols, including any
+    /// version suffixes, in order of preference.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter(|proto| !proto.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_merges_lines() {
+        let upgrade = test_decode::<Upgrade>(&["HTTP/2.0, SHTTP/1.3", "IRC/6.9, RTA/x11"]).unwrap();
+        assert_eq!(
+            upgrade.iter().collect::<Vec<_>>(),
+            vec!["HTTP/2.0", "SHTTP/1.3", "IRC/6.9", "RTA/x11"]
+        );
+        assert!(upgrade.contains("http/2.0"));
+        assert!(upgrade.contains("irc"));
+        assert!(!upgrade.contains("HTTP/1.1"));
+        assert!(!upgrade.is_websocket());
+    }
+
+    #[test]
+    fn websocket() {
+        let upgrade = test_decode::<Upgrade>(&["WebSocket"]).unwrap();
+        assert!(upgrade.is_websocket());
+
+        let headers = test_encode(Upgrade::websocket());
+        assert_eq!(headers["upgrade"], "websocket");
     }
 }
@@ -1,7 +1,8 @@
 use std::fmt;
 use std::str::FromStr;
 
-use util::HeaderValueString;
+use super::via::split_comment;
+use util::{quoted_string, HeaderValueString};
 
 /// `User-Agent` header, defined in
 /// [RFC7231](http://tools.ietf.org/html/rfc7231#section-5.5.3)
@@ -29,7 +30,8 @@ use util::HeaderValueString;
 ///
 /// # Notes
 ///
-/// * The parser does not split the value
+/// * The value is kept as a single string, and only split into products
+///   by [`products`](#method.products)
 ///
 /// # Example
 ///
@@ -37,7 +39,12 @@ use util::HeaderValueString;
 /// # extern crate headers;
 /// use headers::UserAgent;
 ///
-/// let ua = UserAgent::from_static("hyper/0.12.2");
+/// let ua = UserAgent::from_static("Mozilla/5.0 (X11; Linux x86_64) Gecko/20100101");
+///
+/// let mozilla = ua.products().next().unwrap();
+/// assert_eq!(mozilla.name(), "Mozilla");
+/// assert_eq!(mozilla.version(), Some("5.0"));
+/// assert_eq!(mozilla.comment(), Some("X11; Linux x86_64"));
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct UserAgent(HeaderValueString);
@@ -57,10 +64,244 @@ impl UserAgent {
         UserAgent(HeaderValueString::from_static(src))
     }
 
+    /// Create a `UserAgent` with a single product, such as `curl/8.4.0`.
+    ///
+    /// More products and comments can be added with
+    /// [`with_product`](#method.with_product) and
+    /// [`with_comment`](#method.with_comment).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` or `version` isn't a `token`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::UserAgent;
+    ///
+    /// let ua = UserAgent::from_product("mybot", Some("0.3"))
+    ///     .with_comment("+https://example.com/bot")
+    ///     .with_product("libcurl", None);
+    ///
+    /// assert_eq!(ua.as_str(), "mybot/0.3 (+https://example.com/bot) libcurl");
+    /// ```
+    pub fn from_product(name: &str, version: Option<&str>) -> UserAgent {
+        UserAgent(build_products("", |s| push_product(s, name, version)))
+    }
+
+    /// Add a product after the existing ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` or `version` isn't a `token`.
+    pub fn with_product(self, name: &str, version: Option<&str>) -> UserAgent {
+        UserAgent(build_products(self.as_str(), |s| {
+            push_product(s, name, version)
+        }))
+    }
+
+    /// Add a comment after the existing products, wrapped in parentheses.
+    ///
+    /// Parentheses and backslashes in `comment` are escaped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `comment` has characters other than visible ASCII, spaces
+    /// and tabs.
+    pub fn with_comment(self, comment: &str) -> UserAgent {
+        UserAgent(build_products(self.as_str(), |s| push_comment(s, comment)))
+    }
+
+    /// Returns a copy with every comment removed, keeping just the
+    /// products.
+    ///
+    /// Nested comments are removed along with the comment around them, and
+    /// an unbalanced comment removes the rest of the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::UserAgent;
+    ///
+    /// let ua = UserAgent::from_static("Mozilla/5.0 (X11; (nested)) Gecko/20100101");
+    /// assert_eq!(ua.without_comments().as_str(), "Mozilla/5.0 Gecko/20100101");
+    /// ```
+    pub fn without_comments(&self) -> UserAgent {
+        UserAgent(strip_comments(self.as_str()))
+    }
+
     /// View this `UserAgent` as a `&str`.
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
+
+    /// Returns an iterator over the product tokens, each with the comments
+    /// that follow it.
+    pub fn products(&self) -> impl Iterator<Item = Product<'_>> {
+        Products(self.as_str())
+    }
+}
+
+/// A product token of a [`UserAgent`](struct.UserAgent.html) or
+/// [`Server`](struct.Server.html) header, such as `libwww/2.17b3`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Product<'a> {
+    name: &'a str,
+    version: Option<&'a str>,
+    comments: Vec<&'a str>,
+}
+
+impl<'a> Product<'a> {
+    /// Returns the product's name.
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Returns the product's version, if any.
+    pub fn version(&self) -> Option<&'a str> {
+        self.version
+    }
+
+    /// Returns the first comment after the product, without its outer
+    /// parentheses.
+    pub fn comment(&self) -> Option<&'a str> {
+        self.comments.first().cloned()
+    }
+
+    /// Returns every comment after the product, without their outer
+    /// parentheses.
+    ///
+    /// Nested comments and escapes are left as they were sent.
+    pub fn comments(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.comments.iter().cloned()
+    }
+}
+
+// Splits `product *( RWS ( product / comment ) )`.
+//
+// Comments before the first product are skipped, and an unbalanced
+// comment ends the iteration.
+pub(super) struct Products<'a>(pub(super) &'a str);
+
+impl<'a> Iterator for Products<'a> {
+    type Item = Product<'a>;
+
+    fn next(&mut self) -> Option<Product<'a>> {
+        let mut rest = skip_rws(self.0);
+        while rest.starts_with('(') {
+            rest = skip_rws(split_comment(rest)?.1);
+        }
+        if rest.is_empty() {
+            self.0 = rest;
+            return None;
+        }
+
+        let end = rest.find(&[' ', '\t', '('][..]).unwrap_or(rest.len());
+        let (token, mut after) = rest.split_at(end);
+        let mut parts = token.splitn(2, '/');
+        let mut product = Product {
+            name: parts.next().unwrap_or(""),
+            version: parts.next(),
+            comments: Vec::new(),
+        };
+
+        loop {
+            let trimmed = skip_rws(after);
+            if !trimmed.starts_with('(') {
+                break;
+            }
+            match split_comment(trimmed) {
+                Some((comment, next)) => {
+                    product.comments.push(&comment[1..comment.len() - 1]);
+                    after = next;
+                }
+                None => {
+                    after = "";
+                    break;
+                }
+            }
+        }
+
+        self.0 = after;
+        Some(product)
+    }
+}
+
+fn skip_rws(s: &str) -> &str {
+    s.trim_start_matches(&[' ', '\t'][..])
+}
+
+// Builds the value of a `UserAgent` or `Server` by appending a product or
+// comment to the existing value.
+pub(super) fn build_products<F>(existing: &str, push: F) -> HeaderValueString
+where
+    F: FnOnce(&mut String),
+{
+    let mut s = existing.to_owned();
+    push(&mut s);
+    HeaderValueString::from_string(s).expect("products and comments are valid header values")
+}
+
+pub(super) fn push_product(s: &mut String, name: &str, version: Option<&str>) {
+    assert!(
+        quoted_string::is_token(name),
+        "invalid product name: {:?}",
+        name
+    );
+    if !s.is_empty() {
+        s.push(' ');
+    }
+    s.push_str(name);
+    if let Some(version) = version {
+        assert!(
+            quoted_string::is_token(version),
+            "invalid product version: {:?}",
+            version
+        );
+        s.push('/');
+        s.push_str(version);
+    }
+}
+
+pub(super) fn push_comment(s: &mut String, comment: &str) {
+    assert!(
+        comment
+            .bytes()
+            .all(|b| b == b'\t' || (b' '..=b'~').contains(&b)),
+        "invalid product comment: {:?}",
+        comment
+    );
+    s.push_str(" (");
+    for c in comment.chars() {
+        if c == '(' || c == ')' || c == '\\' {
+            s.push('\\');
+        }
+        s.push(c);
+    }
+    s.push(')');
+}
+
+// Removes the comments from the value of a `UserAgent` or `Server`, along
+// with the whitespace before each one.
+pub(super) fn strip_comments(value: &str) -> HeaderValueString {
+    let mut s = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('(') {
+        s.push_str(rest[..start].trim_end_matches(&[' ', '\t'][..]));
+        rest = match split_comment(&rest[start..]) {
+            Some((_, after)) => after,
+            None => "",
+        };
+        // Keep products on either side of the comment apart.
+        if !s.is_empty() && !rest.is_empty() && skip_rws(rest).len() == rest.len() {
+            s.push(' ');
+        }
+    }
+    s.push_str(rest);
+    let s = skip_rws(&s);
+    HeaderValueString::from_string(s.to_owned()).expect("a valid value without comments is valid")
 }
 
 error_type!(InvalidUserAgent);
@@ -79,3 +320,93 @@ impl fmt::Display for UserAgent {
         fmt::Display::fmt(&self.0, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn products() {
+        let ua = UserAgent::from_static("CERN-LineMode/2.15 libwww/2.17b3");
+        let products = ua.products().collect::<Vec<_>>();
+        assert_eq!(products.len(), 2);
+        assert_eq!(products[0].name(), "CERN-LineMode");
+        assert_eq!(products[0].version(), Some("2.15"));
+        assert_eq!(products[1].name(), "libwww");
+        assert_eq!(products[1].version(), Some("2.17b3"));
+        assert_eq!(products[1].comment(), None);
+    }
+
+    #[test]
+    fn nested_comments() {
+        let ua = UserAgent::from_static(
+            "Mozilla/5.0 (compatible; (nested \\) paren); bot) (second) Bunnies",
+        );
+        let products = ua.products().collect::<Vec<_>>();
+        assert_eq!(products.len(), 2);
+        assert_eq!(
+            products[0].comments().collect::<Vec<_>>(),
+            vec!["compatible; (nested \\) paren); bot", "second"]
+        );
+        assert_eq!(products[1].name(), "Bunnies");
+        assert_eq!(products[1].version(), None);
+    }
+
+    #[test]
+    fn unbalanced_comment() {
+        let ua = UserAgent::from_static("foo/1 (oops bar/2");
+        let products = ua.products().collect::<Vec<_>>();
+        assert_eq!(products.len(), 1);
+        assert_eq!(products[0].name(), "foo");
+        assert_eq!(products[0].comment(), None);
+    }
+
+    #[test]
+    fn from_product() {
+        let ua = UserAgent::from_product("mybot", Some("0.3"))
+            .with_comment("say (hi) \\o/")
+            .with_product("libcurl", Some("8.4.0"))
+            .with_product("extra", None);
+        assert_eq!(
+            ua.as_str(),
+            "mybot/0.3 (say \\(hi\\) \\\\o/) libcurl/8.4.0 extra"
+        );
+
+        let products = ua.products().collect::<Vec<_>>();
+        assert_eq!(products.len(), 3);
+        assert_eq!(products[0].comment(), Some("say \\(hi\\) \\\\o/"));
+        assert_eq!(products[1].version(), Some("8.4.0"));
+    }
+
+    #[test]
+    fn without_comments() {
+        let ua = UserAgent::from_static(
+            "Mozilla/5.0 (compatible; (nested \\) paren); bot)  (second) Bunnies(x)Gecko",
+        );
+        assert_eq!(ua.without_comments().as_str(), "Mozilla/5.0 Bunnies Gecko");
+
+        let ua = UserAgent::from_static("(leading) foo/1   bar/2");
+        assert_eq!(ua.without_comments().as_str(), "foo/1   bar/2");
+
+        let ua = UserAgent::from_static("foo/1 (oops bar/2");
+        assert_eq!(ua.without_comments().as_str(), "foo/1");
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_product_invalid_name() {
+        let _ = UserAgent::from_product("my bot", None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_product_invalid_version() {
+        let _ = UserAgent::from_product("mybot", Some("1/2"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn with_comment_invalid() {
+        let _ = UserAgent::from_product("mybot", None).with_comment("a\nb");
+    }
+}
@@ -1,6 +1,7 @@
-use util::FlatCsv;
+use std::iter::FromIterator;
 
-use HeaderValue;
+use util::{FlatCsv, TryFromValues};
+use {HeaderName, HeaderValue};
 
 /// `Vary` header, defined in [RFC7231](https://tools.ietf.org/html/rfc7231#section-7.1.4)
 ///
@@ -29,12 +30,48 @@ use HeaderValue;
 ///
 /// let vary = Vary::any();
 /// ```
+///
+/// ```
+/// # extern crate headers;
+/// extern crate http;
+/// use http::header::{ACCEPT_ENCODING, ACCEPT_LANGUAGE};
+/// use headers::Vary;
+///
+/// let vary = vec![ACCEPT_ENCODING, ACCEPT_LANGUAGE]
+///     .into_iter()
+///     .collect::<Vary>();
+///
+/// assert!(vary.contains(&ACCEPT_ENCODING));
+/// assert!(!vary.is_any());
+/// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct Vary(FlatCsv);
 
-derive_header! {
-    Vary(_),
-    name: VARY
+impl ::Header for Vary {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::VARY
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        let valid = csv
+            .iter()
+            .filter(|val| !val.is_empty() && *val != "*")
+            .all(|val| val.parse::<HeaderName>().is_ok());
+        if valid {
+            Ok(normalize(csv))
+        } else {
+            Err(::Error::invalid())
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
+
+    fn encode_to<W: Extend<HeaderValue>>(&self, values: &mut W) {
+        self.0.encode_to(values);
+    }
 }
 
 impl Vary {
@@ -43,7 +80,11 @@ impl Vary {
         Vary(HeaderValue::from_static("*").into())
     }
 
-    /// Check if this includes `*`.
+    /// Check if this is `*`, so every request header may have influenced
+    /// the response.
+    ///
+    /// A `Vary` that lists `*` alongside names is decoded as just `*`, so
+    /// this is all a cache needs to check.
     pub fn is_any(&self) -> bool {
         self.0.iter().any(|val| val == "*")
     }
@@ -52,6 +93,51 @@ impl Vary {
     pub fn iter_strs(&self) -> impl Iterator<Item = &str> {
         self.0.iter()
     }
+
+    /// Returns an iterator over the `HeaderName`s of this `Vary`.
+    ///
+    /// This is empty for `Vary: *`.
+    pub fn iter(&self) -> impl Iterator<Item = HeaderName> + '_ {
+        self.0
+            .iter()
+            .filter(|val| !val.is_empty() && *val != "*")
+            .filter_map(|val| val.parse().ok())
+    }
+
+    /// Check if both list the same header names, ignoring case, order and
+    /// duplicates.
+    ///
+    /// `==` compares the header exactly as it was received.
+    pub fn semantically_eq(&self, other: &Vary) -> bool {
+        self.0.set_eq(&other.0, true)
+    }
+
+    /// Check if this lists a header name, ignoring case.
+    ///
+    /// This is false for a header that's only covered by `*`.
+    pub fn contains(&self, name: &HeaderName) -> bool {
+        self.0
+            .iter()
+            .any(|val| val.eq_ignore_ascii_case(name.as_str()))
+    }
+}
+
+impl FromIterator<HeaderName> for Vary {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = HeaderName>,
+    {
+        normalize(iter.into_iter().map(HeaderValue::from).collect())
+    }
+}
+
+fn normalize(csv: FlatCsv) -> Vary {
+    if csv.iter().any(|val| val == "*") {
+        // `*` already matches every request, so the names are dropped.
+        Vary::any()
+    } else {
+        Vary(csv)
+    }
 }
 
 /*
@@ -75,10 +161,140 @@ test_vary {
 
 #[cfg(test)]
 mod tests {
+    use super::super::{test_decode, test_encode};
     use super::*;
 
     #[test]
     fn any_is_any() {
         assert!(Vary::any().is_any());
     }
+
+    #[test]
+    fn iter_and_contains() {
+        let vary = test_decode::<Vary>(&["Accept-Encoding, cookie"]).unwrap();
+        assert!(!vary.is_any());
+        assert_eq!(
+            vary.iter().collect::<Vec<_>>(),
+            vec![::http::header::ACCEPT_ENCODING, ::http::header::COOKIE]
+        );
+        assert!(vary.contains(&::http::header::ACCEPT_ENCODING));
+        assert!(!vary.contains(&::http::header::ETAG));
+    }
+
+    #[test]
+    fn any_with_names_is_any() {
+        let vary = test_decode::<Vary>(&["Accept-Encoding, *"]).unwrap();
+        assert!(vary.is_any());
+        assert_eq!(vary, Vary::any());
+        assert_eq!(vary.iter().count(), 0);
+        assert!(!vary.contains(&::http::header::ACCEPT_ENCODING));
+
+        let vary = test_decode::<Vary>(&["*", "accept-encoding"]).unwrap();
+        let headers = test_encode(vary);
+        assert_eq!(headers["vary"], "*");
+    }
+
+    #[test]
+    fn from_iter_any_with_names_is_any() {
+        let vary = vec![
+            HeaderName::from_static("*"),
+            ::http::header::ACCEPT_ENCODING,
+        ]
+        .into_iter()
+        .collect::<Vary>();
+        assert_eq!(vary, Vary::any());
+
+        let headers = test_encode(vary);
+        assert_eq!(headers["vary"], "*");
+    }
+
+    #[test]
+    fn semantically_eq() {
+        let a = test_decode::<Vary>(&["Accept-Encoding, origin"]).unwrap();
+        let b = test_decode::<Vary>(&["Origin", "accept-encoding, origin"]).unwrap();
+        assert_ne!(a, b);
+        assert!(a.semantically_eq(&b));
+
+        let c = test_decode::<Vary>(&["origin"]).unwrap();
+        assert!(!a.semantically_eq(&c));
+        assert!(!Vary::any().semantically_eq(&c));
+    }
+
+    #[test]
+    fn decode_invalid_name() {
+        assert_eq!(test_decode::<Vary>(&["accept encoding"]), None);
+        assert_eq!(test_decode::<Vary>(&["accept, (cookie)"]), None);
+    }
+
+    #[test]
+    fn from_iter() {
+        let vary = vec![::http::header::ACCEPT, ::http::header::ORIGIN]
+            .into_iter()
+            .collect::<Vary>();
+        let headers = test_encode(vary);
+        assert_eq!(headers["vary"], "accept, origin");
+    }
+
+    #[test]
+    fn encode_to_keeps_values_separate() {
+        use Header;
+
+        let vary = test_decode::<Vary>(&["accept-encoding", "origin, cookie"]).unwrap();
+        let mut values = Vec::new();
+        vary.encode_to(&mut values);
+        assert_eq!(values, ["accept-encoding", "origin, cookie"]);
+
+        let mut values = Vec::new();
+        vary.encode(&mut values);
+        assert_eq!(values, ["accept-encoding, origin, cookie"]);
+    }
+
+    #[cfg(feature = "nightly")]
+    mod bench_large {
+        use super::*;
+        use Header;
+
+        fn large() -> Vary {
+            (0..64)
+                .map(|i| HeaderName::from_bytes(format!("x-vary-{}", i).as_bytes()).unwrap())
+                .collect()
+        }
+
+        #[bench]
+        fn bench_encode(b: &mut ::test::Bencher) {
+            let vary = large();
+            b.iter(|| {
+                let mut values = Vec::new();
+                vary.encode(&mut values);
+                values
+            });
+        }
+
+        #[bench]
+        fn bench_encode_to(b: &mut ::test::Bencher) {
+            let vary = large();
+            b.iter(|| {
+                let mut values = Vec::new();
+                vary.encode_to(&mut values);
+                values
+            });
+        }
+    }
+
+    bench_header!(
+        bench_one_line,
+        Vary,
+        "accept-encoding, accept-language, origin, cookie, user-agent"
+    );
+    bench_header!(
+        bench_many_lines,
+        Vary,
+        [
+            "accept-encoding",
+            "accept-language",
+            "origin",
+            "cookie",
+            "user-agent"
+        ]
+    );
 }
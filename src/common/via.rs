@@ -0,0 +1,329 @@
+use std::fmt;
+use std::iter::FromIterator;
+
+use util::csv::{decode_list, encode_list, trim_ows};
+use util::quoted_string;
+use HeaderValue;
+
+/// `Via` header, defined in [RFC7230](https://tools.ietf.org/html/rfc7230#section-5.7.1)
+///
+/// The `Via` header field indicates the presence of intermediate protocols
+/// and recipients between the user agent and the server (on requests) or
+/// between the origin server and the client (on responses). Each proxy
+/// appends an entry for the hop it received the message on.
+///
+/// # ABNF
+///
+/// ```text
+/// Via = 1#( received-protocol RWS received-by [ RWS comment ] )
+///
+/// received-protocol = [ protocol-name "/" ] protocol-version
+/// received-by       = ( uri-host [ ":" port ] ) / pseudonym
+/// comment           = "(" *( ctext / quoted-pair / comment ) ")"
+/// ```
+///
+/// # Example values
+///
+/// * `1.1 vegur`
+/// * `1.0 fred, 1.1 p.example.net`
+/// * `HTTP/2.0 edge.example:8443 (cache (nginx))`
+///
+/// # Examples
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{Via, ViaEntry};
+///
+/// let mut via = vec![ViaEntry::new("1.0", "fred")]
+///     .into_iter()
+///     .collect::<Via>();
+/// via.push(ViaEntry::new("HTTP/1.1", "proxy.example:8080").with_comment("squid"));
+///
+/// let hops = via.iter().map(|entry| entry.received_by()).collect::<Vec<_>>();
+/// assert_eq!(hops, vec!["fred", "proxy.example:8080"]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Via(Vec<ViaEntry>);
+
+/// A single hop in a [`Via`](struct.Via.html) header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViaEntry {
+    received_protocol: String,
+    received_by: String,
+    comment: Option<String>,
+}
+
+impl ::Header for Via {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::VIA
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        decode_list(values, parse_entry).map(Via)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_list(&self.0)));
+    }
+}
+
+impl Via {
+    /// Returns an iterator over the hops, starting with the one closest to
+    /// the sender.
+    pub fn iter(&self) -> impl Iterator<Item = &ViaEntry> {
+        self.0.iter()
+    }
+
+    /// Appends a hop, as a proxy forwarding the message would.
+    pub fn push(&mut self, entry: ViaEntry) {
+        self.0.push(entry);
+    }
+
+    /// Returns a copy with the comment of every hop removed, which often
+    /// names the software a proxy runs.
+    pub fn without_comments(&self) -> Via {
+        self.0
+            .iter()
+            .map(|entry| ViaEntry {
+                comment: None,
+                ..entry.clone()
+            })
+            .collect()
+    }
+}
+
+impl FromIterator<ViaEntry> for Via {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = ViaEntry>,
+    {
+        Via(iter.into_iter().collect())
+    }
+}
+
+// ===== impl ViaEntry =====
+
+impl ViaEntry {
+    /// Creates an entry for a hop, such as `ViaEntry::new("1.1", "proxy")`.
+    ///
+    /// The protocol name may be left out when it's `HTTP`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `received_protocol` isn't `[ name "/" ] version` made of
+    /// tokens, or `received_by` is empty or contains whitespace, commas or
+    /// parentheses.
+    pub fn new<P, B>(received_protocol: P, received_by: B) -> ViaEntry
+    where
+        P: Into<String>,
+        B: Into<String>,
+    {
+        let received_protocol = received_protocol.into();
+        let received_by = received_by.into();
+        assert!(
+            is_received_protocol(&received_protocol),
+            "invalid Via received-protocol: {:?}",
+            received_protocol
+        );
+        assert!(
+            is_received_by(&received_by),
+            "invalid Via received-by: {:?}",
+            received_by
+        );
+        ViaEntry {
+            received_protocol,
+            received_by,
+            comment: None,
+        }
+    }
+
+    /// Sets the comment, written inside parentheses.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `comment` contains control characters or unbalanced
+    /// parentheses.
+    pub fn with_comment<C: Into<String>>(mut self, comment: C) -> ViaEntry {
+        let comment = comment.into();
+        let wrapped = format!("({})", comment);
+        assert!(
+            comment.chars().all(|c| c == '\t' || !c.is_control())
+                && split_comment(&wrapped) == Some((&wrapped[..], "")),
+            "invalid Via comment: {:?}",
+            comment
+        );
+        self.comment = Some(comment);
+        self
+    }
+
+    /// Returns the protocol the hop received the message with, such as
+    /// `1.1` or `HTTP/2.0`.
+    pub fn received_protocol(&self) -> &str {
+        &self.received_protocol
+    }
+
+    /// Returns the host, or pseudonym, of the hop.
+    pub fn received_by(&self) -> &str {
+        &self.received_by
+    }
+
+    /// Returns the comment without its outer parentheses, if there is one.
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+}
+
+impl fmt::Display for ViaEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.received_protocol, self.received_by)?;
+        if let Some(ref comment) = self.comment {
+            write!(f, " ({})", comment)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_received_protocol(s: &str) -> bool {
+    let mut parts = s.splitn(2, '/');
+    let first = parts.next().unwrap_or("");
+    match parts.next() {
+        Some(version) => quoted_string::is_token(first) && quoted_string::is_token(version),
+        None => quoted_string::is_token(first),
+    }
+}
+
+fn is_received_by(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_graphic() && b != b',' && b != b'(' && b != b')')
+}
+
+fn split_word(s: &str) -> (&str, &str) {
+    let end = s.find(&[' ', '\t', ','][..]).unwrap_or(s.len());
+    (&s[..end], &s[end..])
+}
+
+// Splits a comment, including its parentheses, from the start of `s`.
+//
+// Nested comments must be balanced, and a backslash escapes the next
+// character.
+pub(super) fn split_comment(s: &str) -> Option<(&str, &str)> {
+    if !s.starts_with('(') {
+        return None;
+    }
+    let mut depth = 0;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(s.split_at(i + 1));
+                }
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+fn parse_entry(s: &str) -> Option<(ViaEntry, &str)> {
+    let (received_protocol, rest) = split_word(s);
+    if !is_received_protocol(received_protocol) {
+        return None;
+    }
+
+    let after = trim_ows(rest);
+    if after.len() == rest.len() {
+        return None;
+    }
+    let (received_by, rest) = split_word(after);
+    if !is_received_by(received_by) {
+        return None;
+    }
+
+    let after = trim_ows(rest);
+    let (comment, rest) = if after.len() < rest.len() && after.starts_with('(') {
+        let (comment, rest) = split_comment(after)?;
+        (Some(comment[1..comment.len() - 1].to_owned()), rest)
+    } else {
+        (None, rest)
+    };
+
+    let entry = ViaEntry {
+        received_protocol: received_protocol.to_owned(),
+        received_by: received_by.to_owned(),
+        comment,
+    };
+    Some((entry, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let via =
+            test_decode::<Via>(&["1.0 fred, 1.1 p.example.net", "HTTP/2.0 edge:8443"]).unwrap();
+        let entries = via.iter().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].received_protocol(), "1.0");
+        assert_eq!(entries[0].received_by(), "fred");
+        assert_eq!(entries[1].received_by(), "p.example.net");
+        assert_eq!(entries[2].received_protocol(), "HTTP/2.0");
+        assert_eq!(entries[2].received_by(), "edge:8443");
+        assert_eq!(entries[2].comment(), None);
+    }
+
+    #[test]
+    fn decode_nested_comments() {
+        let via =
+            test_decode::<Via>(&[r"1.1 cache (squid (v4.1), with \) escaped), 1.1 next"]).unwrap();
+        let entries = via.iter().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].comment(), Some(r"squid (v4.1), with \) escaped"));
+        assert_eq!(entries[1].received_by(), "next");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Via>(&[""]), None);
+        assert_eq!(test_decode::<Via>(&["1.1"]), None);
+        assert_eq!(test_decode::<Via>(&["1.1 fred (unbalanced"]), None);
+        assert_eq!(test_decode::<Via>(&["1.1 fred garbage"]), None);
+        assert_eq!(test_decode::<Via>(&["HTTP/ fred"]), None);
+    }
+
+    #[test]
+    fn push_and_encode() {
+        let mut via = test_decode::<Via>(&["1.0 fred (old (very))"]).unwrap();
+        via.push(ViaEntry::new("1.1", "proxy.example:8080").with_comment("squid"));
+
+        let headers = test_encode(via);
+        assert_eq!(
+            headers["via"],
+            "1.0 fred (old (very)), 1.1 proxy.example:8080 (squid)"
+        );
+    }
+
+    #[test]
+    fn without_comments() {
+        let via = test_decode::<Via>(&["1.0 fred (old (very)), 1.1 next, 2 edge (cdn)"]).unwrap();
+        let headers = test_encode(via.without_comments());
+        assert_eq!(headers["via"], "1.0 fred, 1.1 next, 2 edge");
+    }
+
+    #[test]
+    #[should_panic]
+    fn with_comment_unbalanced() {
+        let _ = ViaEntry::new("1.1", "fred").with_comment("a) (b");
+    }
+}
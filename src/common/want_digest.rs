@@ -0,0 +1,160 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+use util::csv::encode_list;
+use util::quoted_string;
+use util::QualityValue;
+use HeaderValue;
+
+static WANT_DIGEST: ::HeaderName = ::HeaderName::from_static("want-digest");
+
+/// `Want-Digest` header, defined in
+/// [RFC3230](https://tools.ietf.org/html/rfc3230#section-4.3.1)
+///
+/// The `Want-Digest` header lists the digest algorithms the sender would
+/// like to receive in a `Digest` header, each with an optional weight.
+///
+/// Collecting a `WantDigest` from `&str`s panics if an algorithm isn't a
+/// token.
+///
+/// # ABNF
+///
+/// ```text
+/// Want-Digest = "Want-Digest" ":" #(digest-algorithm [ ";" "q" "=" qvalue])
+/// ```
+///
+/// # Example values
+///
+/// * `sha-256`
+/// * `SHA;q=0.3, sha-256;q=1, unixsum;q=0`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::WantDigest;
+///
+/// let want = vec!["sha-256", "sha-512"]
+///     .into_iter()
+///     .collect::<WantDigest>();
+///
+/// assert_eq!(want.prefers("SHA-512"), Some(1.0));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct WantDigest(Vec<QualityValue<Algorithm>>);
+
+#[derive(Clone, Debug, PartialEq)]
+struct Algorithm(String);
+
+impl ::Header for WantDigest {
+    fn name() -> &'static ::HeaderName {
+        &WANT_DIGEST
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let algorithms = QualityValue::decode_list(values)?;
+
+        if algorithms.is_empty() {
+            return Err(::Error::invalid());
+        }
+        Ok(WantDigest(algorithms))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_list(&self.0)));
+    }
+}
+
+impl WantDigest {
+    /// Returns the weight given to an algorithm, ignoring case, if listed.
+    pub fn prefers(&self, algorithm: &str) -> Option<f32> {
+        self.0
+            .iter()
+            .find(|algo| algo.value().0.eq_ignore_ascii_case(algorithm))
+            .map(|algo| algo.quality().as_f32())
+    }
+
+    /// Returns an iterator over the algorithms and their weights, in
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.0
+            .iter()
+            .map(|algo| (algo.value().0.as_str(), algo.quality().as_f32()))
+    }
+}
+
+impl<'a> FromIterator<&'a str> for WantDigest {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        WantDigest(
+            iter.into_iter()
+                .map(|algo| {
+                    assert!(
+                        quoted_string::is_token(algo),
+                        "invalid digest algorithm: {:?}",
+                        algo
+                    );
+                    QualityValue::from(Algorithm(algo.to_owned()))
+                })
+                .collect(),
+        )
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = ::Error;
+
+    fn from_str(s: &str) -> Result<Algorithm, ::Error> {
+        if quoted_string::is_token(s) {
+            Ok(Algorithm(s.to_owned()))
+        } else {
+            Err(::Error::invalid())
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_weights() {
+        let want = test_decode::<WantDigest>(&["SHA;q=0.3, sha-256;q=1", "unixsum;q=0"]).unwrap();
+        assert_eq!(
+            want.iter().collect::<Vec<_>>(),
+            vec![("SHA", 0.3), ("sha-256", 1.0), ("unixsum", 0.0)]
+        );
+        assert_eq!(want.prefers("sha"), Some(0.3));
+        assert_eq!(want.prefers("md5"), None);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<WantDigest>(&[""]), None);
+        assert_eq!(test_decode::<WantDigest>(&["sha 256"]), None);
+        assert_eq!(test_decode::<WantDigest>(&["sha-256;q=2x"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let want = vec!["sha-256", "sha-512"]
+            .into_iter()
+            .collect::<WantDigest>();
+        let headers = test_encode(want);
+        assert_eq!(headers["want-digest"], "sha-256, sha-512");
+
+        let want = test_decode::<WantDigest>(&["sha;q=0.5"]).unwrap();
+        let headers = test_encode(want);
+        assert_eq!(headers["want-digest"], "sha; q=0.5");
+    }
+}
@@ -0,0 +1,301 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::time::SystemTime;
+
+use util::csv::{decode_list, encode_list};
+use util::quoted_string::{self, Quoted};
+use util::HttpDate;
+use HeaderValue;
+
+/// `Warning` header, defined in [RFC7234](https://tools.ietf.org/html/rfc7234#section-5.5)
+///
+/// The `Warning` header field can be be used to carry additional information
+/// about the status or transformation of a message that might not be reflected
+/// in the status code. This header is sometimes used as backwards
+/// compatible way to notify of a deprecated API.
+///
+/// # ABNF
+///
+/// ```text
+/// Warning       = 1#warning-value
+/// warning-value = warn-code SP warn-agent SP warn-text
+///                                       [ SP warn-date ]
+/// warn-code  = 3DIGIT
+/// warn-agent = ( uri-host [ ":" port ] ) / pseudonym
+///                 ; the name or pseudonym of the server adding
+///                 ; the Warning header field, for use in debugging
+///                 ; a single "-" is recommended when agent unknown
+/// warn-text  = quoted-string
+/// warn-date  = DQUOTE HTTP-date DQUOTE
+/// ```
+///
+/// # Example values
+///
+/// * `112 - "network down" "Sat, 25 Aug 2012 23:34:45 GMT"`
+/// * `299 api.hyper.rs:8080 "Deprecated API : use newapi.hyper.rs instead."`
+/// * `110 - "Response is Stale", 112 - "Disconnected Operation"`
+///
+/// # Examples
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{Warning, WarningValue};
+///
+/// let warning = vec![
+///     WarningValue::new(110, "-", "Response is Stale"),
+///     WarningValue::new(112, "-", "Disconnected Operation"),
+/// ]
+/// .into_iter()
+/// .collect::<Warning>();
+///
+/// assert_eq!(warning.iter().next().unwrap().code(), 110);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Warning(Vec<WarningValue>);
+
+/// A single warning in a [`Warning`](struct.Warning.html) header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WarningValue {
+    code: u16,
+    agent: String,
+    text: String,
+    date: Option<HttpDate>,
+}
+
+impl ::Header for Warning {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::WARNING
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        decode_list(values, parse_warning).map(Warning)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_list(&self.0)));
+    }
+}
+
+impl Warning {
+    /// Returns an iterator over the warnings, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &WarningValue> {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<WarningValue> for Warning {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = WarningValue>,
+    {
+        Warning(iter.into_iter().collect())
+    }
+}
+
+// ===== impl WarningValue =====
+
+impl WarningValue {
+    /// Creates a warning without a date.
+    ///
+    /// Use `"-"` as the `agent` when it isn't known.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `code` isn't three digits, `agent` is empty or contains
+    /// whitespace, or `text` contains control characters.
+    pub fn new<A, T>(code: u16, agent: A, text: T) -> WarningValue
+    where
+        A: Into<String>,
+        T: Into<String>,
+    {
+        let agent = agent.into();
+        let text = text.into();
+        assert!((100..1000).contains(&code), "invalid warn-code: {:?}", code);
+        assert!(is_agent(&agent), "invalid warn-agent: {:?}", agent);
+        assert!(
+            text.chars().all(|c| c == '\t' || !c.is_control()),
+            "invalid warn-text: {:?}",
+            text
+        );
+        WarningValue {
+            code,
+            agent,
+            text,
+            date: None,
+        }
+    }
+
+    /// Sets the date the warning was generated.
+    pub fn with_date(mut self, date: SystemTime) -> WarningValue {
+        self.date = Some(date.into());
+        self
+    }
+
+    /// Returns the three digit warn-code, such as `110` for a stale
+    /// response.
+    pub fn code(&self) -> u16 {
+        self.code
+    }
+
+    /// Returns the host or pseudonym of whoever added the warning.
+    pub fn agent(&self) -> &str {
+        &self.agent
+    }
+
+    /// Returns the unquoted warning text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the date the warning was generated, if it was sent.
+    pub fn date(&self) -> Option<SystemTime> {
+        self.date.map(SystemTime::from)
+    }
+}
+
+impl fmt::Display for WarningValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:03} {} {}", self.code, self.agent, Quoted(&self.text))?;
+        if let Some(ref date) = self.date {
+            write!(f, " \"{}\"", date)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_agent(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_graphic() && b != b'"' && b != b',')
+}
+
+// Parses one warning-value from the start of `s`, returning it and the
+// rest of the string.
+fn parse_warning(s: &str) -> Option<(WarningValue, &str)> {
+    let code = s.get(..3)?;
+    if !code.bytes().all(|b| b.is_ascii_digit()) || code.starts_with('0') {
+        return None;
+    }
+    let rest = strip_space(&s[3..])?;
+
+    let end = rest.find(' ')?;
+    let agent = &rest[..end];
+    if !is_agent(agent) {
+        return None;
+    }
+    let rest = strip_space(&rest[end..])?;
+
+    let (text, rest) = quoted_string::parse(rest)?;
+
+    let after = rest.trim_start();
+    let (date, rest) = if after.len() < rest.len() && after.starts_with('"') {
+        let (date, rest) = quoted_string::parse(after)?;
+        (Some(date.parse::<HttpDate>().ok()?), rest)
+    } else {
+        (None, rest)
+    };
+
+    let warning = WarningValue {
+        code: code.parse().ok()?,
+        agent: agent.to_owned(),
+        text,
+        date,
+    };
+    Some((warning, rest))
+}
+
+// Requires at least one space, and skips any extra ones.
+fn strip_space(s: &str) -> Option<&str> {
+    if s.starts_with(' ') {
+        Some(s.trim_start_matches(' '))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let warning =
+            test_decode::<Warning>(&[r#"112 - "network down" "Sat, 25 Aug 2012 23:34:45 GMT""#])
+                .unwrap();
+        let value = warning.iter().next().unwrap();
+        assert_eq!(value.code(), 112);
+        assert_eq!(value.agent(), "-");
+        assert_eq!(value.text(), "network down");
+        assert_eq!(
+            value.date(),
+            Some(
+                "Sat, 25 Aug 2012 23:34:45 GMT"
+                    .parse::<HttpDate>()
+                    .unwrap()
+                    .into()
+            )
+        );
+    }
+
+    #[test]
+    fn decode_multiple_with_commas_in_text() {
+        let warning = test_decode::<Warning>(&[
+            r#"299 api.hyper.rs:8080 "Deprecated, use \"v2\" instead", 110 - "Response is Stale""#,
+            r#"112 - "Disconnected Operation" "Tue, 15 Nov 1994 08:12:31 GMT""#,
+        ])
+        .unwrap();
+        let values = warning.iter().collect::<Vec<_>>();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0].agent(), "api.hyper.rs:8080");
+        assert_eq!(values[0].text(), r#"Deprecated, use "v2" instead"#);
+        assert_eq!(values[0].date(), None);
+        assert_eq!(values[1].code(), 110);
+        assert_eq!(values[2].code(), 112);
+        assert!(values[2].date().is_some());
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Warning>(&[""]), None);
+        assert_eq!(test_decode::<Warning>(&["11 - \"short code\""]), None);
+        assert_eq!(test_decode::<Warning>(&["110 - unquoted"]), None);
+        assert_eq!(test_decode::<Warning>(&["110 \"no agent\""]), None);
+        assert_eq!(
+            test_decode::<Warning>(&["110 - \"bad date\" \"yesterday\""]),
+            None
+        );
+        assert_eq!(test_decode::<Warning>(&["110 - \"a\" 112 - \"b\""]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let date = "Tue, 15 Nov 1994 08:12:31 GMT".parse::<HttpDate>().unwrap();
+        let warning = vec![
+            WarningValue::new(110, "-", "Response is Stale"),
+            WarningValue::new(299, "cache.example", "say \"hi\"").with_date(date.into()),
+        ]
+        .into_iter()
+        .collect::<Warning>();
+
+        let headers = test_encode(warning);
+        assert_eq!(
+            headers["warning"],
+            r#"110 - "Response is Stale", 299 cache.example "say \"hi\"" "Tue, 15 Nov 1994 08:12:31 GMT""#
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let raw = r#"112 - "network down" "Sat, 25 Aug 2012 23:34:45 GMT""#;
+        let warning = test_decode::<Warning>(&[raw]).unwrap();
+        let headers = test_encode(warning);
+        assert_eq!(headers["warning"], raw);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_invalid_code() {
+        let _ = WarningValue::new(99, "-", "nope");
+    }
+}
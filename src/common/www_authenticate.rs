@@ -0,0 +1,455 @@
+use std::fmt;
+use std::iter::FromIterator;
+
+use util::csv::{encode_list, skip_list_separators, split_token};
+use util::quoted_string::{self, Quoted};
+use util::{FlatCsv, TryFromValues};
+use HeaderValue;
+
+/// `WWW-Authenticate` header, defined in
+/// [RFC7235](https://tools.ietf.org/html/rfc7235#section-4.1)
+///
+/// The `WWW-Authenticate` header field indicates the authentication
+/// scheme(s) and parameters applicable to the target resource. It is
+/// sent in `401 Unauthorized` responses, and lists one or more
+/// challenges.
+///
+/// # ABNF
+///
+/// ```text
+/// WWW-Authenticate = 1#challenge
+/// challenge        = auth-scheme [ 1*SP ( token68 / #auth-param ) ]
+/// auth-param       = token BWS "=" BWS ( token / quoted-string )
+/// ```
+///
+/// Challenges may be sent as separate header lines, or joined into one.
+/// Either way they decode to the same list, without splitting on commas
+/// inside quoted parameters, and encoding always joins them into one
+/// line.
+///
+/// # Example values
+///
+/// * `Basic realm="example"`
+/// * `Bearer realm="example", error="invalid_token", error_description="The access token expired"`
+/// * `Newauth realm="apps", type=1, title="Login to \"apps\"", Basic realm="simple"`
+///
+/// # Examples
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{Challenge, WwwAuthenticate};
+///
+/// let www_auth = vec![Challenge::new("Basic").with_param("realm", "example")]
+///     .into_iter()
+///     .collect::<WwwAuthenticate>();
+///
+/// let basic = www_auth.challenges().next().unwrap();
+/// assert_eq!(basic.param("realm"), Some("example"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct WwwAuthenticate(pub(super) Vec<Challenge>);
+
+/// A single authentication challenge, such as `Basic realm="example"`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Challenge {
+    scheme: String,
+    token68: Option<String>,
+    params: Vec<Param>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Param {
+    name: String,
+    value: String,
+    // Remembered so re-encoding keeps the value looking like it did.
+    quoted: bool,
+}
+
+impl ::Header for WwwAuthenticate {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::WWW_AUTHENTICATE
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        let value = csv.to_value();
+        let s = value.to_str().map_err(|_| ::Error::invalid())?;
+        parse_challenges(s)
+            .filter(|challenges| !challenges.is_empty())
+            .map(WwwAuthenticate)
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(encode_challenges(&self.0)));
+    }
+}
+
+pub(super) fn encode_challenges(challenges: &[Challenge]) -> HeaderValue {
+    encode_list(challenges)
+}
+
+impl WwwAuthenticate {
+    /// Returns an iterator over the challenges, in order.
+    pub fn challenges(&self) -> impl Iterator<Item = &Challenge> {
+        self.0.iter()
+    }
+
+    /// Returns the `error` parameter of the first `Bearer` challenge.
+    ///
+    /// This is used by [RFC6750](https://tools.ietf.org/html/rfc6750#section-3.1)
+    /// to explain why a token was rejected, for instance `invalid_token`
+    /// when it has expired.
+    pub fn bearer_error(&self) -> Option<&str> {
+        self.challenges()
+            .find(|challenge| challenge.scheme().eq_ignore_ascii_case("Bearer"))
+            .and_then(|challenge| challenge.param("error"))
+    }
+}
+
+impl FromIterator<Challenge> for WwwAuthenticate {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Challenge>,
+    {
+        WwwAuthenticate(iter.into_iter().collect())
+    }
+}
+
+// ===== impl Challenge =====
+
+impl Challenge {
+    /// Creates a challenge for `scheme`, with no parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scheme` isn't a `token`.
+    pub fn new<S: Into<String>>(scheme: S) -> Challenge {
+        let scheme = scheme.into();
+        assert!(
+            quoted_string::is_token(&scheme),
+            "invalid auth-scheme: {:?}",
+            scheme
+        );
+        Challenge {
+            scheme,
+            token68: None,
+            params: Vec::new(),
+        }
+    }
+
+    /// Adds an auth-param. The value is always sent quoted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a `token`, or `value` contains control
+    /// characters.
+    pub fn with_param<N, V>(mut self, name: N, value: V) -> Challenge
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        let name = name.into();
+        let value = value.into();
+        assert!(
+            quoted_string::is_token(&name),
+            "invalid auth-param name: {:?}",
+            name
+        );
+        assert!(
+            value.chars().all(|c| c == '\t' || !c.is_control()),
+            "invalid auth-param value: {:?}",
+            value
+        );
+        self.params.push(Param {
+            name,
+            value,
+            quoted: true,
+        });
+        self
+    }
+
+    /// Returns the auth-scheme, as it was written.
+    ///
+    /// Schemes are case-insensitive, so compare with
+    /// `eq_ignore_ascii_case`.
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// Returns the unquoted value of the auth-param called `name`.
+    ///
+    /// Parameter names are case-insensitive.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|param| param.name.eq_ignore_ascii_case(name))
+            .map(|param| param.value.as_str())
+    }
+
+    /// Returns an iterator over all auth-params, in order.
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .map(|param| (param.name.as_str(), param.value.as_str()))
+    }
+
+    /// Returns the `token68` sent instead of auth-params, if any.
+    pub fn token68(&self) -> Option<&str> {
+        self.token68.as_deref()
+    }
+}
+
+impl fmt::Display for Challenge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.scheme)?;
+        if let Some(ref token68) = self.token68 {
+            write!(f, " {}", token68)?;
+        }
+        for (i, param) in self.params.iter().enumerate() {
+            f.write_str(if i == 0 { " " } else { ", " })?;
+            if param.quoted {
+                write!(f, "{}={}", param.name, Quoted(&param.value))?;
+            } else {
+                write!(f, "{}={}", param.name, param.value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ===== parsing =====
+
+pub(super) fn parse_challenges(s: &str) -> Option<Vec<Challenge>> {
+    let mut challenges: Vec<Challenge> = Vec::new();
+    let mut rest = skip_list_separators(s);
+
+    while !rest.is_empty() {
+        let (token, after) = split_token(rest)?;
+        let after_ws = after.trim_start();
+
+        if after_ws.starts_with('=') && !challenges.is_empty() && !is_token68_end(after_ws) {
+            // `name=value`, another auth-param of the current challenge
+            let (param, after) = parse_param_value(token, after_ws[1..].trim_start())?;
+            challenges
+                .last_mut()
+                .expect("checked not empty")
+                .params
+                .push(param);
+            rest = after;
+        } else {
+            // a new challenge, starting with its auth-scheme
+            let mut challenge = Challenge {
+                scheme: token.to_owned(),
+                token68: None,
+                params: Vec::new(),
+            };
+            rest = after_ws;
+            if !rest.is_empty() && !rest.starts_with(',') {
+                if after.len() == after_ws.len() {
+                    // the scheme must be followed by a space
+                    return None;
+                }
+                let (word, after) = split_token68(rest)?;
+                let after_ws = after.trim_start();
+                if after_ws.is_empty() || after_ws.starts_with(',') {
+                    challenge.token68 = Some(word.to_owned());
+                    rest = after_ws;
+                } else {
+                    let (name, after) = split_token(rest)?;
+                    let after = after.trim_start();
+                    if !after.starts_with('=') {
+                        return None;
+                    }
+                    let (param, after) = parse_param_value(name, after[1..].trim_start())?;
+                    challenge.params.push(param);
+                    rest = after;
+                }
+            }
+            challenges.push(challenge);
+        }
+
+        rest = rest.trim_start();
+        if !rest.is_empty() && !rest.starts_with(',') {
+            return None;
+        }
+        rest = skip_list_separators(rest);
+    }
+
+    Some(challenges)
+}
+
+// token68 = 1*( ALPHA / DIGIT / "-" / "." / "_" / "~" / "+" / "/" ) *"="
+fn split_token68(s: &str) -> Option<(&str, &str)> {
+    let is_char = |b: u8| b.is_ascii_alphanumeric() || b"-._~+/".contains(&b);
+    let mut end = s.bytes().position(|b| !is_char(b)).unwrap_or(s.len());
+    if end == 0 {
+        return None;
+    }
+    end += s[end..].bytes().take_while(|&b| b == b'=').count();
+    Some((&s[..end], &s[end..]))
+}
+
+// Whether `s`, starting with `=`, is just the padding at the end of a token68.
+fn is_token68_end(s: &str) -> bool {
+    let after = s.trim_start_matches('=').trim_start();
+    after.is_empty() || after.starts_with(',')
+}
+
+fn parse_param_value<'a>(name: &str, s: &'a str) -> Option<(Param, &'a str)> {
+    if s.starts_with('"') {
+        let (value, rest) = quoted_string::parse(s)?;
+        Some((
+            Param {
+                name: name.to_owned(),
+                value,
+                quoted: true,
+            },
+            rest,
+        ))
+    } else {
+        let (value, rest) = split_token(s)?;
+        Some((
+            Param {
+                name: name.to_owned(),
+                value: value.to_owned(),
+                quoted: false,
+            },
+            rest,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_single() {
+        let www_auth = test_decode::<WwwAuthenticate>(&[r#"Basic realm="example""#]).unwrap();
+        let challenges = www_auth.challenges().collect::<Vec<_>>();
+        assert_eq!(challenges.len(), 1);
+        assert_eq!(challenges[0].scheme(), "Basic");
+        assert_eq!(challenges[0].param("Realm"), Some("example"));
+    }
+
+    #[test]
+    fn decode_multiple_with_quoted_commas() {
+        let www_auth = test_decode::<WwwAuthenticate>(&[
+            r#"Newauth realm="apps, and more", type=1, title="Login to \"apps\"", Basic realm="simple""#,
+        ])
+        .unwrap();
+        let challenges = www_auth.challenges().collect::<Vec<_>>();
+        assert_eq!(challenges.len(), 2);
+
+        assert_eq!(challenges[0].scheme(), "Newauth");
+        assert_eq!(
+            challenges[0].params().collect::<Vec<_>>(),
+            vec![
+                ("realm", "apps, and more"),
+                ("type", "1"),
+                ("title", "Login to \"apps\""),
+            ]
+        );
+        assert_eq!(challenges[1].scheme(), "Basic");
+        assert_eq!(challenges[1].param("realm"), Some("simple"));
+    }
+
+    #[test]
+    fn decode_separate_lines_same_as_joined() {
+        let joined = test_decode::<WwwAuthenticate>(&[
+            r#"Negotiate, Basic realm="staff, contractors", charset="UTF-8""#,
+        ])
+        .unwrap();
+        let lines = test_decode::<WwwAuthenticate>(&[
+            "Negotiate",
+            r#"Basic realm="staff, contractors", charset="UTF-8""#,
+        ])
+        .unwrap();
+        assert_eq!(joined, lines);
+
+        let challenges = lines.challenges().collect::<Vec<_>>();
+        assert_eq!(challenges.len(), 2);
+        assert_eq!(challenges[0].scheme(), "Negotiate");
+        assert_eq!(challenges[1].param("realm"), Some("staff, contractors"));
+        assert_eq!(challenges[1].param("charset"), Some("UTF-8"));
+
+        // empty list elements around the lines are skipped
+        let sloppy = test_decode::<WwwAuthenticate>(&[
+            "Negotiate, ",
+            r#", Basic realm="staff, contractors", charset="UTF-8""#,
+        ])
+        .unwrap();
+        assert_eq!(sloppy, joined);
+
+        let headers = test_encode(lines);
+        assert_eq!(
+            headers["www-authenticate"],
+            r#"Negotiate, Basic realm="staff, contractors", charset="UTF-8""#
+        );
+    }
+
+    #[test]
+    fn decode_token68_and_bare_schemes() {
+        let www_auth =
+            test_decode::<WwwAuthenticate>(&["Negotiate", "Custom abc+/def==, Other"]).unwrap();
+        let challenges = www_auth.challenges().collect::<Vec<_>>();
+        assert_eq!(challenges.len(), 3);
+        assert_eq!(challenges[0].scheme(), "Negotiate");
+        assert_eq!(challenges[0].token68(), None);
+        assert_eq!(challenges[1].token68(), Some("abc+/def=="));
+        assert_eq!(challenges[2].scheme(), "Other");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<WwwAuthenticate>(&[""]), None);
+        assert_eq!(test_decode::<WwwAuthenticate>(&["=foo"]), None);
+        assert_eq!(
+            test_decode::<WwwAuthenticate>(&[r#"Basic realm="unterminated"#]),
+            None
+        );
+        assert_eq!(
+            test_decode::<WwwAuthenticate>(&[r#"Basic realm="a" junk"#]),
+            None
+        );
+    }
+
+    #[test]
+    fn bearer_error() {
+        let www_auth = test_decode::<WwwAuthenticate>(&[
+            r#"Basic realm="api", Bearer realm="api", error="invalid_token", error_description="expired, sorry""#,
+        ])
+        .unwrap();
+        assert_eq!(www_auth.bearer_error(), Some("invalid_token"));
+
+        let www_auth = test_decode::<WwwAuthenticate>(&[r#"Bearer realm="api""#]).unwrap();
+        assert_eq!(www_auth.bearer_error(), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let raw = r#"Newauth realm="apps", type=1, Custom abc==, Basic realm="simple""#;
+        let www_auth = test_decode::<WwwAuthenticate>(&[raw]).unwrap();
+        let headers = test_encode(www_auth);
+        assert_eq!(headers["www-authenticate"], raw);
+    }
+
+    #[test]
+    fn encode_builder() {
+        let www_auth = vec![
+            Challenge::new("Bearer")
+                .with_param("realm", "api")
+                .with_param("error", "invalid_token"),
+            Challenge::new("Basic"),
+        ]
+        .into_iter()
+        .collect::<WwwAuthenticate>();
+        let headers = test_encode(www_auth);
+        assert_eq!(
+            headers["www-authenticate"],
+            r#"Bearer realm="api", error="invalid_token", Basic"#
+        );
+    }
+}
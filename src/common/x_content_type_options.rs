@@ -0,0 +1,99 @@
+use util::IterExt;
+use HeaderValue;
+
+/// `X-Content-Type-Options` header, defined in the
+/// [Fetch standard](https://fetch.spec.whatwg.org/#x-content-type-options-header)
+///
+/// The `X-Content-Type-Options` response header, with its only value
+/// `nosniff`, tells the user agent not to guess a response's media type,
+/// and to block scripts and stylesheets whose `Content-Type` doesn't
+/// match.
+///
+/// # ABNF
+///
+/// ```text
+/// X-Content-Type-Options = "nosniff"
+/// ```
+///
+/// # Example values
+///
+/// * `nosniff`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{HeaderMap, HeaderMapExt, XContentTypeOptions};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.typed_insert(XContentTypeOptions::nosniff());
+///
+/// assert_eq!(headers["x-content-type-options"], "nosniff");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct XContentTypeOptions(());
+
+impl ::Header for XContentTypeOptions {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::X_CONTENT_TYPE_OPTIONS
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        values
+            .just_one()
+            .and_then(|value| value.to_str().ok())
+            .filter(|s| s.trim().eq_ignore_ascii_case("nosniff"))
+            .map(|_| XContentTypeOptions::nosniff())
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(HeaderValue::from_static("nosniff")));
+    }
+}
+
+impl XContentTypeOptions {
+    /// Creates `X-Content-Type-Options: nosniff`.
+    pub fn nosniff() -> XContentTypeOptions {
+        XContentTypeOptions(())
+    }
+
+    /// Returns true if this is `nosniff`.
+    ///
+    /// That is the only value that decodes, so this always returns true.
+    pub fn is_nosniff(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let opts = test_decode::<XContentTypeOptions>(&["NoSniff"]);
+        assert_eq!(opts, Some(XContentTypeOptions::nosniff()));
+        assert!(opts.unwrap().is_nosniff());
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<XContentTypeOptions>(&["sniff"]), None);
+        assert_eq!(
+            test_decode::<XContentTypeOptions>(&["nosniff, nosniff"]),
+            None
+        );
+        assert_eq!(
+            test_decode::<XContentTypeOptions>(&["nosniff", "nosniff"]),
+            None
+        );
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(XContentTypeOptions::nosniff());
+        assert_eq!(headers["x-content-type-options"], "nosniff");
+    }
+}
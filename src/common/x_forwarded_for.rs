@@ -0,0 +1,153 @@
+use std::iter::FromIterator;
+use std::net::{IpAddr, SocketAddr};
+
+use util::{FlatCsv, TryFromValues};
+use {HeaderName, HeaderValue};
+
+static X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+
+/// `X-Forwarded-For` header, a de-facto standard for identifying the
+/// originating IP address of a client connecting through proxies.
+///
+/// Each proxy appends the address it received the request from, so the
+/// leftmost entry is the original client. Only trust entries that were
+/// added by proxies you control.
+///
+/// See [`Forwarded`](struct.Forwarded.html) for the standardized
+/// replacement.
+///
+/// # ABNF
+///
+/// ```text
+/// X-Forwarded-For = 1#( IP-address / "unknown" )
+/// ```
+///
+/// # Example values
+///
+/// * `203.0.113.195`
+/// * `203.0.113.195, 2001:db8:85a3:8d3:1319:8a2e:370:7348`
+/// * `unknown, 198.51.100.17`
+///
+/// # Examples
+///
+/// ```
+/// # extern crate headers;
+/// use std::net::IpAddr;
+/// use headers::XForwardedFor;
+///
+/// let peer: IpAddr = "198.51.100.17".parse().unwrap();
+/// let xff = vec![peer].into_iter().collect::<XForwardedFor>();
+///
+/// assert_eq!(xff.client_ip(), Some(peer));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct XForwardedFor(FlatCsv);
+
+impl ::Header for XForwardedFor {
+    fn name() -> &'static HeaderName {
+        &X_FORWARDED_FOR
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        TryFromValues::try_from_values(values).map(XForwardedFor)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
+
+    fn encode_to<W: Extend<HeaderValue>>(&self, values: &mut W) {
+        self.0.encode_to(values);
+    }
+}
+
+impl XForwardedFor {
+    /// Returns an iterator over the addresses, from the client to the
+    /// closest proxy.
+    ///
+    /// Entries that aren't IP addresses, like `unknown`, are skipped. An
+    /// address with a port, such as `[::1]:8080`, yields just the address.
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = IpAddr> + 'a {
+        self.0.iter().filter_map(|s| {
+            s.parse::<IpAddr>()
+                .or_else(|_| s.parse::<SocketAddr>().map(|addr| addr.ip()))
+                .ok()
+        })
+    }
+
+    /// Returns the leftmost address, which should be the originating client.
+    pub fn client_ip(&self) -> Option<IpAddr> {
+        self.iter().next()
+    }
+}
+
+impl FromIterator<IpAddr> for XForwardedFor {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = IpAddr>,
+    {
+        let flat = iter
+            .into_iter()
+            .map(|ip| {
+                ip.to_string()
+                    .parse::<HeaderValue>()
+                    .expect("IpAddr is a valid HeaderValue")
+            })
+            .collect::<Vec<_>>();
+        XForwardedFor(flat.iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn decode() {
+        let xff = test_decode::<XForwardedFor>(&[
+            " 203.0.113.195 ,2001:db8:85a3:8d3:1319:8a2e:370:7348",
+            "198.51.100.17",
+        ])
+        .unwrap();
+        assert_eq!(
+            xff.iter().collect::<Vec<_>>(),
+            vec![
+                ip("203.0.113.195"),
+                ip("2001:db8:85a3:8d3:1319:8a2e:370:7348"),
+                ip("198.51.100.17"),
+            ]
+        );
+        assert_eq!(xff.client_ip(), Some(ip("203.0.113.195")));
+    }
+
+    #[test]
+    fn skips_invalid_entries() {
+        let xff =
+            test_decode::<XForwardedFor>(&["unknown, _hidden, 192.0.2.1:8080, [::1]:80"]).unwrap();
+        assert_eq!(
+            xff.iter().collect::<Vec<_>>(),
+            vec![ip("192.0.2.1"), ip("::1")]
+        );
+        assert_eq!(xff.client_ip(), Some(ip("192.0.2.1")));
+
+        let xff = test_decode::<XForwardedFor>(&["unknown"]).unwrap();
+        assert_eq!(xff.client_ip(), None);
+    }
+
+    #[test]
+    fn append_peer() {
+        let xff = test_decode::<XForwardedFor>(&["203.0.113.195"]).unwrap();
+        let xff = xff
+            .iter()
+            .chain(Some(ip("2001:db8::1")))
+            .collect::<XForwardedFor>();
+
+        let headers = test_encode(xff);
+        assert_eq!(headers["x-forwarded-for"], "203.0.113.195, 2001:db8::1");
+    }
+}
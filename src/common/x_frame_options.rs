@@ -0,0 +1,77 @@
+token_enum_header! {
+    /// `X-Frame-Options` header, defined in
+    /// [RFC7034](https://tools.ietf.org/html/rfc7034#section-2)
+    ///
+    /// The `X-Frame-Options` response header tells the user agent whether
+    /// the page may be displayed in a frame, to protect against
+    /// clickjacking. The `frame-ancestors` directive of a Content Security
+    /// Policy supersedes it, but older user agents only understand this
+    /// header.
+    ///
+    /// The obsolete `ALLOW-FROM` option isn't supported, and fails to
+    /// decode.
+    ///
+    /// # ABNF
+    ///
+    /// ```text
+    /// X-Frame-Options = "DENY" / "SAMEORIGIN"
+    /// ```
+    ///
+    /// # Example values
+    ///
+    /// * `DENY`
+    /// * `SAMEORIGIN`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::{HeaderMap, HeaderMapExt, XFrameOptions};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.typed_insert(XFrameOptions::SameOrigin);
+    ///
+    /// assert_eq!(headers["x-frame-options"], "SAMEORIGIN");
+    /// ```
+    pub enum XFrameOptions {
+        /// `DENY`, the page can't be framed at all.
+        Deny => "DENY",
+        /// `SAMEORIGIN`, the page can only be framed by its own origin.
+        SameOrigin => "SAMEORIGIN",
+    }
+
+    name: &::http::header::X_FRAME_OPTIONS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            test_decode::<XFrameOptions>(&["deny"]),
+            Some(XFrameOptions::Deny)
+        );
+        assert_eq!(
+            test_decode::<XFrameOptions>(&["SameOrigin"]),
+            Some(XFrameOptions::SameOrigin)
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(
+            test_decode::<XFrameOptions>(&["ALLOW-FROM https://example.com"]),
+            None
+        );
+        assert_eq!(test_decode::<XFrameOptions>(&["SAME-ORIGIN"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(XFrameOptions::Deny);
+        assert_eq!(headers["x-frame-options"], "DENY");
+    }
+}
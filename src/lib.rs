@@ -71,18 +71,36 @@
 //!     }
 //! }
 //! ```
+//!
+//! ## Opaque string headers
+//!
+//! A header that's just a string, such as `X-Request-Id`, can be defined
+//! in one line with the [`define_string_header!`](macro.define_string_header.html)
+//! macro.
 
 extern crate base64;
 #[macro_use]
 extern crate bitflags;
 extern crate bytes;
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "getrandom")]
+extern crate getrandom;
 extern crate headers_core;
 extern crate http;
 extern crate httpdate;
 extern crate mime;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(any(feature = "serde_json", all(test, feature = "serde")))]
+extern crate serde_json;
 extern crate sha1;
 #[cfg(all(test, feature = "nightly"))]
 extern crate test;
+#[cfg(feature = "time")]
+extern crate time;
+#[cfg(feature = "url")]
+extern crate url;
 
 pub use headers_core::{Error, Header};
 
@@ -94,8 +112,13 @@ pub use http::header::{HeaderName, HeaderValue};
 
 #[macro_use]
 mod util;
+#[macro_use]
+mod macros;
 mod common;
 mod map_ext;
+#[cfg(feature = "serde")]
+mod serde_impls;
 
 pub use self::common::*;
-pub use self::map_ext::HeaderMapExt;
+pub use self::map_ext::{HeaderMapExt, TypedEntry};
+pub use self::util::{CsvList, Quality, QualityValue};
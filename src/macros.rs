@@ -0,0 +1,168 @@
+/// Defines a header whose value is an opaque string, such as
+/// `X-Powered-By` or `X-Request-Id`.
+///
+/// `define_string_header!(Name, "header-name")` generates a `pub struct
+/// Name` wrapping the `HeaderValue`, along with:
+///
+/// - the `Header` impl, which decodes exactly one field value,
+/// - `Name::from_static(&'static str)`, which panics if the string isn't
+///   a valid value,
+/// - `FromStr` and `TryFrom<&str>`, which return an error instead,
+/// - `as_str()`, and `Display`.
+///
+/// A valid value is visible ASCII, spaces and tabs. Attributes, such as
+/// doc comments, can be placed before the name.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::HeaderMapExt;
+///
+/// define_string_header! {
+///     /// The `X-Powered-By` header.
+///     XPoweredBy, "x-powered-by"
+/// }
+///
+/// # fn main() {
+/// let mut map = http::HeaderMap::new();
+/// map.typed_insert(XPoweredBy::from_static("headers"));
+///
+/// let powered_by = map.typed_get::<XPoweredBy>().unwrap();
+/// assert_eq!(powered_by.as_str(), "headers");
+///
+/// assert!("bad\nvalue".parse::<XPoweredBy>().is_err());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! define_string_header {
+    ($(#[$attr:meta])* $name:ident, $header:expr) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub struct $name($crate::HeaderValue);
+
+        impl $name {
+            /// Creates the header from a static string.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the string isn't a valid header value.
+            pub fn from_static(value: &'static str) -> $name {
+                $name($crate::HeaderValue::from_static(value))
+            }
+
+            /// Returns the value as a string.
+            pub fn as_str(&self) -> &str {
+                self.0
+                    .to_str()
+                    .expect("checked to be visible ASCII when constructed")
+            }
+        }
+
+        impl $crate::Header for $name {
+            fn name() -> &'static $crate::HeaderName {
+                static NAME: $crate::HeaderName = $crate::HeaderName::from_static($header);
+                &NAME
+            }
+
+            fn decode<'i, I>(values: &mut I) -> Result<Self, $crate::Error>
+            where
+                I: Iterator<Item = &'i $crate::HeaderValue>,
+            {
+                match (values.next(), values.next()) {
+                    (Some(value), None) if value.to_str().is_ok() => Ok($name(value.clone())),
+                    _ => Err($crate::Error::invalid()),
+                }
+            }
+
+            fn encode<E: Extend<$crate::HeaderValue>>(&self, values: &mut E) {
+                values.extend(::std::iter::once(self.0.clone()));
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = $crate::Error;
+
+            fn from_str(s: &str) -> Result<$name, $crate::Error> {
+                // `HeaderValue` also allows `obs-text`, which `as_str` can't
+                // return.
+                if !s.bytes().all(|b| b == b'\t' || (b' '..=b'~').contains(&b)) {
+                    return Err($crate::Error::invalid());
+                }
+                $crate::HeaderValue::from_str(s)
+                    .map($name)
+                    .map_err(|_| $crate::Error::invalid())
+            }
+        }
+
+        impl<'a> ::std::convert::TryFrom<&'a str> for $name {
+            type Error = $crate::Error;
+
+            fn try_from(s: &'a str) -> Result<$name, $crate::Error> {
+                s.parse()
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::super::Header;
+    use HeaderValue;
+
+    define_string_header! {
+        /// A header for the tests.
+        XRequestId, "x-request-id"
+    }
+
+    #[test]
+    fn round_trip() {
+        let id = XRequestId::try_from("f058ebd6-02f7-4d3f-942e-904344e8cde5").unwrap();
+        assert_eq!(XRequestId::name(), "x-request-id");
+        assert_eq!(id.to_string(), "f058ebd6-02f7-4d3f-942e-904344e8cde5");
+
+        let mut values = Vec::new();
+        id.encode(&mut values);
+        let decoded = XRequestId::decode(&mut values.iter()).unwrap();
+        assert_eq!(decoded, id);
+        assert_eq!(XRequestId::from_static("abc").as_str(), "abc");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        let one = HeaderValue::from_static("a");
+        let obs_text = HeaderValue::from_bytes(b"caf\xe9").unwrap();
+
+        assert!(XRequestId::decode(&mut ::std::iter::empty()).is_err());
+        assert!(XRequestId::decode(&mut vec![&one, &one].into_iter()).is_err());
+        assert!(XRequestId::decode(&mut Some(&obs_text).into_iter()).is_err());
+    }
+
+    #[test]
+    fn from_str_invalid() {
+        assert!("a\r\nb".parse::<XRequestId>().is_err());
+        assert!("caf\u{e9}".parse::<XRequestId>().is_err());
+        assert!("with space\tand tab".parse::<XRequestId>().is_ok());
+    }
+
+    #[test]
+    fn try_from_invalid() {
+        assert!(XRequestId::try_from("a\nb").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_static_invalid_panics() {
+        let _ = XRequestId::from_static("a\nb");
+    }
+}
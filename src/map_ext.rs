@@ -1,3 +1,5 @@
+use std::marker::PhantomData;
+
 use super::{Error, Header, HeaderValue};
 use http;
 
@@ -14,9 +16,133 @@ pub trait HeaderMapExt: self::sealed::Sealed {
         H: Header;
 
     /// Tries to find the header by name, and then decode it into `H`.
+    ///
+    /// If decoding fails, the returned `Error` includes the header name,
+    /// and the value that was rejected when it can be narrowed down to one.
     fn typed_try_get<H>(&self) -> Result<Option<H>, Error>
     where
         H: Header;
+
+    /// Decodes every occurrence of the header into `H`.
+    ///
+    /// For a header whose fields are separate values, like `Set-Cookie`,
+    /// each field is decoded on its own, and fields that fail to decode are
+    /// skipped. Any other header is decoded from all of its fields at once,
+    /// just like `typed_get`, so at most one value is returned.
+    fn typed_get_all<H>(&self) -> Vec<H>
+    where
+        H: Header;
+
+    /// Removes the header from this `HeaderMap`, returning it decoded
+    /// into `H`.
+    ///
+    /// All fields of the header are removed, even if they can't be decoded.
+    fn typed_remove<H>(&mut self) -> Option<H>
+    where
+        H: Header;
+
+    /// Inserts the typed `Header` only if the header isn't already in this
+    /// `HeaderMap`, returning whether it was inserted.
+    ///
+    /// An existing header is kept even if it can't be decoded into `H`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// # extern crate http;
+    /// use headers::{HeaderMapExt, XFrameOptions};
+    ///
+    /// let mut map = http::HeaderMap::new();
+    /// map.typed_insert(XFrameOptions::SameOrigin);
+    ///
+    /// assert!(!map.typed_insert_if_absent(XFrameOptions::Deny));
+    /// assert_eq!(map["x-frame-options"], "SAMEORIGIN");
+    /// ```
+    fn typed_insert_if_absent<H>(&mut self, header: H) -> bool
+    where
+        H: Header;
+
+    /// Gets the entry for the header, to inspect it and then insert into it
+    /// with a single lookup.
+    fn typed_entry<H>(&mut self) -> TypedEntry<'_, H>
+    where
+        H: Header;
+}
+
+/// The entry for a typed header in a `HeaderMap`, returned by
+/// [`HeaderMapExt::typed_entry`](trait.HeaderMapExt.html#tymethod.typed_entry).
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{ContentType, HeaderMapExt};
+///
+/// let mut map = http::HeaderMap::new();
+///
+/// let entry = map.typed_entry::<ContentType>();
+/// match entry.get() {
+///     Some(ref ct) if *ct == ContentType::json() => (),
+///     _ => entry.insert(ContentType::text_utf8()),
+/// }
+///
+/// assert_eq!(map.typed_get(), Some(ContentType::text_utf8()));
+/// ```
+#[derive(Debug)]
+pub struct TypedEntry<'a, H> {
+    entry: http::header::Entry<'a, HeaderValue>,
+    _header: PhantomData<fn() -> H>,
+}
+
+impl<'a, H: Header> TypedEntry<'a, H> {
+    /// Check if the header is already in the map.
+    pub fn is_occupied(&self) -> bool {
+        match self.entry {
+            http::header::Entry::Occupied(_) => true,
+            http::header::Entry::Vacant(_) => false,
+        }
+    }
+
+    /// Decodes the header that's already in the map, just like
+    /// `typed_get`.
+    pub fn get(&self) -> Option<H> {
+        match self.entry {
+            http::header::Entry::Occupied(ref entry) => H::decode(&mut entry.iter()).ok(),
+            http::header::Entry::Vacant(_) => None,
+        }
+    }
+
+    /// Inserts the header, replacing every existing field of it.
+    pub fn insert(self, header: H) {
+        let mut values = ToValues {
+            state: State::First(self.entry),
+        };
+        header.encode(&mut values);
+    }
+
+    /// Inserts the header if it isn't already in the map, returning
+    /// whether it was inserted.
+    pub fn or_insert(self, header: H) -> bool {
+        self.or_insert_with(|| header)
+    }
+
+    /// Inserts the header made by `default` if it isn't already in the
+    /// map, returning whether it was inserted.
+    ///
+    /// `default` is only called if the header is inserted.
+    pub fn or_insert_with<F>(self, default: F) -> bool
+    where
+        F: FnOnce() -> H,
+    {
+        if self.is_occupied() {
+            false
+        } else {
+            self.insert(default());
+            true
+        }
+    }
 }
 
 impl HeaderMapExt for http::HeaderMap {
@@ -46,11 +172,78 @@ impl HeaderMapExt for http::HeaderMap {
         if values.size_hint() == (0, Some(0)) {
             Ok(None)
         } else {
-            H::decode(&mut values).map(Some)
+            H::decode(&mut values)
+                .map(Some)
+                .map_err(|err| with_context::<H>(err, self))
+        }
+    }
+
+    fn typed_get_all<H>(&self) -> Vec<H>
+    where
+        H: Header,
+    {
+        if H::separate_fields() {
+            self.get_all(H::name())
+                .iter()
+                .filter_map(|value| H::decode(&mut ::std::iter::once(value)).ok())
+                .collect()
+        } else {
+            HeaderMapExt::typed_get(self).into_iter().collect()
+        }
+    }
+
+    fn typed_remove<H>(&mut self) -> Option<H>
+    where
+        H: Header,
+    {
+        let header = HeaderMapExt::typed_get(self);
+        self.remove(H::name());
+        header
+    }
+
+    fn typed_insert_if_absent<H>(&mut self, header: H) -> bool
+    where
+        H: Header,
+    {
+        HeaderMapExt::typed_entry(self).or_insert(header)
+    }
+
+    fn typed_entry<H>(&mut self) -> TypedEntry<'_, H>
+    where
+        H: Header,
+    {
+        TypedEntry {
+            entry: self.entry(H::name()),
+            _header: PhantomData,
         }
     }
 }
 
+// Fills in which header, and which of its values, failed to decode.
+fn with_context<H: Header>(mut err: Error, map: &http::HeaderMap) -> Error {
+    if err.name().is_none() {
+        err = err.with_name(H::name().clone());
+    }
+    if err.value().is_none() {
+        let values = map.get_all(H::name());
+        let mut iter = values.iter();
+        let rejected = match (iter.next(), iter.next()) {
+            (Some(value), None) => Some(value),
+            _ => values
+                .iter()
+                .find(|value| H::decode(&mut ::std::iter::once(*value)).is_err()),
+        };
+        match rejected {
+            Some(value) => err = err.with_value(value.clone()),
+            None if err.message().is_none() => {
+                err = err.with_message("header can't have multiple values")
+            }
+            None => (),
+        }
+    }
+    err
+}
+
 struct ToValues<'a> {
     state: State<'a>,
 }
@@ -86,3 +279,117 @@ mod sealed {
     pub trait Sealed {}
     impl Sealed for ::http::HeaderMap {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {ContentLength, ContentType, SetCookie, Vary};
+
+    #[test]
+    fn typed_get_all_separate_fields() {
+        let mut map = http::HeaderMap::new();
+        map.append("set-cookie", "foo=bar".parse().unwrap());
+        map.append("set-cookie", "baz=quux; Path=/".parse().unwrap());
+        map.append("set-cookie", "=invalid".parse().unwrap());
+
+        let cookies = map.typed_get_all::<SetCookie>();
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].name(), "foo");
+        assert_eq!(cookies[1].name(), "baz");
+        assert_eq!(cookies[1].path(), Some("/"));
+
+        // Decoded together, the invalid field fails the whole header.
+        assert_eq!(map.typed_get::<SetCookie>(), None);
+    }
+
+    #[test]
+    fn typed_get_all_combined_fields() {
+        let mut map = http::HeaderMap::new();
+        map.append("vary", "accept".parse().unwrap());
+        map.append("vary", "accept-encoding".parse().unwrap());
+
+        let vary = map.typed_get_all::<Vary>();
+        assert_eq!(vary.len(), 1);
+        assert_eq!(
+            vary[0].iter_strs().collect::<Vec<_>>(),
+            vec!["accept", "accept-encoding"]
+        );
+
+        map.insert("content-length", "10".parse().unwrap());
+        let lens = map.typed_get_all::<ContentLength>();
+        assert_eq!(lens, vec![ContentLength(10)]);
+
+        assert!(map.typed_get_all::<SetCookie>().is_empty());
+    }
+
+    #[test]
+    fn typed_try_get_error_context() {
+        let mut map = http::HeaderMap::new();
+        map.insert("content-length", "ten".parse().unwrap());
+        let err = map.typed_try_get::<ContentLength>().unwrap_err();
+        assert!(err.is_invalid());
+        assert_eq!(err.name(), Some(&::http::header::CONTENT_LENGTH));
+        assert_eq!(err.value().unwrap(), "ten");
+        assert_eq!(err.to_string(), "invalid content-length header \"ten\"");
+
+        map.append("content-length", "10".parse().unwrap());
+        let err = map.typed_try_get::<ContentLength>().unwrap_err();
+        assert_eq!(err.value().unwrap(), "ten");
+
+        let mut map = http::HeaderMap::new();
+        map.append("content-length", "10".parse().unwrap());
+        map.append("content-length", "11".parse().unwrap());
+        let err = map.typed_try_get::<ContentLength>().unwrap_err();
+        assert_eq!(err.value(), None);
+        assert_eq!(err.message(), Some("header can't have multiple values"));
+    }
+
+    #[test]
+    fn typed_insert_if_absent() {
+        let mut map = http::HeaderMap::new();
+        assert!(map.typed_insert_if_absent(ContentLength(10)));
+        assert!(!map.typed_insert_if_absent(ContentLength(20)));
+        assert_eq!(map.typed_get(), Some(ContentLength(10)));
+
+        // A value that can't be decoded is still kept.
+        map.insert("content-length", "nope".parse().unwrap());
+        assert!(!map.typed_insert_if_absent(ContentLength(20)));
+        assert_eq!(map["content-length"], "nope");
+    }
+
+    #[test]
+    fn typed_entry() {
+        let mut map = http::HeaderMap::new();
+        {
+            let entry = map.typed_entry::<ContentType>();
+            assert!(!entry.is_occupied());
+            assert_eq!(entry.get(), None);
+            assert!(entry.or_insert_with(ContentType::json));
+        }
+
+        let entry = map.typed_entry::<ContentType>();
+        assert!(entry.is_occupied());
+        assert_eq!(entry.get(), Some(ContentType::json()));
+        assert!(!entry.or_insert_with(|| panic!("default shouldn't be called")));
+
+        map.append("vary", "accept".parse().unwrap());
+        map.append("vary", "cookie".parse().unwrap());
+        map.typed_entry::<Vary>().insert(Vary::any());
+        assert_eq!(map.get_all("vary").iter().collect::<Vec<_>>(), vec!["*"]);
+    }
+
+    #[test]
+    fn typed_remove() {
+        let mut map = http::HeaderMap::new();
+        map.append("content-length", "10".parse().unwrap());
+        map.append("content-length", "10".parse().unwrap());
+
+        assert_eq!(map.typed_remove::<ContentLength>(), Some(ContentLength(10)));
+        assert!(map.is_empty());
+        assert_eq!(map.typed_remove::<ContentLength>(), None);
+
+        map.insert("content-length", "nope".parse().unwrap());
+        assert_eq!(map.typed_remove::<ContentLength>(), None);
+        assert!(map.is_empty());
+    }
+}
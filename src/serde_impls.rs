@@ -0,0 +1,246 @@
+//! `Serialize` and `Deserialize` for the header types.
+//!
+//! Headers are (de)serialized as their field value string, going through
+//! `Header::encode` and `Header::decode`. A header that encodes to several
+//! field values is serialized as a sequence of strings instead.
+
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeSeq, Serializer};
+
+use common::authorization::Credentials;
+use common::*;
+use {Header, HeaderValue};
+
+fn serialize<H, S>(header: &H, serializer: S) -> Result<S::Ok, S::Error>
+where
+    H: Header,
+    S: Serializer,
+{
+    let mut values = Vec::new();
+    header.encode(&mut values);
+    let values = values
+        .iter()
+        .map(|value| value.to_str())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| ser::Error::custom("header value is not visible ASCII"))?;
+
+    if values.len() == 1 {
+        serializer.serialize_str(values[0])
+    } else {
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for value in values {
+            seq.serialize_element(value)?;
+        }
+        seq.end()
+    }
+}
+
+fn deserialize<'de, H, D>(deserializer: D) -> Result<H, D::Error>
+where
+    H: Header,
+    D: Deserializer<'de>,
+{
+    struct ValuesVisitor;
+
+    impl<'de> Visitor<'de> for ValuesVisitor {
+        type Value = Vec<HeaderValue>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a header value string, or a sequence of them")
+        }
+
+        fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+            HeaderValue::from_str(s)
+                .map(|value| vec![value])
+                .map_err(E::custom)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut values = Vec::new();
+            while let Some(s) = seq.next_element::<String>()? {
+                values.push(HeaderValue::from_str(&s).map_err(de::Error::custom)?);
+            }
+            Ok(values)
+        }
+    }
+
+    let values = deserializer.deserialize_any(ValuesVisitor)?;
+    H::decode(&mut values.iter())
+        .map_err(|_| de::Error::custom(format_args!("invalid {} header", H::name())))
+}
+
+macro_rules! serde_header {
+    ($($ty:ty,)+) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serialize(self, serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserialize(deserializer)
+                }
+            }
+        )+
+    };
+}
+
+serde_header! {
+    Accept,
+    AcceptEncoding,
+    AcceptLanguage,
+    AcceptPatch,
+    AcceptRanges,
+    AccessControlAllowCredentials,
+    AccessControlAllowHeaders,
+    AccessControlAllowMethods,
+    AccessControlAllowOrigin,
+    AccessControlExposeHeaders,
+    AccessControlMaxAge,
+    AccessControlRequestHeaders,
+    AccessControlRequestMethod,
+    Age,
+    Allow,
+    AltSvc,
+    CacheControl,
+    ClearSiteData,
+    Connection,
+    ContentDisposition,
+    ContentEncoding,
+    ContentLanguage,
+    ContentLength,
+    ContentLocation,
+    ContentRange,
+    ContentSecurityPolicy,
+    ContentSecurityPolicyReportOnly,
+    ContentType,
+    Cookie,
+    CrossOriginEmbedderPolicy,
+    CrossOriginOpenerPolicy,
+    CrossOriginResourcePolicy,
+    Date,
+    Digest,
+    ETag,
+    Expect,
+    Expires,
+    Forwarded,
+    Host,
+    IfMatch,
+    IfModifiedSince,
+    IfNoneMatch,
+    IfRange,
+    IfUnmodifiedSince,
+    KeepAlive,
+    LastModified,
+    Link,
+    Location,
+    MaxForwards,
+    Origin,
+    PermissionsPolicy,
+    Pragma,
+    Prefer,
+    PreferenceApplied,
+    ProxyAuthenticate,
+    Range,
+    Referer,
+    ReferrerPolicy,
+    Refresh,
+    RetryAfter,
+    SecWebsocketAccept,
+    SecWebsocketKey,
+    SecWebsocketVersion,
+    Server,
+    ServerTiming,
+    SetCookie,
+    StrictTransportSecurity,
+    Te,
+    TimingAllowOrigin,
+    Trailer,
+    TransferEncoding,
+    Upgrade,
+    UserAgent,
+    Vary,
+    Via,
+    WantDigest,
+    Warning,
+    WwwAuthenticate,
+    XContentTypeOptions,
+    XForwardedFor,
+    XFrameOptions,
+}
+
+#[cfg(feature = "serde_json")]
+serde_header! {
+    Nel,
+    ReportTo,
+}
+
+impl<C: Credentials> Serialize for Authorization<C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self, serializer)
+    }
+}
+
+impl<'de, C: Credentials> Deserialize<'de> for Authorization<C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer)
+    }
+}
+
+impl<C: Credentials> Serialize for ProxyAuthorization<C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self, serializer)
+    }
+}
+
+impl<'de, C: Credentials> Deserialize<'de> for ProxyAuthorization<C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::*;
+    use common::authorization::Basic;
+
+    #[test]
+    fn content_type() {
+        let ct = ContentType::from(::mime::TEXT_HTML_UTF_8);
+        let json = serde_json::to_string(&ct).unwrap();
+        assert_eq!(json, r#""text/html; charset=utf-8""#);
+        assert_eq!(serde_json::from_str::<ContentType>(&json).unwrap(), ct);
+    }
+
+    #[test]
+    fn authorization() {
+        let auth = Authorization::basic("Aladdin", "open sesame");
+        let json = serde_json::to_string(&auth).unwrap();
+        assert_eq!(json, r#""Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==""#);
+
+        let de = serde_json::from_str::<Authorization<Basic>>(&json).unwrap();
+        assert_eq!(de.username(), "Aladdin");
+    }
+
+    #[test]
+    fn deserialize_sequence() {
+        let allow = serde_json::from_str::<Allow>(r#"["GET, HEAD", "POST"]"#).unwrap();
+        let json = serde_json::to_string(&allow).unwrap();
+        assert_eq!(json, r#""GET, HEAD, POST""#);
+    }
+
+    #[test]
+    fn deserialize_invalid() {
+        let err = serde_json::from_str::<ContentLength>(r#""lots""#).unwrap_err();
+        assert!(err.to_string().contains("invalid content-length header"));
+
+        assert!(serde_json::from_str::<ContentLength>(r#""1\n2""#).is_err());
+        assert!(serde_json::from_str::<ContentLength>("12").is_err());
+    }
+}
@@ -1,26 +1,8 @@
 use std::fmt;
 
-/// Reads a comma-delimited raw header into a Vec.
-pub(crate) fn from_comma_delimited<'i, I, T, E>(values: &mut I) -> Result<E, ::Error>
-where
-    I: Iterator<Item = &'i ::HeaderValue>,
-    T: ::std::str::FromStr,
-    E: ::std::iter::FromIterator<T>,
-{
-    values
-        .flat_map(|value| {
-            value.to_str().into_iter().flat_map(|string| {
-                string
-                    .split(',')
-                    .filter_map(|x| match x.trim() {
-                        "" => None,
-                        y => Some(y),
-                    })
-                    .map(|x| x.parse().map_err(|_| ::Error::invalid()))
-            })
-        })
-        .collect()
-}
+use util::quoted_string;
+use util::{FlatCsv, TryFromValues};
+use HeaderValue;
 
 /// Format an array into a comma-delimited string.
 pub(crate) fn fmt_comma_delimited<T: fmt::Display>(
@@ -36,3 +18,128 @@ pub(crate) fn fmt_comma_delimited<T: fmt::Display>(
     }
     Ok(())
 }
+
+/// Decode a `1#element` list from every field of a header.
+///
+/// Each element is parsed from the front of the rest of the list by
+/// `parse`, so elements can contain commas, such as in quoted strings,
+/// unlike with a `FlatCsv`. See `parse_list`.
+pub(crate) fn decode_list<'i, I, T, F>(values: &mut I, parse: F) -> Result<Vec<T>, ::Error>
+where
+    I: Iterator<Item = &'i HeaderValue>,
+    F: FnMut(&str) -> Option<(T, &str)>,
+{
+    let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+    let value = csv.to_value();
+    let s = value.to_str().map_err(|_| ::Error::invalid())?;
+    parse_list(s, parse).ok_or_else(::Error::invalid)
+}
+
+/// Parse a `1#element` list, with `parse` returning each element and the
+/// rest of the string.
+///
+/// Empty elements are skipped, but the list can't be empty, and each
+/// element must be followed by a comma or the end.
+pub(crate) fn parse_list<T, F>(s: &str, mut parse: F) -> Option<Vec<T>>
+where
+    F: FnMut(&str) -> Option<(T, &str)>,
+{
+    let mut list = Vec::new();
+    let mut rest = skip_list_separators(s);
+    while !rest.is_empty() {
+        let (elem, after) = parse(rest)?;
+        list.push(elem);
+
+        let after = trim_ows(after);
+        if !after.is_empty() && !after.starts_with(',') {
+            return None;
+        }
+        rest = skip_list_separators(after);
+    }
+
+    if list.is_empty() {
+        None
+    } else {
+        Some(list)
+    }
+}
+
+/// Encode a list of elements, separated by `, `.
+///
+/// # Panics
+///
+/// Panics if the result isn't a valid `HeaderValue`.
+pub(crate) fn encode_list<T: fmt::Display>(list: &[T]) -> HeaderValue {
+    struct List<'a, T: 'a>(&'a [T]);
+
+    impl<'a, T: fmt::Display> fmt::Display for List<'a, T> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt_comma_delimited(f, self.0.iter())
+        }
+    }
+
+    ::util::fmt(List(list))
+}
+
+/// Skip commas and whitespace, such as empty list elements.
+pub(crate) fn skip_list_separators(s: &str) -> &str {
+    s.trim_start_matches(&[',', ' ', '\t'][..])
+}
+
+/// Skip optional whitespace.
+pub(crate) fn trim_ows(s: &str) -> &str {
+    s.trim_start_matches(&[' ', '\t'][..])
+}
+
+/// Split a `token` from the front of `s`.
+pub(crate) fn split_token(s: &str) -> Option<(&str, &str)> {
+    let end = s
+        .bytes()
+        .position(|b| !quoted_string::is_tchar(b))
+        .unwrap_or(s.len());
+    if end == 0 {
+        None
+    } else {
+        Some(s.split_at(end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_word(s: &str) -> Option<(String, &str)> {
+        if s.starts_with('"') {
+            quoted_string::parse(s)
+        } else {
+            split_token(s).map(|(token, rest)| (token.to_owned(), rest))
+        }
+    }
+
+    #[test]
+    fn parse_list_skips_empty_elements() {
+        assert_eq!(
+            parse_list(", a ,, \"b, c\"\t,", parse_word),
+            Some(vec!["a".to_owned(), "b, c".to_owned()])
+        );
+    }
+
+    #[test]
+    fn parse_list_invalid() {
+        assert_eq!(parse_list("", parse_word), None);
+        assert_eq!(parse_list(" , ", parse_word), None);
+        assert_eq!(parse_list("a b", parse_word), None);
+        assert_eq!(parse_list("a, (b)", parse_word), None);
+    }
+
+    #[test]
+    fn decode_and_encode_list() {
+        let values = [
+            HeaderValue::from_static("a, \"b, c\""),
+            HeaderValue::from_static("d"),
+        ];
+        let list = decode_list(&mut values.iter(), parse_word).unwrap();
+        assert_eq!(list, vec!["a", "b, c", "d"]);
+        assert_eq!(encode_list(&["a", "d"]), "a, d");
+    }
+}
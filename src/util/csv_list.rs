@@ -0,0 +1,137 @@
+use std::iter::FromIterator;
+
+use util::{FlatCsv, TryFromValues};
+use HeaderValue;
+
+/// A comma-separated list of header values, for building custom list
+/// headers.
+///
+/// Decoding merges every field of the header into one list, and
+/// iterating splits it on commas again, skipping commas inside
+/// quoted strings.
+///
+/// Collecting a `CsvList` from `String`s panics if an element isn't a
+/// valid `HeaderValue`.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{CsvList, Header, HeaderName, HeaderValue};
+///
+/// static X_FEATURES: HeaderName = HeaderName::from_static("x-features");
+///
+/// struct XFeatures(CsvList);
+///
+/// impl Header for XFeatures {
+///     fn name() -> &'static HeaderName {
+///         &X_FEATURES
+///     }
+///
+///     fn decode<'i, I>(values: &mut I) -> Result<Self, headers::Error>
+///     where
+///         I: Iterator<Item = &'i HeaderValue>,
+///     {
+///         CsvList::decode(values).map(XFeatures)
+///     }
+///
+///     fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+///         values.extend(std::iter::once((&self.0).into()));
+///     }
+/// }
+///
+/// let features = vec!["gzip".to_owned(), "tabs=\"a,b\"".to_owned()]
+///     .into_iter()
+///     .collect::<CsvList>();
+/// let features = XFeatures(features);
+///
+/// assert_eq!(
+///     features.0.iter().collect::<Vec<_>>(),
+///     vec!["gzip", "tabs=\"a,b\""]
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvList(FlatCsv);
+
+impl CsvList {
+    /// Decodes the list from every field of a header.
+    ///
+    /// This never fails, though the list may be empty.
+    pub fn decode<'i, I>(values: &mut I) -> Result<CsvList, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        TryFromValues::try_from_values(values)
+    }
+
+    /// Returns an iterator over the trimmed elements of the list.
+    ///
+    /// Empty elements, such as from `a, , b`, are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter(|elem| !elem.is_empty())
+    }
+}
+
+impl TryFromValues for CsvList {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        FlatCsv::try_from_values(values).map(CsvList)
+    }
+}
+
+impl<'a> From<&'a CsvList> for HeaderValue {
+    fn from(list: &'a CsvList) -> HeaderValue {
+        (&list.0).into()
+    }
+}
+
+impl FromIterator<String> for CsvList {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let values = iter
+            .into_iter()
+            .map(|elem| HeaderValue::from_str(&elem).expect("invalid CsvList element"))
+            .collect::<Vec<_>>();
+        CsvList(values.iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_merges_fields() {
+        let values = [
+            HeaderValue::from_static("a, \"b,c\""),
+            HeaderValue::from_static(" d ,, e"),
+        ];
+        let list = CsvList::decode(&mut values.iter()).unwrap();
+        assert_eq!(
+            list.iter().collect::<Vec<_>>(),
+            vec!["a", "\"b,c\"", "d", "e"]
+        );
+    }
+
+    #[test]
+    fn from_iter_and_encode() {
+        let list = vec!["a".to_owned(), "b c".to_owned()]
+            .into_iter()
+            .collect::<CsvList>();
+        assert_eq!(HeaderValue::from(&list), "a, b c");
+
+        let empty = Vec::<String>::new().into_iter().collect::<CsvList>();
+        assert_eq!(empty.iter().next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_iter_invalid() {
+        let _ = vec!["a\nb".to_owned()].into_iter().collect::<CsvList>();
+    }
+}
@@ -58,6 +58,11 @@ impl<T: AsRef<[u8]>> EntityTag<T> {
         }
     }
 
+    /// Get the whole entity-tag, including the quotes and any `W/`.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+
     /// Return if this is a "weak" tag.
     pub(crate) fn is_weak(&self) -> bool {
         self.0.as_ref()[0] == b'W'
@@ -126,29 +131,19 @@ impl<T: AsRef<[u8]>> EntityTag<T> {
 }
 
 impl EntityTag {
-    /*
-    /// Constructs a new EntityTag.
-    /// # Panics
-    /// If the tag contains invalid characters.
-    pub fn new(weak: bool, tag: String) -> EntityTag {
-        assert!(check_slice_validity(&tag), "Invalid tag: {:?}", tag);
-        EntityTag { weak: weak, tag: tag }
-    }
-
-    /// Constructs a new weak EntityTag.
-    /// # Panics
-    /// If the tag contains invalid characters.
-    pub fn weak(tag: String) -> EntityTag {
-        EntityTag::new(true, tag)
-    }
-
-    /// Constructs a new strong EntityTag.
-    /// # Panics
-    /// If the tag contains invalid characters.
-    pub fn strong(tag: String) -> EntityTag {
-        EntityTag::new(false, tag)
+    /// Constructs an entity-tag from an opaque tag, adding the quotes and
+    /// the `W/` prefix if it's weak.
+    ///
+    /// Returns `None` if the tag has a character that isn't `etagc`.
+    pub(crate) fn from_tag(weak: bool, tag: &str) -> Option<EntityTag> {
+        if !check_slice_validity(tag.as_bytes()) {
+            return None;
+        }
+        let prefix = if weak { "W/" } else { "" };
+        HeaderValue::from_str(&format!("{}\"{}\"", prefix, tag))
+            .ok()
+            .map(EntityTag)
     }
-    */
 
     #[cfg(test)]
     pub fn from_static(bytes: &'static str) -> EntityTag {
@@ -208,18 +203,9 @@ impl<'a> From<&'a EntityTag> for HeaderValue {
 /// 2. in the range `%x23` to `%x7E`, or
 /// 3. above `%x80`
 fn check_slice_validity(slice: &[u8]) -> bool {
-    slice.iter().all(|&c| {
-        // HeaderValue already validates that this doesnt contain control
-        // characters, so we only need to look for DQUOTE (`"`).
-        //
-        // The debug_assert is just in case we use check_slice_validity in
-        // some new context that didnt come from a HeaderValue.
-        debug_assert!(
-            (c >= b'\x21' && c <= b'\x7e') | (c >= b'\x80'),
-            "EntityTag expects HeaderValue to have check for control characters"
-        );
-        c != b'"'
-    })
+    slice
+        .iter()
+        .all(|&c| c == b'\x21' || (b'\x23'..=b'\x7e').contains(&c) || c >= b'\x80')
 }
 
 // ===== impl EntityTagRange =====
@@ -233,6 +219,20 @@ impl EntityTagRange {
         self.matches_if(entity, |a, b| a.weak_eq(b))
     }
 
+    /// Returns the listed entity tags, skipping any that fail to parse.
+    ///
+    /// `*` yields nothing.
+    pub(crate) fn tags(&self) -> impl Iterator<Item = EntityTag> + '_ {
+        let tags = match *self {
+            EntityTagRange::Any => None,
+            EntityTagRange::Tags(ref tags) => Some(tags.iter()),
+        };
+        tags.into_iter()
+            .flatten()
+            .filter_map(|tag| HeaderValue::from_str(tag).ok())
+            .filter_map(EntityTag::from_owned)
+    }
+
     fn matches_if<F>(&self, entity: &EntityTag, func: F) -> bool
     where
         F: Fn(&EntityTag<&str>, &EntityTag) -> bool,
@@ -254,7 +254,7 @@ impl super::TryFromValues for EntityTagRange {
         I: Iterator<Item = &'i HeaderValue>,
     {
         let flat = FlatCsv::try_from_values(values)?;
-        if flat.value == "*" {
+        if flat.to_value() == "*" {
             Ok(EntityTagRange::Any)
         } else {
             Ok(EntityTagRange::Tags(flat))
@@ -0,0 +1,159 @@
+//! Extended parameter values (`ext-value`), as defined in
+//! [RFC5987](https://tools.ietf.org/html/rfc5987#section-3.2).
+//!
+//! ```text
+//! ext-value     = charset  "'" [ language ] "'" value-chars
+//! value-chars   = *( pct-encoded / attr-char )
+//! attr-char     = ALPHA / DIGIT
+//!               / "!" / "#" / "$" / "&" / "+" / "-" / "."
+//!               / "^" / "_" / "`" / "|" / "~"
+//!               ; token except ( "*" / "'" / "%" )
+//! ```
+
+use std::fmt;
+
+/// A parsed `ext-value`, with the value still as raw octets.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ExtendedValue<'a> {
+    pub(crate) charset: &'a str,
+    pub(crate) language: Option<&'a str>,
+    pub(crate) value: Vec<u8>,
+}
+
+impl<'a> ExtendedValue<'a> {
+    /// Parses the `charset'language'value-chars` form.
+    pub(crate) fn parse(s: &'a str) -> Option<ExtendedValue<'a>> {
+        let mut parts = s.splitn(3, '\'');
+        let charset = parts.next()?;
+        let language = parts.next()?;
+        let value = parts.next()?;
+
+        if charset.is_empty() || !charset.bytes().all(is_mime_charsetc) {
+            return None;
+        }
+
+        Some(ExtendedValue {
+            charset,
+            language: if language.is_empty() {
+                None
+            } else {
+                Some(language)
+            },
+            value: percent_decode(value)?,
+        })
+    }
+
+    /// Decodes the value into a `String`.
+    ///
+    /// Only the `UTF-8` and `ISO-8859-1` charsets, which every recipient
+    /// must support, can be decoded.
+    pub(crate) fn decode(&self) -> Option<String> {
+        if self.charset.eq_ignore_ascii_case("utf-8") {
+            String::from_utf8(self.value.clone()).ok()
+        } else if self.charset.eq_ignore_ascii_case("iso-8859-1") {
+            Some(self.value.iter().map(|&b| b as char).collect())
+        } else {
+            None
+        }
+    }
+}
+
+/// Displays a string as a `UTF-8` `ext-value` with no language.
+pub(crate) struct Utf8<'a>(pub(crate) &'a str);
+
+impl<'a> fmt::Display for Utf8<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("UTF-8''")?;
+        for &b in self.0.as_bytes() {
+            if is_attr_char(b) {
+                write!(f, "{}", b as char)?;
+            } else {
+                write!(f, "%{:02X}", b)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn percent_decode(s: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(s.len());
+    let mut bytes = s.bytes();
+    while let Some(b) = bytes.next() {
+        match b {
+            b'%' => {
+                let hi = hex(bytes.next()?)?;
+                let lo = hex(bytes.next()?)?;
+                out.push(hi << 4 | lo);
+            }
+            b if is_attr_char(b) => out.push(b),
+            _ => return None,
+        }
+    }
+    Some(out)
+}
+
+fn hex(b: u8) -> Option<u8> {
+    (b as char).to_digit(16).map(|d| d as u8)
+}
+
+fn is_attr_char(b: u8) -> bool {
+    match b {
+        b'!' | b'#' | b'$' | b'&' | b'+' | b'-' | b'.' | b'^' | b'_' | b'`' | b'|' | b'~' => true,
+        _ => b.is_ascii_alphanumeric(),
+    }
+}
+
+fn is_mime_charsetc(b: u8) -> bool {
+    match b {
+        b'!' | b'#' | b'$' | b'%' | b'&' | b'+' | b'-' | b'^' | b'_' | b'`' | b'{' | b'}'
+        | b'~' => true,
+        _ => b.is_ascii_alphanumeric(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_with_language() {
+        // RFC 5987, Section 3.2.2
+        let ext = ExtendedValue::parse("iso-8859-1'en'%A3%20rates").unwrap();
+        assert_eq!(ext.charset, "iso-8859-1");
+        assert_eq!(ext.language, Some("en"));
+        assert_eq!(ext.value, b"\xA3 rates");
+        assert_eq!(ext.decode().unwrap(), "\u{A3} rates");
+    }
+
+    #[test]
+    fn parse_empty_language() {
+        let ext = ExtendedValue::parse("UTF-8''%c2%a3%20and%20%e2%82%ac%20rates").unwrap();
+        assert_eq!(ext.charset, "UTF-8");
+        assert_eq!(ext.language, None);
+        assert_eq!(ext.decode().unwrap(), "\u{A3} and \u{20AC} rates");
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(ExtendedValue::parse("UTF-8"), None);
+        assert_eq!(ExtendedValue::parse("UTF-8'en"), None);
+        assert_eq!(ExtendedValue::parse("''foo"), None);
+        assert_eq!(ExtendedValue::parse("UTF-8''%E2%8"), None);
+        assert_eq!(ExtendedValue::parse("UTF-8''a b"), None);
+    }
+
+    #[test]
+    fn unknown_charset_is_not_decoded() {
+        let ext = ExtendedValue::parse("koi8-r''abc").unwrap();
+        assert_eq!(ext.decode(), None);
+    }
+
+    #[test]
+    fn display_utf8() {
+        assert_eq!(
+            Utf8("\u{20AC} rates.txt").to_string(),
+            "UTF-8''%E2%82%AC%20rates.txt"
+        );
+        assert_eq!(Utf8("it's 100%").to_string(), "UTF-8''it%27s%20100%25");
+    }
+}
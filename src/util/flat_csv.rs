@@ -1,4 +1,5 @@
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
 use std::marker::PhantomData;
 
@@ -6,13 +7,25 @@ use bytes::BytesMut;
 use util::TryFromValues;
 use HeaderValue;
 
-// A single `HeaderValue` that can flatten multiple values with commas.
-#[derive(Clone, PartialEq, Eq, Hash)]
+// One or more `HeaderValue`s that are treated as a single list, as if they
+// were joined with commas.
+//
+// The values are kept as they were received, so iterating the list borrows
+// from them directly, and they are only joined when converting back into a
+// single `HeaderValue`.
+#[derive(Clone)]
 pub(crate) struct FlatCsv<Sep = Comma> {
-    pub(crate) value: HeaderValue,
+    values: Values,
     _marker: PhantomData<Sep>,
 }
 
+#[derive(Clone)]
+enum Values {
+    One(HeaderValue),
+    // Always at least 2, since no values is stored as one empty value.
+    Many(Vec<HeaderValue>),
+}
+
 pub(crate) trait Separator {
     const BYTE: u8;
     const CHAR: char;
@@ -36,31 +49,103 @@ impl Separator for SemiColon {
 
 impl<Sep: Separator> FlatCsv<Sep> {
     pub(crate) fn iter(&self) -> impl Iterator<Item = &str> {
-        self.value.to_str().ok().into_iter().flat_map(|value_str| {
-            let mut in_quotes = false;
-            value_str
-                .split(move |c| {
-                    if in_quotes {
-                        if c == '"' {
-                            in_quotes = false;
-                        }
-                        false // dont split
+        // Like a joined value, nothing is yielded unless every value is a
+        // valid `str`. A single value is checked as it's split instead.
+        let values = self.values();
+        let all_str = values.len() == 1 || values.iter().all(|value| value.to_str().is_ok());
+        values
+            .iter()
+            .filter(move |_| all_str)
+            .flat_map(|value| split::<Sep>(value.to_str().unwrap_or("")))
+    }
+
+    /// Compares the non-empty elements as sets, so order and duplicates
+    /// don't matter, optionally ignoring ASCII case too.
+    pub(crate) fn set_eq(&self, other: &FlatCsv<Sep>, ignore_case: bool) -> bool {
+        let set = |csv: &FlatCsv<Sep>| {
+            let mut elems = csv
+                .iter()
+                .filter(|elem| !elem.is_empty())
+                .map(|elem| {
+                    if ignore_case {
+                        elem.to_ascii_lowercase()
                     } else {
-                        if c == Sep::CHAR {
-                            true // split
-                        } else {
-                            if c == '"' {
-                                in_quotes = true;
-                            }
-                            false // dont split
-                        }
+                        elem.to_owned()
                     }
                 })
-                .map(|item| item.trim())
+                .collect::<Vec<_>>();
+            elems.sort();
+            elems.dedup();
+            elems
+        };
+        set(self) == set(other)
+    }
+
+    /// Joins the values into one, only allocating if there are several.
+    pub(crate) fn to_value(&self) -> HeaderValue {
+        match self.values {
+            Values::One(ref value) => value.clone(),
+            Values::Many(ref values) => {
+                let mut buf = BytesMut::new();
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        buf.extend_from_slice(&[Sep::BYTE, b' ']);
+                    }
+                    buf.extend_from_slice(value.as_bytes());
+                }
+                HeaderValue::from_maybe_shared(buf.freeze())
+                    .expect("comma separated HeaderValues are valid")
+            }
+        }
+    }
+
+    fn values(&self) -> &[HeaderValue] {
+        match self.values {
+            Values::One(ref value) => ::std::slice::from_ref(value),
+            Values::Many(ref values) => values,
+        }
+    }
+
+    // The bytes of the joined value, without joining them.
+    fn bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        self.values().iter().enumerate().flat_map(|(i, value)| {
+            let sep: &[u8] = if i > 0 { &[Sep::BYTE, b' '] } else { &[] };
+            sep.iter().chain(value.as_bytes()).cloned()
         })
     }
 }
 
+impl FlatCsv<Comma> {
+    // Comma-separated fields can be sent separately without changing what
+    // they mean, so the values are emitted as they are, without joining.
+    pub(crate) fn encode_to<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(self.values().iter().cloned());
+    }
+}
+
+fn split<Sep: Separator>(value_str: &str) -> impl Iterator<Item = &str> {
+    let mut in_quotes = false;
+    value_str
+        .split(move |c| {
+            if in_quotes {
+                if c == '"' {
+                    in_quotes = false;
+                }
+                false // dont split
+            } else {
+                if c == Sep::CHAR {
+                    true // split
+                } else {
+                    if c == '"' {
+                        in_quotes = true;
+                    }
+                    false // dont split
+                }
+            }
+        })
+        .map(|item| item.trim())
+}
+
 impl<Sep: Separator> TryFromValues for FlatCsv<Sep> {
     fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
     where
@@ -74,21 +159,39 @@ impl<Sep: Separator> TryFromValues for FlatCsv<Sep> {
 impl<Sep> From<HeaderValue> for FlatCsv<Sep> {
     fn from(value: HeaderValue) -> Self {
         FlatCsv {
-            value,
+            values: Values::One(value),
             _marker: PhantomData,
         }
     }
 }
 
-impl<'a, Sep> From<&'a FlatCsv<Sep>> for HeaderValue {
+impl<'a, Sep: Separator> From<&'a FlatCsv<Sep>> for HeaderValue {
     fn from(flat: &'a FlatCsv<Sep>) -> HeaderValue {
-        flat.value.clone()
+        flat.to_value()
+    }
+}
+
+// Equality and hashing act on the joined value, so how the list was split
+// across fields doesn't matter.
+impl<Sep: Separator> PartialEq for FlatCsv<Sep> {
+    fn eq(&self, other: &FlatCsv<Sep>) -> bool {
+        self.bytes().eq(other.bytes())
+    }
+}
+
+impl<Sep: Separator> Eq for FlatCsv<Sep> {}
+
+impl<Sep: Separator> Hash for FlatCsv<Sep> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for b in self.bytes() {
+            state.write_u8(b);
+        }
     }
 }
 
-impl<Sep> fmt::Debug for FlatCsv<Sep> {
+impl<Sep: Separator> fmt::Debug for FlatCsv<Sep> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(&self.value, f)
+        fmt::Debug::fmt(&self.to_value(), f)
     }
 }
 
@@ -97,64 +200,35 @@ impl<'a, Sep: Separator> FromIterator<&'a HeaderValue> for FlatCsv<Sep> {
     where
         I: IntoIterator<Item = &'a HeaderValue>,
     {
-        let mut values = iter.into_iter();
-
-        // Common case is there is only 1 value, optimize for that
-        if let (1, Some(1)) = values.size_hint() {
-            return values
-                .next()
-                .expect("size_hint claimed 1 item")
-                .clone()
-                .into();
-        }
-
-        // Otherwise, there are multiple, so this should merge them into 1.
-        let mut buf = values
-            .next()
-            .cloned()
-            .map(|val| BytesMut::from(val.as_bytes()))
-            .unwrap_or_else(|| BytesMut::new());
-
-        for val in values {
-            buf.extend_from_slice(&[Sep::BYTE, b' ']);
-            buf.extend_from_slice(val.as_bytes());
-        }
-
-        let val =
-            HeaderValue::from_maybe_shared(buf.freeze()).expect("comma separated HeaderValues are valid");
-
-        val.into()
+        iter.into_iter().cloned().collect()
     }
 }
 
-// TODO: would be great if there was a way to de-dupe these with above
 impl<Sep: Separator> FromIterator<HeaderValue> for FlatCsv<Sep> {
     fn from_iter<I>(iter: I) -> Self
     where
         I: IntoIterator<Item = HeaderValue>,
     {
-        let mut values = iter.into_iter();
+        let mut iter = iter.into_iter();
 
         // Common case is there is only 1 value, optimize for that
-        if let (1, Some(1)) = values.size_hint() {
-            return values.next().expect("size_hint claimed 1 item").into();
-        }
-
-        // Otherwise, there are multiple, so this should merge them into 1.
-        let mut buf = values
-            .next()
-            .map(|val| BytesMut::from(val.as_bytes()))
-            .unwrap_or_else(|| BytesMut::new());
+        let first = match iter.next() {
+            Some(first) => first,
+            None => return HeaderValue::from_static("").into(),
+        };
+        let second = match iter.next() {
+            Some(second) => second,
+            None => return first.into(),
+        };
 
-        for val in values {
-            buf.extend_from_slice(&[Sep::BYTE, b' ']);
-            buf.extend_from_slice(val.as_bytes());
+        let mut values = Vec::with_capacity(2 + iter.size_hint().0);
+        values.push(first);
+        values.push(second);
+        values.extend(iter);
+        FlatCsv {
+            values: Values::Many(values),
+            _marker: PhantomData,
         }
-
-        let val =
-            HeaderValue::from_maybe_shared(buf.freeze()).expect("comma separated HeaderValues are valid");
-
-        val.into()
     }
 }
 
@@ -196,4 +270,38 @@ mod tests {
         assert_eq!(values.next(), Some("sherlock=holmes"));
         assert_eq!(values.next(), None);
     }
+
+    #[test]
+    fn many_values() {
+        let vals = [
+            HeaderValue::from_static("aaa, b"),
+            HeaderValue::from_static("ccc"),
+            HeaderValue::from_static(""),
+        ];
+        let csv = vals.iter().collect::<FlatCsv>();
+
+        assert_eq!(csv.iter().collect::<Vec<_>>(), vec!["aaa", "b", "ccc", ""]);
+        assert_eq!(HeaderValue::from(&csv), "aaa, b, ccc, ");
+        assert_eq!(
+            csv,
+            FlatCsv::from(HeaderValue::from_static("aaa, b, ccc, "))
+        );
+    }
+
+    #[test]
+    fn no_values() {
+        let csv = Vec::<HeaderValue>::new().into_iter().collect::<FlatCsv>();
+        assert_eq!(csv.iter().collect::<Vec<_>>(), vec![""]);
+        assert_eq!(HeaderValue::from(&csv), "");
+    }
+
+    #[test]
+    fn invalid_str_yields_nothing() {
+        let vals = [
+            HeaderValue::from_static("aaa"),
+            HeaderValue::from_bytes(b"\xff").unwrap(),
+        ];
+        let csv = vals.iter().collect::<FlatCsv>();
+        assert_eq!(csv.iter().next(), None);
+    }
 }
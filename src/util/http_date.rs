@@ -1,6 +1,6 @@
 use std::fmt;
 use std::str::FromStr;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 use bytes::Bytes;
 use http::header::HeaderValue;
@@ -74,7 +74,110 @@ impl<'a> From<&'a HttpDate> for HeaderValue {
 impl FromStr for HttpDate {
     type Err = Error;
     fn from_str(s: &str) -> Result<HttpDate, Error> {
-        Ok(HttpDate(s.parse().map_err(|_| Error(()))?))
+        s.parse::<httpdate::HttpDate>()
+            .ok()
+            .or_else(|| parse_lenient(s).map(Into::into))
+            .map(HttpDate)
+            .ok_or(Error(()))
+    }
+}
+
+// `httpdate` rejects a weekday that doesn't match the date, and a leap
+// second. Recipients should accept both, as long as every other field is in
+// range, so this parses the three formats again with those allowed. A leap
+// second is clamped to `59`.
+fn parse_lenient(s: &str) -> Option<SystemTime> {
+    const DAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    const LONG_DAYS: [&str; 7] = [
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ];
+
+    let parts = s.split_whitespace().collect::<Vec<_>>();
+    let (wday, day, mon, year, time) = match parts[..] {
+        // IMF-fixdate
+        [wday, day, mon, year, time, "GMT"] if day.len() == 2 && year.len() == 4 => (
+            DAYS.contains(&wday.strip_suffix(',')?),
+            day,
+            mon,
+            num(year)?,
+            time,
+        ),
+        // RFC 850
+        [wday, date, time, "GMT"] => {
+            let mut date = date.split('-');
+            let (day, mon, year) = (date.next()?, date.next()?, date.next()?);
+            if day.len() != 2 || year.len() != 2 || date.next().is_some() {
+                return None;
+            }
+            let year = num(year)?;
+            let year = if year < 70 { year + 2000 } else { year + 1900 };
+            (
+                LONG_DAYS.contains(&wday.strip_suffix(',')?),
+                day,
+                mon,
+                year,
+                time,
+            )
+        }
+        // asctime
+        [wday, mon, day, time, year] if day.len() <= 2 && year.len() == 4 => {
+            (DAYS.contains(&wday), day, mon, num(year)?, time)
+        }
+        _ => return None,
+    };
+    if !wday {
+        return None;
+    }
+
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let mon = MONTHS.iter().position(|&m| m == mon)? as u64 + 1;
+    let day = num(day)?;
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match mon {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    if !(1970..=9999).contains(&year) || day == 0 || day > days_in_month {
+        return None;
+    }
+
+    let mut time = time.split(':');
+    let (hour, min, sec) = (time.next()?, time.next()?, time.next()?);
+    if time.next().is_some() || [hour, min, sec].iter().any(|t| t.len() != 2) {
+        return None;
+    }
+    let (hour, min, sec) = (num(hour)?, num(min)?, num(sec)?);
+    if hour > 23 || min > 59 || sec > 60 {
+        return None;
+    }
+
+    // Days since the epoch, from Howard Hinnant's `days_from_civil`.
+    let y = if mon <= 2 { year - 1 } else { year };
+    let era = y / 400;
+    let yoe = y % 400;
+    let doy = (153 * ((mon + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    let secs = days * 86_400 + hour * 3600 + min * 60 + sec.min(59);
+    Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+fn num(s: &str) -> Option<u64> {
+    if s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
     }
 }
 
@@ -102,6 +205,36 @@ impl From<HttpDate> for SystemTime {
     }
 }
 
+// `SystemTime` has no offset, so going through it normalizes to UTC, and
+// the fraction of a second is dropped by `httpdate`.
+#[cfg(feature = "chrono")]
+impl<Tz: ::chrono::TimeZone> From<::chrono::DateTime<Tz>> for HttpDate {
+    fn from(time: ::chrono::DateTime<Tz>) -> HttpDate {
+        SystemTime::from(time).into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<HttpDate> for ::chrono::DateTime<::chrono::Utc> {
+    fn from(date: HttpDate) -> ::chrono::DateTime<::chrono::Utc> {
+        SystemTime::from(date).into()
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<::time::OffsetDateTime> for HttpDate {
+    fn from(time: ::time::OffsetDateTime) -> HttpDate {
+        SystemTime::from(time).into()
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<HttpDate> for ::time::OffsetDateTime {
+    fn from(date: HttpDate) -> ::time::OffsetDateTime {
+        SystemTime::from(date).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::HttpDate;
@@ -149,4 +282,84 @@ mod tests {
     fn test_no_date() {
         assert!("this-is-no-date".parse::<HttpDate>().is_err());
     }
+
+    #[test]
+    fn test_mismatched_weekday() {
+        for s in &[
+            "Sun, 07 Nov 1994 08:48:37 GMT",
+            "Sunday, 07-Nov-94 08:48:37 GMT",
+            "Sun Nov  7 08:48:37 1994",
+        ] {
+            assert_eq!(s.parse::<HttpDate>().unwrap(), nov_07(), "{:?}", s);
+        }
+        assert!("Sun, 29 Feb 2024 12:00:00 GMT".parse::<HttpDate>().is_ok());
+    }
+
+    #[test]
+    fn test_leap_second_is_clamped() {
+        let date = "Mon, 07 Nov 1994 08:48:60 GMT".parse::<HttpDate>().unwrap();
+        assert_eq!(date.to_string(), "Mon, 07 Nov 1994 08:48:59 GMT");
+        let date = "Mon Nov  7 08:48:60 1994".parse::<HttpDate>().unwrap();
+        assert_eq!(date.to_string(), "Mon, 07 Nov 1994 08:48:59 GMT");
+    }
+
+    #[test]
+    fn test_invalid_fields() {
+        for s in &[
+            "Sat, 32 Jan 2024 99:99:99 GMT",
+            "Wed, 32 Jan 2024 12:00:00 GMT",
+            "Wed, 00 Jan 2024 12:00:00 GMT",
+            "Thu, 31 Apr 2024 12:00:00 GMT",
+            "Thu, 30 Feb 2024 12:00:00 GMT",
+            "Sun, 29 Feb 2023 12:00:00 GMT",
+            "Mon, 01 Foo 2024 12:00:00 GMT",
+            "Mon, 01 jan 2024 12:00:00 GMT",
+            "Mon, 01 Jan 1969 12:00:00 GMT",
+            "Mon, 01 Jan 2024 24:00:00 GMT",
+            "Mon, 01 Jan 2024 12:60:00 GMT",
+            "Mon, 01 Jan 2024 12:00:61 GMT",
+            "Mon, 01 Jan 2024 12:00 GMT",
+            "Mon, 01 Jan 2024 12:00:00 UTC",
+            "Xyz, 01 Jan 2024 12:00:00 GMT",
+            "Monday, 01 Jan 2024 12:00:00 GMT",
+            "Mon, 01-Jan-24 12:00:00 GMT",
+            "Monday, 32-Jan-24 12:00:00 GMT",
+            "Mon Jan 32 12:00:00 2024",
+            "Mon Jan  1 12:00:60:00 2024",
+            "Mon Jan +1 12:00:00 2024",
+        ] {
+            assert!(s.parse::<HttpDate>().is_err(), "{:?}", s);
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono() {
+        use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
+
+        let utc = Utc.timestamp_opt(784198117, 0).unwrap();
+        let local = (utc + Duration::milliseconds(750))
+            .with_timezone(&FixedOffset::west_opt(5 * 3600).unwrap());
+
+        let date = HttpDate::from(local);
+        assert_eq!(date, nov_07());
+        assert_eq!(DateTime::<Utc>::from(date), utc);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_offset_date_time() {
+        use time::{Duration, OffsetDateTime, UtcOffset};
+
+        let utc = OffsetDateTime::from_unix_timestamp(784198117).unwrap();
+        let local =
+            (utc + Duration::milliseconds(750)).to_offset(UtcOffset::from_hms(-5, 0, 0).unwrap());
+
+        let date = HttpDate::from(local);
+        assert_eq!(date, nov_07());
+
+        let back = OffsetDateTime::from(date);
+        assert_eq!(back, utc);
+        assert_eq!(back.offset(), UtcOffset::UTC);
+    }
 }
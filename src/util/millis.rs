@@ -0,0 +1,144 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use util::IterExt;
+use HeaderValue;
+
+/// A duration written as a decimal number of milliseconds, such as
+/// `12.5`.
+///
+/// Unlike `Seconds`, fractions are kept, down to the nanosecond. Anything
+/// more precise is truncated when parsing.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Millis(Duration);
+
+impl Millis {
+    pub(crate) fn from_val(val: &HeaderValue) -> Option<Self> {
+        val.to_str().ok()?.parse().ok()
+    }
+
+    pub(crate) fn as_f64(&self) -> f64 {
+        self.0.as_secs() as f64 * 1000.0 + f64::from(self.0.subsec_nanos()) / 1_000_000.0
+    }
+}
+
+impl super::TryFromValues for Millis {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        values
+            .just_one()
+            .and_then(Millis::from_val)
+            .ok_or_else(::Error::invalid)
+    }
+}
+
+impl<'a> From<&'a Millis> for HeaderValue {
+    fn from(millis: &'a Millis) -> HeaderValue {
+        HeaderValue::from_str(&millis.to_string()).expect("Millis is a valid HeaderValue")
+    }
+}
+
+impl From<Duration> for Millis {
+    fn from(dur: Duration) -> Millis {
+        Millis(dur)
+    }
+}
+
+impl From<Millis> for Duration {
+    fn from(millis: Millis) -> Duration {
+        millis.0
+    }
+}
+
+impl FromStr for Millis {
+    type Err = ::Error;
+
+    // Only plain decimals are accepted, `1*DIGIT [ "." 1*DIGIT ]`, without
+    // signs or exponents.
+    fn from_str(s: &str) -> Result<Millis, ::Error> {
+        let (whole, frac) = match s.find('.') {
+            Some(dot) => (&s[..dot], Some(&s[dot + 1..])),
+            None => (s, None),
+        };
+        let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        if !is_digits(whole) || !frac.into_iter().all(is_digits) {
+            return Err(::Error::invalid());
+        }
+
+        let ms = whole.parse::<u64>().map_err(|_| ::Error::invalid())?;
+        let nanos = frac
+            .unwrap_or("")
+            .bytes()
+            .chain(::std::iter::repeat(b'0'))
+            .take(6)
+            .fold(0, |nanos, b| nanos * 10 + u32::from(b - b'0'));
+
+        Ok(Millis(
+            Duration::from_millis(ms) + Duration::from_nanos(u64::from(nanos)),
+        ))
+    }
+}
+
+impl fmt::Debug for Millis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}ms", self)
+    }
+}
+
+impl fmt::Display for Millis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ms = self.0.as_secs() as u128 * 1000 + u128::from(self.0.subsec_millis());
+        let nanos = self.0.subsec_nanos() % 1_000_000;
+        if nanos == 0 {
+            write!(f, "{}", ms)
+        } else {
+            let frac = format!("{:06}", nanos);
+            write!(f, "{}.{}", ms, frac.trim_end_matches('0'))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(s: &str) -> Duration {
+        s.parse::<Millis>().unwrap().into()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(millis("0"), Duration::from_millis(0));
+        assert_eq!(millis("12.5"), Duration::from_micros(12_500));
+        assert_eq!(millis("12.500"), Duration::from_micros(12_500));
+        assert_eq!(millis("0.000001"), Duration::from_nanos(1));
+        assert_eq!(millis("0.0000019"), Duration::from_nanos(1));
+        assert_eq!(millis("90000"), Duration::from_secs(90));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for s in &["", ".5", "5.", "-1", "+1", "1e3", "1.2.3", "NaN"] {
+            assert!(s.parse::<Millis>().is_err(), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn display() {
+        let fmt = |dur: Duration| Millis::from(dur).to_string();
+        assert_eq!(fmt(Duration::from_millis(0)), "0");
+        assert_eq!(fmt(Duration::from_micros(12_500)), "12.5");
+        assert_eq!(fmt(Duration::from_micros(12_050)), "12.05");
+        assert_eq!(fmt(Duration::from_nanos(2_000_001)), "2.000001");
+        assert_eq!(fmt(Duration::from_secs(90)), "90000");
+    }
+
+    #[test]
+    fn as_f64() {
+        assert_eq!("47.2".parse::<Millis>().unwrap().as_f64(), 47.2);
+        assert_eq!("1500".parse::<Millis>().unwrap().as_f64(), 1500.0);
+    }
+}
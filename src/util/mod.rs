@@ -1,26 +1,36 @@
 use HeaderValue;
 
 //pub use self::charset::Charset;
+pub use self::csv_list::CsvList;
 //pub use self::encoding::Encoding;
 pub(crate) use self::entity::{EntityTag, EntityTagRange};
 pub(crate) use self::flat_csv::{FlatCsv, SemiColon};
 pub(crate) use self::fmt::fmt;
 pub(crate) use self::http_date::HttpDate;
 pub(crate) use self::iter::IterExt;
+pub(crate) use self::millis::Millis;
 //pub use language_tags::LanguageTag;
-//pub use self::quality_value::{Quality, QualityValue};
-pub(crate) use self::seconds::Seconds;
+pub use self::quality_value::{Quality, QualityValue};
+#[cfg(feature = "getrandom")]
+pub(crate) use self::random::random_bytes;
+pub(crate) use self::seconds::{parse_u64, Seconds};
 pub(crate) use self::value_string::HeaderValueString;
 
 //mod charset;
 pub(crate) mod csv;
+mod csv_list;
 //mod encoding;
 mod entity;
+pub(crate) mod extended_value;
 mod flat_csv;
 mod fmt;
 mod http_date;
 mod iter;
-//mod quality_value;
+mod millis;
+mod quality_value;
+pub(crate) mod quoted_string;
+#[cfg(feature = "getrandom")]
+mod random;
 mod seconds;
 mod value_string;
 
@@ -47,8 +57,22 @@ macro_rules! error_type {
     };
 }
 
+// Implements `Header` for a newtype over a `TryFromValues` type.
+//
+// With `list`, the newtype is over a comma-separated `FlatCsv`, and
+// `encode_to` emits its values without joining them.
 macro_rules! derive_header {
     ($type:ident(_), name: $name:ident) => {
+        derive_header!(@impl $type, $name, {});
+    };
+    ($type:ident(_), name: $name:ident, list) => {
+        derive_header!(@impl $type, $name, {
+            fn encode_to<W: Extend<::HeaderValue>>(&self, values: &mut W) {
+                self.0.encode_to(values);
+            }
+        });
+    };
+    (@impl $type:ident, $name:ident, { $($encode_to:tt)* }) => {
         impl crate::Header for $type {
             fn name() -> &'static ::http::header::HeaderName {
                 &::http::header::$name
@@ -64,6 +88,70 @@ macro_rules! derive_header {
             fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
                 values.extend(::std::iter::once((&self.0).into()));
             }
+
+            $($encode_to)*
+        }
+    };
+}
+
+// A header whose value is exactly one token from a fixed set, such as
+// `Cross-Origin-Resource-Policy: same-origin`.
+//
+// Tokens are matched case-insensitively, and unknown ones fail to decode.
+macro_rules! token_enum_header {
+    (
+        $(#[$attr:meta])*
+        pub enum $type:ident {
+            $(
+                $(#[$vattr:meta])*
+                $variant:ident => $token:expr,
+            )+
+        }
+
+        name: $name:expr
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum $type {
+            $(
+                $(#[$vattr])*
+                $variant,
+            )+
+        }
+
+        impl $type {
+            /// Returns the token for this value, as it is encoded.
+            pub fn as_str(&self) -> &'static str {
+                match *self {
+                    $($type::$variant => $token,)+
+                }
+            }
+        }
+
+        impl ::Header for $type {
+            fn name() -> &'static ::HeaderName {
+                $name
+            }
+
+            fn decode<'i, I>(values: &mut I) -> Result<Self, ::Error>
+            where
+                I: Iterator<Item = &'i ::HeaderValue>,
+            {
+                let s = ::util::IterExt::just_one(values)
+                    .and_then(|value| value.to_str().ok())
+                    .ok_or_else(::Error::invalid)?
+                    .trim();
+                $(
+                    if s.eq_ignore_ascii_case($token) {
+                        return Ok($type::$variant);
+                    }
+                )+
+                Err(::Error::invalid())
+            }
+
+            fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
+                values.extend(::std::iter::once(::HeaderValue::from_static(self.as_str())));
+            }
         }
     };
 }
@@ -82,6 +170,6 @@ impl TryFromValues for HeaderValue {
     where
         I: Iterator<Item = &'i HeaderValue>,
     {
-        values.next().cloned().ok_or_else(|| ::Error::invalid())
+        values.next().cloned().ok_or_else(::Error::missing)
     }
 }
@@ -0,0 +1,356 @@
+use std::cmp;
+use std::fmt;
+use std::str::FromStr;
+
+use util::{FlatCsv, TryFromValues};
+use HeaderValue;
+
+/// Represents a quality used in quality values.
+///
+/// The quality value is defined as a number between 0 and 1 with three
+/// decimal places, so it is stored as thousandths in a `u16`, e.g.
+/// `q=0.532` is stored as `532`.
+///
+/// Anything more precise is rounded to the nearest thousandth, so
+/// `q=0.9996` becomes `q=1` and `q=0.0004` becomes `q=0`. Values outside
+/// of `0..=1` are clamped rather than rejected.
+///
+/// [RFC7231 Section 5.3.1](https://tools.ietf.org/html/rfc7231#section-5.3.1)
+/// gives more information on quality values in HTTP header fields.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::Quality;
+///
+/// let q = "0.5".parse::<Quality>().unwrap();
+/// assert_eq!(q.as_u16(), 500);
+/// assert_eq!(q.to_string(), "0.5");
+/// assert_eq!(Quality::from_f32(0.9996), Quality::ONE);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Quality(u16);
+
+impl Quality {
+    /// The highest quality, `q=1`.
+    pub const ONE: Quality = Quality(1000);
+    /// The lowest quality, `q=0`, meaning "not acceptable".
+    pub const ZERO: Quality = Quality(0);
+
+    /// Creates a `Quality` from thousandths, clamping it to at most `1000`.
+    pub fn from_u16(thousandths: u16) -> Quality {
+        Quality(cmp::min(thousandths, 1000))
+    }
+
+    /// Returns the quality in thousandths, between `0` and `1000`.
+    pub fn as_u16(&self) -> u16 {
+        self.0
+    }
+
+    /// Creates a `Quality` from a float, clamping it into `0.0..=1.0` and
+    /// rounding it to the nearest thousandth.
+    pub fn from_f32(f: f32) -> Quality {
+        if f.is_nan() || f <= 0.0 {
+            Quality(0)
+        } else if f >= 1.0 {
+            Quality(1000)
+        } else {
+            Quality((f * 1000.0).round() as u16)
+        }
+    }
+
+    /// Returns the quality as a float between `0.0` and `1.0`.
+    pub fn as_f32(&self) -> f32 {
+        f32::from(self.0) / 1000.0
+    }
+}
+
+impl Default for Quality {
+    fn default() -> Quality {
+        Quality::ONE
+    }
+}
+
+impl FromStr for Quality {
+    type Err = ::Error;
+
+    // Parses the part after `q=`. Values outside of `0..=1` are clamped
+    // instead of rejected, since being lenient here is more useful than
+    // throwing away the whole header.
+    fn from_str(s: &str) -> Result<Quality, ::Error> {
+        if s.is_empty()
+            || !s
+                .bytes()
+                .all(|b| b.is_ascii_digit() || b == b'.' || b == b'-' || b == b'+')
+        {
+            return Err(::Error::invalid());
+        }
+        s.parse::<f32>()
+            .map(Quality::from_f32)
+            .map_err(|_| ::Error::invalid())
+    }
+}
+
+impl fmt::Display for Quality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            1000 => f.write_str("1"),
+            0 => f.write_str("0"),
+            x => write!(f, "0.{}", format!("{:03}", x).trim_end_matches('0')),
+        }
+    }
+}
+
+/// Represents an item with a quality value as defined in
+/// [RFC7231](https://tools.ietf.org/html/rfc7231#section-5.3.1).
+///
+/// Parsing takes the first `q` parameter as the weight and keeps any
+/// parameters before it as part of the item, so `text/html; level=1; q=0.7`
+/// parses `text/html; level=1` as `T`. A missing weight means `q=1`, which
+/// is also left out when formatting. Comparing two values only compares
+/// their weights.
+///
+/// This is what `Accept`, `AcceptEncoding`, `AcceptLanguage` and `Te` are
+/// built on, and it can be used for custom quality-list headers too.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{HeaderValue, Quality, QualityValue};
+///
+/// let values = vec![HeaderValue::from_static("br;q=0.5, gzip")];
+/// let list = QualityValue::<String>::decode_list(&mut values.iter()).unwrap();
+///
+/// assert_eq!(list[0].value(), "br");
+/// assert_eq!(list[0].quality(), Quality::from_u16(500));
+/// assert_eq!(list[0].to_string(), "br; q=0.5");
+/// assert_eq!(list[1].to_string(), "gzip");
+/// assert!(list[1] > list[0]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct QualityValue<T> {
+    value: T,
+    quality: Quality,
+}
+
+impl<T> QualityValue<T> {
+    /// Creates a new `QualityValue` from an item and a quality.
+    pub fn new(value: T, quality: Quality) -> QualityValue<T> {
+        QualityValue { value, quality }
+    }
+
+    /// Returns the item.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the quality of the item.
+    pub fn quality(&self) -> Quality {
+        self.quality
+    }
+
+    /// Consumes the `QualityValue`, returning the item.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<T: FromStr> QualityValue<T> {
+    /// Decodes a comma-separated list of weighted items from every field of
+    /// a header.
+    ///
+    /// Empty elements are skipped, so the list may be empty. Any element
+    /// that fails to parse fails the whole list.
+    pub fn decode_list<'i, I>(values: &mut I) -> Result<Vec<QualityValue<T>>, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        TryFromValues::try_from_values(values)
+    }
+}
+
+impl<T: FromStr> TryFromValues for Vec<QualityValue<T>> {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let csv: FlatCsv = TryFromValues::try_from_values(values)?;
+        csv.iter()
+            .filter(|elem| !elem.is_empty())
+            .map(|elem| elem.parse())
+            .collect()
+    }
+}
+
+impl<T> From<T> for QualityValue<T> {
+    fn from(value: T) -> QualityValue<T> {
+        QualityValue::new(value, Quality::default())
+    }
+}
+
+impl<T: PartialEq> cmp::PartialOrd for QualityValue<T> {
+    fn partial_cmp(&self, other: &QualityValue<T>) -> Option<cmp::Ordering> {
+        self.quality.partial_cmp(&other.quality)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for QualityValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)?;
+        if self.quality != Quality::ONE {
+            write!(f, "; q={}", self.quality)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: FromStr> FromStr for QualityValue<T> {
+    type Err = ::Error;
+
+    fn from_str(s: &str) -> Result<QualityValue<T>, ::Error> {
+        let (item, quality) = split_weight(s)?;
+        item.parse::<T>()
+            .map(|value| QualityValue::new(value, quality))
+            .map_err(|_| ::Error::invalid())
+    }
+}
+
+/// Splits `item; q=0.5; ext` into the item and its weight.
+///
+/// Only the first `q` parameter is the weight, anything after it is an
+/// accept-extension and is ignored. Semicolons within quotes are skipped.
+fn split_weight(s: &str) -> Result<(&str, Quality), ::Error> {
+    let mut in_quotes = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => {
+                let param = s[i + 1..].trim_start();
+                if param.starts_with("q=") || param.starts_with("Q=") {
+                    let q = param[2..].split(';').next().unwrap_or("").trim();
+                    return Ok((s[..i].trim(), q.parse()?));
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok((s.trim(), Quality::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fmt_q_1() {
+        let x = QualityValue::from("foo");
+        assert_eq!(x.to_string(), "foo");
+    }
+
+    #[test]
+    fn fmt_q_0001() {
+        let x = QualityValue::new("foo", Quality(1));
+        assert_eq!(x.to_string(), "foo; q=0.001");
+    }
+
+    #[test]
+    fn fmt_q_05() {
+        let x = QualityValue::new("foo", Quality(500));
+        assert_eq!(x.to_string(), "foo; q=0.5");
+    }
+
+    #[test]
+    fn fmt_q_0() {
+        let x = QualityValue::new("foo", Quality(0));
+        assert_eq!(x.to_string(), "foo; q=0");
+    }
+
+    #[test]
+    fn from_str() {
+        let x: QualityValue<String> = "chunked".parse().unwrap();
+        assert_eq!(x, QualityValue::new("chunked".to_owned(), Quality(1000)));
+
+        let x: QualityValue<String> = "chunked; q=1".parse().unwrap();
+        assert_eq!(x, QualityValue::new("chunked".to_owned(), Quality(1000)));
+
+        let x: QualityValue<String> = "gzip; q=0.5".parse().unwrap();
+        assert_eq!(x, QualityValue::new("gzip".to_owned(), Quality(500)));
+
+        let x: QualityValue<String> = "gzip;Q=0.273".parse().unwrap();
+        assert_eq!(x, QualityValue::new("gzip".to_owned(), Quality(273)));
+    }
+
+    #[test]
+    fn from_str_keeps_params_before_weight() {
+        let x: QualityValue<String> = "text/html; level=1; q=0.7; ext=foo".parse().unwrap();
+        assert_eq!(
+            x,
+            QualityValue::new("text/html; level=1".to_owned(), Quality(700))
+        );
+
+        let x: QualityValue<String> = "text/html; a=\";q=0\"".parse().unwrap();
+        assert_eq!(x.quality(), Quality::ONE);
+    }
+
+    #[test]
+    fn from_str_clamps() {
+        let x: QualityValue<String> = "gzip; q=2.0".parse().unwrap();
+        assert_eq!(x.quality(), Quality::ONE);
+
+        let x: QualityValue<String> = "gzip; q=-1".parse().unwrap();
+        assert_eq!(x.quality(), Quality::ZERO);
+    }
+
+    #[test]
+    fn from_str_invalid() {
+        assert!("gzip; q=".parse::<QualityValue<String>>().is_err());
+        assert!("gzip; q=abc".parse::<QualityValue<String>>().is_err());
+        assert!("gzip; q=NaN".parse::<QualityValue<String>>().is_err());
+    }
+
+    #[test]
+    fn ordering() {
+        let x: QualityValue<String> = "gzip; q=0.5".parse().unwrap();
+        let y: QualityValue<String> = "gzip; q=0.273".parse().unwrap();
+        assert!(x > y)
+    }
+
+    #[test]
+    fn from_f32() {
+        assert_eq!(Quality::from_f32(0.5), Quality(500));
+        assert_eq!(Quality::from_f32(2.0), Quality(1000));
+        assert_eq!(Quality::from_f32(-1.0), Quality(0));
+        assert_eq!(Quality(250).as_f32(), 0.25);
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq!("0.9996".parse::<Quality>().unwrap(), Quality::ONE);
+        assert_eq!("0.0004".parse::<Quality>().unwrap(), Quality::ZERO);
+        assert_eq!("0.1234".parse::<Quality>().unwrap(), Quality(123));
+        assert_eq!(Quality::from_u16(5000), Quality::ONE);
+    }
+
+    #[test]
+    fn decode_list() {
+        let values = [
+            HeaderValue::from_static("gzip;q=0.5, , br"),
+            HeaderValue::from_static("identity;q=0"),
+        ];
+        let list = QualityValue::<String>::decode_list(&mut values.iter()).unwrap();
+        assert_eq!(
+            list,
+            vec![
+                QualityValue::new("gzip".to_owned(), Quality(500)),
+                QualityValue::new("br".to_owned(), Quality::ONE),
+                QualityValue::new("identity".to_owned(), Quality::ZERO),
+            ]
+        );
+
+        let bad = [HeaderValue::from_static("gzip;q=x")];
+        assert!(QualityValue::<String>::decode_list(&mut bad.iter()).is_err());
+    }
+}
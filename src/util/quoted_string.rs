@@ -0,0 +1,86 @@
+//! Helpers for the `token` and `quoted-string` rules of
+//! [RFC7230](https://tools.ietf.org/html/rfc7230#section-3.2.6).
+
+use std::fmt;
+
+/// Whether `s` is a non-empty `token`.
+pub(crate) fn is_token(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(is_tchar)
+}
+
+pub(crate) fn is_tchar(b: u8) -> bool {
+    match b {
+        b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' | b'-' | b'.' | b'^' | b'_'
+        | b'`' | b'|' | b'~' => true,
+        _ => b.is_ascii_alphanumeric(),
+    }
+}
+
+/// Parses a `quoted-string` at the start of `s`.
+///
+/// Returns the unescaped contents, and whatever follows the closing quote.
+pub(crate) fn parse(s: &str) -> Option<(String, &str)> {
+    let mut chars = s.char_indices();
+    match chars.next() {
+        Some((_, '"')) => (),
+        _ => return None,
+    }
+
+    let mut out = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((out, &s[i + 1..])),
+            '\\' => out.push(chars.next()?.1),
+            c => out.push(c),
+        }
+    }
+    // unterminated
+    None
+}
+
+/// Displays a string as a `quoted-string`, escaping as needed.
+pub(crate) struct Quoted<'a>(pub(crate) &'a str);
+
+impl<'a> fmt::Display for Quoted<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("\"")?;
+        for c in self.0.chars() {
+            if c == '"' || c == '\\' {
+                f.write_str("\\")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        f.write_str("\"")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token() {
+        assert!(is_token("gzip"));
+        assert!(is_token("x-custom_1.0"));
+        assert!(!is_token(""));
+        assert!(!is_token("two words"));
+        assert!(!is_token("a=b"));
+    }
+
+    #[test]
+    fn parse_quoted() {
+        assert_eq!(
+            parse(r#""foo bar"; rest"#),
+            Some(("foo bar".into(), "; rest"))
+        );
+        assert_eq!(parse(r#""a \"b\" \\c""#), Some((r#"a "b" \c"#.into(), "")));
+        assert_eq!(parse(r#""unterminated"#), None);
+        assert_eq!(parse(r#"no quotes"#), None);
+    }
+
+    #[test]
+    fn display_quoted() {
+        assert_eq!(Quoted("foo bar").to_string(), r#""foo bar""#);
+        assert_eq!(Quoted(r#"a "b" \c"#).to_string(), r#""a \"b\" \\c""#);
+    }
+}
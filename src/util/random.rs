@@ -0,0 +1,10 @@
+// 16 random bytes from the operating system, through `getrandom`.
+//
+// Panics if the operating system can't provide them, which only happens
+// on unusual platforms or very early in boot.
+pub(crate) fn random_bytes() -> [u8; 16] {
+    let mut bytes = [0u8; 16];
+    ::getrandom::getrandom(&mut bytes)
+        .expect("the operating system failed to provide random bytes");
+    bytes
+}
@@ -9,9 +9,7 @@ pub(crate) struct Seconds(Duration);
 
 impl Seconds {
     pub(crate) fn from_val(val: &HeaderValue) -> Option<Self> {
-        let secs = val.to_str().ok()?.parse().ok()?;
-
-        Some(Self::from_secs(secs))
+        parse_u64(val.as_bytes()).map(Self::from_secs)
     }
 
     pub(crate) fn from_secs(secs: u64) -> Self {
@@ -23,6 +21,28 @@ impl Seconds {
     }
 }
 
+/// Parses an integer straight from header bytes, accepting the same input
+/// as `str::parse::<u64>`: an optional `+` and then at least one digit.
+///
+/// This skips the `to_str` check, since anything that isn't a digit is
+/// rejected anyway.
+pub(crate) fn parse_u64(bytes: &[u8]) -> Option<u64> {
+    let digits = match bytes.split_first() {
+        Some((&b'+', rest)) => rest,
+        _ => bytes,
+    };
+    if digits.is_empty() {
+        return None;
+    }
+    digits.iter().try_fold(0u64, |n, &b| {
+        if b.is_ascii_digit() {
+            n.checked_mul(10)?.checked_add(u64::from(b - b'0'))
+        } else {
+            None
+        }
+    })
+}
+
 impl super::TryFromValues for Seconds {
     fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
     where
@@ -65,3 +85,29 @@ impl fmt::Display for Seconds {
         fmt::Display::fmt(&self.0.as_secs(), f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_u64_matches_str_parse() {
+        let cases = [
+            "0",
+            "3600",
+            "+5",
+            "007",
+            "18446744073709551615",
+            "18446744073709551616",
+            "",
+            "+",
+            "-1",
+            " 1",
+            "1.0",
+            "1e3",
+        ];
+        for s in &cases {
+            assert_eq!(parse_u64(s.as_bytes()), s.parse().ok(), "{:?}", s);
+        }
+    }
+}